@@ -152,6 +152,10 @@ impl Application for CliApp {
 
         tracing::info!("running Hermes v{}", clap::crate_version!());
 
+        if let Some(masp_params_dir) = &config.global.masp_params_dir {
+            ibc_relayer::chain::namada::set_masp_params_dir(masp_params_dir);
+        }
+
         self.config.set_once(config);
 
         Ok(())
@@ -18,7 +18,7 @@ use ibc_chain_registry::formatter::{SimpleGrpcFormatter, UriFormatter};
 use ibc_chain_registry::paths::IBCPath;
 use ibc_chain_registry::querier::*;
 use ibc_relayer::chain::cosmos::config::CosmosSdkConfig;
-use ibc_relayer::config::dynamic_gas::DynamicGasPrice;
+use ibc_relayer::config::dynamic_gas::{DynamicGasPrice, DynamicGasPriceProvider};
 use ibc_relayer::config::filter::{FilterPattern, PacketFilter};
 use ibc_relayer::config::gas_multiplier::GasMultiplier;
 use ibc_relayer::config::types::{MaxMsgNum, MaxTxSize, Memo, TrustThreshold};
@@ -119,6 +119,7 @@ where
     // Use EIP-1559 dynamic gas price for Osmosis
     let dynamic_gas_price = if chain_data.chain_id.as_str() == "osmosis-1" {
         DynamicGasPrice::unsafe_new(true, 1.1, 0.6)
+            .with_provider(DynamicGasPriceProvider::OsmosisTxfees)
     } else {
         DynamicGasPrice::disabled()
     };
@@ -133,6 +134,8 @@ where
         },
         rpc_timeout: default::rpc_timeout(),
         trusted_node: default::trusted_node(),
+        witnesses: Vec::new(),
+        min_witnesses: 1,
         genesis_restart: None,
         account_prefix: chain_data.bech32_prefix,
         key_name: String::new(),
@@ -145,6 +148,7 @@ where
         gas_multiplier: Some(GasMultiplier::new(1.1).unwrap()),
         dynamic_gas_price,
         fee_granter: None,
+        fee_granters: Default::default(),
         max_msg_num: MaxMsgNum::default(),
         max_tx_size: MaxTxSize::default(),
         max_grpc_decoding_size: default::max_grpc_decoding_size(),
@@ -162,7 +166,12 @@ where
             price: avg_gas_price,
             denom: asset.base.to_owned(),
         },
+        fallback_fee_tokens: Vec::new(),
         packet_filter: packet_filter.unwrap_or_default(),
+        packet_priority: Default::default(),
+        gas_model: Default::default(),
+        tx_replacement: Default::default(),
+        unordered_tx: Default::default(),
         address_type: AddressType::default(),
         sequential_batch_tx: false,
         extension_options: Vec::new(),
@@ -170,6 +179,8 @@ where
         clear_interval: None,
         excluded_sequences: ExcludedSequences::new(BTreeMap::new()),
         allow_ccq: true,
+        backend: None,
+        record_fixtures: None,
     }))
 }
 
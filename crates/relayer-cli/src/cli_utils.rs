@@ -5,6 +5,7 @@ use eyre::eyre;
 use tokio::runtime::Runtime as TokioRuntime;
 use tracing::debug;
 
+use ibc_relayer::chain::capabilities::Capability;
 use ibc_relayer::chain::counterparty::{channel_connection_client, ChannelConnectionClient};
 use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
 use ibc_relayer::chain::requests::{
@@ -181,3 +182,15 @@ pub fn check_can_send_on_channel<Chain: ChainHandle>(
 
     Ok(())
 }
+
+/// Check that the given chain supports the given capability, so that commands
+/// can bail out with a clear message before attempting any unsupported work.
+pub fn check_capability<Chain: ChainHandle>(
+    chain: &Chain,
+    capability: Capability,
+) -> Result<(), Error> {
+    chain
+        .capabilities()
+        .and_then(|capabilities| capabilities.ensure(&chain.id(), capability))
+        .map_err(Error::relayer)
+}
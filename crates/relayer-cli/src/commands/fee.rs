@@ -6,10 +6,12 @@ use ibc_relayer::config::Config;
 
 use self::register_counterparty_payee::RegisterCounterpartyPayeeCmd;
 use self::register_payee::RegisterPayeeCmd;
+use self::report::FeeReportCmd;
 use self::transfer::FeeTransferCmd;
 
 pub mod register_counterparty_payee;
 pub mod register_payee;
+pub mod report;
 pub mod transfer;
 
 #[allow(clippy::large_enum_variant)]
@@ -23,6 +25,9 @@ pub enum FeeCmd {
 
     /// Perform a token transfer supported with a fee
     Transfer(FeeTransferCmd),
+
+    /// Report the fees escrowed and distributed per channel
+    Report(FeeReportCmd),
 }
 
 impl Override<Config> for FeeCmd {
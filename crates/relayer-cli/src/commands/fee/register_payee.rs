@@ -1,6 +1,7 @@
 use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
 use core::str::FromStr;
+use ibc_relayer::chain::capabilities::Capability;
 use ibc_relayer::chain::handle::ChainHandle;
 use ibc_relayer::chain::tracking::TrackedMsgs;
 use ibc_relayer_types::applications::ics29_fee::msgs::register_payee::build_register_payee_message;
@@ -8,7 +9,7 @@ use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId
 use ibc_relayer_types::signer::Signer;
 
 use crate::application::app_config;
-use crate::cli_utils::spawn_chain_runtime;
+use crate::cli_utils::{check_capability, spawn_chain_runtime};
 use crate::conclude::{exit_with_unrecoverable_error, Output};
 use crate::error::Error;
 
@@ -78,6 +79,8 @@ fn run_register_payee_command(
 
     let chain_handle = spawn_chain_runtime(&config, chain_id)?;
 
+    check_capability(&chain_handle, Capability::Fees)?;
+
     let signer = chain_handle.get_signer().map_err(Error::relayer)?;
 
     let message =
@@ -0,0 +1,65 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use ibc_relayer::fee_accounting::{report_path, FeeReport};
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::application::app_config;
+use crate::conclude::{exit_with_unrecoverable_error, Output};
+use crate::error::Error;
+
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct FeeReportCmd {
+    #[clap(
+        long = "chain",
+        value_name = "CHAIN_ID",
+        help = "Only report the fees of the given chain"
+    )]
+    chain_id: Option<ChainId>,
+}
+
+impl Runnable for FeeReportCmd {
+    fn run(&self) {
+        let report = run_fee_report_command(self.chain_id.as_ref())
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        Output::success(report).exit()
+    }
+}
+
+fn run_fee_report_command(chain_id: Option<&ChainId>) -> Result<FeeReport, Error> {
+    let config = app_config();
+
+    let path = report_path(&config.fee_accounting).map_err(Error::relayer)?;
+    let report = FeeReport::load_or_default(&path).map_err(Error::relayer)?;
+
+    match chain_id {
+        Some(chain_id) => Ok(report.for_chain(chain_id)),
+        None => Ok(report),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FeeReportCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_fee_report() {
+        assert_eq!(
+            FeeReportCmd { chain_id: None },
+            FeeReportCmd::parse_from(["test"])
+        )
+    }
+
+    #[test]
+    fn test_fee_report_chain() {
+        assert_eq!(
+            FeeReportCmd {
+                chain_id: Some(ChainId::from_string("chain_a"))
+            },
+            FeeReportCmd::parse_from(["test", "--chain", "chain_a"])
+        )
+    }
+}
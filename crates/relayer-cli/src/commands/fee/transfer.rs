@@ -4,7 +4,7 @@ use abscissa_core::{clap::Parser, config::Override, FrameworkError, FrameworkErr
 use eyre::eyre;
 
 use ibc_relayer::{
-    chain::handle::ChainHandle,
+    chain::{capabilities::Capability, handle::ChainHandle},
     config::Config,
     transfer::{build_transfer_messages, send_messages, TransferOptions},
 };
@@ -17,7 +17,7 @@ use ibc_relayer_types::{
 };
 
 use crate::{
-    cli_utils::{check_can_send_on_channel, ChainHandlePair},
+    cli_utils::{check_can_send_on_channel, check_capability, ChainHandlePair},
     conclude::{exit_with_unrecoverable_error, Output},
     prelude::*,
 };
@@ -247,18 +247,20 @@ impl From<FeeTransferOptions> for TransferOptions {
         TransferOptions {
             src_port_id: f.src_port_id,
             src_channel_id: f.src_channel_id,
-            amount: f.amount,
-            denom: f.denom,
+            tokens: vec![Coin::new(f.denom, f.amount)],
             receiver: f.receiver,
             timeout_height_offset: f.timeout_height_offset,
             timeout_duration: f.timeout_duration,
             number_msgs: f.number_msgs,
             memo: f.memo,
+            forwarding: None,
         }
     }
 }
 
 fn fee_transfer(chains: ChainHandlePair, opts: FeeTransferOptions) -> Result<(), eyre::Report> {
+    check_capability(&chains.src, Capability::Fees)?;
+
     check_can_send_on_channel(
         &chains.src,
         &opts.src_channel_id,
@@ -292,7 +294,7 @@ fn fee_transfer(chains: ChainHandlePair, opts: FeeTransferOptions) -> Result<(),
         msgs.push(t);
     }
 
-    let res = send_messages(&chains.src, msgs);
+    let res = send_messages(&chains.src, msgs, "ft-transfer");
 
     match res {
         Ok(ev) => Output::success(ev).exit(),
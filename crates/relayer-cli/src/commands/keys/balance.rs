@@ -3,12 +3,13 @@ use std::fmt::Write;
 use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
 
+use ibc_relayer::chain::capabilities::Capability;
 use ibc_relayer::chain::handle::ChainHandle;
 use ibc_relayer::config::ChainConfig;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
 use crate::application::app_config;
-use crate::cli_utils::spawn_chain_runtime;
+use crate::cli_utils::{check_capability, spawn_chain_runtime};
 use crate::conclude::{exit_with_unrecoverable_error, json, Output};
 
 /// The data structure that represents the arguments when invoking the `keys balance` CLI command.
@@ -59,6 +60,9 @@ impl Runnable for KeyBalanceCmd {
         let chain = spawn_chain_runtime(&config, &self.chain_id)
             .unwrap_or_else(exit_with_unrecoverable_error);
 
+        check_capability(&chain, Capability::Balances)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
         let key_name = self.key_name.clone();
 
         if self.all {
@@ -80,7 +84,7 @@ fn get_balance(chain: impl ChainHandle, key_name: Option<String>, denom: Option<
                     ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
                         chain_config.key_name
                     }
-                    ChainConfig::Penumbra(_) => unimplemented!("not yet supported for penumbra"),
+                    ChainConfig::Penumbra(chain_config) => chain_config.address(),
                 }
             });
 
@@ -105,7 +109,7 @@ fn get_balances(chain: impl ChainHandle, key_name: Option<String>) {
                     ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
                         chain_config.key_name
                     }
-                    ChainConfig::Penumbra(_) => unimplemented!("not yet supported for penumbra"),
+                    ChainConfig::Penumbra(chain_config) => chain_config.address(),
                 }
             });
 
@@ -184,11 +184,12 @@ fn subscribe(
         }
         ChainConfig::Penumbra(config) => {
             let (event_source, monitor_tx) = match &config.event_source {
-                EventSourceMode::Push { url, batch_delay } => EventSource::websocket(
+                EventSourceMode::Push { url, batch_delay } => EventSource::penumbra_websocket(
                     chain_config.id().clone(),
                     url.clone(),
                     compat_mode,
                     *batch_delay,
+                    HttpClient::new(config.rpc_addr.clone())?,
                     rt,
                 ),
                 EventSourceMode::Pull {
@@ -16,6 +16,9 @@ mod client;
 mod clients;
 mod connection;
 mod connections;
+mod gas_model;
+mod ica;
+mod nft;
 mod packet;
 mod transfer;
 mod tx;
@@ -48,6 +51,9 @@ pub enum QueryCmd {
     #[clap(subcommand)]
     Packet(QueryPacketCmds),
 
+    /// Query the gas learnt for each type of message sent to a chain
+    GasModel(gas_model::QueryGasModelCmd),
+
     /// Query information about transactions
     #[clap(subcommand)]
     Tx(tx::QueryTxCmd),
@@ -55,6 +61,14 @@ pub enum QueryCmd {
     /// Query information about token transfers
     #[clap(subcommand)]
     Transfer(transfer::TransferCmd),
+
+    /// Query information about interchain accounts
+    #[clap(subcommand)]
+    Ica(ica::QueryIcaCmd),
+
+    /// Query information about non-fungible token transfers
+    #[clap(subcommand)]
+    Nft(nft::NftCmd),
 }
 
 #[derive(Command, Debug, Parser, Runnable)]
@@ -0,0 +1,65 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use ibc_relayer::chain::cosmos::gas_model::{gas_model_path, GasModelStats};
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::application::app_config;
+use crate::conclude::{exit_with_unrecoverable_error, Output};
+use crate::error::Error;
+
+/// Query the gas learnt for each type of message sent to a chain
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct QueryGasModelCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain to query"
+    )]
+    chain_id: ChainId,
+}
+
+impl Runnable for QueryGasModelCmd {
+    fn run(&self) {
+        let stats = run_query_gas_model_command(&self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        Output::success(stats).exit()
+    }
+}
+
+fn run_query_gas_model_command(chain_id: &ChainId) -> Result<GasModelStats, Error> {
+    let config = app_config();
+
+    let chain_config = config
+        .find_chain(chain_id)
+        .ok_or_else(|| Error::missing_chain_config(chain_id.clone()))?;
+
+    let path = gas_model_path(chain_id, &chain_config.gas_model()).map_err(Error::relayer)?;
+
+    GasModelStats::load_or_default(&path).map_err(Error::relayer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryGasModelCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_query_gas_model() {
+        assert_eq!(
+            QueryGasModelCmd {
+                chain_id: ChainId::from_string("chain_id")
+            },
+            QueryGasModelCmd::parse_from(["test", "--chain", "chain_id"])
+        )
+    }
+
+    #[test]
+    fn test_query_gas_model_no_chain() {
+        assert!(QueryGasModelCmd::try_parse_from(["test"]).is_err())
+    }
+}
@@ -0,0 +1,139 @@
+//! `query ica` subcommands
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer::chain::capabilities::Capability;
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ConnectionId};
+use ibc_relayer_types::signer::Signer;
+
+use crate::application::app_config;
+use crate::cli_utils::{check_capability, spawn_chain_runtime};
+use crate::conclude::{exit_with_unrecoverable_error, json, Output};
+
+/// `query ica` subcommands
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum QueryIcaCmd {
+    /// Query the address of an interchain account on its host chain
+    Address(QueryIcaAddressCmd),
+}
+
+/// The data structure that represents the arguments when invoking the `query ica address` CLI command.
+///
+/// The command has the following format:
+///
+/// `query ica address --chain <CHAIN_ID> --connection <CONNECTION_ID> [--owner <OWNER>]`
+///
+/// If successful the address of the interchain account on the host chain will be displayed.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct QueryIcaAddressCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the controller chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "connection",
+        visible_alias = "conn",
+        required = true,
+        value_name = "CONNECTION_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the connection to the host chain"
+    )]
+    connection_id: ConnectionId,
+
+    #[clap(
+        long = "owner",
+        value_name = "OWNER",
+        help = "Address of the owner of the interchain account on the controller chain. \
+                If omitted, the address of the configured key of the controller chain will be used"
+    )]
+    owner: Option<Signer>,
+}
+
+impl Runnable for QueryIcaAddressCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        check_capability(&chain, Capability::InterchainAccounts)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let owner = match &self.owner {
+            Some(owner) => owner.clone(),
+            None => chain
+                .get_signer()
+                .unwrap_or_else(exit_with_unrecoverable_error),
+        };
+
+        match chain.query_interchain_account(owner, self.connection_id.clone()) {
+            Ok(address) if json() => Output::success(address).exit(),
+            Ok(address) => Output::success_msg(address).exit(),
+            Err(e) => Output::error(format!(
+                "there was a problem querying the interchain account address: {e}"
+            ))
+            .exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryIcaAddressCmd;
+
+    use std::str::FromStr;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ConnectionId};
+    use ibc_relayer_types::signer::Signer;
+
+    #[test]
+    fn test_query_ica_address() {
+        assert_eq!(
+            QueryIcaAddressCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                connection_id: ConnectionId::from_str("connection-0").unwrap(),
+                owner: Some(Signer::from_str("cosmos1owner").unwrap()),
+            },
+            QueryIcaAddressCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--connection",
+                "connection-0",
+                "--owner",
+                "cosmos1owner"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_query_ica_address_no_owner() {
+        assert_eq!(
+            QueryIcaAddressCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                connection_id: ConnectionId::from_str("connection-0").unwrap(),
+                owner: None,
+            },
+            QueryIcaAddressCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--conn",
+                "connection-0"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_query_ica_address_no_connection() {
+        assert!(QueryIcaAddressCmd::try_parse_from(["test", "--chain", "chain_id"]).is_err())
+    }
+}
@@ -0,0 +1,13 @@
+//! `query nft` subcommand
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+mod class_trace;
+
+/// `query nft` subcommand
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum NftCmd {
+    /// Query the ICS721 class trace info from a trace hash
+    ClassTrace(class_trace::ClassTraceCmd),
+}
@@ -0,0 +1,92 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer::chain::capabilities::Capability;
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::application::app_config;
+use crate::cli_utils::{check_capability, spawn_chain_runtime};
+use crate::conclude::{exit_with_unrecoverable_error, json, Output};
+
+/// The data structure that represents the arguments when invoking the `query nft class-trace` CLI command.
+///
+/// The command has the following format:
+///
+/// `query nft class-trace --chain <CHAIN_ID> --hash <HASH>`
+///
+/// If successful the base class identifier and the path will be displayed.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct ClassTraceCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "hash",
+        required = true,
+        help_heading = "REQUIRED",
+        help = "Trace hash to query"
+    )]
+    hash: String,
+}
+
+impl Runnable for ClassTraceCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        check_capability(&chain, Capability::NftTransfers)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        // Accept class identifiers of the form `ibc/{hash}` as well
+        let hash = self.hash.trim_start_matches("ibc/").to_owned();
+
+        match chain.query_class_trace(hash) {
+            Ok(class_trace) if json() => Output::success(class_trace).exit(),
+            Ok(class_trace) => Output::success_msg(format!(
+                "base_class_id: {}\n path: {}",
+                class_trace.base_class_id, class_trace.path
+            ))
+            .exit(),
+            Err(e) => {
+                Output::error(format!("there was a problem querying the class trace: {e}")).exit()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ClassTraceCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_nft_class_trace() {
+        assert_eq!(
+            ClassTraceCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                hash: "abcdefg".to_owned()
+            },
+            ClassTraceCmd::parse_from(["test", "--chain", "chain_id", "--hash", "abcdefg"])
+        )
+    }
+
+    #[test]
+    fn test_nft_class_trace_no_hash() {
+        assert!(ClassTraceCmd::try_parse_from(["test", "--chain", "chain_id"]).is_err())
+    }
+
+    #[test]
+    fn test_nft_class_trace_no_chain() {
+        assert!(ClassTraceCmd::try_parse_from(["test", "--hash", "abcdefg"]).is_err())
+    }
+}
@@ -1,11 +1,12 @@
 use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
 
+use ibc_relayer::chain::capabilities::Capability;
 use ibc_relayer::chain::handle::ChainHandle;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
 use crate::application::app_config;
-use crate::cli_utils::spawn_chain_runtime;
+use crate::cli_utils::{check_capability, spawn_chain_runtime};
 use crate::conclude::{exit_with_unrecoverable_error, json, Output};
 
 /// The data structure that represents the arguments when invoking the `query transfer denom-trace` CLI command.
@@ -41,6 +42,9 @@ impl Runnable for DenomTraceCmd {
         let chain = spawn_chain_runtime(&config, &self.chain_id)
             .unwrap_or_else(exit_with_unrecoverable_error);
 
+        check_capability(&chain, Capability::DenomTraces)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
         match chain.query_denom_trace(self.hash.clone()) {
             Ok(denom_trace) if json() => Output::success(denom_trace).exit(),
             Ok(denom_trace) => Output::success_msg(format!(
@@ -6,6 +6,8 @@ use ibc_relayer::config::Config;
 mod channel;
 pub(crate) mod client;
 mod connection;
+mod ica;
+mod nft_transfer;
 mod packet;
 mod transfer;
 mod upgrade;
@@ -65,6 +67,13 @@ pub enum TxCmd {
     /// Send a fungible token transfer test transaction (ICS20 MsgTransfer)
     FtTransfer(transfer::TxIcs20MsgTransferCmd),
 
+    /// Send a non-fungible token transfer transaction (ICS721 MsgTransfer)
+    NftTransfer(nft_transfer::TxIcs721MsgTransferCmd),
+
+    /// Manage interchain accounts (ICS27)
+    #[clap(subcommand)]
+    Ica(ica::TxIcaCmd),
+
     /// Relay receive or timeout packets
     PacketRecv(packet::TxPacketRecvCmd),
 
@@ -79,6 +88,8 @@ impl Override<Config> for TxCmd {
     fn override_config(&self, config: Config) -> Result<Config, abscissa_core::FrameworkError> {
         match self {
             Self::FtTransfer(cmd) => cmd.override_config(config),
+            Self::NftTransfer(cmd) => cmd.override_config(config),
+            Self::Ica(cmd) => cmd.override_config(config),
             _ => Ok(config),
         }
     }
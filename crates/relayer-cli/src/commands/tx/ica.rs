@@ -0,0 +1,334 @@
+//! `tx ica` subcommands
+use core::time::Duration;
+use std::path::PathBuf;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{config::Override, FrameworkErrorKind};
+use eyre::eyre;
+
+use ibc_relayer::config::Config;
+use ibc_relayer::ica::{
+    parse_messages, register_interchain_account, send_interchain_tx,
+    RegisterInterchainAccountOptions, SendInterchainTxOptions,
+};
+use ibc_relayer_types::core::ics04_channel::channel::Ordering;
+use ibc_relayer_types::core::ics04_channel::version::Version;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ConnectionId};
+
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::{exit_with_unrecoverable_error, Output};
+use crate::prelude::*;
+
+/// `tx ica` subcommands
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum TxIcaCmd {
+    /// Register an interchain account on a controller chain (MsgRegisterInterchainAccount)
+    Register(TxIcaRegisterCmd),
+
+    /// Send a transaction to be executed by an interchain account (MsgSendTx)
+    Send(TxIcaSendCmd),
+}
+
+impl Override<Config> for TxIcaCmd {
+    fn override_config(&self, config: Config) -> Result<Config, abscissa_core::FrameworkError> {
+        match self {
+            Self::Register(cmd) => override_key_name(config, &cmd.chain_id, &cmd.key_name),
+            Self::Send(cmd) => override_key_name(config, &cmd.chain_id, &cmd.key_name),
+        }
+    }
+}
+
+fn override_key_name(
+    mut config: Config,
+    chain_id: &ChainId,
+    key_name: &Option<String>,
+) -> Result<Config, abscissa_core::FrameworkError> {
+    let chain_config = config.find_chain_mut(chain_id).ok_or_else(|| {
+        FrameworkErrorKind::ComponentError
+            .context(format!("missing configuration for chain '{chain_id}'"))
+    })?;
+
+    if let Some(ref key_name) = key_name {
+        chain_config.set_key_name(key_name.to_string());
+    }
+
+    Ok(config)
+}
+
+/// The data structure that represents the arguments when invoking the `tx ica register` CLI command.
+///
+/// The command has the following format:
+///
+/// `tx ica register --chain <CHAIN_ID> --connection <CONNECTION_ID> [--order <ORDER>] [--channel-version <VERSION>]`
+///
+/// The interchain account is owned by the signing key of the controller chain.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TxIcaRegisterCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the controller chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "connection",
+        visible_alias = "conn",
+        required = true,
+        value_name = "CONNECTION_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the connection to the host chain"
+    )]
+    connection_id: ConnectionId,
+
+    #[clap(
+        long = "order",
+        value_name = "ORDER",
+        help = "The ordering of the interchain account channel, valid options 'unordered' (default) and 'ordered'. \
+                Ordered channels require ibc-go v8 or later on the controller chain",
+        default_value_t
+    )]
+    order: Ordering,
+
+    #[clap(
+        long = "channel-version",
+        visible_alias = "chan-version",
+        value_name = "VERSION",
+        help = "The version of the interchain account channel. \
+                If omitted, the controller chain will use its default metadata"
+    )]
+    version: Option<Version>,
+
+    #[clap(
+        long = "key-name",
+        value_name = "KEY_NAME",
+        help = "Use the given signing key name (default: `key_name` config)"
+    )]
+    key_name: Option<String>,
+}
+
+impl Runnable for TxIcaRegisterCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let opts = RegisterInterchainAccountOptions {
+            connection_id: self.connection_id.clone(),
+            version: self.version.clone().unwrap_or_else(Version::empty),
+            ordering: self.order,
+        };
+
+        match register_interchain_account(&chain, &opts) {
+            Ok(events) => Output::success(events).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+/// The data structure that represents the arguments when invoking the `tx ica send` CLI command.
+///
+/// The command has the following format:
+///
+/// `tx ica send --chain <CHAIN_ID> --connection <CONNECTION_ID> --messages <MESSAGES_FILE>`
+///
+/// The messages file must contain a JSON array of Cosmos messages tagged with their type URL, e.g.
+/// `[{ "@type": "/cosmos.bank.v1beta1.MsgSend", "from_address": "...", "to_address": "...", "amount": [...] }]`,
+/// which will be executed on the host chain by the interchain account of the signing key
+/// of the controller chain. Messages of other types than the common bank, staking,
+/// distribution, gov and IBC transfer ones must be given as their protobuf encoding, e.g.
+/// `{ "@type": "/cosmwasm.wasm.v1.MsgExecuteContract", "value": "<base64 or 0x-prefixed hex>" }`.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TxIcaSendCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the controller chain"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "connection",
+        visible_alias = "conn",
+        required = true,
+        value_name = "CONNECTION_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the connection to the host chain"
+    )]
+    connection_id: ConnectionId,
+
+    #[clap(
+        long = "messages",
+        visible_alias = "msgs",
+        required = true,
+        value_name = "MESSAGES_FILE",
+        help_heading = "REQUIRED",
+        help = "Path to a JSON file containing the array of messages to execute on the host chain"
+    )]
+    messages: PathBuf,
+
+    #[clap(
+        long = "memo",
+        value_name = "MEMO",
+        help = "Optional memo included in the interchain account packet"
+    )]
+    memo: Option<String>,
+
+    #[clap(
+        long = "timeout-seconds",
+        default_value = "600",
+        value_name = "TIMEOUT_SECONDS",
+        help = "Timeout in seconds, relative to the time of the controller chain"
+    )]
+    timeout_seconds: u64,
+
+    #[clap(
+        long = "key-name",
+        value_name = "KEY_NAME",
+        help = "Use the given signing key name (default: `key_name` config)"
+    )]
+    key_name: Option<String>,
+}
+
+impl TxIcaSendCmd {
+    fn validate_options(&self) -> eyre::Result<SendInterchainTxOptions> {
+        let content = std::fs::read_to_string(&self.messages).map_err(|e| {
+            eyre!(
+                "failed to read the messages file '{}': {e}",
+                self.messages.display()
+            )
+        })?;
+
+        let messages = parse_messages(&content)?;
+
+        Ok(SendInterchainTxOptions {
+            connection_id: self.connection_id.clone(),
+            messages,
+            memo: self.memo.clone(),
+            relative_timeout: Duration::from_secs(self.timeout_seconds),
+        })
+    }
+}
+
+impl Runnable for TxIcaSendCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let opts = match self.validate_options() {
+            Err(err) => Output::error(err).exit(),
+            Ok(result) => result,
+        };
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        match send_interchain_tx(&chain, &opts) {
+            Ok(events) => Output::success(events).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TxIcaRegisterCmd, TxIcaSendCmd};
+
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics04_channel::channel::Ordering;
+    use ibc_relayer_types::core::ics04_channel::version::Version;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ConnectionId};
+
+    #[test]
+    fn test_ica_register() {
+        assert_eq!(
+            TxIcaRegisterCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                connection_id: ConnectionId::from_str("connection-0").unwrap(),
+                order: Ordering::Unordered,
+                version: None,
+                key_name: None,
+            },
+            TxIcaRegisterCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--connection",
+                "connection-0"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_ica_register_ordered_version() {
+        assert_eq!(
+            TxIcaRegisterCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                connection_id: ConnectionId::from_str("connection-0").unwrap(),
+                order: Ordering::Ordered,
+                version: Some(Version::new("ics27-1".to_owned())),
+                key_name: Some("key_name".to_owned()),
+            },
+            TxIcaRegisterCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--conn",
+                "connection-0",
+                "--order",
+                "ordered",
+                "--chan-version",
+                "ics27-1",
+                "--key-name",
+                "key_name"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_ica_register_no_connection() {
+        assert!(TxIcaRegisterCmd::try_parse_from(["test", "--chain", "chain_id"]).is_err())
+    }
+
+    #[test]
+    fn test_ica_send() {
+        assert_eq!(
+            TxIcaSendCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                connection_id: ConnectionId::from_str("connection-0").unwrap(),
+                messages: PathBuf::from("msgs.json"),
+                memo: None,
+                timeout_seconds: 600,
+                key_name: None,
+            },
+            TxIcaSendCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--connection",
+                "connection-0",
+                "--messages",
+                "msgs.json"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_ica_send_no_messages() {
+        assert!(TxIcaSendCmd::try_parse_from([
+            "test",
+            "--chain",
+            "chain_id",
+            "--connection",
+            "connection-0"
+        ])
+        .is_err())
+    }
+}
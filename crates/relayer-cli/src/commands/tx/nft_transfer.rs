@@ -0,0 +1,331 @@
+use core::time::Duration;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{config::Override, FrameworkErrorKind};
+use eyre::eyre;
+
+use ibc_relayer::{
+    chain::handle::ChainHandle,
+    config::Config,
+    event::IbcEventWithHeight,
+    nft_transfer::{build_and_send_nft_transfer_messages, NftTransferOptions},
+};
+use ibc_relayer_types::{
+    applications::ics721_nft::TokenId,
+    core::ics24_host::identifier::{ChainId, ChannelId, PortId},
+};
+
+use crate::cli_utils::{check_can_send_on_channel, ChainHandlePair};
+use crate::conclude::{exit_with_unrecoverable_error, Output};
+use crate::error::Error;
+use crate::prelude::*;
+
+/// The data structure that represents the arguments when invoking the `tx nft-transfer` CLI command.
+///
+/// The command has the following format:
+///
+/// `tx nft-transfer --dst-chain <DST_CHAIN_ID> --src-chain <SRC_CHAIN_ID> --src-port <SRC_PORT_ID>
+///  --src-channel <SRC_CHANNEL_ID> --class-id <CLASS_ID> --token-id <TOKEN_ID>...`
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TxIcs721MsgTransferCmd {
+    #[clap(
+        long = "dst-chain",
+        required = true,
+        value_name = "DST_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the destination chain"
+    )]
+    dst_chain_id: ChainId,
+
+    #[clap(
+        long = "src-chain",
+        required = true,
+        value_name = "SRC_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source chain"
+    )]
+    src_chain_id: ChainId,
+
+    #[clap(
+        long = "src-port",
+        required = true,
+        value_name = "SRC_PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source port (e.g. `nft-transfer`)"
+    )]
+    src_port_id: PortId,
+
+    #[clap(
+        long = "src-channel",
+        visible_alias = "src-chan",
+        required = true,
+        value_name = "SRC_CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the source channel"
+    )]
+    src_channel_id: ChannelId,
+
+    #[clap(
+        long = "class-id",
+        required = true,
+        value_name = "CLASS_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the class of the tokens to send, \
+                either as its full trace path or as its `ibc/{hash}` identifier"
+    )]
+    class_id: String,
+
+    #[clap(
+        long = "token-id",
+        required = true,
+        value_name = "TOKEN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of a token to send. Can be repeated to send several tokens of the same class"
+    )]
+    token_ids: Vec<TokenId>,
+
+    #[clap(
+        long = "timeout-height-offset",
+        default_value = "0",
+        value_name = "TIMEOUT_HEIGHT_OFFSET",
+        help = "Timeout in number of blocks since current"
+    )]
+    timeout_height_offset: u64,
+
+    #[clap(
+        long = "timeout-seconds",
+        default_value = "0",
+        value_name = "TIMEOUT_SECONDS",
+        help = "Timeout in seconds since current"
+    )]
+    timeout_seconds: u64,
+
+    #[clap(
+        long = "receiver",
+        value_name = "RECEIVER",
+        help = "The account address on the destination chain which will receive the tokens. If omitted, the relayer's wallet on the destination chain will be used"
+    )]
+    receiver: Option<String>,
+
+    #[clap(
+        long = "key-name",
+        value_name = "KEY_NAME",
+        help = "Use the given signing key name (default: `key_name` config)"
+    )]
+    key_name: Option<String>,
+
+    #[clap(
+        long = "memo",
+        value_name = "MEMO",
+        help = "Optional memo included in the transfer"
+    )]
+    memo: Option<String>,
+}
+
+impl Override<Config> for TxIcs721MsgTransferCmd {
+    fn override_config(&self, mut config: Config) -> Result<Config, abscissa_core::FrameworkError> {
+        let src_chain_config = config.find_chain_mut(&self.src_chain_id).ok_or_else(|| {
+            FrameworkErrorKind::ComponentError.context(format!(
+                "missing configuration for source chain '{}'",
+                self.src_chain_id
+            ))
+        })?;
+
+        if let Some(ref key_name) = self.key_name {
+            src_chain_config.set_key_name(key_name.to_string());
+        }
+
+        Ok(config)
+    }
+}
+
+impl TxIcs721MsgTransferCmd {
+    fn validate_options(&self, config: &Config) -> eyre::Result<NftTransferOptions> {
+        config.find_chain(&self.src_chain_id).ok_or_else(|| {
+            eyre!(
+                "missing configuration for source chain '{}'",
+                self.src_chain_id
+            )
+        })?;
+
+        config.find_chain(&self.dst_chain_id).ok_or_else(|| {
+            eyre!(
+                "missing configuration for destination chain '{}'",
+                self.dst_chain_id
+            )
+        })?;
+
+        let opts = NftTransferOptions {
+            src_port_id: self.src_port_id.clone(),
+            src_channel_id: self.src_channel_id.clone(),
+            class_id: self.class_id.clone(),
+            token_ids: self.token_ids.iter().map(ToString::to_string).collect(),
+            receiver: self.receiver.clone(),
+            timeout_height_offset: self.timeout_height_offset,
+            timeout_duration: Duration::from_secs(self.timeout_seconds),
+            memo: self.memo.clone(),
+        };
+
+        Ok(opts)
+    }
+}
+
+impl Runnable for TxIcs721MsgTransferCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let opts = match self.validate_options(&config) {
+            Err(err) => Output::error(err).exit(),
+            Ok(result) => result,
+        };
+
+        let chains = ChainHandlePair::spawn(&config, &self.src_chain_id, &self.dst_chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        check_can_send_on_channel(
+            &chains.src,
+            &opts.src_channel_id,
+            &opts.src_port_id,
+            &chains.dst.id(),
+        )
+        .unwrap_or_else(exit_with_unrecoverable_error);
+
+        // Checks pass, build and send the tx
+        let res: Result<Vec<IbcEventWithHeight>, Error> =
+            build_and_send_nft_transfer_messages(&chains.src, &chains.dst, &opts)
+                .map_err(Error::transfer);
+
+        match res {
+            Ok(ev) => Output::success(ev).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_relayer_types::{
+        applications::ics721_nft::TokenId,
+        core::ics24_host::identifier::{ChainId, ChannelId, PortId},
+    };
+
+    use super::TxIcs721MsgTransferCmd;
+
+    use abscissa_core::clap::Parser;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_nft_transfer_required_only() {
+        assert_eq!(
+            TxIcs721MsgTransferCmd {
+                dst_chain_id: ChainId::from_string("chain_receiver"),
+                src_chain_id: ChainId::from_string("chain_sender"),
+                src_port_id: PortId::from_str("nft-transfer").unwrap(),
+                src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
+                class_id: "kitties".to_owned(),
+                token_ids: vec![TokenId::from_str("kitty-1").unwrap()],
+                timeout_height_offset: 0,
+                timeout_seconds: 0,
+                receiver: None,
+                key_name: None,
+                memo: None,
+            },
+            TxIcs721MsgTransferCmd::parse_from([
+                "test",
+                "--dst-chain",
+                "chain_receiver",
+                "--src-chain",
+                "chain_sender",
+                "--src-port",
+                "nft-transfer",
+                "--src-chan",
+                "channel_sender",
+                "--class-id",
+                "kitties",
+                "--token-id",
+                "kitty-1"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_nft_transfer_several_tokens() {
+        assert_eq!(
+            TxIcs721MsgTransferCmd {
+                dst_chain_id: ChainId::from_string("chain_receiver"),
+                src_chain_id: ChainId::from_string("chain_sender"),
+                src_port_id: PortId::from_str("nft-transfer").unwrap(),
+                src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
+                class_id: "nft-transfer/channel-0/kitties".to_owned(),
+                token_ids: vec![
+                    TokenId::from_str("kitty-1").unwrap(),
+                    TokenId::from_str("kitty-2").unwrap()
+                ],
+                timeout_height_offset: 0,
+                timeout_seconds: 600,
+                receiver: Some("receiver_addr".to_owned()),
+                key_name: Some("key_name".to_owned()),
+                memo: None,
+            },
+            TxIcs721MsgTransferCmd::parse_from([
+                "test",
+                "--dst-chain",
+                "chain_receiver",
+                "--src-chain",
+                "chain_sender",
+                "--src-port",
+                "nft-transfer",
+                "--src-channel",
+                "channel_sender",
+                "--class-id",
+                "nft-transfer/channel-0/kitties",
+                "--token-id",
+                "kitty-1",
+                "--token-id",
+                "kitty-2",
+                "--timeout-seconds",
+                "600",
+                "--receiver",
+                "receiver_addr",
+                "--key-name",
+                "key_name"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_nft_transfer_no_token_id() {
+        assert!(TxIcs721MsgTransferCmd::try_parse_from([
+            "test",
+            "--dst-chain",
+            "chain_receiver",
+            "--src-chain",
+            "chain_sender",
+            "--src-port",
+            "nft-transfer",
+            "--src-channel",
+            "channel_sender",
+            "--class-id",
+            "kitties"
+        ])
+        .is_err())
+    }
+
+    #[test]
+    fn test_nft_transfer_no_class_id() {
+        assert!(TxIcs721MsgTransferCmd::try_parse_from([
+            "test",
+            "--dst-chain",
+            "chain_receiver",
+            "--src-chain",
+            "chain_sender",
+            "--src-port",
+            "nft-transfer",
+            "--src-channel",
+            "channel_sender",
+            "--token-id",
+            "kitty-1"
+        ])
+        .is_err())
+    }
+}
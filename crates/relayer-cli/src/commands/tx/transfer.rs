@@ -1,4 +1,6 @@
 use core::time::Duration;
+use std::fs;
+use std::path::PathBuf;
 
 use abscissa_core::clap::Parser;
 use abscissa_core::{config::Override, FrameworkErrorKind};
@@ -8,10 +10,13 @@ use ibc_relayer::{
     chain::handle::ChainHandle,
     config::Config,
     event::IbcEventWithHeight,
-    transfer::{build_and_send_transfer_messages, TransferOptions},
+    transfer::{
+        build_and_send_shielded_transfer, build_and_send_transfer_messages,
+        ShieldedTransferOptions, TransferOptions,
+    },
 };
 use ibc_relayer_types::{
-    applications::transfer::Amount,
+    applications::transfer::{msgs::transfer::Forwarding, Amount, Coin, TracePrefix},
     core::ics24_host::identifier::{ChainId, ChannelId, PortId},
 };
 
@@ -64,9 +69,11 @@ pub struct TxIcs20MsgTransferCmd {
         required = true,
         value_name = "AMOUNT",
         help_heading = "REQUIRED",
-        help = "Amount of coins (samoleans, by default) to send (e.g. `100000`)"
+        help = "Amount of coins (samoleans, by default) to send (e.g. `100000`). \
+                Can be repeated along with `--denom` to send several tokens at once \
+                over an `ics20-2` channel"
     )]
-    amount: Amount,
+    amount: Vec<Amount>,
 
     #[clap(
         long = "timeout-height-offset",
@@ -94,10 +101,11 @@ pub struct TxIcs20MsgTransferCmd {
     #[clap(
         long = "denom",
         value_name = "DENOM",
-        help = "Denomination of the coins to send",
+        help = "Denomination of the coins to send. \
+                Must be repeated as many times as `--amount`",
         default_value = "samoleans"
     )]
-    denom: String,
+    denom: Vec<String>,
 
     #[clap(
         long = "number-msgs",
@@ -119,6 +127,47 @@ pub struct TxIcs20MsgTransferCmd {
         help = "Optional memo included in the transfer"
     )]
     memo: Option<String>,
+
+    #[clap(
+        long = "forwarding",
+        value_name = "PORT_ID/CHANNEL_ID",
+        help = "Hop through which the tokens must be forwarded once received by the destination chain. \
+                Can be repeated to forward the tokens through several chains, \
+                and requires an `ics20-2` channel"
+    )]
+    forwarding: Vec<TracePrefix>,
+
+    #[clap(
+        long = "unwind",
+        help = "Send the tokens back to their native chain before forwarding them, \
+                which requires an `ics20-2` channel"
+    )]
+    unwind: bool,
+
+    #[clap(
+        long = "shielded-source-file",
+        value_name = "SPENDING_KEY_FILE",
+        help = "Namada only: path to the file holding the MASP spending key whose notes are unshielded and sent, \
+                or /dev/stdin to read the key from stdin"
+    )]
+    shielded_source_file: Option<PathBuf>,
+
+    #[clap(
+        long = "shielded-birthday",
+        value_name = "HEIGHT",
+        requires = "shielded-source-file",
+        help = "Namada only: a block height preceding the creation of the MASP spending key, \
+                from which its notes are scanned (default: genesis)"
+    )]
+    shielded_birthday: Option<u64>,
+
+    #[clap(
+        long = "shielded-receiver",
+        value_name = "PAYMENT_ADDRESS",
+        conflicts_with_all = &["receiver", "memo"],
+        help = "Namada only: the MASP payment address on the destination chain to which the tokens are shielded"
+    )]
+    shielded_receiver: Option<String>,
 }
 
 impl Override<Config> for TxIcs20MsgTransferCmd {
@@ -139,6 +188,28 @@ impl Override<Config> for TxIcs20MsgTransferCmd {
 }
 
 impl TxIcs20MsgTransferCmd {
+    fn shielded_options(&self) -> eyre::Result<Option<ShieldedTransferOptions>> {
+        if self.shielded_source_file.is_none() && self.shielded_receiver.is_none() {
+            return Ok(None);
+        }
+
+        let source = self
+            .shielded_source_file
+            .as_ref()
+            .map(|file| {
+                fs::read_to_string(file)
+                    .map(|key| key.trim().to_owned())
+                    .map_err(|_| eyre!("error reading the spending key file"))
+            })
+            .transpose()?;
+
+        Ok(Some(ShieldedTransferOptions {
+            source,
+            birthday: self.shielded_birthday,
+            target: self.shielded_receiver.clone(),
+        }))
+    }
+
     fn validate_options(&self, config: &Config) -> eyre::Result<TransferOptions> {
         config.find_chain(&self.src_chain_id).ok_or_else(|| {
             eyre!(
@@ -154,7 +225,29 @@ impl TxIcs20MsgTransferCmd {
             )
         })?;
 
-        let denom = self.denom.clone();
+        if self.amount.len() != self.denom.len() {
+            return Err(eyre!(
+                "got {} amount(s) for {} denomination(s), each `--amount` must be paired with a `--denom`",
+                self.amount.len(),
+                self.denom.len()
+            ));
+        }
+
+        let tokens = self
+            .denom
+            .iter()
+            .zip(self.amount.iter())
+            .map(|(denom, amount)| Coin::new(denom.clone(), *amount))
+            .collect();
+
+        let forwarding = if self.unwind || !self.forwarding.is_empty() {
+            Some(Forwarding {
+                unwind: self.unwind,
+                hops: self.forwarding.clone(),
+            })
+        } else {
+            None
+        };
 
         let number_msgs = self.number_msgs.unwrap_or(1);
         if number_msgs == 0 {
@@ -164,13 +257,13 @@ impl TxIcs20MsgTransferCmd {
         let opts = TransferOptions {
             src_port_id: self.src_port_id.clone(),
             src_channel_id: self.src_channel_id.clone(),
-            amount: self.amount,
-            denom,
+            tokens,
             receiver: self.receiver.clone(),
             timeout_height_offset: self.timeout_height_offset,
             timeout_duration: Duration::from_secs(self.timeout_seconds),
             number_msgs,
             memo: self.memo.clone(),
+            forwarding,
         };
 
         Ok(opts)
@@ -186,6 +279,11 @@ impl Runnable for TxIcs20MsgTransferCmd {
             Ok(result) => result,
         };
 
+        let shielded = match self.shielded_options() {
+            Err(err) => Output::error(err).exit(),
+            Ok(result) => result,
+        };
+
         let chains = ChainHandlePair::spawn(&config, &self.src_chain_id, &self.dst_chain_id)
             .unwrap_or_else(exit_with_unrecoverable_error);
 
@@ -198,9 +296,13 @@ impl Runnable for TxIcs20MsgTransferCmd {
         .unwrap_or_else(exit_with_unrecoverable_error);
 
         // Checks pass, build and send the tx
-        let res: Result<Vec<IbcEventWithHeight>, Error> =
-            build_and_send_transfer_messages(&chains.src, &chains.dst, &opts)
-                .map_err(Error::transfer);
+        let res: Result<Vec<IbcEventWithHeight>, Error> = match shielded {
+            Some(shielded) => {
+                build_and_send_shielded_transfer(&chains.src, &chains.dst, &opts, &shielded)
+            }
+            None => build_and_send_transfer_messages(&chains.src, &chains.dst, &opts),
+        }
+        .map_err(Error::transfer);
 
         match res {
             Ok(ev) => Output::success(ev).exit(),
@@ -212,13 +314,14 @@ impl Runnable for TxIcs20MsgTransferCmd {
 #[cfg(test)]
 mod tests {
     use ibc_relayer_types::{
-        applications::transfer::Amount,
+        applications::transfer::{Amount, TracePrefix},
         core::ics24_host::identifier::{ChainId, ChannelId, PortId},
     };
 
     use super::TxIcs20MsgTransferCmd;
 
     use abscissa_core::clap::Parser;
+    use std::path::PathBuf;
     use std::str::FromStr;
 
     #[test]
@@ -229,14 +332,19 @@ mod tests {
                 src_chain_id: ChainId::from_string("chain_sender"),
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
-                amount: Amount::from(42u64),
+                amount: vec![Amount::from(42u64)],
                 timeout_height_offset: 0,
                 timeout_seconds: 0,
                 receiver: None,
-                denom: "samoleans".to_owned(),
+                denom: vec!["samoleans".to_owned()],
                 number_msgs: None,
                 key_name: None,
                 memo: None,
+                forwarding: vec![],
+                unwind: false,
+                shielded_source_file: None,
+                shielded_birthday: None,
+                shielded_receiver: None,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -262,14 +370,19 @@ mod tests {
                 src_chain_id: ChainId::from_string("chain_sender"),
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
-                amount: Amount::from(42u64),
+                amount: vec![Amount::from(42u64)],
                 timeout_height_offset: 0,
                 timeout_seconds: 0,
                 receiver: None,
-                denom: "samoleans".to_owned(),
+                denom: vec!["samoleans".to_owned()],
                 number_msgs: None,
                 key_name: None,
                 memo: None,
+                forwarding: vec![],
+                unwind: false,
+                shielded_source_file: None,
+                shielded_birthday: None,
+                shielded_receiver: None,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -295,14 +408,19 @@ mod tests {
                 src_chain_id: ChainId::from_string("chain_sender"),
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
-                amount: Amount::from(42u64),
+                amount: vec![Amount::from(42u64)],
                 timeout_height_offset: 0,
                 timeout_seconds: 0,
                 receiver: None,
-                denom: "my_denom".to_owned(),
+                denom: vec!["my_denom".to_owned()],
                 number_msgs: None,
                 key_name: None,
                 memo: None,
+                forwarding: vec![],
+                unwind: false,
+                shielded_source_file: None,
+                shielded_birthday: None,
+                shielded_receiver: None,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -330,14 +448,19 @@ mod tests {
                 src_chain_id: ChainId::from_string("chain_sender"),
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
-                amount: Amount::from(42u64),
+                amount: vec![Amount::from(42u64)],
                 timeout_height_offset: 0,
                 timeout_seconds: 0,
                 receiver: None,
-                denom: "samoleans".to_owned(),
+                denom: vec!["samoleans".to_owned()],
                 number_msgs: None,
                 key_name: Some("key_name".to_owned()),
                 memo: None,
+                forwarding: vec![],
+                unwind: false,
+                shielded_source_file: None,
+                shielded_birthday: None,
+                shielded_receiver: None,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -365,14 +488,19 @@ mod tests {
                 src_chain_id: ChainId::from_string("chain_sender"),
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
-                amount: Amount::from(42u64),
+                amount: vec![Amount::from(42u64)],
                 timeout_height_offset: 0,
                 timeout_seconds: 0,
                 receiver: None,
-                denom: "samoleans".to_owned(),
+                denom: vec!["samoleans".to_owned()],
                 number_msgs: Some(21),
                 key_name: None,
                 memo: None,
+                forwarding: vec![],
+                unwind: false,
+                shielded_source_file: None,
+                shielded_birthday: None,
+                shielded_receiver: None,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -400,14 +528,19 @@ mod tests {
                 src_chain_id: ChainId::from_string("chain_sender"),
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
-                amount: Amount::from(42u64),
+                amount: vec![Amount::from(42u64)],
                 timeout_height_offset: 0,
                 timeout_seconds: 0,
                 receiver: Some("receiver_addr".to_owned()),
-                denom: "samoleans".to_owned(),
+                denom: vec!["samoleans".to_owned()],
                 number_msgs: None,
                 key_name: None,
                 memo: None,
+                forwarding: vec![],
+                unwind: false,
+                shielded_source_file: None,
+                shielded_birthday: None,
+                shielded_receiver: None,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -435,14 +568,19 @@ mod tests {
                 src_chain_id: ChainId::from_string("chain_sender"),
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
-                amount: Amount::from(42u64),
+                amount: vec![Amount::from(42u64)],
                 timeout_height_offset: 21,
                 timeout_seconds: 0,
                 receiver: None,
-                denom: "samoleans".to_owned(),
+                denom: vec!["samoleans".to_owned()],
                 number_msgs: None,
                 key_name: None,
                 memo: None,
+                forwarding: vec![],
+                unwind: false,
+                shielded_source_file: None,
+                shielded_birthday: None,
+                shielded_receiver: None,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -470,14 +608,19 @@ mod tests {
                 src_chain_id: ChainId::from_string("chain_sender"),
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
-                amount: Amount::from(42u64),
+                amount: vec![Amount::from(42u64)],
                 timeout_height_offset: 0,
                 timeout_seconds: 21,
                 receiver: None,
-                denom: "samoleans".to_owned(),
+                denom: vec!["samoleans".to_owned()],
                 number_msgs: None,
                 key_name: None,
                 memo: None,
+                forwarding: vec![],
+                unwind: false,
+                shielded_source_file: None,
+                shielded_birthday: None,
+                shielded_receiver: None,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -505,14 +648,19 @@ mod tests {
                 src_chain_id: ChainId::from_string("chain_sender"),
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
-                amount: Amount::from(42u64),
+                amount: vec![Amount::from(42u64)],
                 timeout_height_offset: 0,
                 timeout_seconds: 0,
                 receiver: None,
-                denom: "samoleans".to_owned(),
+                denom: vec!["samoleans".to_owned()],
                 number_msgs: None,
                 key_name: None,
                 memo: Some("test memo".to_owned()),
+                forwarding: vec![],
+                unwind: false,
+                shielded_source_file: None,
+                shielded_birthday: None,
+                shielded_receiver: None,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -532,6 +680,164 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_ft_transfer_multi_denom_forwarding() {
+        assert_eq!(
+            TxIcs20MsgTransferCmd {
+                dst_chain_id: ChainId::from_string("chain_receiver"),
+                src_chain_id: ChainId::from_string("chain_sender"),
+                src_port_id: PortId::from_str("port_sender").unwrap(),
+                src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
+                amount: vec![Amount::from(42u64), Amount::from(21u64)],
+                timeout_height_offset: 0,
+                timeout_seconds: 0,
+                receiver: None,
+                denom: vec!["samoleans".to_owned(), "stake".to_owned()],
+                number_msgs: None,
+                key_name: None,
+                memo: None,
+                forwarding: vec![
+                    TracePrefix::from_str("transfer/channel-1").unwrap(),
+                    TracePrefix::from_str("transfer/channel-2").unwrap(),
+                ],
+                unwind: true,
+                shielded_source_file: None,
+                shielded_birthday: None,
+                shielded_receiver: None,
+            },
+            TxIcs20MsgTransferCmd::parse_from([
+                "test",
+                "--dst-chain",
+                "chain_receiver",
+                "--src-chain",
+                "chain_sender",
+                "--src-port",
+                "port_sender",
+                "--src-channel",
+                "channel_sender",
+                "--amount",
+                "42",
+                "--denom",
+                "samoleans",
+                "--amount",
+                "21",
+                "--denom",
+                "stake",
+                "--forwarding",
+                "transfer/channel-1",
+                "--forwarding",
+                "transfer/channel-2",
+                "--unwind",
+            ])
+        )
+    }
+
+    #[test]
+    fn test_ft_transfer_shielded() {
+        assert_eq!(
+            TxIcs20MsgTransferCmd {
+                dst_chain_id: ChainId::from_string("chain_receiver"),
+                src_chain_id: ChainId::from_string("chain_sender"),
+                src_port_id: PortId::from_str("port_sender").unwrap(),
+                src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
+                amount: vec![Amount::from(42u64)],
+                timeout_height_offset: 0,
+                timeout_seconds: 0,
+                receiver: None,
+                denom: vec!["samoleans".to_owned()],
+                number_msgs: None,
+                key_name: None,
+                memo: None,
+                forwarding: vec![],
+                unwind: false,
+                shielded_source_file: Some(PathBuf::from("spending_key_file")),
+                shielded_birthday: Some(1000),
+                shielded_receiver: Some("payment_address".to_owned()),
+            },
+            TxIcs20MsgTransferCmd::parse_from([
+                "test",
+                "--dst-chain",
+                "chain_receiver",
+                "--src-chain",
+                "chain_sender",
+                "--src-port",
+                "port_sender",
+                "--src-channel",
+                "channel_sender",
+                "--amount",
+                "42",
+                "--shielded-source-file",
+                "spending_key_file",
+                "--shielded-birthday",
+                "1000",
+                "--shielded-receiver",
+                "payment_address",
+            ])
+        )
+    }
+
+    #[test]
+    fn test_ft_transfer_shielded_receiver_with_receiver() {
+        assert!(TxIcs20MsgTransferCmd::try_parse_from([
+            "test",
+            "--dst-chain",
+            "chain_receiver",
+            "--src-chain",
+            "chain_sender",
+            "--src-port",
+            "port_sender",
+            "--src-channel",
+            "channel_sender",
+            "--amount",
+            "42",
+            "--receiver",
+            "receiver_addr",
+            "--shielded-receiver",
+            "payment_address"
+        ])
+        .is_err())
+    }
+
+    #[test]
+    fn test_ft_transfer_shielded_birthday_without_source() {
+        assert!(TxIcs20MsgTransferCmd::try_parse_from([
+            "test",
+            "--dst-chain",
+            "chain_receiver",
+            "--src-chain",
+            "chain_sender",
+            "--src-port",
+            "port_sender",
+            "--src-channel",
+            "channel_sender",
+            "--amount",
+            "42",
+            "--shielded-birthday",
+            "1000"
+        ])
+        .is_err())
+    }
+
+    #[test]
+    fn test_ft_transfer_invalid_forwarding() {
+        assert!(TxIcs20MsgTransferCmd::try_parse_from([
+            "test",
+            "--dst-chain",
+            "chain_receiver",
+            "--src-chain",
+            "chain_sender",
+            "--src-port",
+            "port_sender",
+            "--src-channel",
+            "channel_sender",
+            "--amount",
+            "42",
+            "--forwarding",
+            "transfer"
+        ])
+        .is_err())
+    }
+
     #[test]
     fn test_ft_transfer_no_amount() {
         assert!(TxIcs20MsgTransferCmd::try_parse_from([
@@ -7,6 +7,8 @@ use crossbeam_channel as channel;
 use ibc_relayer::supervisor::dump_state::SupervisorState;
 use ibc_relayer::{
     config::ChainConfig,
+    fee_accounting::FeeReport,
+    price::Prices,
     rest::{
         request::{reply_channel, ReplySender, Request, VersionInfo},
         RestApiError,
@@ -76,6 +78,34 @@ pub fn trigger_clear_packets(
     })
 }
 
+pub fn prices(sender: &channel::Sender<Request>) -> Result<Prices, RestApiError> {
+    submit_request(sender, |reply_to| Request::GetPrices { reply_to })
+}
+
+/// Submit a request to update the prices used for evaluating
+/// the profitability of relaying incentivized packets.
+pub fn update_prices(
+    sender: &channel::Sender<Request>,
+    prices: Prices,
+) -> Result<(), RestApiError> {
+    submit_request(sender, |reply_to| Request::UpdatePrices {
+        prices,
+        reply_to,
+    })
+}
+
+/// Submit a request for the report of the ICS-29 fees escrowed and distributed,
+/// either on all chains or only on the chain with the specified `chain_id`.
+pub fn fee_report(
+    sender: &channel::Sender<Request>,
+    chain_id: Option<ChainId>,
+) -> Result<FeeReport, RestApiError> {
+    submit_request(sender, |reply_to| Request::GetFeeReport {
+        chain_id,
+        reply_to,
+    })
+}
+
 pub fn assemble_version_info(sender: &channel::Sender<Request>) -> Vec<VersionInfo> {
     // Fetch the relayer library version
     let lib_version = submit_request(sender, |reply_to| Request::Version { reply_to })
@@ -14,10 +14,12 @@ use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use serde::{Deserialize, Serialize};
 use tokio::task::JoinHandle;
 
+use ibc_relayer::price::Prices;
 use ibc_relayer::rest::{request::Request, RestApiError};
 
 use crate::handle::{
-    all_chain_ids, assemble_version_info, chain_config, supervisor_state, trigger_clear_packets,
+    all_chain_ids, assemble_version_info, chain_config, fee_report, prices, supervisor_state,
+    trigger_clear_packets, update_prices,
 };
 
 pub type BoxError = Box<dyn Error + Send + Sync>;
@@ -84,6 +86,32 @@ async fn clear_packets(
     Json(JsonResult::from(result))
 }
 
+async fn get_prices(Extension(sender): Extension<Sender>) -> impl IntoResponse {
+    let result = prices(&sender);
+    Json(JsonResult::from(result))
+}
+
+async fn post_prices(
+    Extension(sender): Extension<Sender>,
+    Json(prices): Json<Prices>,
+) -> impl IntoResponse {
+    let result = update_prices(&sender, prices);
+    Json(JsonResult::from(result))
+}
+
+#[derive(Debug, Deserialize)]
+struct FeeReportParams {
+    chain: Option<ChainId>,
+}
+
+async fn get_fees(
+    Extension(sender): Extension<Sender>,
+    Query(params): Query<FeeReportParams>,
+) -> impl IntoResponse {
+    let result = fee_report(&sender, params.chain);
+    Json(JsonResult::from(result))
+}
+
 type Sender = channel::Sender<Request>;
 
 async fn run(addr: SocketAddr, sender: Sender) {
@@ -93,6 +121,8 @@ async fn run(addr: SocketAddr, sender: Sender) {
         .route("/chain/:id", get(get_chain))
         .route("/state", get(get_state))
         .route("/clear_packets", post(clear_packets))
+        .route("/prices", get(get_prices).post(post_prices))
+        .route("/fees", get(get_fees))
         .layer(Extension(sender));
 
     Server::bind(&addr)
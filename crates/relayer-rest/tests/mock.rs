@@ -4,6 +4,8 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use ibc_relayer::{
     config::ChainConfig,
+    fee_accounting::FeeReport,
+    price::Prices,
     rest::request::{Request, VersionInfo},
     supervisor::dump_state::SupervisorState,
 };
@@ -146,3 +148,36 @@ async fn state() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn get_prices() {
+    let prices = Prices::from([("uatom".to_string(), 0.5)]);
+    let result: JsonResult<_, ()> = JsonResult::Success(prices.clone());
+
+    run_test(19105, "/prices", result, |req| match req {
+        Request::GetPrices { reply_to } => {
+            reply_to.send(Ok(prices)).unwrap();
+            TestResult::Success
+        }
+        req => TestResult::WrongRequest(req),
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn get_fee_report() {
+    let report = FeeReport::default();
+    let result: JsonResult<_, ()> = JsonResult::Success(report.clone());
+
+    run_test(19106, "/fees", result, |req| match req {
+        Request::GetFeeReport {
+            chain_id: None,
+            reply_to,
+        } => {
+            reply_to.send(Ok(report)).unwrap();
+            TestResult::Success
+        }
+        req => TestResult::WrongRequest(req),
+    })
+    .await;
+}
@@ -0,0 +1,178 @@
+use std::fmt::{Display, Error as FmtError, Formatter};
+use std::str::FromStr;
+
+use derive_more::Display;
+use ibc_proto::ibc::applications::nft_transfer::v1::ClassTrace as RawClassTrace;
+use serde::{Deserialize, Serialize};
+
+use super::error::Error;
+use crate::applications::transfer::{TracePath, TracePrefix};
+use crate::serializers::serde_string;
+
+/// Base class identifier type, e.g. `wasm.stars1...` or `kitties`.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize, Display)]
+#[serde(transparent)]
+pub struct BaseClassId(String);
+
+impl FromStr for BaseClassId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            Err(Error::empty_base_class_id())
+        } else {
+            Ok(BaseClassId(s.to_owned()))
+        }
+    }
+}
+
+/// Identifier of a non-fungible token within its class.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize, Display)]
+#[serde(transparent)]
+pub struct TokenId(String);
+
+impl FromStr for TokenId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            Err(Error::empty_token_id())
+        } else {
+            Ok(TokenId(s.to_owned()))
+        }
+    }
+}
+
+/// A type that contains the base class identifier for ICS721 and the source tracing information path.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct PrefixedClassId {
+    /// A series of `{port-id}/{channel-id}`s for tracing the source of the class.
+    #[serde(with = "serde_string")]
+    trace_path: TracePath,
+    /// Base class identifier of the relayed non-fungible tokens.
+    base_class_id: BaseClassId,
+}
+
+impl PrefixedClassId {
+    /// Removes the specified prefix from the trace path if there is a match, otherwise does nothing.
+    pub fn remove_trace_prefix(&mut self, prefix: &TracePrefix) {
+        self.trace_path.remove_prefix(prefix)
+    }
+
+    /// Adds the specified prefix to the trace path.
+    pub fn add_trace_prefix(&mut self, prefix: TracePrefix) {
+        self.trace_path.add_prefix(prefix)
+    }
+
+    pub fn trace_path(&self) -> &TracePath {
+        &self.trace_path
+    }
+
+    pub fn base_class_id(&self) -> &BaseClassId {
+        &self.base_class_id
+    }
+}
+
+/// Class identifiers commonly contain slashes, e.g. `wasm.stars1.../collection`, so the
+/// `{port-id}/{channel-id}` pairs are only consumed for as long as the channel identifier
+/// is of the form `channel-{N}`, the remainder of the path being the base class identifier.
+impl FromStr for PrefixedClassId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (trace_path, base_class_id) = TracePath::split_path(s).map_err(Error::invalid_trace)?;
+
+        Ok(Self {
+            trace_path,
+            base_class_id: BaseClassId::from_str(base_class_id)?,
+        })
+    }
+}
+
+impl TryFrom<RawClassTrace> for PrefixedClassId {
+    type Error = Error;
+
+    fn try_from(value: RawClassTrace) -> Result<Self, Self::Error> {
+        let base_class_id = BaseClassId::from_str(&value.base_class_id)?;
+        let trace_path = TracePath::from_str(&value.path).map_err(Error::invalid_trace)?;
+        Ok(Self {
+            trace_path,
+            base_class_id,
+        })
+    }
+}
+
+impl From<PrefixedClassId> for RawClassTrace {
+    fn from(value: PrefixedClassId) -> Self {
+        Self {
+            path: value.trace_path.to_string(),
+            base_class_id: value.base_class_id.to_string(),
+        }
+    }
+}
+
+impl From<BaseClassId> for PrefixedClassId {
+    fn from(class_id: BaseClassId) -> Self {
+        Self {
+            trace_path: Default::default(),
+            base_class_id: class_id,
+        }
+    }
+}
+
+impl Display for PrefixedClassId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        if self.trace_path.is_empty() {
+            write!(f, "{}", self.base_class_id)
+        } else {
+            write!(f, "{}/{}", self.trace_path, self.base_class_id)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_class_id_validation() {
+        assert!(BaseClassId::from_str("").is_err(), "empty base class id");
+        assert!(PrefixedClassId::from_str("").is_err(), "empty class id");
+        assert!(
+            PrefixedClassId::from_str("nft-transfer/channel-0/").is_err(),
+            "empty base class id with trace"
+        );
+        assert!(
+            PrefixedClassId::from_str("(nft-transfer)/channel-0/kitties").is_err(),
+            "invalid port"
+        );
+        assert!(TokenId::from_str(" ").is_err(), "empty token id");
+    }
+
+    #[test]
+    fn test_class_id_trace() -> Result<(), Error> {
+        let class_id = PrefixedClassId::from_str("kitties")?;
+        assert!(class_id.trace_path().is_empty());
+        assert_eq!(class_id.to_string(), "kitties");
+
+        let class_id = PrefixedClassId::from_str(
+            "nft-transfer/channel-1/nft-transfer/channel-0/wasm.stars1/x",
+        )?;
+        assert_eq!(class_id.trace_path().len(), 2);
+        assert_eq!(class_id.base_class_id().to_string(), "wasm.stars1/x");
+        assert_eq!(
+            class_id.to_string(),
+            "nft-transfer/channel-1/nft-transfer/channel-0/wasm.stars1/x"
+        );
+
+        let mut class_id = class_id;
+        class_id.remove_trace_prefix(&"nft-transfer/channel-1".parse().unwrap());
+        assert_eq!(class_id.to_string(), "nft-transfer/channel-0/wasm.stars1/x");
+
+        let raw = RawClassTrace::from(class_id.clone());
+        assert_eq!(raw.path, "nft-transfer/channel-0");
+        assert_eq!(PrefixedClassId::try_from(raw)?, class_id);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use flex_error::{define_error, TraceError};
+
+use crate::applications::transfer::error::Error as TransferError;
+use crate::core::ics24_host::error::ValidationError;
+use crate::signer::SignerError;
+
+define_error! {
+    #[derive(Debug, PartialEq, Eq)]
+    Error {
+        InvalidPortId
+            { context: String }
+            [ ValidationError ]
+            | _ | { "invalid port identifier" },
+
+        InvalidChannelId
+            { context: String }
+            [ ValidationError ]
+            | _ | { "invalid channel identifier" },
+
+        InvalidPacketTimeoutHeight
+            { context: String }
+            | _ | { "invalid packet timeout height value" },
+
+        InvalidPacketTimeoutTimestamp
+            { timestamp: u64 }
+            | _ | { "invalid packet timeout timestamp value" },
+
+        EmptyBaseClassId
+            |_| { "base class identifier is empty" },
+
+        InvalidTrace
+            [ TransferError ]
+            | _ | { "invalid class trace" },
+
+        EmptyTokenIds
+            | _ | { "at least one token identifier must be transferred" },
+
+        EmptyTokenId
+            | _ | { "token identifier is empty" },
+
+        TokenMetadataLengthMismatch
+            { token_ids: usize, metadata: usize }
+            | e | {
+                format_args!("expected metadata for {0} tokens but got {1} entries",
+                    e.token_ids, e.metadata)
+            },
+
+        Signer
+            [ SignerError ]
+            | _ | { "failed to parse signer" },
+
+        PacketDataDeserialization
+            | _ | { "failed to deserialize packet data" },
+
+        DecodeRawMsg
+            [ TraceError<ibc_proto::Error> ]
+            | _ | { "error decoding raw msg" },
+
+        UnknownMsgType
+            { msg_type: String }
+            | e | { format_args!("unknown msg type: {0}", e.msg_type) },
+    }
+}
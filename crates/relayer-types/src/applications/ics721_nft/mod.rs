@@ -0,0 +1,20 @@
+//! ICS 721: Non-Fungible Token Transfer implementation allows for multi-chain class handling,
+//! which constitutes a "non-fungible token transfer bridge module" between the IBC routing
+//! module and an NFT module.
+
+pub mod class;
+pub mod error;
+pub mod msgs;
+pub mod packet;
+
+pub use class::*;
+
+/// Module identifier for the ICS721 application.
+pub const MODULE_ID_STR: &str = "nft-transfer";
+
+/// The port identifier that the ICS721 applications
+/// typically bind with.
+pub const PORT_ID_STR: &str = "nft-transfer";
+
+/// ICS721 application current version.
+pub const VERSION: &str = "ics721-1";
@@ -0,0 +1,129 @@
+//! This is the definition of a transfer messages that an application submits to a chain.
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::applications::nft_transfer::v1::MsgTransfer as RawMsgTransfer;
+use ibc_proto::Protobuf;
+
+use crate::applications::ics721_nft::error::Error;
+use crate::core::ics04_channel::timeout::TimeoutHeight;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+use crate::timestamp::Timestamp;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.applications.nft_transfer.v1.MsgTransfer";
+
+/// Message used to build an ICS721 non-fungible token transfer packet.
+///
+/// As with ICS20 transfers, the sequence number and destination port/channel
+/// of the packet are filled in by the sending chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgTransfer {
+    /// the port on which the packet will be sent
+    pub source_port: PortId,
+    /// the channel by which the packet will be sent
+    pub source_channel: ChannelId,
+    /// the class of the tokens to be transferred, either as its full
+    /// trace path or as its `ibc/{hash}` identifier
+    pub class_id: String,
+    /// the identifiers of the tokens to be transferred
+    pub token_ids: Vec<String>,
+    /// the sender address
+    pub sender: Signer,
+    /// the recipient address on the destination chain
+    pub receiver: Signer,
+    /// Timeout height relative to the current block height.
+    /// The timeout is disabled when set to None.
+    pub timeout_height: TimeoutHeight,
+    /// Timeout timestamp relative to the current block timestamp.
+    /// The timeout is disabled when set to 0.
+    pub timeout_timestamp: Timestamp,
+    /// optional memo
+    pub memo: Option<String>,
+}
+
+impl Msg for MsgTransfer {
+    type ValidationError = Error;
+    type Raw = RawMsgTransfer;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl TryFrom<RawMsgTransfer> for MsgTransfer {
+    type Error = Error;
+
+    fn try_from(raw_msg: RawMsgTransfer) -> Result<Self, Self::Error> {
+        let timeout_timestamp = Timestamp::from_nanoseconds(raw_msg.timeout_timestamp)
+            .map_err(|_| Error::invalid_packet_timeout_timestamp(raw_msg.timeout_timestamp))?;
+
+        let timeout_height: TimeoutHeight = raw_msg.timeout_height.try_into().map_err(|e| {
+            Error::invalid_packet_timeout_height(format!("invalid timeout height {e}"))
+        })?;
+
+        if raw_msg.token_ids.is_empty() {
+            return Err(Error::empty_token_ids());
+        }
+
+        Ok(MsgTransfer {
+            source_port: raw_msg
+                .source_port
+                .parse()
+                .map_err(|e| Error::invalid_port_id(raw_msg.source_port.clone(), e))?,
+            source_channel: raw_msg
+                .source_channel
+                .parse()
+                .map_err(|e| Error::invalid_channel_id(raw_msg.source_channel.clone(), e))?,
+            class_id: raw_msg.class_id,
+            token_ids: raw_msg.token_ids,
+            sender: raw_msg.sender.parse().map_err(Error::signer)?,
+            receiver: raw_msg.receiver.parse().map_err(Error::signer)?,
+            timeout_height,
+            timeout_timestamp,
+            memo: Some(raw_msg.memo).filter(|m| !m.is_empty()),
+        })
+    }
+}
+
+impl From<MsgTransfer> for RawMsgTransfer {
+    fn from(domain_msg: MsgTransfer) -> Self {
+        RawMsgTransfer {
+            source_port: domain_msg.source_port.to_string(),
+            source_channel: domain_msg.source_channel.to_string(),
+            class_id: domain_msg.class_id,
+            token_ids: domain_msg.token_ids,
+            sender: domain_msg.sender.to_string(),
+            receiver: domain_msg.receiver.to_string(),
+            timeout_height: domain_msg.timeout_height.into(),
+            timeout_timestamp: domain_msg.timeout_timestamp.nanoseconds(),
+            memo: domain_msg.memo.unwrap_or_default(),
+        }
+    }
+}
+
+impl Protobuf<RawMsgTransfer> for MsgTransfer {}
+
+impl TryFrom<Any> for MsgTransfer {
+    type Error = Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            TYPE_URL => MsgTransfer::decode_vec(&raw.value).map_err(Error::decode_raw_msg),
+            _ => Err(Error::unknown_msg_type(raw.type_url)),
+        }
+    }
+}
+
+impl From<MsgTransfer> for Any {
+    fn from(msg: MsgTransfer) -> Self {
+        Self {
+            type_url: TYPE_URL.to_string(),
+            value: msg.encode_vec(),
+        }
+    }
+}
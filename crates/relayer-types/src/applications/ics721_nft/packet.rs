@@ -0,0 +1,134 @@
+use std::str::FromStr;
+
+use ibc_proto::ibc::applications::nft_transfer::v1::NonFungibleTokenPacketData as RawPacketData;
+use serde::{Deserialize, Serialize};
+
+use super::error::Error;
+use super::{PrefixedClassId, TokenId};
+use crate::signer::Signer;
+
+/// Packet data of the ICS721 non-fungible token transfer application.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawPacketData", into = "RawPacketData")]
+pub struct PacketData {
+    pub class_id: PrefixedClassId,
+    pub class_uri: Option<String>,
+    pub class_data: Option<String>,
+    pub token_ids: Vec<TokenId>,
+    /// Either empty or of the same length as `token_ids`
+    pub token_uris: Vec<String>,
+    /// Either empty or of the same length as `token_ids`
+    pub token_data: Vec<String>,
+    pub sender: Signer,
+    pub receiver: Signer,
+    pub memo: Option<String>,
+}
+
+impl PacketData {
+    /// Decodes JSON-encoded ICS721 packet data.
+    pub fn decode(data: &[u8]) -> Result<Self, Error> {
+        serde_json::from_slice(data).map_err(|_| Error::packet_data_deserialization())
+    }
+}
+
+impl TryFrom<RawPacketData> for PacketData {
+    type Error = Error;
+
+    fn try_from(raw_pkt_data: RawPacketData) -> Result<Self, Self::Error> {
+        if raw_pkt_data.token_ids.is_empty() {
+            return Err(Error::empty_token_ids());
+        }
+
+        let token_ids = raw_pkt_data
+            .token_ids
+            .iter()
+            .map(|id| TokenId::from_str(id))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for metadata in [&raw_pkt_data.token_uris, &raw_pkt_data.token_data] {
+            if !metadata.is_empty() && metadata.len() != token_ids.len() {
+                return Err(Error::token_metadata_length_mismatch(
+                    token_ids.len(),
+                    metadata.len(),
+                ));
+            }
+        }
+
+        Ok(Self {
+            class_id: PrefixedClassId::from_str(&raw_pkt_data.class_id)?,
+            class_uri: Some(raw_pkt_data.class_uri).filter(|u| !u.is_empty()),
+            class_data: Some(raw_pkt_data.class_data).filter(|d| !d.is_empty()),
+            token_ids,
+            token_uris: raw_pkt_data.token_uris,
+            token_data: raw_pkt_data.token_data,
+            sender: raw_pkt_data.sender.parse().map_err(Error::signer)?,
+            receiver: raw_pkt_data.receiver.parse().map_err(Error::signer)?,
+            memo: Some(raw_pkt_data.memo).filter(|m| !m.is_empty()),
+        })
+    }
+}
+
+impl From<PacketData> for RawPacketData {
+    fn from(pkt_data: PacketData) -> Self {
+        Self {
+            class_id: pkt_data.class_id.to_string(),
+            class_uri: pkt_data.class_uri.unwrap_or_default(),
+            class_data: pkt_data.class_data.unwrap_or_default(),
+            token_ids: pkt_data.token_ids.iter().map(ToString::to_string).collect(),
+            token_uris: pkt_data.token_uris,
+            token_data: pkt_data.token_data,
+            sender: pkt_data.sender.to_string(),
+            receiver: pkt_data.receiver.to_string(),
+            memo: pkt_data.memo.unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PacketData;
+
+    #[test]
+    fn decode_nft_packet_data() {
+        let data = br#"{
+            "classId": "nft-transfer/channel-0/wasm.stars1abc",
+            "classUri": "https://example.com/class",
+            "tokenIds": ["1", "2"],
+            "tokenUris": ["https://example.com/1", "https://example.com/2"],
+            "sender": "stars1sender",
+            "receiver": "cosmos1receiver"
+        }"#;
+
+        let packet_data = PacketData::decode(data).unwrap();
+        assert_eq!(
+            packet_data.class_id.to_string(),
+            "nft-transfer/channel-0/wasm.stars1abc"
+        );
+        assert_eq!(packet_data.class_data, None);
+        assert_eq!(packet_data.token_ids.len(), 2);
+        assert_eq!(packet_data.memo, None);
+
+        let encoded = serde_json::to_vec(&packet_data).unwrap();
+        assert_eq!(PacketData::decode(&encoded).unwrap(), packet_data);
+    }
+
+    #[test]
+    fn decode_invalid_nft_packet_data() {
+        let no_tokens =
+            br#"{ "classId": "kitties", "tokenIds": [], "sender": "a", "receiver": "b" }"#;
+        assert!(PacketData::decode(no_tokens).is_err());
+
+        let uris_mismatch = br#"{
+            "classId": "kitties",
+            "tokenIds": ["1", "2"],
+            "tokenUris": ["https://example.com/1"],
+            "sender": "a",
+            "receiver": "b"
+        }"#;
+        assert!(PacketData::decode(uris_mismatch).is_err());
+
+        // ICS20 packet data is not NFT packet data
+        let ics20 = br#"{ "denom": "uatom", "amount": "10", "sender": "a", "receiver": "b" }"#;
+        assert!(PacketData::decode(ics20).is_err());
+    }
+}
@@ -4,4 +4,5 @@ pub mod ics27_ica;
 pub mod ics28_ccv;
 pub mod ics29_fee;
 pub mod ics31_icq;
+pub mod ics721_nft;
 pub mod transfer;
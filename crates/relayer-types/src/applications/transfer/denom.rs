@@ -6,6 +6,7 @@ use ibc_proto::ibc::applications::transfer::v1::DenomTrace as RawDenomTrace;
 use serde::{Deserialize, Serialize};
 
 use super::error::Error;
+use super::raw::{Denom as RawDenom, Hop as RawHop};
 use crate::core::ics24_host::identifier::{ChannelId, PortId};
 use crate::serializers::serde_string;
 
@@ -39,6 +40,51 @@ impl TracePrefix {
             channel_id,
         }
     }
+
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id
+    }
+
+    pub fn channel_id(&self) -> &ChannelId {
+        &self.channel_id
+    }
+}
+
+impl FromStr for TracePrefix {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut path = TracePath::from_str(s)?;
+        match (path.0.pop(), path.is_empty()) {
+            (Some(prefix), true) => Ok(prefix),
+            _ => Err(Error::invalid_trace_length(s.split('/').count())),
+        }
+    }
+}
+
+impl TryFrom<RawHop> for TracePrefix {
+    type Error = Error;
+
+    fn try_from(value: RawHop) -> Result<Self, Self::Error> {
+        let port_id = value
+            .port_id
+            .parse()
+            .map_err(|e| Error::invalid_port_id(value.port_id.clone(), e))?;
+        let channel_id = value
+            .channel_id
+            .parse()
+            .map_err(|e| Error::invalid_channel_id(value.channel_id.clone(), e))?;
+        Ok(Self::new(port_id, channel_id))
+    }
+}
+
+impl From<TracePrefix> for RawHop {
+    fn from(value: TracePrefix) -> Self {
+        Self {
+            port_id: value.port_id.to_string(),
+            channel_id: value.channel_id.to_string(),
+        }
+    }
 }
 
 impl Display for TracePrefix {
@@ -76,6 +122,42 @@ impl TracePath {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Returns the number of hops in the path.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the prefixes of this path in the order in which they appear in
+    /// its string representation, i.e. the last hop the token went through first.
+    pub fn hops(&self) -> impl Iterator<Item = &TracePrefix> {
+        self.0.iter().rev()
+    }
+
+    /// Splits a full path into its leading `{port-id}/{channel-id}` pairs and the
+    /// remainder of the path, consuming pairs for as long as the channel identifier
+    /// is of the form `channel-{N}`. The remainder always contains at least one segment.
+    pub fn split_path(path: &str) -> Result<(Self, &str), Error> {
+        let parts: Vec<&str> = path.split('/').collect();
+
+        let mut trace = vec![];
+        let mut pos = 0;
+        let mut offset = 0;
+        while parts.len() - pos > 2 && is_channel_id(parts[pos + 1]) {
+            let port_id = PortId::from_str(parts[pos])
+                .map_err(|e| Error::invalid_trace_port_id(trace.len(), e))?;
+            let channel_id = ChannelId::from_str(parts[pos + 1])
+                .map_err(|e| Error::invalid_trace_channel_id(trace.len(), e))?;
+            trace.push(TracePrefix::new(port_id, channel_id));
+            offset += parts[pos].len() + parts[pos + 1].len() + 2;
+            pos += 2;
+        }
+
+        // `TracePath` stores its prefixes in reverse order
+        trace.reverse();
+
+        Ok((trace.into(), &path[offset..]))
+    }
 }
 
 impl<'a> TryFrom<Vec<&'a str>> for TracePath {
@@ -152,6 +234,38 @@ impl PrefixedDenom {
     pub fn add_trace_prefix(&mut self, prefix: TracePrefix) {
         self.trace_path.add_prefix(prefix)
     }
+
+    pub fn trace_path(&self) -> &TracePath {
+        &self.trace_path
+    }
+
+    pub fn base_denom(&self) -> &BaseDenom {
+        &self.base_denom
+    }
+
+    /// Decodes a full denomination path, e.g. `transfer/channel-0/transfer/channel-1/gamm/pool/1`.
+    ///
+    /// Unlike the `FromStr` implementation, which always treats the last segment as the
+    /// base denomination, this consumes `{port-id}/{channel-id}` pairs for as long as the
+    /// channel identifier is of the form `channel-{N}` and keeps the remainder of the path
+    /// as the base denomination, which may therefore contain slashes. This matches how
+    /// `ics20-2` chains decode multi-hop denomination traces.
+    pub fn from_path(path: &str) -> Result<Self, Error> {
+        let (trace_path, base_denom) = TracePath::split_path(path)?;
+
+        Ok(Self {
+            trace_path,
+            base_denom: BaseDenom::from_str(base_denom)?,
+        })
+    }
+}
+
+/// Returns true if the given path segment is a channel identifier of the form `channel-{N}`.
+fn is_channel_id(segment: &str) -> bool {
+    segment
+        .strip_prefix("channel-")
+        .map(|counter| !counter.is_empty() && counter.bytes().all(|b| b.is_ascii_digit()))
+        .unwrap_or(false)
 }
 
 /// Returns true if the denomination originally came from the sender chain and
@@ -249,6 +363,41 @@ impl From<PrefixedDenom> for RawDenomTrace {
     }
 }
 
+impl TryFrom<RawDenom> for PrefixedDenom {
+    type Error = Error;
+
+    fn try_from(value: RawDenom) -> Result<Self, Self::Error> {
+        let base_denom = BaseDenom::from_str(&value.base)?;
+
+        let mut trace = vec![];
+        for (pos, hop) in value.trace.into_iter().enumerate() {
+            let port_id =
+                PortId::from_str(&hop.port_id).map_err(|e| Error::invalid_trace_port_id(pos, e))?;
+            let channel_id = ChannelId::from_str(&hop.channel_id)
+                .map_err(|e| Error::invalid_trace_channel_id(pos, e))?;
+            trace.push(TracePrefix::new(port_id, channel_id));
+        }
+
+        // The first hop of the raw trace is the last channel the token went through,
+        // whereas `TracePath` stores its prefixes in reverse order.
+        trace.reverse();
+
+        Ok(Self {
+            trace_path: trace.into(),
+            base_denom,
+        })
+    }
+}
+
+impl From<PrefixedDenom> for RawDenom {
+    fn from(value: PrefixedDenom) -> Self {
+        Self {
+            base: value.base_denom.to_string(),
+            trace: value.trace_path.hops().cloned().map(RawHop::from).collect(),
+        }
+    }
+}
+
 impl From<BaseDenom> for PrefixedDenom {
     fn from(denom: BaseDenom) -> Self {
         Self {
@@ -383,6 +532,52 @@ mod tests {
         trace_path.remove_prefix(&prefix_1);
         assert!(trace_path.is_empty());
 
+        assert_eq!(TracePrefix::from_str("transfer/channel-1")?, prefix_1);
+        assert!(
+            TracePrefix::from_str("transfer/channel-0/transfer/channel-1").is_err(),
+            "more than one hop"
+        );
+        assert!(TracePrefix::from_str("").is_err(), "empty hop");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_denom_from_path() -> Result<(), Error> {
+        let denom = PrefixedDenom::from_path("transfer/channel-0/transfer/channel-1/gamm/pool/1")?;
+        assert_eq!(denom.base_denom().to_string(), "gamm/pool/1");
+        assert_eq!(
+            denom.trace_path(),
+            &TracePath::from_str("transfer/channel-0/transfer/channel-1")?
+        );
+
+        let denom = PrefixedDenom::from_path("transfer/atom")?;
+        assert!(denom.trace_path().is_empty(), "not a channel identifier");
+        assert_eq!(denom.base_denom().to_string(), "transfer/atom");
+
+        let denom = PrefixedDenom::from_path("transfer/channel-0/uatom")?;
+        assert_eq!(denom, PrefixedDenom::from_str("transfer/channel-0/uatom")?);
+
+        assert!(
+            PrefixedDenom::from_path("(transfer)/channel-0/uatom").is_err(),
+            "invalid port"
+        );
+        assert!(PrefixedDenom::from_path("").is_err(), "empty denom");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_raw_denom_roundtrip() -> Result<(), Error> {
+        let denom = PrefixedDenom::from_str("transfer/channel-0/transfer/channel-1/uatom")?;
+        let raw = RawDenom::from(denom.clone());
+
+        assert_eq!(raw.base, "uatom");
+        assert_eq!(raw.trace.len(), 2);
+        assert_eq!(raw.trace[0].channel_id, "channel-0");
+        assert_eq!(raw.trace[1].channel_id, "channel-1");
+        assert_eq!(PrefixedDenom::try_from(raw)?, denom);
+
         Ok(())
     }
 }
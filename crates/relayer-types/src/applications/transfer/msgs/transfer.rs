@@ -2,10 +2,13 @@
 
 use ibc_proto::cosmos::base::v1beta1::Coin;
 use ibc_proto::google::protobuf::Any;
-use ibc_proto::ibc::applications::transfer::v1::MsgTransfer as RawMsgTransfer;
 use ibc_proto::Protobuf;
 
 use crate::applications::transfer::error::Error;
+use crate::applications::transfer::raw::{
+    Forwarding as RawForwarding, MsgTransfer as RawMsgTransfer,
+};
+use crate::applications::transfer::TracePrefix;
 use crate::core::ics04_channel::timeout::TimeoutHeight;
 use crate::core::ics24_host::identifier::{ChannelId, PortId};
 use crate::signer::Signer;
@@ -27,8 +30,11 @@ pub struct MsgTransfer<C = Coin> {
     pub source_port: PortId,
     /// the channel by which the packet will be sent
     pub source_channel: ChannelId,
-    /// the tokens to be transferred
+    /// the token to be transferred
     pub token: C,
+    /// the tokens to be transferred over an `ics20-2` channel,
+    /// in which case `token` must be left empty
+    pub tokens: Vec<C>,
     /// the sender address
     pub sender: Signer,
     /// the recipient address on the destination chain
@@ -41,6 +47,42 @@ pub struct MsgTransfer<C = Coin> {
     pub timeout_timestamp: Timestamp,
     /// optional memo
     pub memo: Option<String>,
+    /// optional forwarding information, only supported by `ics20-2` channels
+    pub forwarding: Option<Forwarding>,
+}
+
+/// The hops through which the tokens of a transfer must be forwarded
+/// once received by the destination chain.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Forwarding {
+    /// whether the tokens must first be sent back to their native chain
+    pub unwind: bool,
+    /// the hops through which the tokens must then be forwarded
+    pub hops: Vec<TracePrefix>,
+}
+
+impl TryFrom<RawForwarding> for Forwarding {
+    type Error = Error;
+
+    fn try_from(raw: RawForwarding) -> Result<Self, Self::Error> {
+        Ok(Self {
+            unwind: raw.unwind,
+            hops: raw
+                .hops
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<Forwarding> for RawForwarding {
+    fn from(forwarding: Forwarding) -> Self {
+        Self {
+            unwind: forwarding.unwind,
+            hops: forwarding.hops.into_iter().map(Into::into).collect(),
+        }
+    }
 }
 
 impl Msg for MsgTransfer {
@@ -69,6 +111,15 @@ impl TryFrom<RawMsgTransfer> for MsgTransfer {
 
         let memo = Some(raw_msg.memo).filter(|m| !m.is_empty());
 
+        let forwarding = raw_msg.forwarding.map(TryInto::try_into).transpose()?;
+
+        // Multi-denom transfers leave the single `token` field empty
+        let token = match raw_msg.token {
+            Some(token) => token,
+            None if !raw_msg.tokens.is_empty() => Coin::default(),
+            None => return Err(Error::invalid_token()),
+        };
+
         Ok(MsgTransfer {
             source_port: raw_msg
                 .source_port
@@ -78,12 +129,14 @@ impl TryFrom<RawMsgTransfer> for MsgTransfer {
                 .source_channel
                 .parse()
                 .map_err(|e| Error::invalid_channel_id(raw_msg.source_channel.clone(), e))?,
-            token: raw_msg.token.ok_or_else(Error::invalid_token)?,
+            token,
+            tokens: raw_msg.tokens,
             sender: raw_msg.sender.parse().map_err(Error::signer)?,
             receiver: raw_msg.receiver.parse().map_err(Error::signer)?,
             timeout_height,
             timeout_timestamp,
             memo,
+            forwarding,
         })
     }
 }
@@ -92,15 +145,20 @@ impl From<MsgTransfer> for RawMsgTransfer {
     fn from(domain_msg: MsgTransfer) -> Self {
         let memo = domain_msg.memo.unwrap_or_default();
 
+        // The single `token` field must be left empty for multi-denom transfers
+        let token = Some(domain_msg.token).filter(|_| domain_msg.tokens.is_empty());
+
         RawMsgTransfer {
             source_port: domain_msg.source_port.to_string(),
             source_channel: domain_msg.source_channel.to_string(),
-            token: Some(domain_msg.token),
+            token,
             sender: domain_msg.sender.to_string(),
             receiver: domain_msg.receiver.to_string(),
             timeout_height: domain_msg.timeout_height.into(),
             timeout_timestamp: domain_msg.timeout_timestamp.nanoseconds(),
             memo,
+            tokens: domain_msg.tokens,
+            forwarding: domain_msg.forwarding.map(Into::into),
         }
     }
 }
@@ -161,12 +219,14 @@ pub mod test_util {
                 amount: U256::from(10).into(),
             }
             .into(),
+            tokens: vec![],
             sender: address.clone(),
             receiver: address,
             timeout_timestamp: timeout_timestamp
                 .unwrap_or_else(|| Timestamp::now().add(Duration::from_secs(10)).unwrap()),
             timeout_height,
             memo: None,
+            forwarding: None,
         }
     }
 
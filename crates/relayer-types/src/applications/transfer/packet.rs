@@ -1,10 +1,15 @@
 use std::str::FromStr;
 
 use ibc_proto::ibc::applications::transfer::v2::FungibleTokenPacketData as RawPacketData;
+use prost::Message;
 use serde::{Deserialize, Serialize};
 
 use super::error::Error;
-use super::{Amount, PrefixedCoin, PrefixedDenom};
+use super::raw::{
+    ForwardingPacketData as RawForwardingPacketData, FungibleTokenPacketDataV2 as RawPacketDataV2,
+    Token as RawToken,
+};
+use super::{Amount, PrefixedCoin, PrefixedDenom, TracePrefix};
 use crate::signer::Signer;
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -45,3 +50,194 @@ impl From<PacketData> for RawPacketData {
         }
     }
 }
+
+/// Packet data of the `ics20-2` version of the transfer application,
+/// which allows transferring several tokens at once and forwarding
+/// them through intermediate chains.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PacketDataV2 {
+    pub tokens: Vec<PrefixedCoin>,
+    pub sender: Signer,
+    pub receiver: Signer,
+    pub memo: Option<String>,
+    pub forwarding: ForwardingPacketData,
+}
+
+/// The hops through which the tokens of an `ics20-2` packet must be forwarded
+/// once received, along with the memo to use on the final destination.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ForwardingPacketData {
+    pub destination_memo: Option<String>,
+    pub hops: Vec<TracePrefix>,
+}
+
+impl PacketDataV2 {
+    /// Decodes ICS-20 packet data of either version.
+    ///
+    /// `ics20-1` packet data is JSON-encoded and is converted into a packet
+    /// carrying a single token, while `ics20-2` packet data is Protobuf-encoded.
+    pub fn decode(data: &[u8]) -> Result<Self, Error> {
+        if let Ok(packet_data) = serde_json::from_slice::<PacketData>(data) {
+            return Ok(packet_data.into());
+        }
+
+        Self::decode_v2(data)
+    }
+
+    /// Decodes `ics20-2` packet data only, which is Protobuf-encoded.
+    pub fn decode_v2(data: &[u8]) -> Result<Self, Error> {
+        RawPacketDataV2::decode(data)
+            .map_err(|_| Error::packet_data_deserialization())?
+            .try_into()
+    }
+
+    /// Returns true if the tokens must be forwarded to another chain
+    /// once received by the destination chain.
+    pub fn is_forwarded(&self) -> bool {
+        !self.forwarding.hops.is_empty()
+    }
+}
+
+impl From<PacketData> for PacketDataV2 {
+    fn from(pkt_data: PacketData) -> Self {
+        Self {
+            tokens: vec![pkt_data.token],
+            sender: pkt_data.sender,
+            receiver: pkt_data.receiver,
+            memo: pkt_data.memo,
+            forwarding: ForwardingPacketData::default(),
+        }
+    }
+}
+
+impl TryFrom<RawPacketDataV2> for PacketDataV2 {
+    type Error = Error;
+
+    fn try_from(raw_pkt_data: RawPacketDataV2) -> Result<Self, Self::Error> {
+        let tokens = raw_pkt_data
+            .tokens
+            .into_iter()
+            .map(|token| {
+                let denom = token.denom.ok_or_else(Error::invalid_token)?.try_into()?;
+                let amount = Amount::from_str(&token.amount)?;
+                Ok(PrefixedCoin { denom, amount })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        if tokens.is_empty() {
+            return Err(Error::invalid_token());
+        }
+
+        let memo = Some(raw_pkt_data.memo).filter(|m| !m.is_empty());
+        let forwarding = raw_pkt_data
+            .forwarding
+            .map(TryInto::try_into)
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(Self {
+            tokens,
+            sender: raw_pkt_data.sender.parse().map_err(Error::signer)?,
+            receiver: raw_pkt_data.receiver.parse().map_err(Error::signer)?,
+            memo,
+            forwarding,
+        })
+    }
+}
+
+impl From<PacketDataV2> for RawPacketDataV2 {
+    fn from(pkt_data: PacketDataV2) -> Self {
+        Self {
+            tokens: pkt_data
+                .tokens
+                .into_iter()
+                .map(|token| RawToken {
+                    denom: Some(token.denom.into()),
+                    amount: token.amount.to_string(),
+                })
+                .collect(),
+            sender: pkt_data.sender.to_string(),
+            receiver: pkt_data.receiver.to_string(),
+            memo: pkt_data.memo.unwrap_or_default(),
+            forwarding: Some(pkt_data.forwarding.into()),
+        }
+    }
+}
+
+impl TryFrom<RawForwardingPacketData> for ForwardingPacketData {
+    type Error = Error;
+
+    fn try_from(raw: RawForwardingPacketData) -> Result<Self, Self::Error> {
+        Ok(Self {
+            destination_memo: Some(raw.destination_memo).filter(|m| !m.is_empty()),
+            hops: raw
+                .hops
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<ForwardingPacketData> for RawForwardingPacketData {
+    fn from(forwarding: ForwardingPacketData) -> Self {
+        Self {
+            destination_memo: forwarding.destination_memo.unwrap_or_default(),
+            hops: forwarding.hops.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dummy_packet_data() -> PacketData {
+        PacketData {
+            token: PrefixedCoin {
+                denom: "transfer/channel-0/uatom".parse().unwrap(),
+                amount: 10u64.into(),
+            },
+            sender: "cosmos1sender".parse().unwrap(),
+            receiver: "cosmos1receiver".parse().unwrap(),
+            memo: Some("memo".to_owned()),
+        }
+    }
+
+    #[test]
+    fn decode_v1_packet_data() {
+        let data = serde_json::to_vec(&dummy_packet_data()).unwrap();
+        let decoded = PacketDataV2::decode(&data).unwrap();
+
+        assert_eq!(decoded, PacketDataV2::from(dummy_packet_data()));
+        assert!(!decoded.is_forwarded());
+    }
+
+    #[test]
+    fn decode_v2_packet_data() {
+        let mut pkt_data = PacketDataV2::from(dummy_packet_data());
+        pkt_data.tokens.push(PrefixedCoin {
+            denom: PrefixedDenom::from_path("transfer/channel-1/gamm/pool/1").unwrap(),
+            amount: 42u64.into(),
+        });
+        pkt_data.forwarding = ForwardingPacketData {
+            destination_memo: Some("destination".to_owned()),
+            hops: vec![TracePrefix::new(
+                "transfer".parse().unwrap(),
+                "channel-7".parse().unwrap(),
+            )],
+        };
+
+        let data = RawPacketDataV2::from(pkt_data.clone()).encode_to_vec();
+        let decoded = PacketDataV2::decode(&data).unwrap();
+
+        assert_eq!(decoded, pkt_data);
+        assert!(decoded.is_forwarded());
+    }
+
+    #[test]
+    fn decode_invalid_packet_data() {
+        assert!(PacketDataV2::decode(b"not packet data").is_err());
+        assert!(PacketDataV2::decode(&RawPacketDataV2::default().encode_to_vec()).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+//! Protobuf definitions for the `ics20-2` version of the
+//! `ibc.applications.transfer` package.
+//!
+//! TODO: remove once `ibc-proto` ships the ICS-20 v2 transfer types,
+//! which are only available from ibc-go v9 onwards.
+
+use ibc_proto::cosmos::base::v1beta1::Coin;
+use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+
+/// Hop defines a port ID, channel ID pair specifying where tokens must be
+/// forwarded next in a multihop transfer, or the trace of an existing token.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Hop {
+    #[prost(string, tag = "1")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub channel_id: ::prost::alloc::string::String,
+}
+
+/// Denom holds the base denom of a Token and a trace of the chains it was sent through.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Denom {
+    /// the base token denomination
+    #[prost(string, tag = "1")]
+    pub base: ::prost::alloc::string::String,
+    /// the trace of the token, the first hop being the last channel the token went through
+    #[prost(message, repeated, tag = "3")]
+    pub trace: ::prost::alloc::vec::Vec<Hop>,
+}
+
+/// Token defines a struct which represents a token to be transferred.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Token {
+    #[prost(message, optional, tag = "1")]
+    pub denom: ::core::option::Option<Denom>,
+    #[prost(string, tag = "2")]
+    pub amount: ::prost::alloc::string::String,
+}
+
+/// Forwarding defines a list of port ID, channel ID pairs determining the path
+/// through which a packet must be forwarded, and whether the tokens should be
+/// unwound to their native chain first.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Forwarding {
+    #[prost(bool, tag = "1")]
+    pub unwind: bool,
+    #[prost(message, repeated, tag = "2")]
+    pub hops: ::prost::alloc::vec::Vec<Hop>,
+}
+
+/// ForwardingPacketData defines a list of port ID, channel ID pairs determining
+/// the path through which a packet must be forwarded, and the memo for the
+/// final destination.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ForwardingPacketData {
+    #[prost(string, tag = "1")]
+    pub destination_memo: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub hops: ::prost::alloc::vec::Vec<Hop>,
+}
+
+/// FungibleTokenPacketDataV2 defines a struct for the packet payload of
+/// the `ics20-2` version of the transfer application.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FungibleTokenPacketDataV2 {
+    #[prost(message, repeated, tag = "1")]
+    pub tokens: ::prost::alloc::vec::Vec<Token>,
+    #[prost(string, tag = "2")]
+    pub sender: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub receiver: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub memo: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "5")]
+    pub forwarding: ::core::option::Option<ForwardingPacketData>,
+}
+
+/// MsgTransfer defines a msg to transfer fungible tokens (i.e Coins) between
+/// ICS20 enabled chains, including the `tokens` and `forwarding` fields
+/// introduced with `ics20-2`.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgTransfer {
+    #[prost(string, tag = "1")]
+    pub source_port: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub source_channel: ::prost::alloc::string::String,
+    /// the token to be transferred, must be left empty when `tokens` is used
+    #[prost(message, optional, tag = "3")]
+    pub token: ::core::option::Option<Coin>,
+    #[prost(string, tag = "4")]
+    pub sender: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub receiver: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "6")]
+    pub timeout_height: ::core::option::Option<RawHeight>,
+    #[prost(uint64, tag = "7")]
+    pub timeout_timestamp: u64,
+    #[prost(string, tag = "8")]
+    pub memo: ::prost::alloc::string::String,
+    /// the tokens to be transferred, only supported by `ics20-2` channels
+    #[prost(message, repeated, tag = "9")]
+    pub tokens: ::prost::alloc::vec::Vec<Coin>,
+    /// optional forwarding information, only supported by `ics20-2` channels
+    #[prost(message, optional, tag = "10")]
+    pub forwarding: ::core::option::Option<Forwarding>,
+}
@@ -92,6 +92,20 @@ define_error! {
         ZeroPacketData
             | _ | { "packet data bytes cannot be empty" },
 
+        ZeroPacketPayloads
+            | _ | { "packet must carry at least one payload" },
+
+        MissingAcknowledgement
+            | _ | { "there is no acknowledgement in this message" },
+
+        PacketTimeoutTimestampOverflow
+            { seconds: u64 }
+            | e | { format_args!("packet timeout timestamp {} (in seconds) is out of range", e.seconds) },
+
+        DecodeRaw
+            [ TraceError<prost::DecodeError> ]
+            | _ | { "error decoding raw protobuf value" },
+
         InvalidTimeoutHeight
             | _ | { "invalid timeout height for the packet" },
 
@@ -417,6 +431,10 @@ define_error! {
         MalformedEventAttributeValue
             { key: String }
             | e | { format_args!("event attribute value for key {} is not valid UTF-8", e.key) },
+
+        MissingEventAttribute
+            { key: String }
+            | e | { format_args!("missing event attribute {}", e.key) },
     }
 }
 
@@ -11,4 +11,5 @@ pub mod packet_id;
 pub mod timeout;
 pub mod upgrade;
 pub mod upgrade_fields;
+pub mod v2;
 pub mod version;
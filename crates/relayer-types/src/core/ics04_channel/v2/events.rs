@@ -0,0 +1,218 @@
+//! Types for the IBC v2 packet events.
+//!
+//! IBC v2 packet events share their type with the ICS-04 packet events
+//! (e.g. `send_packet`), but carry a different set of attributes: the
+//! packet is routed by a pair of client identifiers and is emitted as
+//! a hex-encoded protobuf value under [`PKT_ENCODED_HEX_ATTRIBUTE_KEY`].
+
+use std::fmt::{Display, Error as FmtError, Formatter};
+
+use prost::Message;
+use serde_derive::Serialize;
+use subtle_encoding::hex;
+use tendermint::abci;
+
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::v2::packet::{Acknowledgement, Packet};
+use crate::core::ics04_channel::v2::raw::{
+    Acknowledgement as RawAcknowledgement, Packet as RawPacket,
+};
+use crate::core::ics24_host::identifier::ClientId;
+use crate::events::IbcEvent;
+
+/// IBC v2 packet event attribute keys
+pub const PKT_SRC_CLIENT_ATTRIBUTE_KEY: &str = "packet_source_client";
+pub const PKT_DST_CLIENT_ATTRIBUTE_KEY: &str = "packet_dest_client";
+pub const PKT_ENCODED_HEX_ATTRIBUTE_KEY: &str = "encoded_packet_hex";
+pub const PKT_ENCODED_ACK_HEX_ATTRIBUTE_KEY: &str = "encoded_acknowledgement_hex";
+
+/// Returns whether the given packet event attributes are those of an IBC v2 packet event.
+pub fn is_v2_packet_event(attributes: &[abci::EventAttribute]) -> bool {
+    attributes
+        .iter()
+        .any(|attr| attr.key_bytes() == PKT_ENCODED_HEX_ATTRIBUTE_KEY.as_bytes())
+}
+
+fn find_value<'a>(key: &str, entries: &'a [abci::EventAttribute]) -> Option<&'a str> {
+    entries
+        .iter()
+        .find(|attr| attr.key_bytes() == key.as_bytes())
+        .and_then(|attr| attr.value_str().ok())
+}
+
+fn extract_packet(entries: &[abci::EventAttribute]) -> Result<Packet, Error> {
+    let encoded = find_value(PKT_ENCODED_HEX_ATTRIBUTE_KEY, entries)
+        .ok_or_else(|| Error::missing_event_attribute(PKT_ENCODED_HEX_ATTRIBUTE_KEY.to_string()))?;
+
+    let bytes = hex::decode(encoded).map_err(|_| Error::invalid_packet_data(encoded.into()))?;
+    let raw = RawPacket::decode(bytes.as_slice()).map_err(Error::decode_raw)?;
+
+    raw.try_into()
+}
+
+fn extract_ack(entries: &[abci::EventAttribute]) -> Result<Acknowledgement, Error> {
+    let encoded = find_value(PKT_ENCODED_ACK_HEX_ATTRIBUTE_KEY, entries).ok_or_else(|| {
+        Error::missing_event_attribute(PKT_ENCODED_ACK_HEX_ATTRIBUTE_KEY.to_string())
+    })?;
+
+    let bytes = hex::decode(encoded).map_err(|_| Error::invalid_packet_ack(encoded.into()))?;
+    let raw = RawAcknowledgement::decode(bytes.as_slice()).map_err(Error::decode_raw)?;
+
+    Ok(raw.into())
+}
+
+macro_rules! impl_v2_packet_event {
+    ($event:ident, $variant:ident) => {
+        impl $event {
+            pub fn src_client_id(&self) -> &ClientId {
+                &self.packet.source_client
+            }
+            pub fn dst_client_id(&self) -> &ClientId {
+                &self.packet.destination_client
+            }
+        }
+
+        impl From<$event> for IbcEvent {
+            fn from(v: $event) -> Self {
+                IbcEvent::$variant(v)
+            }
+        }
+    };
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SendPacket {
+    pub packet: Packet,
+}
+
+impl_v2_packet_event!(SendPacket, SendPacketV2);
+
+impl Display for SendPacket {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(f, "SendPacketV2 {{ packet: {} }}", self.packet)
+    }
+}
+
+impl<'a> TryFrom<&'a [abci::EventAttribute]> for SendPacket {
+    type Error = Error;
+
+    fn try_from(entries: &'a [abci::EventAttribute]) -> Result<Self, Error> {
+        Ok(Self {
+            packet: extract_packet(entries)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct WriteAcknowledgement {
+    pub packet: Packet,
+    pub ack: Acknowledgement,
+}
+
+impl_v2_packet_event!(WriteAcknowledgement, WriteAcknowledgementV2);
+
+impl Display for WriteAcknowledgement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(
+            f,
+            "WriteAcknowledgementV2 {{ packet: {}, acks: {} }}",
+            self.packet,
+            self.ack.app_acknowledgements.len()
+        )
+    }
+}
+
+impl<'a> TryFrom<&'a [abci::EventAttribute]> for WriteAcknowledgement {
+    type Error = Error;
+
+    fn try_from(entries: &'a [abci::EventAttribute]) -> Result<Self, Error> {
+        Ok(Self {
+            packet: extract_packet(entries)?,
+            ack: extract_ack(entries)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AcknowledgePacket {
+    pub packet: Packet,
+}
+
+impl_v2_packet_event!(AcknowledgePacket, AcknowledgePacketV2);
+
+impl Display for AcknowledgePacket {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(f, "AcknowledgePacketV2 {{ packet: {} }}", self.packet)
+    }
+}
+
+impl<'a> TryFrom<&'a [abci::EventAttribute]> for AcknowledgePacket {
+    type Error = Error;
+
+    fn try_from(entries: &'a [abci::EventAttribute]) -> Result<Self, Error> {
+        Ok(Self {
+            packet: extract_packet(entries)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TimeoutPacket {
+    pub packet: Packet,
+}
+
+impl_v2_packet_event!(TimeoutPacket, TimeoutPacketV2);
+
+impl Display for TimeoutPacket {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(f, "TimeoutPacketV2 {{ packet: {} }}", self.packet)
+    }
+}
+
+impl<'a> TryFrom<&'a [abci::EventAttribute]> for TimeoutPacket {
+    type Error = Error;
+
+    fn try_from(entries: &'a [abci::EventAttribute]) -> Result<Self, Error> {
+        Ok(Self {
+            packet: extract_packet(entries)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+    use crate::core::ics04_channel::v2::packet::test_utils::get_dummy_raw_packet_v2;
+
+    #[test]
+    fn write_ack_v2_from_abci_attributes() {
+        let raw_packet = get_dummy_raw_packet_v2(1_700_000_000);
+        let raw_ack = RawAcknowledgement {
+            app_acknowledgements: vec![b"ok".to_vec()],
+        };
+
+        let attributes: Vec<abci::EventAttribute> = vec![
+            (PKT_SRC_CLIENT_ATTRIBUTE_KEY, "07-tendermint-0").into(),
+            (PKT_DST_CLIENT_ATTRIBUTE_KEY, "07-tendermint-1").into(),
+            (
+                PKT_ENCODED_HEX_ATTRIBUTE_KEY,
+                String::from_utf8(hex::encode(raw_packet.encode_to_vec())).unwrap(),
+            )
+                .into(),
+            (
+                PKT_ENCODED_ACK_HEX_ATTRIBUTE_KEY,
+                String::from_utf8(hex::encode(raw_ack.encode_to_vec())).unwrap(),
+            )
+                .into(),
+        ];
+
+        assert!(is_v2_packet_event(&attributes));
+
+        let event = WriteAcknowledgement::try_from(attributes.as_slice()).unwrap();
+        assert_eq!(RawPacket::from(event.packet.clone()), raw_packet);
+        assert_eq!(event.ack.app_acknowledgements, raw_ack.app_acknowledgements);
+        assert_eq!(event.dst_client_id().as_str(), "07-tendermint-1");
+    }
+}
@@ -0,0 +1,8 @@
+//! IBC v2 packets, which are routed between a pair of light clients
+//! rather than over an ICS-04 channel end, and may carry several
+//! application payloads at once.
+
+pub mod events;
+pub mod msgs;
+pub mod packet;
+pub mod raw;
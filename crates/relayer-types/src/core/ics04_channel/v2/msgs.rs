@@ -0,0 +1,5 @@
+//! Message definitions for the IBC v2 packet datagrams.
+
+pub mod acknowledgement;
+pub mod recv_packet;
+pub mod timeout;
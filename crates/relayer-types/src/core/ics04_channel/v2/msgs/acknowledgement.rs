@@ -0,0 +1,99 @@
+use ibc_proto::Protobuf;
+
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::v2::packet::{Acknowledgement, Packet};
+use crate::core::ics04_channel::v2::raw::MsgAcknowledgement as RawMsgAcknowledgement;
+use crate::proofs::Proofs;
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.core.channel.v2.MsgAcknowledgement";
+
+///
+/// Message definition for IBC v2 packet acknowledgements.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgAcknowledgement {
+    pub packet: Packet,
+    pub acknowledgement: Acknowledgement,
+    pub proofs: Proofs,
+    pub signer: Signer,
+}
+
+impl MsgAcknowledgement {
+    pub fn new(
+        packet: Packet,
+        acknowledgement: Acknowledgement,
+        proofs: Proofs,
+        signer: Signer,
+    ) -> MsgAcknowledgement {
+        Self {
+            packet,
+            acknowledgement,
+            proofs,
+            signer,
+        }
+    }
+}
+
+impl Msg for MsgAcknowledgement {
+    type ValidationError = Error;
+    type Raw = RawMsgAcknowledgement;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgAcknowledgement> for MsgAcknowledgement {}
+
+impl TryFrom<RawMsgAcknowledgement> for MsgAcknowledgement {
+    type Error = Error;
+
+    fn try_from(raw_msg: RawMsgAcknowledgement) -> Result<Self, Self::Error> {
+        let proofs = Proofs::new(
+            raw_msg
+                .proof_acked
+                .try_into()
+                .map_err(Error::invalid_proof)?,
+            None,
+            None,
+            None,
+            None,
+            raw_msg
+                .proof_height
+                .and_then(|raw_height| raw_height.try_into().ok())
+                .ok_or_else(Error::missing_height)?,
+        )
+        .map_err(Error::invalid_proof)?;
+
+        Ok(MsgAcknowledgement {
+            packet: raw_msg
+                .packet
+                .ok_or_else(Error::missing_packet)?
+                .try_into()?,
+            acknowledgement: raw_msg
+                .acknowledgement
+                .ok_or_else(Error::missing_acknowledgement)?
+                .into(),
+            signer: raw_msg.signer.parse().map_err(Error::signer)?,
+            proofs,
+        })
+    }
+}
+
+impl From<MsgAcknowledgement> for RawMsgAcknowledgement {
+    fn from(domain_msg: MsgAcknowledgement) -> Self {
+        RawMsgAcknowledgement {
+            packet: Some(domain_msg.packet.into()),
+            acknowledgement: Some(domain_msg.acknowledgement.into()),
+            proof_acked: domain_msg.proofs.object_proof().clone().into(),
+            proof_height: Some(domain_msg.proofs.height().into()),
+            signer: domain_msg.signer.to_string(),
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use ibc_proto::Protobuf;
+
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::v2::packet::Packet;
+use crate::core::ics04_channel::v2::raw::MsgRecvPacket as RawMsgRecvPacket;
+use crate::proofs::Proofs;
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.core.channel.v2.MsgRecvPacket";
+
+///
+/// Message definition for the IBC v2 "packet receiving" datagram.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgRecvPacket {
+    pub packet: Packet,
+    pub proofs: Proofs,
+    pub signer: Signer,
+}
+
+impl MsgRecvPacket {
+    pub fn new(packet: Packet, proofs: Proofs, signer: Signer) -> MsgRecvPacket {
+        Self {
+            packet,
+            proofs,
+            signer,
+        }
+    }
+}
+
+impl Msg for MsgRecvPacket {
+    type ValidationError = Error;
+    type Raw = RawMsgRecvPacket;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgRecvPacket> for MsgRecvPacket {}
+
+impl TryFrom<RawMsgRecvPacket> for MsgRecvPacket {
+    type Error = Error;
+
+    fn try_from(raw_msg: RawMsgRecvPacket) -> Result<Self, Self::Error> {
+        let proofs = Proofs::new(
+            raw_msg
+                .proof_commitment
+                .try_into()
+                .map_err(Error::invalid_proof)?,
+            None,
+            None,
+            None,
+            None,
+            raw_msg
+                .proof_height
+                .and_then(|raw_height| raw_height.try_into().ok())
+                .ok_or_else(Error::missing_height)?,
+        )
+        .map_err(Error::invalid_proof)?;
+
+        Ok(MsgRecvPacket {
+            packet: raw_msg
+                .packet
+                .ok_or_else(Error::missing_packet)?
+                .try_into()?,
+            proofs,
+            signer: raw_msg.signer.parse().map_err(Error::signer)?,
+        })
+    }
+}
+
+impl From<MsgRecvPacket> for RawMsgRecvPacket {
+    fn from(domain_msg: MsgRecvPacket) -> Self {
+        RawMsgRecvPacket {
+            packet: Some(domain_msg.packet.into()),
+            proof_commitment: domain_msg.proofs.object_proof().clone().into(),
+            proof_height: Some(domain_msg.proofs.height().into()),
+            signer: domain_msg.signer.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_log::test;
+
+    use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+
+    use crate::core::ics04_channel::v2::msgs::recv_packet::MsgRecvPacket;
+    use crate::core::ics04_channel::v2::packet::test_utils::get_dummy_raw_packet_v2;
+    use crate::core::ics04_channel::v2::raw::MsgRecvPacket as RawMsgRecvPacket;
+    use crate::test_utils::{get_dummy_bech32_account, get_dummy_proof};
+
+    #[test]
+    fn to_and_from() {
+        let raw = RawMsgRecvPacket {
+            packet: Some(get_dummy_raw_packet_v2(1_700_000_000)),
+            proof_commitment: get_dummy_proof(),
+            proof_height: Some(RawHeight {
+                revision_number: 0,
+                revision_height: 10,
+            }),
+            signer: get_dummy_bech32_account(),
+        };
+
+        let msg = MsgRecvPacket::try_from(raw.clone()).unwrap();
+        let raw_back = RawMsgRecvPacket::from(msg.clone());
+        let msg_back = MsgRecvPacket::try_from(raw_back.clone()).unwrap();
+
+        assert_eq!(raw, raw_back);
+        assert_eq!(msg, msg_back);
+    }
+}
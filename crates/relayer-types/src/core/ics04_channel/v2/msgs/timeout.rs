@@ -0,0 +1,90 @@
+use ibc_proto::Protobuf;
+
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::v2::packet::Packet;
+use crate::core::ics04_channel::v2::raw::MsgTimeout as RawMsgTimeout;
+use crate::proofs::Proofs;
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.core.channel.v2.MsgTimeout";
+
+///
+/// Message definition for the IBC v2 packet timeout datagram.
+///
+/// Unlike its ICS-04 counterpart, it does not carry the next receive
+/// sequence, as IBC v2 packets are always unordered.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgTimeout {
+    pub packet: Packet,
+    pub proofs: Proofs,
+    pub signer: Signer,
+}
+
+impl MsgTimeout {
+    pub fn new(packet: Packet, proofs: Proofs, signer: Signer) -> MsgTimeout {
+        Self {
+            packet,
+            proofs,
+            signer,
+        }
+    }
+}
+
+impl Msg for MsgTimeout {
+    type ValidationError = Error;
+    type Raw = RawMsgTimeout;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgTimeout> for MsgTimeout {}
+
+impl TryFrom<RawMsgTimeout> for MsgTimeout {
+    type Error = Error;
+
+    fn try_from(raw_msg: RawMsgTimeout) -> Result<Self, Self::Error> {
+        let proofs = Proofs::new(
+            raw_msg
+                .proof_unreceived
+                .try_into()
+                .map_err(Error::invalid_proof)?,
+            None,
+            None,
+            None,
+            None,
+            raw_msg
+                .proof_height
+                .and_then(|raw_height| raw_height.try_into().ok())
+                .ok_or_else(Error::missing_height)?,
+        )
+        .map_err(Error::invalid_proof)?;
+
+        Ok(MsgTimeout {
+            packet: raw_msg
+                .packet
+                .ok_or_else(Error::missing_packet)?
+                .try_into()?,
+            proofs,
+            signer: raw_msg.signer.parse().map_err(Error::signer)?,
+        })
+    }
+}
+
+impl From<MsgTimeout> for RawMsgTimeout {
+    fn from(domain_msg: MsgTimeout) -> Self {
+        RawMsgTimeout {
+            packet: Some(domain_msg.packet.into()),
+            proof_unreceived: domain_msg.proofs.object_proof().clone().into(),
+            proof_height: Some(domain_msg.proofs.height().into()),
+            signer: domain_msg.signer.to_string(),
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use serde_derive::{Deserialize, Serialize};
+
+use crate::core::ics04_channel::error::Error;
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics04_channel::v2::raw::{
+    Acknowledgement as RawAcknowledgement, Packet as RawPacket, Payload as RawPayload,
+};
+use crate::core::ics24_host::identifier::{ClientId, PortId};
+use crate::timestamp::{Expiry::Expired, Timestamp};
+
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+
+/// Enumeration of proof carrying IBC v2 packet messages, helper for relayer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum PacketMsgType {
+    Recv,
+    Ack,
+    Timeout,
+}
+
+impl core::fmt::Display for PacketMsgType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PacketMsgType::Recv => write!(f, "(PacketMsgTypeV2::Recv)"),
+            PacketMsgType::Ack => write!(f, "(PacketMsgTypeV2::Ack)"),
+            PacketMsgType::Timeout => write!(f, "(PacketMsgTypeV2::Timeout)"),
+        }
+    }
+}
+
+/// A single application payload carried by an IBC v2 [`Packet`].
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Payload {
+    pub source_port: PortId,
+    pub destination_port: PortId,
+    pub version: String,
+    pub encoding: String,
+    #[serde(serialize_with = "crate::serializers::ser_hex_upper")]
+    pub value: Vec<u8>,
+}
+
+impl TryFrom<RawPayload> for Payload {
+    type Error = Error;
+
+    fn try_from(raw: RawPayload) -> Result<Self, Self::Error> {
+        if raw.value.is_empty() {
+            return Err(Error::zero_packet_data());
+        }
+
+        Ok(Payload {
+            source_port: raw.source_port.parse().map_err(Error::identifier)?,
+            destination_port: raw.destination_port.parse().map_err(Error::identifier)?,
+            version: raw.version,
+            encoding: raw.encoding,
+            value: raw.value,
+        })
+    }
+}
+
+impl From<Payload> for RawPayload {
+    fn from(payload: Payload) -> Self {
+        RawPayload {
+            source_port: payload.source_port.to_string(),
+            destination_port: payload.destination_port.to_string(),
+            version: payload.version,
+            encoding: payload.encoding,
+            value: payload.value,
+        }
+    }
+}
+
+/// An IBC v2 packet.
+///
+/// Unlike ICS-04 packets, IBC v2 packets are not sent over a channel end.
+/// They are routed between a pair of light clients, identified on either
+/// side by `source_client` and `destination_client`, and can carry several
+/// application payloads at once.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Packet {
+    pub sequence: Sequence,
+    pub source_client: ClientId,
+    pub destination_client: ClientId,
+    pub timeout_timestamp: Timestamp,
+    pub payloads: Vec<Payload>,
+}
+
+impl Packet {
+    /// Checks whether the packet is timed-out relative to the timestamp
+    /// `dst_chain_ts` of the latest block of the destination chain.
+    ///
+    /// IBC v2 packets only support timestamp timeouts.
+    pub fn timed_out(&self, dst_chain_ts: &Timestamp) -> bool {
+        dst_chain_ts.check_expiry(&self.timeout_timestamp) == Expired
+    }
+}
+
+impl core::fmt::Display for Packet {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(
+            f,
+            "seq:{}, path:{}->{}, payloads:{}, tos:{}",
+            self.sequence,
+            self.source_client,
+            self.destination_client,
+            self.payloads.len(),
+            self.timeout_timestamp
+        )
+    }
+}
+
+impl TryFrom<RawPacket> for Packet {
+    type Error = Error;
+
+    fn try_from(raw_pkt: RawPacket) -> Result<Self, Self::Error> {
+        if Sequence::from(raw_pkt.sequence).is_zero() {
+            return Err(Error::zero_packet_sequence());
+        }
+
+        if raw_pkt.payloads.is_empty() {
+            return Err(Error::zero_packet_payloads());
+        }
+
+        // IBC v2 packets express their timeout in seconds rather than nanoseconds
+        let timeout_nanos = raw_pkt
+            .timeout_timestamp
+            .checked_mul(NANOS_PER_SECOND)
+            .ok_or_else(|| Error::packet_timeout_timestamp_overflow(raw_pkt.timeout_timestamp))?;
+
+        let timeout_timestamp =
+            Timestamp::from_nanoseconds(timeout_nanos).map_err(Error::invalid_packet_timestamp)?;
+
+        Ok(Packet {
+            sequence: Sequence::from(raw_pkt.sequence),
+            source_client: raw_pkt.source_client.parse().map_err(Error::identifier)?,
+            destination_client: raw_pkt
+                .destination_client
+                .parse()
+                .map_err(Error::identifier)?,
+            timeout_timestamp,
+            payloads: raw_pkt
+                .payloads
+                .into_iter()
+                .map(Payload::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<Packet> for RawPacket {
+    fn from(packet: Packet) -> Self {
+        RawPacket {
+            sequence: packet.sequence.into(),
+            source_client: packet.source_client.to_string(),
+            destination_client: packet.destination_client.to_string(),
+            timeout_timestamp: packet.timeout_timestamp.nanoseconds() / NANOS_PER_SECOND,
+            payloads: packet.payloads.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// The acknowledgement of an IBC v2 packet, holding one
+/// acknowledgement per payload, in the order of the payloads.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Acknowledgement {
+    pub app_acknowledgements: Vec<Vec<u8>>,
+}
+
+impl From<RawAcknowledgement> for Acknowledgement {
+    fn from(raw: RawAcknowledgement) -> Self {
+        Self {
+            app_acknowledgements: raw.app_acknowledgements,
+        }
+    }
+}
+
+impl From<Acknowledgement> for RawAcknowledgement {
+    fn from(ack: Acknowledgement) -> Self {
+        Self {
+            app_acknowledgements: ack.app_acknowledgements,
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_utils {
+    use crate::core::ics04_channel::v2::raw::{Packet as RawPacket, Payload as RawPayload};
+
+    /// Returns a dummy IBC v2 `RawPacket`, for testing only!
+    pub fn get_dummy_raw_packet_v2(timeout_timestamp: u64) -> RawPacket {
+        RawPacket {
+            sequence: 1,
+            source_client: "07-tendermint-0".to_string(),
+            destination_client: "07-tendermint-1".to_string(),
+            timeout_timestamp,
+            payloads: vec![RawPayload {
+                source_port: "transfer".to_string(),
+                destination_port: "transfer".to_string(),
+                version: "ics20-1".to_string(),
+                encoding: "application/json".to_string(),
+                value: vec![0],
+            }],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::test_utils::get_dummy_raw_packet_v2;
+    use super::*;
+
+    #[test]
+    fn packet_v2_try_from_raw() {
+        let raw = get_dummy_raw_packet_v2(1_700_000_000);
+        let packet = Packet::try_from(raw.clone()).unwrap();
+
+        assert_eq!(
+            packet.timeout_timestamp.nanoseconds(),
+            1_700_000_000 * NANOS_PER_SECOND
+        );
+        assert_eq!(RawPacket::from(packet), raw);
+    }
+
+    #[test]
+    fn packet_v2_try_from_raw_rejects_invalid() {
+        let mut no_payloads = get_dummy_raw_packet_v2(1);
+        no_payloads.payloads.clear();
+        assert!(Packet::try_from(no_payloads).is_err());
+
+        let mut zero_sequence = get_dummy_raw_packet_v2(1);
+        zero_sequence.sequence = 0;
+        assert!(Packet::try_from(zero_sequence).is_err());
+
+        assert!(Packet::try_from(get_dummy_raw_packet_v2(u64::MAX)).is_err());
+    }
+}
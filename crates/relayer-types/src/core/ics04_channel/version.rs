@@ -27,6 +27,10 @@ impl Version {
         Self::new(transfer::VERSION.to_string())
     }
 
+    pub fn ics20_v2() -> Self {
+        Self::new(transfer::VERSION_V2.to_string())
+    }
+
     pub fn ics20_with_fee() -> Self {
         let val = json::json!({
             "fee_version": "ics29-1",
@@ -128,6 +128,65 @@ pub struct ReceiptsPath {
     pub sequence: Sequence,
 }
 
+/// Key prefixes of the IBC v2 packet paths.
+/// - The key identifying a packet commitment
+const PACKET_COMMITMENT_V2_PREFIX: u8 = 1;
+/// - The key identifying a packet receipt
+const PACKET_RECEIPT_V2_PREFIX: u8 = 2;
+/// - The key identifying a packet acknowledgement
+const PACKET_ACK_V2_PREFIX: u8 = 3;
+
+/// Builds the binary key under which IBC v2 packet state is stored,
+/// ie. `clientID | prefix | bigEndian(sequence)`.
+fn packet_v2_key(client_id: &ClientId, prefix: u8, sequence: Sequence) -> Vec<u8> {
+    let mut key = client_id.as_str().as_bytes().to_vec();
+    key.push(prefix);
+    key.extend_from_slice(&u64::from(sequence).to_be_bytes());
+    key
+}
+
+/// Path of the commitment of an IBC v2 packet.
+///
+/// Unlike the ICS-024 paths above, IBC v2 packet paths are binary keys
+/// and are therefore not part of [`Path`], which only holds string paths.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CommitmentsV2Path {
+    pub client_id: ClientId,
+    pub sequence: Sequence,
+}
+
+impl CommitmentsV2Path {
+    pub fn into_bytes(self) -> Vec<u8> {
+        packet_v2_key(&self.client_id, PACKET_COMMITMENT_V2_PREFIX, self.sequence)
+    }
+}
+
+/// Path of the receipt of an IBC v2 packet.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ReceiptsV2Path {
+    pub client_id: ClientId,
+    pub sequence: Sequence,
+}
+
+impl ReceiptsV2Path {
+    pub fn into_bytes(self) -> Vec<u8> {
+        packet_v2_key(&self.client_id, PACKET_RECEIPT_V2_PREFIX, self.sequence)
+    }
+}
+
+/// Path of the acknowledgement of an IBC v2 packet.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AcksV2Path {
+    pub client_id: ClientId,
+    pub sequence: Sequence,
+}
+
+impl AcksV2Path {
+    pub fn into_bytes(self) -> Vec<u8> {
+        packet_v2_key(&self.client_id, PACKET_ACK_V2_PREFIX, self.sequence)
+    }
+}
+
 /// Paths that are specific for client upgrades.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
 pub enum ClientUpgradePath {
@@ -1002,4 +1061,42 @@ mod tests {
             Path::Upgrade(ClientUpgradePath::UpgradedClientConsensusState(0)),
         );
     }
+
+    #[test]
+    fn packet_v2_paths_into_bytes() {
+        let client_id = ClientId::from_str("07-tendermint-0").unwrap();
+        let sequence = Sequence::from(258);
+
+        let mut expected = b"07-tendermint-0".to_vec();
+        expected.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 1, 2]);
+
+        assert_eq!(
+            CommitmentsV2Path {
+                client_id: client_id.clone(),
+                sequence,
+            }
+            .into_bytes(),
+            expected
+        );
+
+        expected[15] = 2;
+        assert_eq!(
+            ReceiptsV2Path {
+                client_id: client_id.clone(),
+                sequence,
+            }
+            .into_bytes(),
+            expected
+        );
+
+        expected[15] = 3;
+        assert_eq!(
+            AcksV2Path {
+                client_id,
+                sequence
+            }
+            .into_bytes(),
+            expected
+        );
+    }
 }
@@ -20,6 +20,8 @@ use crate::core::ics04_channel::error as channel_error;
 use crate::core::ics04_channel::events::Attributes as ChannelAttributes;
 use crate::core::ics04_channel::events::{self as ChannelEvents, UpgradeAttributes};
 use crate::core::ics04_channel::packet::Packet;
+use crate::core::ics04_channel::v2::events as ChannelEventsV2;
+use crate::core::ics04_channel::v2::packet::Packet as PacketV2;
 use crate::core::ics24_host::error::ValidationError;
 use crate::timestamp::ParseTimestampError;
 use crate::utils::pretty::PrettySlice;
@@ -318,6 +320,11 @@ pub enum IbcEvent {
     TimeoutPacket(ChannelEvents::TimeoutPacket),
     TimeoutOnClosePacket(ChannelEvents::TimeoutOnClosePacket),
 
+    SendPacketV2(ChannelEventsV2::SendPacket),
+    WriteAcknowledgementV2(ChannelEventsV2::WriteAcknowledgement),
+    AcknowledgePacketV2(ChannelEventsV2::AcknowledgePacket),
+    TimeoutPacketV2(ChannelEventsV2::TimeoutPacket),
+
     IncentivizedPacket(IncentivizedPacket),
     CrossChainQueryPacket(CrossChainQueryPacket),
 
@@ -365,6 +372,11 @@ impl Display for IbcEvent {
             IbcEvent::TimeoutPacket(ev) => write!(f, "TimeoutPacket({ev})"),
             IbcEvent::TimeoutOnClosePacket(ev) => write!(f, "TimeoutOnClosePacket({ev})"),
 
+            IbcEvent::SendPacketV2(ev) => write!(f, "SendPacketV2({ev})"),
+            IbcEvent::WriteAcknowledgementV2(ev) => write!(f, "WriteAcknowledgementV2({ev})"),
+            IbcEvent::AcknowledgePacketV2(ev) => write!(f, "AcknowledgePacketV2({ev})"),
+            IbcEvent::TimeoutPacketV2(ev) => write!(f, "TimeoutPacketV2({ev})"),
+
             IbcEvent::IncentivizedPacket(ev) => write!(f, "IncenvitizedPacket({ev:?}"),
             IbcEvent::CrossChainQueryPacket(ev) => write!(f, "CrosschainPacket({ev:?})"),
 
@@ -416,6 +428,11 @@ impl IbcEvent {
             IbcEvent::AcknowledgePacket(_) => IbcEventType::AckPacket,
             IbcEvent::TimeoutPacket(_) => IbcEventType::Timeout,
             IbcEvent::TimeoutOnClosePacket(_) => IbcEventType::TimeoutOnClose,
+            // IBC v2 packet events share their event type with ICS-04 packet events
+            IbcEvent::SendPacketV2(_) => IbcEventType::SendPacket,
+            IbcEvent::WriteAcknowledgementV2(_) => IbcEventType::WriteAck,
+            IbcEvent::AcknowledgePacketV2(_) => IbcEventType::AckPacket,
+            IbcEvent::TimeoutPacketV2(_) => IbcEventType::Timeout,
             IbcEvent::IncentivizedPacket(_) => IbcEventType::IncentivizedPacket,
             IbcEvent::CrossChainQueryPacket(_) => IbcEventType::CrossChainQuery,
             IbcEvent::DistributeFeePacket(_) => IbcEventType::DistributionFee,
@@ -470,6 +487,16 @@ impl IbcEvent {
         }
     }
 
+    pub fn packet_v2(&self) -> Option<&PacketV2> {
+        match self {
+            IbcEvent::SendPacketV2(ev) => Some(&ev.packet),
+            IbcEvent::WriteAcknowledgementV2(ev) => Some(&ev.packet),
+            IbcEvent::AcknowledgePacketV2(ev) => Some(&ev.packet),
+            IbcEvent::TimeoutPacketV2(ev) => Some(&ev.packet),
+            _ => None,
+        }
+    }
+
     pub fn cross_chain_query_packet(&self) -> Option<&CrossChainQueryPacket> {
         match self {
             IbcEvent::CrossChainQueryPacket(ev) => Some(ev),
@@ -1,9 +1,12 @@
+pub mod capabilities;
 pub mod client;
 pub mod client_settings;
 pub mod cosmos;
 pub mod counterparty;
 pub mod endpoint;
 pub mod handle;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
 pub mod namada;
 pub mod penumbra;
 pub mod requests;
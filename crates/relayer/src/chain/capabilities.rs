@@ -0,0 +1,158 @@
+//! Description of the features supported by a chain backend.
+//!
+//! Not every chain supported by Hermes implements every IBC application or
+//! every query needed by the relayer. Each [`ChainEndpoint`] therefore describes
+//! the features it supports with [`ChainCapabilities`], which the supervisor,
+//! the workers and the CLI consult before attempting any work which requires them.
+//!
+//! [`ChainEndpoint`]: crate::chain::endpoint::ChainEndpoint
+
+use core::fmt::{Display, Error as FmtError, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::error::Error;
+
+/// A feature which may not be supported by every chain backend.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    /// ICS-29 fee middleware
+    Fees,
+    /// ICS-31 cross-chain queries
+    CrossChainQueries,
+    /// ICS-04 channel upgrades
+    ChannelUpgrades,
+    /// ICS-28 cross-chain validation
+    Ccv,
+    /// Balance queries for the relayer's wallet
+    Balances,
+    /// Push-based event source, via WebSocket
+    PushEvents,
+    /// ICS-20 denom trace queries
+    DenomTraces,
+    /// ICS-27 interchain accounts
+    InterchainAccounts,
+    /// ICS-721 NFT transfers
+    NftTransfers,
+    /// IBC v2 packets
+    IbcV2,
+    /// Estimation of the fees to pay for a transaction
+    FeeEstimation,
+    /// IBC transfers from or to a MASP shielded pool
+    ShieldedTransfers,
+}
+
+impl Display for Capability {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::Fees => write!(f, "ICS-29 fee middleware"),
+            Self::CrossChainQueries => write!(f, "ICS-31 cross-chain queries"),
+            Self::ChannelUpgrades => write!(f, "channel upgrades"),
+            Self::Ccv => write!(f, "ICS-28 cross-chain validation"),
+            Self::Balances => write!(f, "balance queries"),
+            Self::PushEvents => write!(f, "push-based event source"),
+            Self::DenomTraces => write!(f, "denom trace queries"),
+            Self::InterchainAccounts => write!(f, "ICS-27 interchain accounts"),
+            Self::NftTransfers => write!(f, "ICS-721 NFT transfers"),
+            Self::IbcV2 => write!(f, "IBC v2 packets"),
+            Self::FeeEstimation => write!(f, "fee estimation"),
+            Self::ShieldedTransfers => write!(f, "MASP shielded transfers"),
+        }
+    }
+}
+
+/// The features supported by a chain backend.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainCapabilities {
+    pub fees: bool,
+    pub cross_chain_queries: bool,
+    pub channel_upgrades: bool,
+    pub ccv: bool,
+    pub balances: bool,
+    pub push_events: bool,
+    pub denom_traces: bool,
+    pub interchain_accounts: bool,
+    pub nft_transfers: bool,
+    pub ibc_v2: bool,
+    pub fee_estimation: bool,
+    pub shielded_transfers: bool,
+}
+
+impl ChainCapabilities {
+    /// A chain backend which supports every feature
+    pub const fn all() -> Self {
+        Self {
+            fees: true,
+            cross_chain_queries: true,
+            channel_upgrades: true,
+            ccv: true,
+            balances: true,
+            push_events: true,
+            denom_traces: true,
+            interchain_accounts: true,
+            nft_transfers: true,
+            ibc_v2: true,
+            fee_estimation: true,
+            shielded_transfers: true,
+        }
+    }
+
+    pub fn supports(&self, capability: Capability) -> bool {
+        match capability {
+            Capability::Fees => self.fees,
+            Capability::CrossChainQueries => self.cross_chain_queries,
+            Capability::ChannelUpgrades => self.channel_upgrades,
+            Capability::Ccv => self.ccv,
+            Capability::Balances => self.balances,
+            Capability::PushEvents => self.push_events,
+            Capability::DenomTraces => self.denom_traces,
+            Capability::InterchainAccounts => self.interchain_accounts,
+            Capability::NftTransfers => self.nft_transfers,
+            Capability::IbcV2 => self.ibc_v2,
+            Capability::FeeEstimation => self.fee_estimation,
+            Capability::ShieldedTransfers => self.shielded_transfers,
+        }
+    }
+
+    /// Returns an [`Error::unsupported_capability`] error if the given
+    /// chain does not support the given capability.
+    pub fn ensure(&self, chain_id: &ChainId, capability: Capability) -> Result<(), Error> {
+        if self.supports(capability) {
+            Ok(())
+        } else {
+            Err(Error::unsupported_capability(chain_id.clone(), capability))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ensure_capability() {
+        let chain_id = ChainId::from_string("chain-0");
+
+        let capabilities = ChainCapabilities {
+            fees: false,
+            ..ChainCapabilities::all()
+        };
+
+        assert!(capabilities
+            .ensure(&chain_id, Capability::ChannelUpgrades)
+            .is_ok());
+
+        let error = capabilities
+            .ensure(&chain_id, Capability::Fees)
+            .unwrap_err();
+
+        assert!(error.is_unsupported_error());
+        assert_eq!(
+            error.detail().to_string(),
+            "chain 'chain-0' does not support ICS-29 fee middleware"
+        );
+    }
+}
@@ -11,9 +11,10 @@ use prost::Message;
 use std::cmp::Ordering;
 use std::thread;
 use tokio::runtime::Runtime as TokioRuntime;
+use tonic::codegen::http::uri::PathAndQuery;
 use tonic::codegen::http::Uri;
 use tonic::metadata::AsciiMetadataValue;
-use tracing::{debug, error, instrument, trace, warn};
+use tracing::{debug, error, info, instrument, trace, warn};
 
 use ibc_proto::cosmos::base::node::v1beta1::ConfigResponse;
 use ibc_proto::cosmos::staking::v1beta1::{Params as StakingParams, QueryParamsResponse};
@@ -24,7 +25,9 @@ use ibc_proto::ibc::core::channel::v1::{QueryUpgradeErrorRequest, QueryUpgradeRe
 use ibc_proto::interchain_security::ccv::v1::ConsumerParams as CcvConsumerParams;
 use ibc_proto::Protobuf;
 use ibc_relayer_types::applications::ics28_ccv::msgs::{ConsumerChain, ConsumerId};
+use ibc_relayer_types::applications::ics29_fee::packet_fee::IdentifiedPacketFees;
 use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
+use ibc_relayer_types::applications::transfer::RawCoin;
 use ibc_relayer_types::clients::ics07_tendermint::client_state::{
     AllowUpdate, ClientState as TmClientState,
 };
@@ -39,19 +42,22 @@ use ibc_relayer_types::core::ics03_connection::connection::{
 use ibc_relayer_types::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
 use ibc_relayer_types::core::ics04_channel::channel::{State, UpgradeState};
 use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics04_channel::v2::raw::{
+    QueryPacketCommitmentsRequest as RawQueryPacketCommitmentsV2Request,
+    QueryPacketCommitmentsResponse as RawQueryPacketCommitmentsV2Response,
+    QUERY_PACKET_COMMITMENTS_PATH as QUERY_PACKET_COMMITMENTS_V2_PATH,
+};
 use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentPrefix;
 use ibc_relayer_types::core::ics23_commitment::merkle::MerkleProof;
 use ibc_relayer_types::core::ics24_host::identifier::{
     ChainId, ChannelId, ClientId, ConnectionId, PortId,
 };
 use ibc_relayer_types::core::ics24_host::path::{
-    AcksPath, ChannelEndsPath, ChannelUpgradeErrorPath, ChannelUpgradePath,
-    ClientConsensusStatePath, ClientStatePath, CommitmentsPath, ConnectionsPath, ReceiptsPath,
-    SeqRecvsPath,
-};
-use ibc_relayer_types::core::ics24_host::{
-    ClientUpgradePath, Path, IBC_QUERY_PATH, SDK_UPGRADE_QUERY_PATH,
+    AcksPath, AcksV2Path, ChannelEndsPath, ChannelUpgradeErrorPath, ChannelUpgradePath,
+    ClientConsensusStatePath, ClientStatePath, CommitmentsPath, CommitmentsV2Path, ConnectionsPath,
+    ReceiptsPath, ReceiptsV2Path, SeqRecvsPath,
 };
+use ibc_relayer_types::core::ics24_host::{ClientUpgradePath, Path};
 use ibc_relayer_types::core::{
     ics02_client::height::Height, ics04_channel::upgrade::ErrorReceipt,
     ics04_channel::upgrade::Upgrade,
@@ -69,24 +75,32 @@ use tendermint_rpc::endpoint::status;
 use tendermint_rpc::{Client, HttpClient, Order};
 
 use crate::account::Balance;
+use crate::chain::capabilities::{Capability, ChainCapabilities};
 use crate::chain::client::ClientSettings;
+use crate::chain::cosmos::backend::decode_events;
 use crate::chain::cosmos::batch::{
     send_batched_messages_and_wait_check_tx, send_batched_messages_and_wait_commit,
     sequential_send_batched_messages_and_wait_commit,
 };
 use crate::chain::cosmos::encode::key_pair_to_signer;
+use crate::chain::cosmos::estimate::estimate_tx_fees;
 use crate::chain::cosmos::fee::maybe_register_counterparty_payee;
+use crate::chain::cosmos::fee_grant::tx_config_with_fee_granter;
+use crate::chain::cosmos::fixture::FixtureServer;
 use crate::chain::cosmos::gas::{calculate_fee, mul_ceil};
 use crate::chain::cosmos::query::account::get_or_fetch_account;
 use crate::chain::cosmos::query::balance::{query_all_balances, query_balance};
+use crate::chain::cosmos::query::class_trace::query_class_trace;
 use crate::chain::cosmos::query::connection::query_connection_params;
 use crate::chain::cosmos::query::consensus_state::query_consensus_state_heights;
 use crate::chain::cosmos::query::custom::cross_chain_query_via_rpc;
 use crate::chain::cosmos::query::denom_trace::query_denom_trace;
-use crate::chain::cosmos::query::fee::query_incentivized_packet;
+use crate::chain::cosmos::query::fee::{query_incentivized_packet, query_incentivized_packets};
+use crate::chain::cosmos::query::ica::query_interchain_account;
 use crate::chain::cosmos::query::status::query_status;
 use crate::chain::cosmos::query::tx::{
-    filter_matching_event, query_packets_from_block, query_packets_from_txs, query_txs,
+    filter_matching_event, query_packets_from_block, query_packets_from_txs,
+    query_packets_v2_from_txs, query_tx_fee, query_txs,
 };
 use crate::chain::cosmos::query::{abci_query, fetch_version_specs, packet_query, QueryResponse};
 use crate::chain::cosmos::types::account::Account;
@@ -101,7 +115,7 @@ use crate::chain::tracking::TrackedMsgs;
 use crate::chain::version::Specs;
 use crate::client_state::{AnyClientState, IdentifiedAnyClientState};
 use crate::config::Error as ConfigError;
-use crate::config::{parse_gas_prices, ChainConfig, GasPrice};
+use crate::config::{parse_gas_prices, ChainConfig, EventSourceMode, GasPrice};
 use crate::consensus_state::AnyConsensusState;
 use crate::denom::DenomTrace;
 use crate::error::Error;
@@ -111,6 +125,8 @@ use crate::keyring::{KeyRing, Secp256k1KeyPair, SigningKeyPair};
 use crate::light_client::tendermint::LightClient as TmLightClient;
 use crate::light_client::{LightClient, Verified};
 use crate::misbehaviour::MisbehaviourEvidence;
+use crate::nft_transfer::ClassTrace;
+use crate::transfer::{IbcShielding, IbcShieldingRequest, ShieldedTransferRequest};
 use crate::util::collate::CollatedIterExt;
 use crate::util::create_grpc_client;
 use crate::util::pretty::{
@@ -121,6 +137,7 @@ use crate::HERMES_VERSION;
 use self::gas::dynamic_gas_price;
 use self::types::gas::GasConfig;
 
+pub mod backend;
 pub mod batch;
 pub mod client;
 pub mod compatibility;
@@ -129,8 +146,12 @@ pub mod eip_base_fee;
 pub mod encode;
 pub mod estimate;
 pub mod fee;
+pub mod fee_grant;
+pub mod fixture;
 pub mod gas;
+pub mod gas_model;
 pub mod query;
+pub mod replace;
 pub mod retry;
 pub mod simulate;
 pub mod tx;
@@ -168,6 +189,9 @@ pub struct CosmosSdkChain {
     account: Option<Account>,
 
     tx_monitor_cmd: Option<TxEventSourceCmd>,
+
+    /// The server through which the traffic to the node is recorded, if enabled
+    fixture_recorder: Option<FixtureServer>,
 }
 
 impl CosmosSdkChain {
@@ -363,6 +387,8 @@ impl CosmosSdkChain {
         }
         .map_err(Error::event_source)?;
 
+        let event_source = event_source.with_backend(self.tx_config.backend.clone());
+
         thread::spawn(move || event_source.run());
 
         Ok(monitor_tx)
@@ -559,7 +585,7 @@ impl CosmosSdkChain {
         let response = self.block_on(abci_query(
             &self.rpc_client,
             &self.config.rpc_addr,
-            IBC_QUERY_PATH.to_string(),
+            self.config.ibc_query_path(),
             data.to_string(),
             height_query.into(),
             prove,
@@ -570,6 +596,34 @@ impl CosmosSdkChain {
         Ok(response)
     }
 
+    /// Perform an ABCI query for a raw (binary) key in the IBC store.
+    ///
+    /// Used for the IBC v2 packet keys, which are not representable as a [`Path`].
+    fn query_raw_key(
+        &self,
+        key: Vec<u8>,
+        height_query: QueryHeight,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        let res = self.block_on(abci_query(
+            &self.rpc_client,
+            &self.config.rpc_addr,
+            self.config.ibc_query_path(),
+            key,
+            height_query.into(),
+            matches!(include_proof, IncludeProof::Yes),
+        ))?;
+
+        match include_proof {
+            IncludeProof::Yes => {
+                let proof = res.proof.ok_or_else(Error::empty_response_proof)?;
+
+                Ok((res.value, Some(proof)))
+            }
+            IncludeProof::No => Ok((res.value, None)),
+        }
+    }
+
     /// Perform an ABCI query against the client upgrade sub-store.
     ///
     /// The data is returned in its raw format `Vec<u8>`, and is either the
@@ -592,7 +646,7 @@ impl CosmosSdkChain {
         query_data: ClientUpgradePath,
         query_height: ICSHeight,
     ) -> Result<(Vec<u8>, MerkleProof), Error> {
-        let path = SDK_UPGRADE_QUERY_PATH.into();
+        let path = self.config.upgrade_query_path();
 
         let response: QueryResponse = self.block_on(abci_query(
             &self.rpc_client,
@@ -712,10 +766,13 @@ impl CosmosSdkChain {
             self.config.memo_prefix.clone()
         };
 
+        let tx_config =
+            tx_config_with_fee_granter(&self.tx_config, &key_account, &proto_msgs).await;
+
         if self.config.sequential_batch_tx {
             sequential_send_batched_messages_and_wait_commit(
                 &self.rpc_client,
-                &self.tx_config,
+                &tx_config,
                 &key_pair,
                 account,
                 &memo_prefix,
@@ -725,7 +782,7 @@ impl CosmosSdkChain {
         } else {
             send_batched_messages_and_wait_commit(
                 &self.rpc_client,
-                &self.tx_config,
+                &tx_config,
                 &key_pair,
                 account,
                 &memo_prefix,
@@ -769,9 +826,12 @@ impl CosmosSdkChain {
             self.config.memo_prefix.clone()
         };
 
+        let tx_config =
+            tx_config_with_fee_granter(&self.tx_config, &key_account, &proto_msgs).await;
+
         send_batched_messages_and_wait_check_tx(
             &self.rpc_client,
-            &self.tx_config,
+            &tx_config,
             &key_pair,
             account,
             &memo_prefix,
@@ -780,6 +840,38 @@ impl CosmosSdkChain {
         .await
     }
 
+    async fn do_estimate_fee(&mut self, tracked_msgs: TrackedMsgs) -> Result<Vec<RawCoin>, Error> {
+        crate::time!(
+            "estimate_fee",
+            {
+                "src_chain": self.config().id.to_string(),
+            }
+        );
+
+        let key_pair = self.key()?;
+        let key_account = key_pair.account();
+
+        let account =
+            get_or_fetch_account(&self.grpc_addr, &key_account, &mut self.account).await?;
+
+        let (fee, _) = estimate_tx_fees(
+            &self.tx_config,
+            &key_pair,
+            account,
+            &self.config.memo_prefix,
+            &tracked_msgs.msgs,
+        )
+        .await?;
+
+        fee.amount
+            .into_iter()
+            .map(|coin| {
+                let fee = format!("{}{}", coin.amount, coin.denom);
+                RawCoin::try_from(coin).map_err(|e| Error::invalid_estimated_fee(fee, e))
+            })
+            .collect()
+    }
+
     fn query_packet_from_block(
         &self,
         request: &QueryPacketEventDataRequest,
@@ -804,29 +896,28 @@ impl CosmosSdkChain {
         let response_height = ICSHeight::new(self.id().version(), u64::from(response.height))
             .map_err(|_| Error::invalid_height_no_source())?;
 
-        let begin_block_events = response
-            .begin_block_events
-            .unwrap_or_default()
-            .iter()
-            .filter_map(|ev| filter_matching_event(ev, request, seqs))
-            .map(|ev| IbcEventWithHeight::new(ev, response_height))
-            .collect();
+        let backend = self.tx_config.backend.as_ref();
 
-        let mut end_block_events: Vec<_> = response
-            .end_block_events
-            .unwrap_or_default()
-            .iter()
-            .filter_map(|ev| filter_matching_event(ev, request, seqs))
-            .map(|ev| IbcEventWithHeight::new(ev, response_height))
-            .collect();
+        let begin_block_events =
+            decode_events(backend, response.begin_block_events.unwrap_or_default())
+                .iter()
+                .filter_map(|ev| filter_matching_event(ev, request, seqs))
+                .map(|ev| IbcEventWithHeight::new(ev, response_height))
+                .collect();
+
+        let mut end_block_events: Vec<_> =
+            decode_events(backend, response.end_block_events.unwrap_or_default())
+                .iter()
+                .filter_map(|ev| filter_matching_event(ev, request, seqs))
+                .map(|ev| IbcEventWithHeight::new(ev, response_height))
+                .collect();
 
         // Since CometBFT 0.38, block events are returned in the
         // finalize_block_events field and the other *_block_events fields
         // are no longer present. We put these in place of the end_block_events
         // in older protocol.
         end_block_events.extend(
-            response
-                .finalize_block_events
+            decode_events(backend, response.finalize_block_events)
                 .iter()
                 .filter_map(|ev| filter_matching_event(ev, request, seqs))
                 .map(|ev| IbcEventWithHeight::new(ev, response_height)),
@@ -911,11 +1002,41 @@ impl ChainEndpoint for CosmosSdkChain {
 
     fn bootstrap(config: ChainConfig, rt: Arc<TokioRuntime>) -> Result<Self, Error> {
         #[allow(irrefutable_let_patterns)]
-        let ChainConfig::CosmosSdk(config) = config
+        let ChainConfig::CosmosSdk(mut config) = config
         else {
             return Err(Error::config(ConfigError::wrong_type()));
         };
 
+        let fixture_recorder = match &config.record_fixtures {
+            Some(path) => {
+                let websocket_addr = match &config.event_source {
+                    EventSourceMode::Push { url, .. } => Some(url),
+                    EventSourceMode::Pull { .. } => None,
+                };
+
+                let recorder = rt.block_on(FixtureServer::record(
+                    path,
+                    &config.rpc_addr,
+                    &config.grpc_addr,
+                    websocket_addr,
+                ))?;
+
+                info!(
+                    "recording the RPC traffic of chain '{}' to `{}`",
+                    config.id,
+                    path.display()
+                );
+
+                config.rpc_addr = recorder.url();
+                config.grpc_addr = recorder.url();
+                if let EventSourceMode::Push { url, .. } = &mut config.event_source {
+                    *url = recorder.websocket_url();
+                }
+                Some(recorder)
+            }
+            None => None,
+        };
+
         let mut rpc_client = HttpClient::builder(config.rpc_addr.clone().try_into().unwrap())
             .user_agent(format!("hermes/{}", HERMES_VERSION))
             .build()
@@ -939,7 +1060,9 @@ impl ChainEndpoint for CosmosSdkChain {
         let grpc_addr = Uri::from_str(&config.grpc_addr.to_string())
             .map_err(|e| Error::invalid_uri(config.grpc_addr.to_string(), e))?;
 
-        let tx_config = TxConfig::try_from(&config)?;
+        let mut tx_config = TxConfig::try_from(&config)?;
+        tx_config.unordered_tx_timeout =
+            rt.block_on(fetch_unordered_tx_timeout(&rpc_client, &config));
 
         // Retrieve the version specification of this chain
 
@@ -954,6 +1077,7 @@ impl ChainEndpoint for CosmosSdkChain {
             tx_config,
             account: None,
             tx_monitor_cmd: None,
+            fixture_recorder,
         };
 
         Ok(chain)
@@ -964,6 +1088,11 @@ impl ChainEndpoint for CosmosSdkChain {
             monitor_tx.shutdown().map_err(Error::event_source)?;
         }
 
+        // Stop recording after the event source, which queries the node too
+        drop(self.fixture_recorder);
+
+        self.tx_config.gas_model.flush();
+
         Ok(())
     }
 
@@ -1098,6 +1227,44 @@ impl ChainEndpoint for CosmosSdkChain {
         runtime.block_on(self.do_send_messages_and_wait_check_tx(tracked_msgs))
     }
 
+    fn estimate_fee(&mut self, tracked_msgs: TrackedMsgs) -> Result<Vec<RawCoin>, Error> {
+        let runtime = self.rt.clone();
+
+        runtime.block_on(self.do_estimate_fee(tracked_msgs))
+    }
+
+    fn query_tx_fee(&self, tx_hash: QueryTxHash) -> Result<Vec<RawCoin>, Error> {
+        crate::time!(
+            "query_tx_fee",
+            {
+                "src_chain": self.config().id.to_string(),
+            }
+        );
+
+        self.block_on(query_tx_fee(
+            &self.rpc_client,
+            &self.config.rpc_addr,
+            &tx_hash.0,
+        ))
+    }
+
+    fn build_ibc_shielding(&self, _request: IbcShieldingRequest) -> Result<IbcShielding, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::ShieldedTransfers,
+        ))
+    }
+
+    fn send_shielded_transfer(
+        &mut self,
+        _request: ShieldedTransferRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::ShieldedTransfers,
+        ))
+    }
+
     /// Get the account for the signer
     fn get_signer(&self) -> Result<Signer, Error> {
         // Get the key from key seed file
@@ -1122,6 +1289,13 @@ impl ChainEndpoint for CosmosSdkChain {
         Ok(Specs::Cosmos(version_specs))
     }
 
+    fn capabilities(&self) -> ChainCapabilities {
+        ChainCapabilities {
+            shielded_transfers: false,
+            ..ChainCapabilities::all()
+        }
+    }
+
     fn query_balance(&self, key_name: Option<&str>, denom: Option<&str>) -> Result<Balance, Error> {
         crate::time!(
             "query_balance",
@@ -1169,6 +1343,22 @@ impl ChainEndpoint for CosmosSdkChain {
         Ok(denom_trace)
     }
 
+    fn query_class_trace(&self, hash: String) -> Result<ClassTrace, Error> {
+        self.block_on(query_class_trace(&self.grpc_addr, &hash))
+    }
+
+    fn query_interchain_account(
+        &self,
+        owner: Signer,
+        connection_id: ConnectionId,
+    ) -> Result<String, Error> {
+        self.block_on(query_interchain_account(
+            &self.grpc_addr,
+            &owner,
+            &connection_id,
+        ))
+    }
+
     fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error> {
         crate::time!(
             "query_commitment_prefix",
@@ -2201,6 +2391,163 @@ impl ChainEndpoint for CosmosSdkChain {
         }
     }
 
+    fn query_packet_commitment_v2(
+        &self,
+        request: QueryPacketCommitmentV2Request,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        crate::time!(
+            "query_packet_commitment_v2",
+            {
+                "src_chain": self.config().id.to_string(),
+            }
+        );
+        crate::telemetry!(query, self.id(), "query_packet_commitment_v2");
+
+        let key = CommitmentsV2Path {
+            client_id: request.client_id,
+            sequence: request.sequence,
+        }
+        .into_bytes();
+
+        self.query_raw_key(key, request.height, include_proof)
+    }
+
+    fn query_packet_receipt_v2(
+        &self,
+        request: QueryPacketReceiptV2Request,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        crate::time!(
+            "query_packet_receipt_v2",
+            {
+                "src_chain": self.config().id.to_string(),
+            }
+        );
+        crate::telemetry!(query, self.id(), "query_packet_receipt_v2");
+
+        let key = ReceiptsV2Path {
+            client_id: request.client_id,
+            sequence: request.sequence,
+        }
+        .into_bytes();
+
+        self.query_raw_key(key, request.height, include_proof)
+    }
+
+    fn query_packet_acknowledgement_v2(
+        &self,
+        request: QueryPacketAcknowledgementV2Request,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        crate::time!(
+            "query_packet_acknowledgement_v2",
+            {
+                "src_chain": self.config().id.to_string(),
+            }
+        );
+        crate::telemetry!(query, self.id(), "query_packet_acknowledgement_v2");
+
+        let key = AcksV2Path {
+            client_id: request.client_id,
+            sequence: request.sequence,
+        }
+        .into_bytes();
+
+        self.query_raw_key(key, request.height, include_proof)
+    }
+
+    fn query_packet_commitments_v2(
+        &self,
+        request: QueryPacketCommitmentsV2Request,
+    ) -> Result<(Vec<Sequence>, ICSHeight), Error> {
+        crate::time!(
+            "query_packet_commitments_v2",
+            {
+                "src_chain": self.config().id.to_string(),
+            }
+        );
+        crate::telemetry!(query, self.id(), "query_packet_commitments_v2");
+
+        let client = self.block_on(create_grpc_client(
+            &self.grpc_addr,
+            tonic::client::Grpc::new,
+        ))?;
+        let mut client = client
+            .max_decoding_message_size(self.config().max_grpc_decoding_size.get_bytes() as usize);
+
+        let path = PathAndQuery::from_static(QUERY_PACKET_COMMITMENTS_V2_PATH);
+        let mut raw_request = RawQueryPacketCommitmentsV2Request::from(request);
+
+        let mut sequences = Vec::new();
+        let mut height = None;
+
+        loop {
+            let response = self.block_on(async {
+                client.ready().await.map_err(Error::grpc_transport)?;
+
+                client
+                    .unary(
+                        tonic::Request::new(raw_request.clone()),
+                        path.clone(),
+                        tonic::codec::ProstCodec::default(),
+                    )
+                    .await
+                    .map_err(|e| Error::grpc_status(e, "query_packet_commitments_v2".to_owned()))
+            })?;
+
+            let response: RawQueryPacketCommitmentsV2Response = response.into_inner();
+
+            sequences.extend(
+                response
+                    .commitments
+                    .iter()
+                    .map(|commitment| Sequence::from(commitment.sequence)),
+            );
+
+            // Keep the height of the first page, which all sequences are committed at
+            if height.is_none() {
+                height = response.height;
+            }
+
+            match (response.pagination, raw_request.pagination.as_mut()) {
+                (Some(page), Some(next)) if !page.next_key.is_empty() => {
+                    next.key = page.next_key;
+                }
+                _ => break,
+            }
+        }
+
+        let height = height
+            .and_then(|raw_height| raw_height.try_into().ok())
+            .ok_or_else(|| Error::grpc_response_param("height".to_string()))?;
+
+        sequences.sort_unstable();
+
+        Ok((sequences, height))
+    }
+
+    fn query_packet_events_v2(
+        &self,
+        request: QueryPacketEventDataV2Request,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        crate::time!(
+            "query_packet_events_v2",
+            {
+                "src_chain": self.config().id.to_string(),
+            }
+        );
+        crate::telemetry!(query, self.id(), "query_packet_events_v2");
+
+        self.block_on(query_packets_v2_from_txs(
+            self.id(),
+            &self.rpc_client,
+            &self.config.rpc_addr,
+            self.tx_config.backend.as_ref(),
+            &request,
+        ))
+    }
+
     /// Performs a `QueryUnreceivedAcksRequest` gRPC query to fetch the unreceived acknowledgements
     /// sequences associated with a channel.
     fn query_unreceived_acknowledgements(
@@ -2291,7 +2638,9 @@ impl ChainEndpoint for CosmosSdkChain {
             self.id(),
             &self.rpc_client,
             &self.config.rpc_addr,
+            self.tx_config.backend.as_ref(),
             request,
+            Some(&self.tx_config.gas_model),
         ))
     }
 
@@ -2324,6 +2673,7 @@ impl ChainEndpoint for CosmosSdkChain {
                 self.id(),
                 &self.rpc_client,
                 &self.config.rpc_addr,
+                self.tx_config.backend.as_ref(),
                 &request,
             )),
             Qualified::SmallerEqual(_) => {
@@ -2331,6 +2681,7 @@ impl ChainEndpoint for CosmosSdkChain {
                     self.id(),
                     &self.rpc_client,
                     &self.config.rpc_addr,
+                    self.tx_config.backend.as_ref(),
                     &request,
                 ))?;
 
@@ -2544,6 +2895,26 @@ impl ChainEndpoint for CosmosSdkChain {
         Ok(incentivized_response)
     }
 
+    fn query_incentivized_packets(
+        &self,
+        channel_id: ChannelId,
+        port_id: PortId,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error> {
+        crate::time!(
+            "query_incentivized_packets",
+            {
+                "src_chain": self.config().id.to_string(),
+            }
+        );
+        crate::telemetry!(query, self.id(), "query_incentivized_packets");
+
+        self.block_on(query_incentivized_packets(
+            &self.grpc_addr,
+            &channel_id,
+            &port_id,
+        ))
+    }
+
     fn query_consumer_chains(&self) -> Result<Vec<ConsumerChain>, Error> {
         use ibc_proto::interchain_security::ccv::provider::v1::ConsumerPhase;
         use ibc_proto::interchain_security::ccv::provider::v1::QueryConsumerChainsRequest;
@@ -2823,6 +3194,36 @@ fn do_health_check(chain: &CosmosSdkChain) -> Result<(), Error> {
     Ok(())
 }
 
+/// Returns the timeout of the unordered Txs to submit to the chain, provided that
+/// unordered Txs are enabled in the configuration and supported by the chain.
+async fn fetch_unordered_tx_timeout(
+    client: &HttpClient,
+    config: &CosmosSdkConfig,
+) -> Option<Duration> {
+    if !config.unordered_tx.enabled {
+        return None;
+    }
+
+    match fetch_version_specs(&config.id, client, &config.rpc_addr).await {
+        Ok(specs) if specs.supports_unordered_txs() => Some(config.unordered_tx.timeout),
+        Ok(specs) => {
+            warn!(
+                "chain '{}' does not support unordered transactions, which require Cosmos SDK v0.53 or later ({specs}), \
+                falling back on ordered transactions",
+                config.id
+            );
+            None
+        }
+        Err(e) => {
+            warn!(
+                "failed to fetch version specs for chain '{}', falling back on ordered transactions: {e}",
+                config.id
+            );
+            None
+        }
+    }
+}
+
 pub async fn fetch_compat_mode(
     client: &HttpClient,
     config: &CosmosSdkConfig,
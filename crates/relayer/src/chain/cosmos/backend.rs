@@ -0,0 +1,208 @@
+//! Hooks letting a CometBFT-based chain be relayed by the Cosmos SDK endpoint
+//! when it only differs from the Cosmos SDK in how it encodes transactions,
+//! where its IBC state is stored or how its events are shaped.
+//!
+//! A chain is configured with a backend by adding a `backend` table to its
+//! `CosmosSdk` chain configuration:
+//!
+//! ```toml
+//! [chains.backend]
+//! name = 'my-app-chain'
+//! ibc_query_path = 'store/my-ibc/key'
+//! ```
+//!
+//! The backend named in the configuration must have been registered with
+//! [`register_backend`] before the chain is spawned, for instance by a binary
+//! wrapping the Hermes CLI.
+//!
+//! A backend can customize the following queries:
+//!
+//! - the ABCI queries of the IBC state and of its proofs, through
+//!   `ibc_query_path`;
+//! - the ABCI queries of the upgraded client and consensus states,
+//!   through `upgrade_query_path`;
+//! - the events returned by the `tx_search` and `block_results` RPC queries,
+//!   through [`Backend::decode_event`].
+//!
+//! All the other queries, such as the gRPC queries of the IBC, bank and
+//! auth modules, are sent as for a Cosmos SDK chain.
+
+use alloc::collections::BTreeMap;
+use core::fmt::Debug;
+use std::sync::{Arc, RwLock};
+
+use ibc_proto::cosmos::tx::v1beta1::Fee;
+use ibc_proto::google::protobuf::Any;
+use once_cell::sync::Lazy;
+use serde_derive::{Deserialize, Serialize};
+use tendermint::abci;
+
+use crate::chain::cosmos::encode::sign_and_encode_tx;
+use crate::chain::cosmos::types::account::Account;
+use crate::chain::cosmos::types::config::TxConfig;
+use crate::config::types::Memo;
+use crate::error::Error;
+use crate::keyring::Secp256k1KeyPair;
+
+/// The name of the backend used by the chains without a `backend` configuration
+pub const COSMOS_SDK_BACKEND: &str = "cosmos-sdk";
+
+static BACKENDS: Lazy<RwLock<BTreeMap<String, Arc<dyn Backend>>>> = Lazy::new(|| {
+    let default: Arc<dyn Backend> = Arc::new(CosmosSdkBackend);
+    RwLock::new(BTreeMap::from([(COSMOS_SDK_BACKEND.to_string(), default)]))
+});
+
+/// Configuration of the backend of a CometBFT-based chain
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BackendConfig {
+    /// The name the backend was registered under
+    pub name: String,
+
+    /// The ABCI query path of the IBC store, `store/ibc/key` by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ibc_query_path: Option<String>,
+
+    /// The ABCI query path of the upgrade store, `store/upgrade/key` by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upgrade_query_path: Option<String>,
+}
+
+/// The transaction to be signed and encoded by a [`Backend`]
+pub struct EncodeTxRequest<'a> {
+    pub config: &'a TxConfig,
+    pub key_pair: &'a Secp256k1KeyPair,
+    pub account: &'a Account,
+    pub tx_memo: &'a Memo,
+    pub messages: &'a [Any],
+    pub fee: &'a Fee,
+}
+
+/// The hooks of a CometBFT-based chain.
+///
+/// Only [`Backend::encode_tx`] must be implemented, the other hooks default
+/// to the behaviour expected from a Cosmos SDK chain.
+pub trait Backend: Debug + Send + Sync {
+    /// Sign the messages of the request and encode them into the bytes
+    /// of a transaction, ready to be broadcast.
+    fn encode_tx(&self, request: EncodeTxRequest<'_>) -> Result<Vec<u8>, Error>;
+
+    /// Whether the gas used by a transaction can be estimated by simulating it
+    /// with the Cosmos SDK `Simulate` gRPC service. Otherwise, the configured
+    /// `default_gas` is used.
+    fn simulates_txs(&self) -> bool {
+        true
+    }
+
+    /// Turn an ABCI event of the chain into the shape of the events emitted by
+    /// ibc-go, or drop it by returning `None`.
+    ///
+    /// This hook applies to the events received by the event source, to the
+    /// events of the transactions submitted by Hermes, and to the events
+    /// returned by the `tx_search` and `block_results` RPC queries. The search
+    /// itself is still performed by the node against the attributes of ibc-go,
+    /// as when clearing packets, so the events must be indexed under these
+    /// attributes for the transactions and blocks to be found.
+    fn decode_event(&self, event: abci::Event) -> Option<abci::Event> {
+        Some(event)
+    }
+}
+
+/// The backend of the Cosmos SDK chains
+#[derive(Debug)]
+pub struct CosmosSdkBackend;
+
+impl Backend for CosmosSdkBackend {
+    fn encode_tx(&self, request: EncodeTxRequest<'_>) -> Result<Vec<u8>, Error> {
+        sign_and_encode_tx(
+            request.config,
+            request.key_pair,
+            request.account,
+            request.tx_memo,
+            request.messages,
+            request.fee,
+        )
+    }
+}
+
+/// Register a backend under the given name, replacing any backend
+/// previously registered under that name.
+pub fn register_backend(name: impl Into<String>, backend: Arc<dyn Backend>) {
+    BACKENDS
+        .write()
+        .expect("poisoned lock")
+        .insert(name.into(), backend);
+}
+
+/// Find the backend of a chain given its configuration, which is the
+/// Cosmos SDK backend if the chain does not configure any.
+pub fn find_backend(config: Option<&BackendConfig>) -> Result<Arc<dyn Backend>, Error> {
+    let name = config.map_or(COSMOS_SDK_BACKEND, |config| config.name.as_str());
+
+    BACKENDS
+        .read()
+        .expect("poisoned lock")
+        .get(name)
+        .cloned()
+        .ok_or_else(|| Error::unknown_backend(name.to_string()))
+}
+
+/// Apply the event hook of the backend to the given events
+pub fn decode_events(backend: &dyn Backend, events: Vec<abci::Event>) -> Vec<abci::Event> {
+    events
+        .into_iter()
+        .filter_map(|event| backend.decode_event(event))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct RenamingBackend;
+
+    impl Backend for RenamingBackend {
+        fn encode_tx(&self, _request: EncodeTxRequest<'_>) -> Result<Vec<u8>, Error> {
+            Ok(vec![])
+        }
+
+        fn decode_event(&self, mut event: abci::Event) -> Option<abci::Event> {
+            event.kind = event.kind.strip_prefix("app_")?.to_string();
+            Some(event)
+        }
+    }
+
+    #[test]
+    fn unknown_backend() {
+        let config = BackendConfig {
+            name: "unknown".to_string(),
+            ibc_query_path: None,
+            upgrade_query_path: None,
+        };
+
+        assert!(find_backend(Some(&config)).is_err());
+        assert!(find_backend(None).is_ok());
+    }
+
+    #[test]
+    fn registered_backend_decodes_events() {
+        register_backend("renaming", Arc::new(RenamingBackend));
+
+        let config = BackendConfig {
+            name: "renaming".to_string(),
+            ibc_query_path: None,
+            upgrade_query_path: None,
+        };
+        let backend = find_backend(Some(&config)).unwrap();
+
+        let events = vec![
+            abci::Event::new("app_send_packet", Vec::<abci::EventAttribute>::new()),
+            abci::Event::new("message", Vec::<abci::EventAttribute>::new()),
+        ];
+        let decoded = decode_events(backend.as_ref(), events);
+
+        assert_eq!(decoded.len(), 1);
+        assert_eq!(decoded[0].kind, "send_packet");
+    }
+}
@@ -1,5 +1,8 @@
 use core::mem;
+use std::time::Instant;
 
+use futures::future::try_join_all;
+use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_proto::google::protobuf::Any;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use ibc_relayer_types::events::IbcEvent;
@@ -12,9 +15,10 @@ use tracing::debug;
 use crate::chain::cosmos::encode::encoded_tx_metrics;
 use crate::chain::cosmos::gas::gas_amount_to_fee;
 use crate::chain::cosmos::retry::send_tx_with_account_sequence_retry;
-use crate::chain::cosmos::types::account::Account;
+use crate::chain::cosmos::tx::send_unordered_tx;
+use crate::chain::cosmos::types::account::{Account, AccountSequence};
 use crate::chain::cosmos::types::config::TxConfig;
-use crate::chain::cosmos::types::tx::{TxStatus, TxSyncResult};
+use crate::chain::cosmos::types::tx::{ReplaceableTx, TxStatus, TxSyncResult};
 use crate::chain::cosmos::wait::wait_for_block_commits;
 use crate::config::types::Memo;
 use crate::error::Error;
@@ -43,14 +47,7 @@ pub async fn send_batched_messages_and_wait_commit(
     let mut tx_sync_results =
         send_messages_as_batches(rpc_client, config, key_pair, account, tx_memo, messages).await?;
 
-    wait_for_block_commits(
-        &config.chain_id,
-        rpc_client,
-        &config.rpc_address,
-        &config.rpc_timeout,
-        &mut tx_sync_results,
-    )
-    .await?;
+    wait_for_block_commits(rpc_client, config, key_pair, tx_memo, &mut tx_sync_results).await?;
 
     let events = tx_sync_results
         .into_iter()
@@ -107,10 +104,7 @@ pub async fn send_batched_messages_and_wait_check_tx(
     let mut responses = Vec::new();
 
     for batch in batches {
-        let response = send_tx_with_account_sequence_retry(
-            rpc_client, config, key_pair, account, tx_memo, &batch,
-        )
-        .await?;
+        let (response, _) = send_tx(rpc_client, config, key_pair, account, tx_memo, &batch).await?;
 
         responses.push(response);
     }
@@ -118,6 +112,27 @@ pub async fn send_batched_messages_and_wait_check_tx(
     Ok(responses)
 }
 
+/// Sends a Tx made of the given messages, either as an unordered Tx if the chain
+/// supports it and it is enabled, or at the current account sequence otherwise.
+/// Returns the response to the broadcast along with the fee paid by the Tx.
+async fn send_tx(
+    rpc_client: &HttpClient,
+    config: &TxConfig,
+    key_pair: &Secp256k1KeyPair,
+    account: &mut Account,
+    tx_memo: &Memo,
+    messages: &[Any],
+) -> Result<(Response, Fee), Error> {
+    if config.unordered_tx_timeout.is_some() {
+        send_unordered_tx(rpc_client, config, key_pair, account, tx_memo, messages).await
+    } else {
+        send_tx_with_account_sequence_retry(
+            rpc_client, config, key_pair, account, tx_memo, messages,
+        )
+        .await
+    }
+}
+
 async fn send_messages_as_batches(
     rpc_client: &HttpClient,
     config: &TxConfig,
@@ -141,17 +156,43 @@ async fn send_messages_as_batches(
         config.chain_id
     );
 
+    // Unordered Txs do not contend for the account sequence,
+    // so they can all be broadcast at the same time
+    if config.unordered_tx_timeout.is_some() {
+        let responses =
+            try_join_all(batches.iter().map(|batch| {
+                send_unordered_tx(rpc_client, config, key_pair, account, tx_memo, batch)
+            }))
+            .await?;
+
+        let tx_sync_results = batches
+            .iter()
+            .zip(responses)
+            .map(|(batch, (response, _))| {
+                response_to_tx_sync_result(&config.chain_id, batch.len(), response)
+            })
+            .collect();
+
+        return Ok(tx_sync_results);
+    }
+
     let mut tx_sync_results = Vec::new();
 
     for batch in batches {
         let message_count = batch.len();
 
-        let response = send_tx_with_account_sequence_retry(
+        let (response, fee) = send_tx_with_account_sequence_retry(
             rpc_client, config, key_pair, account, tx_memo, &batch,
         )
         .await?;
 
-        let tx_sync_result = response_to_tx_sync_result(&config.chain_id, message_count, response);
+        let tx_sync_result = with_replacement(
+            config,
+            response_to_tx_sync_result(&config.chain_id, message_count, response),
+            batch,
+            fee,
+            account,
+        );
 
         tx_sync_results.push(tx_sync_result);
     }
@@ -187,23 +228,20 @@ async fn sequential_send_messages_as_batches(
     for batch in batches {
         let message_count = batch.len();
 
-        let response = send_tx_with_account_sequence_retry(
-            rpc_client, config, key_pair, account, tx_memo, &batch,
-        )
-        .await?;
+        let (response, fee) =
+            send_tx(rpc_client, config, key_pair, account, tx_memo, &batch).await?;
 
-        let tx_sync_result = response_to_tx_sync_result(&config.chain_id, message_count, response);
+        let tx_sync_result = with_replacement(
+            config,
+            response_to_tx_sync_result(&config.chain_id, message_count, response),
+            batch,
+            fee,
+            account,
+        );
 
         tx_sync_results.push(tx_sync_result);
 
-        wait_for_block_commits(
-            &config.chain_id,
-            rpc_client,
-            &config.rpc_address,
-            &config.rpc_timeout,
-            &mut tx_sync_results,
-        )
-        .await?;
+        wait_for_block_commits(rpc_client, config, key_pair, tx_memo, &mut tx_sync_results).await?;
     }
 
     Ok(tx_sync_results)
@@ -228,16 +266,54 @@ pub fn response_to_tx_sync_result(
             response,
             events: events_per_tx,
             status: TxStatus::ReceivedResponse,
+            replacement: None,
         }
     } else {
         TxSyncResult {
             response,
             events: Vec::new(),
             status: TxStatus::Pending { message_count },
+            replacement: None,
         }
     }
 }
 
+/// Records the data needed to replace the given Tx if it gets stuck in the mempool,
+/// provided that Tx replacement is enabled, that the chain is not known to reject
+/// replacements, and that the Tx is pending, in which case the account sequence
+/// was incremented past the sequence used by the Tx.
+/// Unordered Txs are never replaced, since the replacement would not supersede them.
+fn with_replacement(
+    config: &TxConfig,
+    mut tx_sync_result: TxSyncResult,
+    messages: Vec<Any>,
+    fee: Fee,
+    account: &Account,
+) -> TxSyncResult {
+    if config.tx_replacement.enabled
+        && config.tx_replacement_support.may_be_supported()
+        && config.unordered_tx_timeout.is_none()
+    {
+        if let TxStatus::Pending { .. } = tx_sync_result.status {
+            let sequence = AccountSequence::new(account.sequence.to_u64().saturating_sub(1));
+
+            tx_sync_result.replacement = Some(ReplaceableTx {
+                messages,
+                fee,
+                account: Account {
+                    sequence,
+                    ..account.clone()
+                },
+                broadcast_at: Instant::now(),
+                fee_multiplier: 1.0,
+                replaced_hashes: Vec::new(),
+            });
+        }
+    }
+
+    tx_sync_result
+}
+
 async fn batch_messages(
     config: &TxConfig,
     key_pair: &Secp256k1KeyPair,
@@ -258,6 +334,7 @@ async fn batch_messages(
         config.gas_config.max_gas,
         &config.chain_id,
         &config.rpc_address,
+        None,
     )
     .await;
 
@@ -379,6 +456,7 @@ mod tests {
             config.gas_config.max_gas,
             &config.chain_id,
             &config.rpc_address,
+            None,
         )
         .await;
         let mut messages = vec![Any {
@@ -467,6 +545,7 @@ mod tests {
             config.gas_config.max_gas,
             &config.chain_id,
             &config.rpc_address,
+            None,
         )
         .await;
         let tx_bytes =
@@ -598,6 +677,7 @@ mod tests {
             config.gas_config.max_gas,
             &config.chain_id,
             &config.rpc_address,
+            None,
         )
         .await;
 
@@ -7,14 +7,17 @@ use tendermint_rpc::Url;
 
 use ibc_relayer_types::core::ics23_commitment::specs::ProofSpecs;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::core::ics24_host::path::{IBC_QUERY_PATH, SDK_UPGRADE_QUERY_PATH};
 
+use crate::chain::cosmos::backend::BackendConfig;
 use crate::chain::cosmos::config::error::Error as ConfigError;
 use crate::config::compat_mode::CompatMode;
 use crate::config::dynamic_gas::DynamicGasPrice;
 use crate::config::gas_multiplier::GasMultiplier;
 use crate::config::types::{MaxMsgNum, MaxTxSize, Memo, TrustThreshold};
 use crate::config::{
-    self, AddressType, EventSourceMode, ExtensionOption, GasPrice, GenesisRestart, PacketFilter,
+    self, AddressType, EventSourceMode, ExtensionOption, FeeGranters, GasModelConfig, GasPrice,
+    GenesisRestart, PacketFilter, PacketPriority, TxReplacementConfig, UnorderedTxConfig,
 };
 use crate::config::{default, RefreshRate};
 use crate::keyring::Store;
@@ -45,6 +48,16 @@ pub struct CosmosSdkConfig {
     #[serde(default = "default::trusted_node")]
     pub trusted_node: bool,
 
+    /// RPC URLs of witness full nodes against which the headers fetched
+    /// from the primary node are cross-checked before being submitted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub witnesses: Vec<Url>,
+
+    /// The minimum number of witnesses which must successfully cross-check
+    /// a header for it to be submitted, when witnesses are configured
+    #[serde(default = "default::min_witnesses")]
+    pub min_witnesses: usize,
+
     pub account_prefix: String,
     pub key_name: String,
     #[serde(default)]
@@ -65,6 +78,9 @@ pub struct CosmosSdkConfig {
 
     pub fee_granter: Option<String>,
 
+    #[serde(default)]
+    pub fee_granters: FeeGranters,
+
     #[serde(default)]
     pub max_msg_num: MaxMsgNum,
 
@@ -135,9 +151,26 @@ pub struct CosmosSdkConfig {
 
     pub gas_price: GasPrice,
 
+    /// Namada only: the whitelisted tokens to pay the fees with, in order of preference,
+    /// when they cannot be paid with the `gas_price` denomination.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_fee_tokens: Vec<String>,
+
     #[serde(default)]
     pub packet_filter: PacketFilter,
 
+    #[serde(default)]
+    pub packet_priority: PacketPriority,
+
+    #[serde(default)]
+    pub gas_model: GasModelConfig,
+
+    #[serde(default)]
+    pub tx_replacement: TxReplacementConfig,
+
+    #[serde(default)]
+    pub unordered_tx: UnorderedTxConfig,
+
     #[serde(default)]
     pub dynamic_gas_price: DynamicGasPrice,
 
@@ -152,14 +185,51 @@ pub struct CosmosSdkConfig {
 
     #[serde(default = "default::allow_ccq")]
     pub allow_ccq: bool,
+
+    /// The hooks of CometBFT-based chains which differ from the Cosmos SDK
+    /// in how they encode txs, where their IBC state is stored or how their
+    /// events are shaped. Cosmos SDK chains leave it unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<BackendConfig>,
+
+    /// The file to which the RPC and gRPC requests sent to this chain are
+    /// recorded, along with their responses, to be replayed in tests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub record_fixtures: Option<PathBuf>,
 }
 
 impl CosmosSdkConfig {
     pub fn validate(&self) -> Result<(), Diagnostic<ConfigError>> {
         validate_trust_threshold(&self.id, self.trust_threshold)?;
         validate_gas_settings(&self.id, self.gas_adjustment)?;
+        validate_dynamic_gas_provider(&self.id, &self.dynamic_gas_price)?;
+        validate_dynamic_gas_denoms(&self.id, &self.gas_price, &self.dynamic_gas_price)?;
+        validate_tx_replacement(&self.id, &self.tx_replacement, self.rpc_timeout)?;
+        validate_witnesses(&self.id, &self.witnesses, self.min_witnesses)?;
+        self.packet_priority.validate().map_err(|reason| {
+            Diagnostic::Error(ConfigError::invalid_packet_priority(
+                self.id.clone(),
+                reason,
+            ))
+        })?;
         Ok(())
     }
+
+    /// The ABCI query path of the IBC store
+    pub fn ibc_query_path(&self) -> String {
+        self.backend
+            .as_ref()
+            .and_then(|backend| backend.ibc_query_path.clone())
+            .unwrap_or_else(|| IBC_QUERY_PATH.to_string())
+    }
+
+    /// The ABCI query path of the upgrade store
+    pub fn upgrade_query_path(&self) -> String {
+        self.backend
+            .as_ref()
+            .and_then(|backend| backend.upgrade_query_path.clone())
+            .unwrap_or_else(|| SDK_UPGRADE_QUERY_PATH.to_string())
+    }
 }
 
 /// Check that the trust threshold is:
@@ -198,6 +268,25 @@ pub fn validate_trust_threshold(
     Ok(())
 }
 
+/// Check that the configured witnesses can satisfy the minimum number of witnesses
+fn validate_witnesses(
+    id: &ChainId,
+    witnesses: &[Url],
+    min_witnesses: usize,
+) -> Result<(), Diagnostic<ConfigError>> {
+    if !witnesses.is_empty() && min_witnesses > witnesses.len() {
+        return Err(Diagnostic::Error(ConfigError::invalid_witnesses(
+            id.clone(),
+            format!(
+                "`min_witnesses` ({min_witnesses}) cannot exceed the number of witnesses ({})",
+                witnesses.len()
+            ),
+        )));
+    }
+
+    Ok(())
+}
+
 fn validate_gas_settings(
     id: &ChainId,
     gas_adjustment: Option<f64>,
@@ -220,3 +309,88 @@ pub enum Diagnostic<E> {
     Warning(E),
     Error(E),
 }
+
+/// Check that the provider of the dynamic gas price is set if it is enabled.
+fn validate_dynamic_gas_provider(
+    id: &ChainId,
+    dynamic_gas_price: &DynamicGasPrice,
+) -> Result<(), Diagnostic<ConfigError>> {
+    if dynamic_gas_price.enabled && dynamic_gas_price.provider.is_none() {
+        return Err(Diagnostic::Error(
+            ConfigError::missing_dynamic_gas_provider(id.clone()),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Check that the fee denoms of the dynamic gas price, if any,
+/// include the denom of the gas price and have a positive value.
+fn validate_dynamic_gas_denoms(
+    id: &ChainId,
+    gas_price: &GasPrice,
+    dynamic_gas_price: &DynamicGasPrice,
+) -> Result<(), Diagnostic<ConfigError>> {
+    let denoms = &dynamic_gas_price.denoms;
+
+    if denoms.is_empty() {
+        return Ok(());
+    }
+
+    if !denoms.contains_key(&gas_price.denom) {
+        return Err(Diagnostic::Error(ConfigError::invalid_dynamic_gas_denoms(
+            id.clone(),
+            format!("the denom of `gas_price` ({}) is missing", gas_price.denom),
+        )));
+    }
+
+    if let Some((denom, value)) = denoms
+        .iter()
+        .find(|(_, value)| !value.is_finite() || **value <= 0.0)
+    {
+        return Err(Diagnostic::Error(ConfigError::invalid_dynamic_gas_denoms(
+            id.clone(),
+            format!("the value of `{denom}` must be positive, found {value}"),
+        )));
+    }
+
+    Ok(())
+}
+
+/// Check that the fee of the replacement transactions, if enabled, is bumped
+/// up to a cap, and that stuck transactions are detected before `rpc_timeout`.
+fn validate_tx_replacement(
+    id: &ChainId,
+    tx_replacement: &TxReplacementConfig,
+    rpc_timeout: Duration,
+) -> Result<(), Diagnostic<ConfigError>> {
+    if !tx_replacement.enabled {
+        return Ok(());
+    }
+
+    let reason = if !tx_replacement.fee_bump.is_finite() || tx_replacement.fee_bump <= 1.0 {
+        format!(
+            "`fee_bump` must be greater than 1.0, found {}",
+            tx_replacement.fee_bump
+        )
+    } else if !tx_replacement.max_fee_multiplier.is_finite()
+        || tx_replacement.max_fee_multiplier <= 1.0
+    {
+        format!(
+            "`max_fee_multiplier` must be greater than 1.0, found {}",
+            tx_replacement.max_fee_multiplier
+        )
+    } else if tx_replacement.stuck_timeout >= rpc_timeout {
+        format!(
+            "`stuck_timeout` ({:?}) must be lower than `rpc_timeout` ({rpc_timeout:?})",
+            tx_replacement.stuck_timeout
+        )
+    } else {
+        return Ok(());
+    };
+
+    Err(Diagnostic::Error(ConfigError::invalid_tx_replacement(
+        id.clone(),
+        reason,
+    )))
+}
@@ -31,6 +31,58 @@ define_error! {
             )
         },
 
+        MissingDynamicGasProvider
+        {
+            chain_id: ChainId,
+        }
+        |e| {
+            format!("config file enables `dynamic_gas_price` for the chain '{0}' without specifying its `provider`, \
+                which must be one of 'feemarket', 'osmosis_txfees', 'ethermint_feemarket' or 'static'; \
+                Osmosis chains, whose provider used to be inferred from their chain identifier, \
+                must set `provider = 'osmosis_txfees'`",
+                e.chain_id)
+        },
+
+        InvalidDynamicGasDenoms
+        {
+            chain_id: ChainId,
+            reason: String,
+        }
+        |e| {
+            format!("config file specifies invalid `dynamic_gas_price.denoms` for the chain '{0}', caused by: {1}",
+                e.chain_id, e.reason)
+        },
+
+        InvalidTxReplacement
+        {
+            chain_id: ChainId,
+            reason: String,
+        }
+        |e| {
+            format!("config file specifies invalid `tx_replacement` settings for the chain '{0}', caused by: {1}",
+                e.chain_id, e.reason)
+        },
+
+        InvalidPacketPriority
+        {
+            chain_id: ChainId,
+            reason: String,
+        }
+        |e| {
+            format!("config file specifies invalid `packet_priority` settings for the chain '{0}', caused by: {1}",
+                e.chain_id, e.reason)
+        },
+
+        InvalidWitnesses
+        {
+            chain_id: ChainId,
+            reason: String,
+        }
+        |e| {
+            format!("config file specifies invalid `witnesses` settings for the chain '{0}', caused by: {1}",
+                e.chain_id, e.reason)
+        },
+
         ExpectedExcludedSequencesArray
         |_| { "expected excluded_sequences to be an array of values" },
 
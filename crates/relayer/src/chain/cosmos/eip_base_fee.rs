@@ -8,33 +8,55 @@ use tendermint_rpc::Url;
 use tracing::{debug, trace};
 
 use ibc_proto::cosmos::base::v1beta1::{DecCoin, DecProto};
-use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
+use crate::config::dynamic_gas::DynamicGasPriceProvider;
+use crate::config::GasPrice;
 use crate::error::Error;
 
-pub async fn query_eip_base_fee(
-    rpc_address: &Url,
-    gas_price_denom: &str,
-    chain_id: &ChainId,
-) -> Result<f64, Error> {
-    debug!("Querying Omosis EIP-1559 base fee from {rpc_address}");
-
-    let chain_name = chain_id.name();
+/// A source of EIP-1559 base fees, ie. of the minimum gas prices currently
+/// accepted by a chain, which can be queried through ABCI.
+pub trait GasPriceProvider: Send + Sync {
+    /// The name of the provider, used for logging.
+    fn name(&self) -> &'static str;
+
+    /// The path of the ABCI query returning the base fees,
+    /// or `None` if the base fees are not queried from the chain.
+    fn query_path(&self) -> Option<&'static str>;
+
+    /// Extracts the base fees from the value of the ABCI query response, if any.
+    /// The configured gas price is used for the denom of the providers which quote
+    /// the base fee in a single denom, and as the base fee of the static provider.
+    fn base_fees(&self, value: Option<&[u8]>, gas_price: &GasPrice)
+        -> Result<Vec<GasPrice>, Error>;
+}
 
-    let is_osmosis = chain_name.starts_with("osmosis") || chain_name.starts_with("osmo-test");
+/// Returns the implementation of the given provider.
+pub fn gas_price_provider(provider: DynamicGasPriceProvider) -> &'static dyn GasPriceProvider {
+    match provider {
+        DynamicGasPriceProvider::Feemarket => &Feemarket,
+        DynamicGasPriceProvider::OsmosisTxfees => &OsmosisTxfees,
+        DynamicGasPriceProvider::EthermintFeemarket => &EthermintFeemarket,
+        DynamicGasPriceProvider::Static => &StaticGasPrice,
+    }
+}
 
-    let url = if is_osmosis {
-        format!(
-            "{}abci_query?path=\"/osmosis.txfees.v1beta1.Query/GetEipBaseFee\"",
-            rpc_address
-        )
-    } else {
-        format!(
-            "{}abci_query?path=\"/feemarket.feemarket.v1.Query/GasPrices\"&denom={}",
-            rpc_address, gas_price_denom
-        )
+/// Queries the EIP-1559 base fees of a chain from the given provider.
+pub async fn query_eip_base_fees(
+    provider: &dyn GasPriceProvider,
+    rpc_address: &Url,
+    gas_price: &GasPrice,
+) -> Result<Vec<GasPrice>, Error> {
+    let Some(query_path) = provider.query_path() else {
+        return provider.base_fees(None, gas_price);
     };
 
+    debug!(
+        "Querying {} EIP-1559 base fee from {rpc_address}",
+        provider.name()
+    );
+
+    let url = format!("{}abci_query?path=\"{}\"", rpc_address, query_path);
+
     let response = reqwest::get(&url).await.map_err(Error::http_request)?;
 
     if !response.status().is_success() {
@@ -58,52 +80,149 @@ pub async fn query_eip_base_fee(
 
     let result: EipBaseFeeHTTPResult = response.json().await.map_err(Error::http_response_body)?;
 
-    let amount = if is_osmosis {
-        extract_dynamic_gas_price_osmosis(result.result.response.value)?
-    } else {
-        extract_dynamic_gas_price(result.result.response.value)?
-    };
+    let decoded = base64::decode(result.result.response.value).map_err(Error::base64_decode)?;
+
+    let base_fees = provider.base_fees(Some(&decoded), gas_price)?;
 
-    trace!("EIP-1559 base fee: {amount}");
+    trace!("EIP-1559 base fees: {base_fees:?}");
 
-    Ok(amount)
+    Ok(base_fees)
 }
 
-/// This method extracts the gas base fee from Skip's feemarket
-fn extract_dynamic_gas_price(encoded: String) -> Result<f64, Error> {
-    let decoded = base64::decode(encoded).map_err(Error::base64_decode)?;
+/// Skip's `x/feemarket` module, which quotes the gas price in every accepted fee denom.
+pub struct Feemarket;
 
-    let gas_price_response: GasPriceResponse =
-        prost::Message::decode(decoded.as_ref()).map_err(|e| {
-            Error::protobuf_decode("feemarket.feemarket.v1.GasPricesResponse".to_string(), e)
-        })?;
-    let dec_coin = gas_price_response.price.unwrap().clone();
-    let base_fee_uint128 = Uint128::from_str(&dec_coin.amount).map_err(Error::parse_int)?;
+impl GasPriceProvider for Feemarket {
+    fn name(&self) -> &'static str {
+        "feemarket"
+    }
 
-    let dec = Decimal::new(base_fee_uint128);
-    f64::from_str(dec.to_string().as_str()).map_err(Error::parse_float)
+    fn query_path(&self) -> Option<&'static str> {
+        Some("/feemarket.feemarket.v1.Query/GasPrices")
+    }
+
+    fn base_fees(
+        &self,
+        value: Option<&[u8]>,
+        _gas_price: &GasPrice,
+    ) -> Result<Vec<GasPrice>, Error> {
+        let gas_prices_response: GasPricesResponse =
+            prost::Message::decode(value.unwrap_or_default()).map_err(|e| {
+                Error::protobuf_decode("feemarket.feemarket.v1.GasPricesResponse".to_string(), e)
+            })?;
+
+        gas_prices_response
+            .prices
+            .into_iter()
+            .map(|dec_coin| {
+                Ok(GasPrice::new(
+                    parse_decimal(&dec_coin.amount)?,
+                    dec_coin.denom,
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Osmosis' `x/txfees` module, which quotes the EIP-1559 base fee in the native denom.
+pub struct OsmosisTxfees;
+
+impl GasPriceProvider for OsmosisTxfees {
+    fn name(&self) -> &'static str {
+        "Osmosis txfees"
+    }
+
+    fn query_path(&self) -> Option<&'static str> {
+        Some("/osmosis.txfees.v1beta1.Query/GetEipBaseFee")
+    }
+
+    fn base_fees(
+        &self,
+        value: Option<&[u8]>,
+        gas_price: &GasPrice,
+    ) -> Result<Vec<GasPrice>, Error> {
+        let dec_proto: DecProto = prost::Message::decode(value.unwrap_or_default())
+            .map_err(|e| Error::protobuf_decode("cosmos.base.v1beta1.DecProto".to_string(), e))?;
+
+        let base_fee = parse_decimal(&dec_proto.dec)?;
+
+        Ok(vec![GasPrice::new(base_fee, gas_price.denom.clone())])
+    }
+}
+
+/// Ethermint's `x/feemarket` module, which quotes the EIP-1559 base fee in the EVM denom.
+pub struct EthermintFeemarket;
+
+impl GasPriceProvider for EthermintFeemarket {
+    fn name(&self) -> &'static str {
+        "Ethermint feemarket"
+    }
+
+    fn query_path(&self) -> Option<&'static str> {
+        Some("/ethermint.feemarket.v1.Query/BaseFee")
+    }
+
+    fn base_fees(
+        &self,
+        value: Option<&[u8]>,
+        gas_price: &GasPrice,
+    ) -> Result<Vec<GasPrice>, Error> {
+        let base_fee_response: QueryBaseFeeResponse =
+            prost::Message::decode(value.unwrap_or_default()).map_err(|e| {
+                Error::protobuf_decode("ethermint.feemarket.v1.QueryBaseFeeResponse".to_string(), e)
+            })?;
+
+        // The base fee is an integer amount of the EVM denom
+        let base_fee = f64::from_str(&base_fee_response.base_fee).map_err(Error::parse_float)?;
+
+        Ok(vec![GasPrice::new(base_fee, gas_price.denom.clone())])
+    }
 }
 
-/// This method extracts the gas base fee from Osmosis EIP-1559
-fn extract_dynamic_gas_price_osmosis(encoded: String) -> Result<f64, Error> {
-    let decoded = base64::decode(encoded).map_err(Error::base64_decode)?;
+/// A provider which does not query the chain, and uses the configured gas price as the base fee.
+pub struct StaticGasPrice;
+
+impl GasPriceProvider for StaticGasPrice {
+    fn name(&self) -> &'static str {
+        "static"
+    }
+
+    fn query_path(&self) -> Option<&'static str> {
+        None
+    }
 
-    let dec_proto: DecProto = prost::Message::decode(decoded.as_ref())
-        .map_err(|e| Error::protobuf_decode("cosmos.base.v1beta1.DecProto".to_string(), e))?;
+    fn base_fees(
+        &self,
+        _value: Option<&[u8]>,
+        gas_price: &GasPrice,
+    ) -> Result<Vec<GasPrice>, Error> {
+        Ok(vec![gas_price.clone()])
+    }
+}
 
-    let base_fee_uint128 = Uint128::from_str(&dec_proto.dec).map_err(Error::parse_int)?;
+/// Parses a `cosmos.math.LegacyDec` encoded as an integer with 18 decimal places.
+fn parse_decimal(amount: &str) -> Result<f64, Error> {
+    let base_fee_uint128 = Uint128::from_str(amount).map_err(Error::parse_int)?;
 
     let dec = Decimal::new(base_fee_uint128);
     f64::from_str(dec.to_string().as_str()).map_err(Error::parse_float)
 }
 
-/// GasPriceResponse is the response type for the Query/GasPrice RPC method.
-/// Returns a gas price in specified denom.
+/// GasPricesResponse is the response type for the Query/GasPrices RPC method.
+/// Returns the gas prices in all the accepted denoms.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GasPricesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub prices: ::prost::alloc::vec::Vec<DecCoin>,
+}
+
+/// QueryBaseFeeResponse is the response type for the Ethermint Query/BaseFee RPC method.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GasPriceResponse {
-    #[prost(message, optional, tag = "1")]
-    pub price: ::core::option::Option<DecCoin>,
+pub struct QueryBaseFeeResponse {
+    #[prost(string, tag = "1")]
+    pub base_fee: ::prost::alloc::string::String,
 }
 
 /// Extracted from `cosmwasm-std`
@@ -1,12 +1,14 @@
 use bech32::{ToBase32, Variant};
 use core::str::FromStr;
+use core::time::Duration;
 use ibc_proto::cosmos::tx::v1beta1::mode_info::{Single, Sum};
 use ibc_proto::cosmos::tx::v1beta1::{AuthInfo, Fee, ModeInfo, SignDoc, SignerInfo, TxBody, TxRaw};
-use ibc_proto::google::protobuf::Any;
+use ibc_proto::google::protobuf::{Any, Timestamp};
 use ibc_relayer_types::core::ics02_client::error::Error as ClientError;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use ibc_relayer_types::signer::Signer;
 use prost::Message;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tendermint::account::Id as AccountId;
 
 use crate::chain::cosmos::types::account::{Account, AccountNumber, AccountSequence};
@@ -84,10 +86,23 @@ pub fn sign_tx(
 ) -> Result<SignedTx, Error> {
     let key_bytes = encode_key_bytes(key_pair)?;
 
-    let signer = encode_signer_info(&config.address_type, account.sequence, key_bytes)?;
+    let unordered_timeout = config.unordered_tx_timeout.map(unordered_timeout_timestamp);
 
-    let (body, body_bytes) =
-        tx_body_and_bytes(messages, tx_memo, config.extension_options.clone())?;
+    // The account sequence is not used by unordered Txs
+    let sequence = if unordered_timeout.is_some() {
+        AccountSequence::new(0)
+    } else {
+        account.sequence
+    };
+
+    let signer = encode_signer_info(&config.address_type, sequence, key_bytes)?;
+
+    let (body, body_bytes) = tx_body_and_bytes(
+        messages,
+        tx_memo,
+        config.extension_options.clone(),
+        unordered_timeout,
+    )?;
 
     let (auth_info, auth_info_bytes) = auth_info_and_bytes(signer, fee.clone())?;
 
@@ -203,10 +218,24 @@ fn auth_info_and_bytes(signer_info: SignerInfo, fee: Fee) -> Result<(AuthInfo, V
     Ok((auth_info, auth_buf))
 }
 
+/// Returns the timestamp at which an unordered Tx signed now expires.
+fn unordered_timeout_timestamp(timeout: Duration) -> Timestamp {
+    let timeout = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        + timeout;
+
+    Timestamp {
+        seconds: timeout.as_secs() as i64,
+        nanos: timeout.subsec_nanos() as i32,
+    }
+}
+
 fn tx_body_and_bytes(
     proto_msgs: &[Any],
     memo: &Memo,
     extension_options: Vec<Any>,
+    unordered_timeout: Option<Timestamp>,
 ) -> Result<(TxBody, Vec<u8>), Error> {
     // Create TxBody
     let body = TxBody {
@@ -220,8 +249,36 @@ fn tx_body_and_bytes(
     // A protobuf serialization of a TxBody
     let mut body_buf = Vec::new();
 
-    Message::encode(&body, &mut body_buf)
-        .map_err(|e| Error::protobuf_encode(String::from("TxBody"), e))?;
+    match unordered_timeout {
+        None => Message::encode(&body, &mut body_buf)
+            .map_err(|e| Error::protobuf_encode(String::from("TxBody"), e))?,
+
+        // `TxBody` lacks the `unordered` (4) and `timeout_timestamp` (5) fields added
+        // in Cosmos SDK v0.53, so they are encoded by hand, in field number order,
+        // between the fields up to `timeout_height` (3) and the extension options.
+        Some(timeout) => {
+            let head = TxBody {
+                extension_options: Vec::new(),
+                non_critical_extension_options: Vec::new(),
+                ..body.clone()
+            };
+
+            let tail = TxBody {
+                extension_options: body.extension_options.clone(),
+                non_critical_extension_options: body.non_critical_extension_options.clone(),
+                ..Default::default()
+            };
+
+            Message::encode(&head, &mut body_buf)
+                .map_err(|e| Error::protobuf_encode(String::from("TxBody"), e))?;
+
+            prost::encoding::bool::encode(4, &true, &mut body_buf);
+            prost::encoding::message::encode(5, &timeout, &mut body_buf);
+
+            Message::encode(&tail, &mut body_buf)
+                .map_err(|e| Error::protobuf_encode(String::from("TxBody"), e))?;
+        }
+    }
 
     Ok((body, body_buf))
 }
@@ -234,3 +291,40 @@ pub fn key_pair_to_signer(key_pair: &Secp256k1KeyPair) -> Result<Signer, Error>
 
     Ok(signer)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_unordered_tx_body() {
+        let memo = Memo::new("hermes").unwrap();
+        let extension_options = vec![Any {
+            type_url: "/ethermint.types.v1.ExtensionOptionDynamicFeeTx".to_string(),
+            value: vec![1, 2, 3],
+        }];
+        let timeout = Timestamp {
+            seconds: 1_700_000_000,
+            nanos: 0,
+        };
+
+        let (body, ordered_bytes) =
+            tx_body_and_bytes(&[], &memo, extension_options.clone(), None).unwrap();
+        let (_, unordered_bytes) =
+            tx_body_and_bytes(&[], &memo, extension_options, Some(timeout)).unwrap();
+
+        // The fields known to `TxBody` are left untouched
+        assert_eq!(TxBody::decode(unordered_bytes.as_slice()).unwrap(), body);
+
+        // The `unordered` and `timeout_timestamp` fields come before the extension options
+        let head_len = TxBody {
+            extension_options: Vec::new(),
+            ..body
+        }
+        .encoded_len();
+
+        assert_eq!(unordered_bytes[..head_len], ordered_bytes[..head_len]);
+        assert_eq!(unordered_bytes[head_len..head_len + 3], [0x20, 0x01, 0x2a]);
+        assert!(unordered_bytes.ends_with(&ordered_bytes[head_len..]));
+    }
+}
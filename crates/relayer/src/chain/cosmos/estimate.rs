@@ -1,4 +1,6 @@
-use ibc_proto::cosmos::tx::v1beta1::{Fee, Tx};
+use core::fmt::{self, Display, Formatter};
+
+use ibc_proto::cosmos::tx::v1beta1::{Fee, TxRaw};
 use ibc_proto::google::protobuf::Any;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use tendermint_rpc::Url;
@@ -6,26 +8,46 @@ use tonic::codegen::http::Uri;
 use tracing::{debug, error, span, warn, Level};
 
 use crate::chain::cosmos::encode::sign_tx;
-use crate::chain::cosmos::gas::gas_amount_to_fee;
+use crate::chain::cosmos::gas::{gas_amount_to_fee, FeePayer};
 use crate::chain::cosmos::simulate::send_tx_simulate;
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
 use crate::chain::cosmos::types::gas::GasConfig;
 use crate::config::types::Memo;
 use crate::error::Error;
-use crate::keyring::Secp256k1KeyPair;
+use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
 use crate::telemetry;
 use crate::util::pretty::PrettyFee;
 
 pub enum EstimatedGas {
     Simulated(u64),
     Default(u64),
+    Learned(u64),
 }
 
 impl EstimatedGas {
     pub fn get_amount(&self) -> u64 {
         match self {
-            Self::Simulated(amount) | Self::Default(amount) => *amount,
+            Self::Simulated(amount) | Self::Default(amount) | Self::Learned(amount) => *amount,
+        }
+    }
+
+    /// The gas to use when a tx cannot be simulated: the amount learnt from
+    /// the previous txs with the same messages if any, or the default gas.
+    fn fallback(gas_config: &GasConfig, learned_gas: Option<u64>) -> Self {
+        match learned_gas {
+            Some(amount) => Self::Learned(amount),
+            None => Self::Default(gas_config.default_gas),
+        }
+    }
+}
+
+impl Display for EstimatedGas {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Simulated(amount) => write!(f, "simulated gas: {amount}"),
+            Self::Default(amount) => write!(f, "default gas: {amount}"),
+            Self::Learned(amount) => write!(f, "learned gas: {amount}"),
         }
     }
 }
@@ -39,6 +61,45 @@ pub async fn estimate_tx_fees(
 ) -> Result<(Fee, EstimatedGas), Error> {
     let gas_config = &config.gas_config;
 
+    let learned_gas = config.gas_model.estimate(messages);
+
+    let payer_address = key_pair.account();
+    let fee_payer = FeePayer {
+        grpc_address: &config.grpc_address,
+        address: &payer_address,
+    };
+
+    if let Some(amount) = learned_gas.filter(|_| config.gas_model.skip_simulation()) {
+        debug!("skipping tx simulation, using the gas amount learned from previous txs: {amount}");
+
+        return fee_for_estimated_gas(
+            gas_config,
+            &config.rpc_address,
+            &config.chain_id,
+            EstimatedGas::Learned(amount),
+            fee_payer,
+        )
+        .await;
+    }
+
+    if !config.backend.simulates_txs() {
+        debug!("skipping tx simulation, which the chain backend does not support");
+
+        let estimated_gas = match learned_gas {
+            Some(amount) => EstimatedGas::Learned(amount),
+            None => EstimatedGas::Default(gas_config.default_gas),
+        };
+
+        return fee_for_estimated_gas(
+            gas_config,
+            &config.rpc_address,
+            &config.chain_id,
+            estimated_gas,
+            fee_payer,
+        )
+        .await;
+    }
+
     debug!(
         "max fee, for use in tx simulation: {}",
         PrettyFee(&gas_config.max_fee)
@@ -53,9 +114,9 @@ pub async fn estimate_tx_fees(
         &gas_config.max_fee,
     )?;
 
-    let tx = Tx {
-        body: Some(signed_tx.body),
-        auth_info: Some(signed_tx.auth_info),
+    let tx = TxRaw {
+        body_bytes: signed_tx.body_bytes,
+        auth_info_bytes: signed_tx.auth_info_bytes,
         signatures: signed_tx.signatures,
     };
 
@@ -66,6 +127,8 @@ pub async fn estimate_tx_fees(
         &config.chain_id,
         tx,
         account,
+        learned_gas,
+        fee_payer,
     )
     .await?;
 
@@ -77,8 +140,10 @@ async fn estimate_fee_with_tx(
     grpc_address: &Uri,
     rpc_address: &Url,
     chain_id: &ChainId,
-    tx: Tx,
+    tx: TxRaw,
     account: &Account,
+    learned_gas: Option<u64>,
+    fee_payer: FeePayer<'_>,
 ) -> Result<(Fee, EstimatedGas), Error> {
     let estimated_gas = {
         crate::time!(
@@ -88,9 +153,19 @@ async fn estimate_fee_with_tx(
             }
 
         );
-        estimate_gas_with_tx(gas_config, grpc_address, tx, account).await
+        estimate_gas_with_tx(gas_config, grpc_address, tx, account, learned_gas).await
     }?;
 
+    fee_for_estimated_gas(gas_config, rpc_address, chain_id, estimated_gas, fee_payer).await
+}
+
+async fn fee_for_estimated_gas(
+    gas_config: &GasConfig,
+    rpc_address: &Url,
+    chain_id: &ChainId,
+    estimated_gas: EstimatedGas,
+    fee_payer: FeePayer<'_>,
+) -> Result<(Fee, EstimatedGas), Error> {
     let estimated_gas_amount = estimated_gas.get_amount();
 
     if estimated_gas_amount > gas_config.max_gas {
@@ -106,8 +181,14 @@ async fn estimate_fee_with_tx(
         ));
     }
 
-    let adjusted_fee =
-        gas_amount_to_fee(gas_config, estimated_gas_amount, chain_id, rpc_address).await;
+    let adjusted_fee = gas_amount_to_fee(
+        gas_config,
+        estimated_gas_amount,
+        chain_id,
+        rpc_address,
+        Some(fee_payer),
+    )
+    .await;
 
     debug!(
         id = %chain_id,
@@ -126,12 +207,14 @@ async fn estimate_fee_with_tx(
 /// [`MsgUpdateClient`, `MsgRecvPacket`, ..., `MsgRecvPacket`]
 ///
 /// If the batch is split in two TX-es, the second one will fail the simulation in `deliverTx` check.
-/// In this case we use the `default_gas` param.
+/// In this case we use the gas learned from previous TX-es with the same messages, if any,
+/// or the `default_gas` param.
 async fn estimate_gas_with_tx(
     gas_config: &GasConfig,
     grpc_address: &Uri,
-    tx: Tx,
+    tx: TxRaw,
     _account: &Account,
+    learned_gas: Option<u64>,
 ) -> Result<EstimatedGas, Error> {
     let simulated_gas = send_tx_simulate(grpc_address, tx)
         .await
@@ -150,20 +233,23 @@ async fn estimate_gas_with_tx(
         }
 
         Ok(None) => {
+            let fallback = EstimatedGas::fallback(gas_config, learned_gas);
+
             warn!(
-                "tx simulation successful but no gas amount used was returned, falling back on default gas: {}",
-                gas_config.default_gas
+                "tx simulation successful but no gas amount used was returned, falling back on {fallback}"
             );
 
-            Ok(EstimatedGas::Default(gas_config.default_gas))
+            Ok(fallback)
         }
 
         // If there is a chance that the tx will be accepted once actually submitted, we fall
-        // back on the default gas and will attempt to send it anyway.
+        // back on the learned or default gas and will attempt to send it anyway.
         // See `can_recover_from_simulation_failure` for more info.
         Err(e) if can_recover_from_simulation_failure(&e) => {
+            let fallback = EstimatedGas::fallback(gas_config, learned_gas);
+
             warn!(
-                "failed to simulate tx, falling back on default gas because the error is potentially recoverable: {}",
+                "failed to simulate tx, falling back on {fallback} because the error is potentially recoverable: {}",
                 e.detail()
             );
 
@@ -174,7 +260,7 @@ async fn estimate_gas_with_tx(
                 get_error_text(&e),
             );
 
-            Ok(EstimatedGas::Default(gas_config.default_gas))
+            Ok(fallback)
         }
 
         Err(e) => {
@@ -46,7 +46,7 @@ pub async fn maybe_register_counterparty_payee(
             )
             .map_err(Error::ics29)?;
 
-            let response = send_tx_with_account_sequence_retry(
+            let (response, _) = send_tx_with_account_sequence_retry(
                 rpc_client,
                 tx_config,
                 key_pair,
@@ -0,0 +1,339 @@
+//! Selection of the fee granter paying for a transaction, based on the
+//! channel of the packets it relays.
+//!
+//! The fee granters configured for a channel are tried in order. A granter whose
+//! allowance has expired, does not allow the messages of the transaction, or cannot
+//! cover the maximum fee anymore is skipped, as is a granter whose allowance cannot
+//! be queried. If none of the granters can pay for the transaction, the default fee
+//! granter, if any, or the relayer account pays for it.
+
+use std::borrow::Cow;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ibc_proto::cosmos::base::v1beta1::Coin;
+use ibc_proto::cosmos::feegrant::v1beta1::{
+    AllowedMsgAllowance, BasicAllowance, PeriodicAllowance,
+};
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::channel::v1::{
+    MsgAcknowledgement, MsgRecvPacket, MsgTimeout, MsgTimeoutOnClose, Packet,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::{
+    acknowledgement, recv_packet, timeout, timeout_on_close,
+};
+use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
+use prost::Message;
+use tracing::{debug, warn};
+
+use crate::chain::cosmos::query::fee_grant::query_allowance;
+use crate::chain::cosmos::types::config::TxConfig;
+
+const BASIC_ALLOWANCE_TYPE_URL: &str = "/cosmos.feegrant.v1beta1.BasicAllowance";
+const PERIODIC_ALLOWANCE_TYPE_URL: &str = "/cosmos.feegrant.v1beta1.PeriodicAllowance";
+const ALLOWED_MSG_ALLOWANCE_TYPE_URL: &str = "/cosmos.feegrant.v1beta1.AllowedMsgAllowance";
+
+/// Returns the Tx config to use for a Tx made of the given messages, in which the fee
+/// granter is the first of the granters configured for the channel of the messages
+/// whose allowance is not exhausted, or the default fee granter otherwise.
+pub async fn tx_config_with_fee_granter<'a>(
+    config: &'a TxConfig,
+    grantee: &str,
+    messages: &[Any],
+) -> Cow<'a, TxConfig> {
+    if config.fee_granters.is_empty() {
+        return Cow::Borrowed(config);
+    }
+
+    let Some((port_id, channel_id)) = packet_channel(messages) else {
+        return Cow::Borrowed(config);
+    };
+
+    let granters = config.fee_granters.granters(&port_id, &channel_id);
+
+    if granters.is_empty() {
+        return Cow::Borrowed(config);
+    }
+
+    for granter in granters {
+        if can_pay(config, granter, grantee, messages).await {
+            debug!(
+                "fee granter {granter} pays for the tx relaying packets of channel {port_id}/{channel_id}"
+            );
+
+            let mut config = config.clone();
+            config.gas_config.fee_granter = granter.clone();
+            config.gas_config.max_fee.granter = granter.clone();
+
+            return Cow::Owned(config);
+        }
+    }
+
+    warn!(
+        "none of the fee granters of channel {port_id}/{channel_id} can pay for the tx, \
+        falling back on the default payer"
+    );
+
+    Cow::Borrowed(config)
+}
+
+/// Returns true if the allowance granted by `granter` to `grantee` can pay
+/// the maximum fee of a Tx made of the given messages. If the allowance cannot
+/// be queried, the granter is assumed not to be able to pay, so that the next one
+/// is tried instead of submitting a Tx which may be rejected.
+async fn can_pay(config: &TxConfig, granter: &str, grantee: &str, messages: &[Any]) -> bool {
+    let allowance = match query_allowance(&config.grpc_address, granter, grantee).await {
+        Ok(Some(allowance)) => allowance,
+        Ok(None) => {
+            debug!("fee granter {granter} has not granted any allowance to {grantee}, skipping it");
+            return false;
+        }
+        Err(e) => {
+            warn!("failed to query the allowance of fee granter {granter}, skipping it: {e}");
+            return false;
+        }
+    };
+
+    let type_urls: Vec<&str> = messages.iter().map(|msg| msg.type_url.as_str()).collect();
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |now| now.as_secs() as i64);
+
+    if is_exhausted(
+        &allowance,
+        &config.gas_config.max_fee.amount,
+        &type_urls,
+        now,
+    ) {
+        debug!("the allowance of fee granter {granter} is exhausted, skipping it");
+        return false;
+    }
+
+    true
+}
+
+/// Returns the port and channel, on the chain the given messages are submitted to, of the
+/// packets they relay, provided that all of them relay packets of the same channel.
+/// Messages which do not relay packets, eg. client updates, are ignored.
+pub fn packet_channel(messages: &[Any]) -> Option<(PortId, ChannelId)> {
+    let mut channels = messages.iter().filter_map(|msg| {
+        let value = msg.value.as_slice();
+
+        // The channel on the receiving chain is the destination channel of a received packet,
+        // and the source channel of an acknowledged or timed out packet
+        let (port, channel) = match msg.type_url.as_str() {
+            recv_packet::TYPE_URL => MsgRecvPacket::decode(value)
+                .ok()?
+                .packet
+                .map(|packet| (packet.destination_port, packet.destination_channel)),
+            acknowledgement::TYPE_URL => {
+                source_channel(MsgAcknowledgement::decode(value).ok()?.packet)
+            }
+            timeout::TYPE_URL => source_channel(MsgTimeout::decode(value).ok()?.packet),
+            timeout_on_close::TYPE_URL => {
+                source_channel(MsgTimeoutOnClose::decode(value).ok()?.packet)
+            }
+            _ => None,
+        }?;
+
+        Some((
+            port.parse::<PortId>().ok()?,
+            channel.parse::<ChannelId>().ok()?,
+        ))
+    });
+
+    let first = channels.next()?;
+
+    channels.all(|channel| channel == first).then_some(first)
+}
+
+fn source_channel(packet: Option<Packet>) -> Option<(String, String)> {
+    packet.map(|packet| (packet.source_port, packet.source_channel))
+}
+
+/// Returns true if the given fee allowance has expired, does not allow all
+/// the given message types, or cannot cover the given fee anymore at the given
+/// time, in seconds since the Unix epoch. Unknown allowances are assumed not
+/// to be exhausted.
+fn is_exhausted(allowance: &Any, fee: &[Coin], type_urls: &[&str], now: i64) -> bool {
+    let value = allowance.value.as_slice();
+
+    match allowance.type_url.as_str() {
+        BASIC_ALLOWANCE_TYPE_URL => BasicAllowance::decode(value)
+            .is_ok_and(|allowance| is_basic_allowance_exhausted(&allowance, fee, now)),
+
+        PERIODIC_ALLOWANCE_TYPE_URL => PeriodicAllowance::decode(value).is_ok_and(|allowance| {
+            let basic_exhausted = allowance
+                .basic
+                .as_ref()
+                .is_some_and(|basic| is_basic_allowance_exhausted(basic, fee, now));
+
+            // The amount which can be spent is reset at the end of the period
+            let period_exhausted = !allowance.period_spend_limit.is_empty()
+                && allowance
+                    .period_reset
+                    .as_ref()
+                    .is_some_and(|reset| reset.seconds > now)
+                && !covers(&allowance.period_can_spend, fee);
+
+            basic_exhausted || period_exhausted
+        }),
+
+        ALLOWED_MSG_ALLOWANCE_TYPE_URL => {
+            AllowedMsgAllowance::decode(value).is_ok_and(|allowance| {
+                let allowed = type_urls
+                    .iter()
+                    .all(|type_url| allowance.allowed_messages.iter().any(|msg| msg == type_url));
+
+                !allowed
+                    || allowance
+                        .allowance
+                        .as_ref()
+                        .is_some_and(|inner| is_exhausted(inner, fee, type_urls, now))
+            })
+        }
+
+        _ => false,
+    }
+}
+
+fn is_basic_allowance_exhausted(allowance: &BasicAllowance, fee: &[Coin], now: i64) -> bool {
+    let expired = allowance
+        .expiration
+        .as_ref()
+        .is_some_and(|expiration| expiration.seconds <= now);
+
+    // An empty spend limit means that the allowance is unlimited
+    let spent = !allowance.spend_limit.is_empty() && !covers(&allowance.spend_limit, fee);
+
+    expired || spent
+}
+
+/// Returns true if the given amounts are at least as large as the given fee, in every denomination.
+fn covers(amounts: &[Coin], fee: &[Coin]) -> bool {
+    fee.iter().all(|fee_coin| {
+        let Ok(needed) = fee_coin.amount.parse::<u128>() else {
+            return false;
+        };
+
+        amounts
+            .iter()
+            .find(|coin| coin.denom == fee_coin.denom)
+            .and_then(|coin| coin.amount.parse::<u128>().ok())
+            .is_some_and(|available| available >= needed)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_proto::google::protobuf::Timestamp;
+
+    fn coins(amount: &str) -> Vec<Coin> {
+        vec![Coin {
+            denom: "uatom".to_string(),
+            amount: amount.to_string(),
+        }]
+    }
+
+    fn basic_allowance(spend_limit: &str, expiration: Option<i64>) -> Any {
+        let allowance = BasicAllowance {
+            spend_limit: coins(spend_limit),
+            expiration: expiration.map(|seconds| Timestamp { seconds, nanos: 0 }),
+        };
+
+        Any {
+            type_url: BASIC_ALLOWANCE_TYPE_URL.to_string(),
+            value: allowance.encode_to_vec(),
+        }
+    }
+
+    #[test]
+    fn exhausted_allowances() {
+        let fee = coins("1000");
+        let msgs = [recv_packet::TYPE_URL];
+
+        assert!(!is_exhausted(
+            &basic_allowance("5000", Some(200)),
+            &fee,
+            &msgs,
+            100
+        ));
+        assert!(is_exhausted(
+            &basic_allowance("999", None),
+            &fee,
+            &msgs,
+            100
+        ));
+        assert!(is_exhausted(
+            &basic_allowance("5000", Some(100)),
+            &fee,
+            &msgs,
+            100
+        ));
+
+        let allowed_msg_allowance = |allowed_messages: &[&str]| Any {
+            type_url: ALLOWED_MSG_ALLOWANCE_TYPE_URL.to_string(),
+            value: AllowedMsgAllowance {
+                allowance: Some(basic_allowance("5000", None)),
+                allowed_messages: allowed_messages.iter().map(|msg| msg.to_string()).collect(),
+            }
+            .encode_to_vec(),
+        };
+
+        assert!(!is_exhausted(
+            &allowed_msg_allowance(&[recv_packet::TYPE_URL]),
+            &fee,
+            &msgs,
+            100
+        ));
+        assert!(is_exhausted(
+            &allowed_msg_allowance(&[timeout::TYPE_URL]),
+            &fee,
+            &msgs,
+            100
+        ));
+    }
+
+    #[test]
+    fn channel_of_packet_messages() {
+        let packet = |source_channel: &str, destination_channel: &str| Packet {
+            source_port: "transfer".to_string(),
+            source_channel: source_channel.to_string(),
+            destination_port: "transfer".to_string(),
+            destination_channel: destination_channel.to_string(),
+            ..Default::default()
+        };
+
+        let recv = Any {
+            type_url: recv_packet::TYPE_URL.to_string(),
+            value: MsgRecvPacket {
+                packet: Some(packet("channel-7", "channel-0")),
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        };
+
+        let ack = |source_channel: &str| Any {
+            type_url: acknowledgement::TYPE_URL.to_string(),
+            value: MsgAcknowledgement {
+                packet: Some(packet(source_channel, "channel-7")),
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        };
+
+        let update_client = Any {
+            type_url: "/ibc.core.client.v1.MsgUpdateClient".to_string(),
+            value: Vec::new(),
+        };
+
+        assert_eq!(
+            packet_channel(&[update_client.clone(), recv.clone(), ack("channel-0")]),
+            Some((PortId::transfer(), ChannelId::new(0)))
+        );
+        assert_eq!(packet_channel(&[recv, ack("channel-1")]), None);
+        assert_eq!(packet_channel(&[update_client]), None);
+    }
+}
@@ -0,0 +1,1037 @@
+//! Recording and replaying of the RPC and gRPC traffic of a chain, to turn the
+//! responses of a node into fixtures for regression tests.
+//!
+//! When `record_fixtures` is set in the configuration of a chain, the JSON-RPC
+//! requests sent to its `rpc_addr`, the unary gRPC requests sent to its `grpc_addr`
+//! and, with the `push` event source, the WebSocket subscriptions to its `event_source`
+//! go through a local [`FixtureServer`], which forwards them to the node and appends
+//! each request along with its response to the fixture file, one JSON object per line.
+//! The events pushed by the node over the WebSocket subscriptions are recorded too.
+//!
+//! A [`FixtureServer`] started with [`FixtureServer::replay`] serves these responses
+//! back without any node. Requests are matched on their method and parameters, and
+//! repeated requests are answered in the order they were recorded, the last response
+//! being served again once all the others have been. The recorded events are pushed
+//! in the order they were recorded, once all the recorded subscriptions have been made.
+
+use alloc::collections::{BTreeMap, VecDeque};
+use core::convert::Infallible;
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use async_tungstenite::tokio::{connect_async, TokioAdapter};
+use async_tungstenite::tungstenite::handshake::derive_accept_key;
+use async_tungstenite::tungstenite::protocol::Role;
+use async_tungstenite::tungstenite::Message;
+use async_tungstenite::WebSocketStream;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use futures::{SinkExt, StreamExt};
+use http::header::{CONNECTION, CONTENT_TYPE, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, UPGRADE};
+use http::uri::PathAndQuery;
+use http::{HeaderMap, HeaderValue, Request, Response, StatusCode};
+use http_body_util::combinators::BoxBody;
+use http_body_util::{BodyExt, Full, StreamBody};
+use hyper::body::{Frame, Incoming};
+use hyper::service::service_fn;
+use hyper::upgrade::Upgraded;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto::Builder;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tendermint_rpc::{Url, WebSocketClientUrl};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tonic::client::Grpc;
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+use tonic::metadata::{AsciiMetadataValue, MetadataKey};
+use tonic::transport::Uri;
+use tonic::{Code, Status};
+use tracing::{debug, warn};
+
+use crate::error::Error;
+use crate::util::create_grpc_client;
+
+/// The prefix of the gRPC metadata which the responses of Cosmos SDK queries depend on,
+/// eg. `x-cosmos-block-height`.
+const COSMOS_METADATA_PREFIX: &str = "x-cosmos-";
+
+/// A request sent to a node along with the response it got.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Interaction {
+    /// A Tendermint JSON-RPC call, whose response is stored without its JSON-RPC id.
+    JsonRpc {
+        method: String,
+        params: Value,
+        response: Value,
+    },
+    /// A unary gRPC call, along with the Cosmos SDK metadata it was sent with.
+    Grpc {
+        path: String,
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        metadata: BTreeMap<String, String>,
+        #[serde(with = "tendermint_proto::serializers::bytes::base64string")]
+        request: Vec<u8>,
+        response: GrpcResponse,
+    },
+    /// A message pushed by the node over a WebSocket subscription, eg. a `NewBlock` event.
+    Event { event: Value },
+}
+
+/// The outcome of a unary gRPC call.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GrpcResponse {
+    /// The encoded response message
+    Message(#[serde(with = "tendermint_proto::serializers::bytes::base64string")] Vec<u8>),
+    /// The error status the call failed with
+    Status { code: i32, message: String },
+}
+
+impl GrpcResponse {
+    fn new(result: &Result<Vec<u8>, Status>) -> Self {
+        match result {
+            Ok(message) => Self::Message(message.clone()),
+            Err(status) => Self::Status {
+                code: status.code().into(),
+                message: status.message().to_string(),
+            },
+        }
+    }
+
+    fn into_result(self) -> Result<Vec<u8>, Status> {
+        match self {
+            Self::Message(message) => Ok(message),
+            Self::Status { code, message } => Err(Status::new(Code::from(code), message)),
+        }
+    }
+}
+
+/// Reads the interactions recorded in the given fixture file.
+pub fn read_fixtures(path: &Path) -> Result<Vec<Interaction>, Error> {
+    let file = File::open(path).map_err(|e| io_error(path, e))?;
+
+    let mut interactions = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| io_error(path, e))?;
+        if !line.trim().is_empty() {
+            interactions.push(serde_json::from_str(&line).map_err(|e| json_error(path, e))?);
+        }
+    }
+
+    Ok(interactions)
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> Error {
+    Error::persisted_file_io("RPC fixtures".to_string(), path.display().to_string(), e)
+}
+
+fn json_error(path: &Path, e: serde_json::Error) -> Error {
+    Error::persisted_file_json("RPC fixtures".to_string(), path.display().to_string(), e)
+}
+
+/// A local endpoint serving both the JSON-RPC and the gRPC requests of a chain,
+/// either by recording the responses of its node or by replaying recorded ones.
+///
+/// The server is stopped when dropped.
+#[derive(Debug)]
+pub struct FixtureServer {
+    addr: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl FixtureServer {
+    /// Starts a server which forwards the requests it receives to the given RPC, gRPC
+    /// and, if any, WebSocket endpoints, and appends them along with their responses
+    /// to the fixture file at `path`.
+    pub async fn record(
+        path: &Path,
+        rpc_addr: &Url,
+        grpc_addr: &Url,
+        websocket_addr: Option<&WebSocketClientUrl>,
+    ) -> Result<Self, Error> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| io_error(path, e))?;
+
+        let grpc_addr = Uri::from_str(&grpc_addr.to_string())
+            .map_err(|e| Error::invalid_uri(grpc_addr.to_string(), e))?;
+
+        let recorder = Recorder {
+            path: path.to_path_buf(),
+            file: Mutex::new(file),
+            rpc_client: reqwest::Client::new(),
+            rpc_addr: rpc_addr.clone(),
+            grpc_addr,
+            websocket_addr: websocket_addr.cloned(),
+        };
+
+        Self::start(Backend::Record(recorder)).await
+    }
+
+    /// Starts a server which answers the requests it receives with the responses
+    /// of the given interactions.
+    pub async fn replay(interactions: Vec<Interaction>) -> Result<Self, Error> {
+        Self::start(Backend::Replay(Mutex::new(Replay::new(interactions)))).await
+    }
+
+    /// The URL of the server, to be used as both the RPC and the gRPC address of the chain.
+    pub fn url(&self) -> Url {
+        Url::from_str(&format!("http://{}", self.addr)).expect("valid URL of a socket address")
+    }
+
+    /// The URL of the server, to be used as the WebSocket address of the `push` event source.
+    pub fn websocket_url(&self) -> WebSocketClientUrl {
+        WebSocketClientUrl::from_str(&format!("ws://{}/websocket", self.addr))
+            .expect("valid URL of a socket address")
+    }
+
+    async fn start(backend: Backend) -> Result<Self, Error> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .await
+            .map_err(Error::io)?;
+
+        let addr = listener.local_addr().map_err(Error::io)?;
+        let backend = Arc::new(backend);
+
+        let task = tokio::spawn(async move {
+            loop {
+                let stream = match listener.accept().await {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        warn!("fixture server failed to accept a connection: {e}");
+                        continue;
+                    }
+                };
+
+                let backend = backend.clone();
+
+                tokio::spawn(async move {
+                    let service = service_fn(move |request| handle(backend.clone(), request));
+
+                    // Serves HTTP/1 for the JSON-RPC requests and the WebSocket subscriptions,
+                    // and HTTP/2 for the gRPC requests
+                    let builder = Builder::new(TokioExecutor::new());
+                    if let Err(e) = builder
+                        .serve_connection_with_upgrades(TokioIo::new(stream), service)
+                        .await
+                    {
+                        debug!("fixture server connection failed: {e}");
+                    }
+                });
+            }
+        });
+
+        Ok(Self { addr, task })
+    }
+}
+
+impl Drop for FixtureServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+type ResponseBody = BoxBody<Bytes, Infallible>;
+
+type WebSocket = WebSocketStream<TokioAdapter<TokioIo<Upgraded>>>;
+
+enum Backend {
+    Record(Recorder),
+    Replay(Mutex<Replay>),
+}
+
+async fn handle(
+    backend: Arc<Backend>,
+    request: Request<Incoming>,
+) -> Result<Response<ResponseBody>, Infallible> {
+    let is_websocket = request
+        .headers()
+        .get(UPGRADE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.eq_ignore_ascii_case("websocket"));
+
+    if is_websocket {
+        return Ok(upgrade_websocket(backend, request));
+    }
+
+    let is_grpc = request
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/grpc"));
+
+    let (parts, body) = request.into_parts();
+
+    let body = match body.collect().await {
+        Ok(body) => body.to_bytes(),
+        Err(e) => return Ok(plain_response(StatusCode::BAD_REQUEST, e.to_string())),
+    };
+
+    if !is_grpc {
+        return Ok(backend.json_rpc(body).await);
+    }
+
+    let path = parts.uri.path().to_string();
+
+    let metadata = parts
+        .headers
+        .iter()
+        .filter(|(name, _)| name.as_str().starts_with(COSMOS_METADATA_PREFIX))
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect();
+
+    let result = match decode_grpc_message(body) {
+        Some(message) => backend.grpc(path, metadata, message.to_vec()).await,
+        None => Err(Status::invalid_argument(
+            "malformed or compressed gRPC message",
+        )),
+    };
+
+    Ok(grpc_response(result))
+}
+
+/// Accepts the WebSocket handshake of a client, whose subscriptions are served
+/// once the connection has been upgraded.
+fn upgrade_websocket(
+    backend: Arc<Backend>,
+    mut request: Request<Incoming>,
+) -> Response<ResponseBody> {
+    let Some(accept_key) = request
+        .headers()
+        .get(SEC_WEBSOCKET_KEY)
+        .map(|key| derive_accept_key(key.as_bytes()))
+        .and_then(|accept_key| HeaderValue::from_str(&accept_key).ok())
+    else {
+        return plain_response(
+            StatusCode::BAD_REQUEST,
+            "missing `Sec-WebSocket-Key` header".to_string(),
+        );
+    };
+
+    let upgrade = hyper::upgrade::on(&mut request);
+
+    tokio::spawn(async move {
+        match upgrade.await {
+            Ok(upgraded) => {
+                let socket = TokioAdapter::new(TokioIo::new(upgraded));
+                let client = WebSocketStream::from_raw_socket(socket, Role::Server, None).await;
+                backend.websocket(client).await;
+            }
+            Err(e) => debug!("fixture server failed to upgrade a WebSocket connection: {e}"),
+        }
+    });
+
+    let mut response = Response::new(Full::new(Bytes::new()).boxed());
+    *response.status_mut() = StatusCode::SWITCHING_PROTOCOLS;
+
+    let headers = response.headers_mut();
+    headers.insert(CONNECTION, HeaderValue::from_static("upgrade"));
+    headers.insert(UPGRADE, HeaderValue::from_static("websocket"));
+    headers.insert(SEC_WEBSOCKET_ACCEPT, accept_key);
+
+    response
+}
+
+impl Backend {
+    async fn json_rpc(&self, body: Bytes) -> Response<ResponseBody> {
+        let request: Value = match serde_json::from_slice(&body) {
+            Ok(request) => request,
+            Err(e) => return plain_response(StatusCode::BAD_REQUEST, e.to_string()),
+        };
+
+        match self {
+            Self::Record(recorder) => {
+                let method = request["method"].as_str().unwrap_or_default().to_string();
+                let params = request["params"].clone();
+
+                recorder.json_rpc(method, params, body).await
+            }
+            Self::Replay(replay) => {
+                let response = replay
+                    .lock()
+                    .expect("poisoned lock")
+                    .json_rpc_response(&request);
+
+                json_response(StatusCode::OK, response.to_string())
+            }
+        }
+    }
+
+    async fn websocket(&self, client: WebSocket) {
+        match self {
+            Self::Record(recorder) => recorder.websocket(client).await,
+            Self::Replay(replay) => replay_websocket(replay, client).await,
+        }
+    }
+
+    async fn grpc(
+        &self,
+        path: String,
+        metadata: BTreeMap<String, String>,
+        message: Vec<u8>,
+    ) -> Result<Vec<u8>, Status> {
+        match self {
+            Self::Record(recorder) => recorder.grpc(path, metadata, message).await,
+            Self::Replay(replay) => {
+                let recorded = replay
+                    .lock()
+                    .expect("poisoned lock")
+                    .grpc(&path, &metadata, &message);
+
+                match recorded {
+                    Some(response) => response.into_result(),
+                    None => {
+                        warn!(%path, ?metadata, "no recorded response for gRPC request");
+
+                        Err(Status::internal(format!(
+                            "no recorded response for `{path}` with metadata {metadata:?}"
+                        )))
+                    }
+                }
+            }
+        }
+    }
+}
+
+struct Recorder {
+    path: PathBuf,
+    file: Mutex<File>,
+    rpc_client: reqwest::Client,
+    rpc_addr: Url,
+    grpc_addr: Uri,
+    websocket_addr: Option<WebSocketClientUrl>,
+}
+
+impl Recorder {
+    async fn json_rpc(&self, method: String, params: Value, body: Bytes) -> Response<ResponseBody> {
+        let response = self
+            .rpc_client
+            .post(self.rpc_addr.to_string())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await;
+
+        let (status, body) = match response {
+            Ok(response) => (response.status().as_u16(), response.bytes().await),
+            Err(e) => return plain_response(StatusCode::BAD_GATEWAY, e.to_string()),
+        };
+
+        let body = match body {
+            Ok(body) => body,
+            Err(e) => return plain_response(StatusCode::BAD_GATEWAY, e.to_string()),
+        };
+
+        let status = StatusCode::from_u16(status).unwrap_or(StatusCode::BAD_GATEWAY);
+
+        // Only record the responses of the node, not the errors of a proxy in front of it
+        if status == StatusCode::OK {
+            if let Ok(mut response) = serde_json::from_slice::<Value>(&body) {
+                if let Some(fields) = response.as_object_mut() {
+                    fields.remove("id");
+                }
+
+                self.write(&Interaction::JsonRpc {
+                    method,
+                    params,
+                    response,
+                });
+            }
+        }
+
+        json_response(status, body)
+    }
+
+    async fn grpc(
+        &self,
+        path: String,
+        metadata: BTreeMap<String, String>,
+        message: Vec<u8>,
+    ) -> Result<Vec<u8>, Status> {
+        let client = create_grpc_client(&self.grpc_addr, Grpc::new)
+            .await
+            .map_err(|e| Status::unavailable(e.to_string()))?;
+
+        let mut client = client.max_decoding_message_size(usize::MAX);
+
+        client
+            .ready()
+            .await
+            .map_err(|e| Status::unavailable(e.to_string()))?;
+
+        let mut request = tonic::Request::new(message.clone());
+
+        for (name, value) in &metadata {
+            if let (Ok(name), Ok(value)) = (
+                MetadataKey::from_bytes(name.as_bytes()),
+                AsciiMetadataValue::try_from(value.as_str()),
+            ) {
+                request.metadata_mut().insert(name, value);
+            }
+        }
+
+        let path_and_query = PathAndQuery::try_from(path.as_str())
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let result = client
+            .unary(request, path_and_query, RawCodec)
+            .await
+            .map(tonic::Response::into_inner);
+
+        self.write(&Interaction::Grpc {
+            path,
+            metadata,
+            request: message,
+            response: GrpcResponse::new(&result),
+        });
+
+        result
+    }
+
+    /// Forwards the messages of a WebSocket client to the node and back, recording
+    /// the requests of the client along with their responses, and the events
+    /// pushed by the node.
+    async fn websocket(&self, mut client: WebSocket) {
+        let Some(websocket_addr) = &self.websocket_addr else {
+            warn!("no WebSocket address to forward the subscriptions of the client to");
+            let _ = client.close(None).await;
+            return;
+        };
+
+        let mut node = match connect_async(websocket_addr.to_string()).await {
+            Ok((node, _)) => node,
+            Err(e) => {
+                warn!("failed to connect to the WebSocket endpoint at {websocket_addr}: {e}");
+                let _ = client.close(None).await;
+                return;
+            }
+        };
+
+        // The method and parameters of the requests waiting for their response, by id
+        let mut pending = HashMap::new();
+
+        loop {
+            tokio::select! {
+                message = client.next() => {
+                    let Some(Ok(Message::Text(text))) = message else {
+                        if is_open(&message) {
+                            continue;
+                        }
+                        break;
+                    };
+
+                    if let Ok(request) = serde_json::from_str::<Value>(&text) {
+                        let method = request["method"].as_str().unwrap_or_default().to_string();
+                        pending.insert(request["id"].to_string(), (method, request["params"].clone()));
+                    }
+
+                    if node.send(Message::Text(text)).await.is_err() {
+                        break;
+                    }
+                }
+
+                message = node.next() => {
+                    let Some(Ok(Message::Text(text))) = message else {
+                        if is_open(&message) {
+                            continue;
+                        }
+                        break;
+                    };
+
+                    if let Ok(mut response) = serde_json::from_str::<Value>(&text) {
+                        let interaction = match pending.remove(&response["id"].to_string()) {
+                            Some((method, params)) => {
+                                if let Some(fields) = response.as_object_mut() {
+                                    fields.remove("id");
+                                }
+
+                                Interaction::JsonRpc { method, params, response }
+                            }
+                            None => Interaction::Event { event: response },
+                        };
+
+                        self.write(&interaction);
+                    }
+
+                    if client.send(Message::Text(text)).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        let _ = node.close(None).await;
+        let _ = client.close(None).await;
+    }
+
+    fn write(&self, interaction: &Interaction) {
+        let result = serde_json::to_string(interaction)
+            .map_err(|e| json_error(&self.path, e))
+            .and_then(|line| {
+                let mut file = self.file.lock().expect("poisoned lock");
+                writeln!(file, "{line}").map_err(|e| io_error(&self.path, e))
+            });
+
+        if let Err(e) = result {
+            warn!("failed to record an RPC interaction: {e}");
+        }
+    }
+}
+
+/// The path, metadata and message of a gRPC request.
+type GrpcRequest = (String, BTreeMap<String, String>, Vec<u8>);
+
+/// Returns `true` if the given message, which is not a text message,
+/// does not end the WebSocket connection, eg. a ping.
+fn is_open<E>(message: &Option<Result<Message, E>>) -> bool {
+    matches!(message, Some(Ok(message)) if !message.is_close())
+}
+
+/// Answers the requests of a WebSocket client with the recorded responses, and pushes
+/// the recorded events once the client has made all the recorded subscriptions.
+async fn replay_websocket(replay: &Mutex<Replay>, mut client: WebSocket) {
+    let mut subscriptions = HashSet::new();
+    let mut events_pushed = false;
+
+    while let Some(Ok(message)) = client.next().await {
+        let text = match message {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+
+        let Ok(request) = serde_json::from_str::<Value>(&text) else {
+            continue;
+        };
+
+        if request["method"] == "subscribe" {
+            if let Some(query) = request["params"]["query"].as_str() {
+                subscriptions.insert(query.to_string());
+            }
+        }
+
+        let messages = {
+            let mut replay = replay.lock().expect("poisoned lock");
+
+            let mut messages = vec![replay.json_rpc_response(&request)];
+
+            if !events_pushed && replay.subscriptions.is_subset(&subscriptions) {
+                messages.extend(replay.events.iter().cloned());
+                events_pushed = true;
+            }
+
+            messages
+        };
+
+        for message in messages {
+            if client
+                .send(Message::Text(message.to_string()))
+                .await
+                .is_err()
+            {
+                return;
+            }
+        }
+    }
+
+    let _ = client.close(None).await;
+}
+
+/// The recorded responses which are left to be served, by request,
+/// and the recorded events along with the subscriptions they were pushed for.
+#[derive(Default)]
+struct Replay {
+    json_rpc: HashMap<(String, String), VecDeque<Value>>,
+    grpc: HashMap<GrpcRequest, VecDeque<GrpcResponse>>,
+    subscriptions: HashSet<String>,
+    events: Vec<Value>,
+}
+
+impl Replay {
+    fn new(interactions: Vec<Interaction>) -> Self {
+        let mut replay = Self::default();
+
+        for interaction in interactions {
+            match interaction {
+                Interaction::JsonRpc {
+                    method,
+                    params,
+                    response,
+                } => {
+                    if method == "subscribe" {
+                        if let Some(query) = params["query"].as_str() {
+                            replay.subscriptions.insert(query.to_string());
+                        }
+                    }
+
+                    replay
+                        .json_rpc
+                        .entry((method, params.to_string()))
+                        .or_default()
+                        .push_back(response)
+                }
+
+                Interaction::Grpc {
+                    path,
+                    metadata,
+                    request,
+                    response,
+                } => replay
+                    .grpc
+                    .entry((path, metadata, request))
+                    .or_default()
+                    .push_back(response),
+
+                Interaction::Event { event } => replay.events.push(event),
+            }
+        }
+
+        replay
+    }
+
+    /// The response to the given JSON-RPC request, or an error
+    /// if no response was recorded for the request.
+    fn json_rpc_response(&mut self, request: &Value) -> Value {
+        let method = request["method"].as_str().unwrap_or_default();
+        let params = &request["params"];
+
+        match self.json_rpc(method, params) {
+            Some(mut response) => {
+                response["id"] = request["id"].clone();
+                response
+            }
+            None => {
+                warn!(%method, %params, "no recorded response for JSON-RPC request");
+
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "error": {
+                        "code": -32603,
+                        "message": "Internal error",
+                        "data": format!("no recorded response for `{method}` with params {params}"),
+                    },
+                })
+            }
+        }
+    }
+
+    fn json_rpc(&mut self, method: &str, params: &Value) -> Option<Value> {
+        self.json_rpc
+            .get_mut(&(method.to_string(), params.to_string()))
+            .and_then(next_response)
+    }
+
+    fn grpc(
+        &mut self,
+        path: &str,
+        metadata: &BTreeMap<String, String>,
+        request: &[u8],
+    ) -> Option<GrpcResponse> {
+        self.grpc
+            .get_mut(&(path.to_string(), metadata.clone(), request.to_vec()))
+            .and_then(next_response)
+    }
+}
+
+/// Takes the next recorded response, keeping the last one for the requests to come.
+fn next_response<T: Clone>(responses: &mut VecDeque<T>) -> Option<T> {
+    if responses.len() > 1 {
+        responses.pop_front()
+    } else {
+        responses.front().cloned()
+    }
+}
+
+/// Passes the encoded gRPC messages through as they are.
+#[derive(Clone, Copy, Debug, Default)]
+struct RawCodec;
+
+impl Codec for RawCodec {
+    type Encode = Vec<u8>;
+    type Decode = Vec<u8>;
+    type Encoder = Self;
+    type Decoder = Self;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        Self
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        Self
+    }
+}
+
+impl Encoder for RawCodec {
+    type Item = Vec<u8>;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        dst.put_slice(&item);
+        Ok(())
+    }
+}
+
+impl Decoder for RawCodec {
+    type Item = Vec<u8>;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        Ok(Some(src.copy_to_bytes(src.remaining()).to_vec()))
+    }
+}
+
+/// Strips the length prefix of an uncompressed gRPC message.
+fn decode_grpc_message(mut body: Bytes) -> Option<Bytes> {
+    if body.remaining() < 5 || body.get_u8() != 0 {
+        return None;
+    }
+
+    let len = body.get_u32() as usize;
+    (body.remaining() == len).then_some(body)
+}
+
+/// Prefixes a gRPC message with its length, marking it as uncompressed.
+fn encode_grpc_message(message: &[u8]) -> Bytes {
+    let mut body = BytesMut::with_capacity(5 + message.len());
+    body.put_u8(0);
+    body.put_u32(message.len() as u32);
+    body.put_slice(message);
+    body.freeze()
+}
+
+fn grpc_response(result: Result<Vec<u8>, Status>) -> Response<ResponseBody> {
+    let mut frames = Vec::new();
+
+    let status = match result {
+        Ok(message) => {
+            frames.push(Frame::data(encode_grpc_message(&message)));
+            Status::new(Code::Ok, "")
+        }
+        Err(status) => status,
+    };
+
+    let mut trailers = HeaderMap::new();
+    if let Err(e) = status.add_header(&mut trailers) {
+        warn!("failed to encode the gRPC status {status:?}: {e}");
+    }
+    frames.push(Frame::trailers(trailers));
+
+    let body = StreamBody::new(futures::stream::iter(frames.into_iter().map(Ok)));
+
+    let mut response = Response::new(BodyExt::boxed(body));
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/grpc"));
+    response
+}
+
+fn json_response(status: StatusCode, body: impl Into<Bytes>) -> Response<ResponseBody> {
+    let mut response = Response::new(Full::new(body.into()).boxed());
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+fn plain_response(status: StatusCode, body: String) -> Response<ResponseBody> {
+    let mut response = Response::new(Full::new(Bytes::from(body)).boxed());
+    *response.status_mut() = status;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_proto::ibc::core::client::v1::query_client::QueryClient;
+    use ibc_proto::ibc::core::client::v1::{QueryClientStateRequest, QueryClientStateResponse};
+    use prost::Message as _;
+    use tendermint_rpc::event::EventData;
+    use tendermint_rpc::query::{EventType, Query};
+    use tendermint_rpc::{Client, HttpClient, SubscriptionClient, WebSocketClient};
+
+    fn health(response: Value) -> Interaction {
+        Interaction::JsonRpc {
+            method: "health".to_string(),
+            params: Value::Null,
+            response: json!({ "jsonrpc": "2.0", "result": response }),
+        }
+    }
+
+    fn client_state(client_id: &str, response: GrpcResponse) -> Interaction {
+        Interaction::Grpc {
+            path: "/ibc.core.client.v1.Query/ClientState".to_string(),
+            metadata: BTreeMap::from([("x-cosmos-block-height".to_string(), "10".to_string())]),
+            request: QueryClientStateRequest {
+                client_id: client_id.to_string(),
+            }
+            .encode_to_vec(),
+            response,
+        }
+    }
+
+    fn subscribe(query: &Query) -> Interaction {
+        Interaction::JsonRpc {
+            method: "subscribe".to_string(),
+            params: json!({ "query": query.to_string() }),
+            response: json!({ "jsonrpc": "2.0", "result": {} }),
+        }
+    }
+
+    fn event(query: &Query, height: u64) -> Interaction {
+        Interaction::Event {
+            event: json!({
+                "jsonrpc": "2.0",
+                "id": "1#event",
+                "result": {
+                    "query": query.to_string(),
+                    "data": { "type": "GenericJsonEvent", "value": { "height": height } },
+                },
+            }),
+        }
+    }
+
+    /// Subscribes to the given query and returns the data of the first `count` events.
+    async fn subscribe_events(
+        server: &FixtureServer,
+        query: Query,
+        count: usize,
+    ) -> Vec<EventData> {
+        let (client, driver) = WebSocketClient::builder(server.websocket_url())
+            .build()
+            .await
+            .unwrap();
+        let driver = tokio::spawn(driver.run());
+
+        let subscription = client.subscribe(query).await.unwrap();
+        let events = subscription
+            .take(count)
+            .map(|event| event.unwrap().data)
+            .collect()
+            .await;
+
+        client.close().unwrap();
+        let _ = driver.await;
+
+        events
+    }
+
+    async fn query_client_state(
+        server: &FixtureServer,
+        client_id: &str,
+    ) -> Result<QueryClientStateResponse, Status> {
+        let uri = Uri::from_str(&server.url().to_string()).unwrap();
+        let mut client = create_grpc_client(&uri, QueryClient::new).await.unwrap();
+
+        let mut request = tonic::Request::new(QueryClientStateRequest {
+            client_id: client_id.to_string(),
+        });
+        request
+            .metadata_mut()
+            .insert("x-cosmos-block-height", "10".parse().unwrap());
+
+        client
+            .client_state(request)
+            .await
+            .map(tonic::Response::into_inner)
+    }
+
+    #[tokio::test]
+    async fn record_and_replay() {
+        let found = QueryClientStateResponse {
+            client_state: None,
+            proof: vec![1, 2, 3],
+            proof_height: None,
+        };
+
+        let new_block = Query::from(EventType::NewBlock);
+
+        let interactions = vec![
+            health(json!({})),
+            client_state(
+                "07-tendermint-0",
+                GrpcResponse::Message(found.encode_to_vec()),
+            ),
+            client_state(
+                "07-tendermint-1",
+                GrpcResponse::Status {
+                    code: Code::NotFound.into(),
+                    message: "client not found".to_string(),
+                },
+            ),
+            subscribe(&new_block),
+            event(&new_block, 1),
+            event(&new_block, 2),
+        ];
+
+        let path = std::env::temp_dir().join(format!("fixtures-{}.jsonl", uuid::Uuid::new_v4()));
+
+        let node = FixtureServer::replay(interactions.clone()).await.unwrap();
+        let recorder =
+            FixtureServer::record(&path, &node.url(), &node.url(), Some(&node.websocket_url()))
+                .await
+                .unwrap();
+
+        // Each server should answer the same way as the node did
+        for server in [&node, &recorder] {
+            let rpc_client = HttpClient::new(server.url()).unwrap();
+            rpc_client.health().await.unwrap();
+
+            let response = query_client_state(server, "07-tendermint-0").await;
+            assert_eq!(response.unwrap(), found);
+
+            let status = query_client_state(server, "07-tendermint-1")
+                .await
+                .unwrap_err();
+            assert_eq!(status.code(), Code::NotFound);
+            assert_eq!(status.message(), "client not found");
+
+            let events = subscribe_events(server, new_block.clone(), 2).await;
+            assert_eq!(
+                events,
+                [
+                    EventData::GenericJsonEvent(json!({ "height": 1 })),
+                    EventData::GenericJsonEvent(json!({ "height": 2 })),
+                ]
+            );
+        }
+
+        let recorded = read_fixtures(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(recorded, interactions);
+
+        let replayed = FixtureServer::replay(recorded).await.unwrap();
+        let response = query_client_state(&replayed, "07-tendermint-0").await;
+        assert_eq!(response.unwrap(), found);
+
+        // Requests which were not recorded should fail instead of hanging
+        let status = query_client_state(&replayed, "07-tendermint-2")
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::Internal);
+
+        let rpc_client = HttpClient::new(replayed.url()).unwrap();
+        assert!(rpc_client.abci_info().await.is_err());
+    }
+
+    #[test]
+    fn repeated_requests_are_answered_in_recorded_order() {
+        let mut replay = Replay::new(vec![health(json!({ "n": 1 })), health(json!({ "n": 2 }))]);
+
+        let mut next = || replay.json_rpc("health", &Value::Null).unwrap()["result"]["n"].clone();
+
+        assert_eq!(next(), 1);
+        assert_eq!(next(), 2);
+        assert_eq!(next(), 2);
+    }
+}
@@ -1,4 +1,7 @@
+use alloc::collections::BTreeMap;
 use core::cmp::min;
+use core::str::FromStr;
+use http::uri::Uri;
 use ibc_proto::cosmos::base::v1beta1::Coin;
 use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
@@ -7,17 +10,28 @@ use num_rational::BigRational;
 use tendermint_rpc::Url;
 use tracing::warn;
 
+use crate::chain::cosmos::query::balance::query_all_balances;
 use crate::chain::cosmos::types::gas::GasConfig;
+use crate::config::dynamic_gas::{DynamicGasPrice, DynamicGasPriceProvider};
 use crate::config::GasPrice;
 use crate::telemetry;
 
-use super::eip_base_fee::query_eip_base_fee;
+use super::eip_base_fee::{gas_price_provider, query_eip_base_fees};
+
+/// The account paying the fees of a transaction, whose balances
+/// restrict the denoms in which the fees can be paid.
+#[derive(Clone, Copy)]
+pub struct FeePayer<'a> {
+    pub grpc_address: &'a Uri,
+    pub address: &'a str,
+}
 
 pub async fn gas_amount_to_fee(
     config: &GasConfig,
     gas_amount: u64,
     chain_id: &ChainId,
     rpc_address: &Url,
+    fee_payer: Option<FeePayer<'_>>,
 ) -> Fee {
     let adjusted_gas_limit = adjust_estimated_gas(AdjustGas {
         gas_multiplier: config.gas_multiplier,
@@ -25,8 +39,24 @@ pub async fn gas_amount_to_fee(
         gas_amount,
     });
 
+    // When the fees can be paid in several denoms, only consider
+    // the ones in which the payer can afford them.
+    let balances = match fee_payer {
+        Some(fee_payer) if config.dynamic_gas_price.denoms.len() > 1 => {
+            fee_payer_balances(config, &fee_payer).await
+        }
+        _ => None,
+    };
+
+    let can_pay = |gas_price: &GasPrice| match &balances {
+        Some(balances) => balances
+            .get(&gas_price.denom)
+            .is_some_and(|balance| *balance >= mul_ceil(adjusted_gas_limit, gas_price.price)),
+        None => true,
+    };
+
     // The fee in coins based on gas amount
-    let dynamic_gas_price = dynamic_gas_price(config, chain_id, rpc_address).await;
+    let dynamic_gas_price = select_dynamic_gas_price(config, chain_id, rpc_address, can_pay).await;
     let amount = calculate_fee(adjusted_gas_limit, &dynamic_gas_price);
 
     Fee {
@@ -37,30 +67,86 @@ pub async fn gas_amount_to_fee(
     }
 }
 
+/// The balances of the account paying the fees, ie. of the fee granter if any,
+/// or `None` if they cannot be queried.
+async fn fee_payer_balances(
+    config: &GasConfig,
+    fee_payer: &FeePayer<'_>,
+) -> Option<BTreeMap<String, BigInt>> {
+    let address = if config.fee_granter.is_empty() {
+        fee_payer.address
+    } else {
+        &config.fee_granter
+    };
+
+    match query_all_balances(fee_payer.grpc_address, address).await {
+        Ok(balances) => Some(
+            balances
+                .into_iter()
+                .filter_map(|balance| {
+                    let amount = BigInt::from_str(&balance.amount).ok()?;
+                    Some((balance.denom, amount))
+                })
+                .collect(),
+        ),
+        Err(e) => {
+            warn!("failed to query the balances of fee payer `{address}`, will not check that it can afford the fees: {e}");
+            None
+        }
+    }
+}
+
 pub async fn dynamic_gas_price(
     config: &GasConfig,
     chain_id: &ChainId,
     rpc_address: &Url,
+) -> GasPrice {
+    select_dynamic_gas_price(config, chain_id, rpc_address, |_| true).await
+}
+
+/// The dynamic gas price of the cheapest accepted fee denom among
+/// the ones satisfying `can_pay`, given the gas price capped to the maximum.
+async fn select_dynamic_gas_price(
+    config: &GasConfig,
+    chain_id: &ChainId,
+    rpc_address: &Url,
+    can_pay: impl Fn(&GasPrice) -> bool,
 ) -> GasPrice {
     if config.dynamic_gas_price.enabled {
-        let dynamic_gas_price = query_eip_base_fee(rpc_address, &config.gas_price.denom, chain_id)
+        // The provider is required when enabled, which is checked by the config validation
+        let provider = gas_price_provider(
+            config
+                .dynamic_gas_price
+                .provider
+                .unwrap_or(DynamicGasPriceProvider::Static),
+        );
+
+        let dynamic_gas_price = match query_eip_base_fees(provider, rpc_address, &config.gas_price)
             .await
-            .map(|base_fee| base_fee * config.dynamic_gas_price.multiplier)
-            .map(|new_price| GasPrice {
-                price: new_price,
-                denom: config.gas_price.denom.clone(),
-            });
-
-        let dynamic_gas_price = match dynamic_gas_price {
-            Ok(dynamic_gas_price) => {
-                telemetry!(
-                    dynamic_gas_queried_success_fees,
-                    chain_id,
-                    dynamic_gas_price.price
-                );
-
-                dynamic_gas_price
-            }
+        {
+            Ok(base_fees) => match cheapest_gas_price(
+                &config.dynamic_gas_price,
+                &config.gas_price.denom,
+                base_fees,
+                can_pay,
+            ) {
+                Some(dynamic_gas_price) => {
+                    telemetry!(
+                        dynamic_gas_queried_success_fees,
+                        chain_id,
+                        dynamic_gas_price.price
+                    );
+
+                    dynamic_gas_price
+                }
+                None => {
+                    warn!(
+                        "no EIP base fee was quoted in an accepted fee denom held by the fee payer, \
+                            will fallback to configured `gas_price`"
+                    );
+                    config.gas_price.clone()
+                }
+            },
             Err(e) => {
                 warn!("failed to query EIP base fee, will fallback to configured `gas_price`: {e}");
                 config.gas_price.clone()
@@ -72,14 +158,20 @@ pub async fn dynamic_gas_price(
             let _ = chain_id;
         }
 
-        if dynamic_gas_price.price > config.dynamic_gas_price.max {
+        let max = max_gas_price(
+            &config.dynamic_gas_price,
+            &config.gas_price.denom,
+            &dynamic_gas_price.denom,
+        );
+
+        if dynamic_gas_price.price > max {
             warn!(
                 "queried EIP gas price is higher than configured max gas price, \
                 will fallback to configured `max`. Queried: {}, maximum: {}",
-                dynamic_gas_price.price, config.dynamic_gas_price.max
+                dynamic_gas_price.price, max
             );
 
-            return GasPrice::new(config.dynamic_gas_price.max, dynamic_gas_price.denom);
+            return GasPrice::new(max, dynamic_gas_price.denom);
         }
 
         telemetry!(dynamic_gas_paid_fees, chain_id, dynamic_gas_price.price);
@@ -90,6 +182,58 @@ pub async fn dynamic_gas_price(
     }
 }
 
+/// Applies the configured multiplier to the given base fees, and returns the resulting
+/// gas price of the accepted fee denom in which the fees are the cheapest, taking into
+/// account the configured maximum gas price, or `None` if no accepted denom was quoted.
+/// The denoms whose gas price, capped to the maximum, does not satisfy `can_pay` are skipped.
+fn cheapest_gas_price(
+    config: &DynamicGasPrice,
+    gas_price_denom: &str,
+    base_fees: Vec<GasPrice>,
+    can_pay: impl Fn(&GasPrice) -> bool,
+) -> Option<GasPrice> {
+    base_fees
+        .into_iter()
+        .filter_map(|base_fee| {
+            let value = denom_value(config, gas_price_denom, &base_fee.denom)?;
+            let price = base_fee.price * config.multiplier;
+            let max = max_gas_price(config, gas_price_denom, &base_fee.denom);
+
+            if !can_pay(&GasPrice::new(price.min(max), base_fee.denom.clone())) {
+                return None;
+            }
+
+            let cost = price.min(max) * value;
+
+            Some((cost, GasPrice::new(price, base_fee.denom)))
+        })
+        .min_by(|(cost_a, _), (cost_b, _)| cost_a.total_cmp(cost_b))
+        .map(|(_, gas_price)| gas_price)
+}
+
+/// The value of one unit of the given denom, relative to the other accepted fee
+/// denoms, or `None` if fees cannot be paid in this denom. If no fee denoms are
+/// configured, only the denom of the configured gas price is accepted.
+fn denom_value(config: &DynamicGasPrice, gas_price_denom: &str, denom: &str) -> Option<f64> {
+    if config.denoms.is_empty() {
+        (denom == gas_price_denom).then_some(1.0)
+    } else {
+        config.denoms.get(denom).copied()
+    }
+}
+
+/// The configured maximum gas price, which is expressed in the denom of the
+/// configured gas price, converted into the given denom.
+fn max_gas_price(config: &DynamicGasPrice, gas_price_denom: &str, denom: &str) -> f64 {
+    match (
+        denom_value(config, gas_price_denom, gas_price_denom),
+        denom_value(config, gas_price_denom, denom),
+    ) {
+        (Some(base_value), Some(value)) if value > 0.0 => config.max * base_value / value,
+        _ => config.max,
+    }
+}
+
 pub fn calculate_fee(adjusted_gas_amount: u64, gas_price: &GasPrice) -> Coin {
     let fee_amount = mul_ceil(adjusted_gas_amount, gas_price.price);
 
@@ -163,7 +307,55 @@ pub fn adjust_estimated_gas(
 
 #[cfg(test)]
 mod tests {
-    use super::{adjust_estimated_gas, AdjustGas};
+    use super::{adjust_estimated_gas, cheapest_gas_price, AdjustGas};
+
+    use alloc::collections::BTreeMap;
+
+    use crate::config::dynamic_gas::DynamicGasPrice;
+    use crate::config::GasPrice;
+
+    #[test]
+    fn cheapest_gas_price_in_accepted_denoms() {
+        let base_fees = vec![
+            GasPrice::new(0.1, "uatom".to_string()),
+            GasPrice::new(0.5, "untrn".to_string()),
+            GasPrice::new(0.01, "uosmo".to_string()),
+        ];
+
+        // Without fee denoms, only the denom of the configured gas price is accepted
+        let config = DynamicGasPrice::unsafe_new(true, 1.0, 0.6);
+        let gas_price = cheapest_gas_price(&config, "untrn", base_fees.clone(), |_| true).unwrap();
+        assert_eq!(gas_price, GasPrice::new(0.5, "untrn".to_string()));
+
+        // 0.1uatom is worth 0.8, 0.5untrn is worth 0.5 and uosmo is not accepted
+        let config = config.with_denoms(BTreeMap::from([
+            ("uatom".to_string(), 8.0),
+            ("untrn".to_string(), 1.0),
+        ]));
+        let gas_price = cheapest_gas_price(&config, "untrn", base_fees.clone(), |_| true).unwrap();
+        assert_eq!(gas_price, GasPrice::new(0.5, "untrn".to_string()));
+
+        // 0.05uatom is worth 0.4, which is cheaper than 0.5untrn
+        let base_fees = vec![
+            GasPrice::new(0.05, "uatom".to_string()),
+            GasPrice::new(0.5, "untrn".to_string()),
+        ];
+        let gas_price = cheapest_gas_price(&config, "untrn", base_fees.clone(), |_| true).unwrap();
+        assert_eq!(gas_price, GasPrice::new(0.05, "uatom".to_string()));
+
+        // The denoms in which the fees cannot be paid are skipped
+        let gas_price = cheapest_gas_price(&config, "untrn", base_fees.clone(), |gas_price| {
+            gas_price.denom != "uatom"
+        })
+        .unwrap();
+        assert_eq!(gas_price, GasPrice::new(0.5, "untrn".to_string()));
+
+        assert_eq!(
+            cheapest_gas_price(&config, "untrn", base_fees, |_| false),
+            None
+        );
+        assert_eq!(cheapest_gas_price(&config, "untrn", vec![], |_| true), None);
+    }
 
     #[test]
     fn adjust_zero_gas() {
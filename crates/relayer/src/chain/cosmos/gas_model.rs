@@ -0,0 +1,534 @@
+//! Learning of the gas used by each type of message submitted to a chain.
+//!
+//! The gas model is built from the results of the confirmed transactions submitted
+//! by the relayer itself, which are tracked from their broadcast. The base gas
+//! used by every transaction regardless of its messages, eg. for verifying its
+//! signature, is learnt from how the gas used varies with the number of messages.
+//! The rest of the gas used by a transaction is split across its messages,
+//! proportionally to the gas learnt so far for their types, or evenly if some of
+//! the types are not known yet. The learnt amounts are then used to estimate the
+//! gas of new transactions when their simulation fails, or instead of simulating
+//! them if configured to do so.
+
+use alloc::collections::{BTreeMap, VecDeque};
+use core::time::Duration;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use ibc_proto::cosmos::tx::v1beta1::{TxBody, TxRaw};
+use ibc_proto::google::protobuf::Any;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use tendermint::Hash;
+use tendermint_rpc::endpoint::tx::Response as TxResponse;
+use tracing::{trace, warn};
+
+use crate::config::GasModelConfig;
+use crate::error::Error;
+use crate::util::lock::{LockExt, RwArc};
+
+/// The default directory of the gas models, relative to the home directory.
+pub const GAS_MODEL_DEFAULT_DIR: &str = ".hermes/gas_models";
+
+/// The number of samples over which the average gas used by a message type is smoothed.
+const SMOOTHING_WINDOW: u64 = 100;
+
+/// The minimum variance of the number of messages per transaction from which the
+/// base gas of a transaction can be told apart from the gas of its messages.
+const MIN_MSGS_VARIANCE: f64 = 0.1;
+
+/// The minimum time between two writes of the gas model to its file.
+const SAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The maximum number of transactions submitted by the relayer awaiting confirmation.
+const MAX_SUBMITTED_TXS: usize = 1000;
+
+/// The gas used by the messages of a given type.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MsgGasStats {
+    /// Number of messages of this type observed in confirmed transactions.
+    pub samples: u64,
+    /// Moving average of the gas used by a message of this type.
+    pub average: u64,
+    /// Maximum gas used by a message of this type.
+    pub max: u64,
+}
+
+impl MsgGasStats {
+    fn record(&mut self, gas_used: u64) {
+        self.samples += 1;
+
+        let window = i128::from(self.samples.min(SMOOTHING_WINDOW));
+        let delta = (i128::from(gas_used) - i128::from(self.average)) / window;
+
+        self.average = (i128::from(self.average) + delta) as u64;
+        self.max = self.max.max(gas_used);
+    }
+}
+
+/// The gas used by whole transactions, as a function of their number of messages.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TxGasStats {
+    /// Number of confirmed transactions observed.
+    pub samples: u64,
+    /// Moving average of the number of messages per transaction.
+    pub mean_msgs: f64,
+    /// Moving average of the gas used by a transaction.
+    pub mean_gas: f64,
+    /// Moving variance of the number of messages per transaction.
+    pub var_msgs: f64,
+    /// Moving covariance of the number of messages and the gas used.
+    pub cov_msgs_gas: f64,
+}
+
+impl TxGasStats {
+    fn record(&mut self, msgs: usize, gas_used: u64) {
+        self.samples += 1;
+
+        let weight = 1.0 / self.samples.min(SMOOTHING_WINDOW) as f64;
+        let delta_msgs = msgs as f64 - self.mean_msgs;
+        let delta_gas = gas_used as f64 - self.mean_gas;
+
+        self.mean_msgs += weight * delta_msgs;
+        self.mean_gas += weight * delta_gas;
+        self.var_msgs = (1.0 - weight) * (self.var_msgs + weight * delta_msgs * delta_msgs);
+        self.cov_msgs_gas = (1.0 - weight) * (self.cov_msgs_gas + weight * delta_msgs * delta_gas);
+    }
+
+    /// Returns the base gas used by a transaction regardless of its messages, as the
+    /// intercept of the regression of the gas used on the number of messages, or `None`
+    /// if not enough transactions with different numbers of messages have been observed.
+    pub fn base_gas(&self) -> Option<u64> {
+        if self.var_msgs < MIN_MSGS_VARIANCE {
+            return None;
+        }
+
+        let gas_per_msg = self.cov_msgs_gas / self.var_msgs;
+        let base_gas = self.mean_gas - gas_per_msg * self.mean_msgs;
+
+        Some(base_gas.clamp(0.0, self.mean_gas).round() as u64)
+    }
+}
+
+/// The gas used by each type of message submitted to a chain, indexed by type URL,
+/// on top of the base gas used by every transaction.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GasModelStats {
+    /// Defaults to no observations for the gas models persisted before it was learnt.
+    #[serde(default)]
+    pub txs: TxGasStats,
+    pub messages: BTreeMap<String, MsgGasStats>,
+}
+
+impl GasModelStats {
+    /// Loads the gas model persisted at the given path.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let stats_json = fs::read_to_string(path).map_err(|e| io_error(path, e))?;
+
+        serde_json::from_str(&stats_json).map_err(|e| json_error(path, e))
+    }
+
+    /// Loads the gas model persisted at the given path, or returns an
+    /// empty model if the file does not exist yet.
+    pub fn load_or_default(path: &Path) -> Result<Self, Error> {
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Persists the gas model at the given path.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let stats_json = serde_json::to_string_pretty(self).map_err(|e| json_error(path, e))?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| io_error(path, e))?;
+        }
+
+        fs::write(path, stats_json).map_err(|e| io_error(path, e))
+    }
+
+    /// Records the gas used by a confirmed transaction made of messages of the given types.
+    pub fn record_tx(&mut self, type_urls: &[String], gas_used: u64) {
+        if type_urls.is_empty() {
+            return;
+        }
+
+        self.txs.record(type_urls.len(), gas_used);
+
+        let msgs_gas = gas_used.saturating_sub(self.txs.base_gas().unwrap_or(0));
+
+        let weights: Option<Vec<u128>> = type_urls
+            .iter()
+            .map(|type_url| {
+                self.messages
+                    .get(type_url)
+                    .filter(|stats| stats.samples > 0)
+                    .map(|stats| u128::from(stats.average.max(1)))
+            })
+            .collect();
+
+        let shares: Vec<u64> = match weights {
+            Some(weights) => {
+                let total: u128 = weights.iter().sum();
+
+                weights
+                    .iter()
+                    .map(|weight| (u128::from(msgs_gas) * weight / total) as u64)
+                    .collect()
+            }
+            None => vec![msgs_gas / type_urls.len() as u64; type_urls.len()],
+        };
+
+        for (type_url, share) in type_urls.iter().zip(shares) {
+            self.messages
+                .entry(type_url.clone())
+                .or_default()
+                .record(share);
+        }
+    }
+
+    /// Estimates the gas needed by a transaction made of messages of the given types,
+    /// or returns `None` if less than `min_samples` messages of some of the types
+    /// have been observed.
+    pub fn estimate(&self, type_urls: &[String], min_samples: u64) -> Option<u64> {
+        if type_urls.is_empty() {
+            return None;
+        }
+
+        let msgs_gas = type_urls.iter().try_fold(0u64, |total, type_url| {
+            self.messages
+                .get(type_url)
+                .filter(|stats| stats.samples >= min_samples.max(1))
+                .map(|stats| total.saturating_add(stats.average))
+        })?;
+
+        match self.txs.base_gas() {
+            Some(base_gas) => Some(base_gas.saturating_add(msgs_gas)),
+            // Until then, the base gas is included in the gas learnt for the messages, in
+            // proportion to the number of messages of the observed transactions, so that
+            // transactions with fewer messages would be underestimated. The average gas
+            // of the observed transactions is used as a floor instead.
+            None => Some(msgs_gas.max(self.txs.mean_gas as u64)),
+        }
+    }
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> Error {
+    Error::persisted_file_io("gas model".to_string(), path.display().to_string(), e)
+}
+
+fn json_error(path: &Path, e: serde_json::Error) -> Error {
+    Error::persisted_file_json("gas model".to_string(), path.display().to_string(), e)
+}
+
+/// Learns the gas used by each type of message submitted to a chain, and
+/// persists it in the configured file, if any.
+#[derive(Clone, Debug, Default)]
+pub struct GasModel {
+    config: GasModelConfig,
+    stats: RwArc<GasModelStats>,
+    file: Option<PathBuf>,
+    saved: RwArc<SaveState>,
+    submitted_txs: RwArc<VecDeque<Hash>>,
+}
+
+#[derive(Debug, Default)]
+struct SaveState {
+    last_saved: Option<Instant>,
+    unsaved: bool,
+}
+
+impl GasModel {
+    /// Sets up the gas model of the given chain, resuming from the amounts persisted
+    /// in the configured file. If the file cannot be read, the model starts afresh.
+    pub fn from_config(chain_id: &ChainId, config: &GasModelConfig) -> Self {
+        if !config.enabled {
+            return Self::default();
+        }
+
+        let file = match gas_model_path(chain_id, config) {
+            Ok(file) => Some(file),
+            Err(e) => {
+                warn!("the gas model of chain {chain_id} will not be persisted: {e}");
+                None
+            }
+        };
+
+        let stats = match &file {
+            Some(file) => GasModelStats::load_or_default(file).unwrap_or_else(|e| {
+                warn!("failed to load the gas model of chain {chain_id}, starting afresh: {e}");
+                GasModelStats::default()
+            }),
+            None => GasModelStats::default(),
+        };
+
+        Self {
+            config: config.clone(),
+            stats: RwArc::new_lock(stats),
+            file,
+            saved: RwArc::default(),
+            submitted_txs: RwArc::default(),
+        }
+    }
+
+    /// Returns true if the learnt amounts are used instead of simulating transactions.
+    pub fn skip_simulation(&self) -> bool {
+        self.config.enabled && self.config.skip_simulation
+    }
+
+    /// Returns a copy of the current gas model.
+    pub fn stats(&self) -> GasModelStats {
+        self.stats.acquire_read().clone()
+    }
+
+    /// Estimates the gas needed by a transaction made of the given messages,
+    /// or returns `None` if not enough of them have been observed yet.
+    pub fn estimate(&self, messages: &[Any]) -> Option<u64> {
+        if !self.config.enabled {
+            return None;
+        }
+
+        let type_urls: Vec<String> = messages.iter().map(|msg| msg.type_url.clone()).collect();
+
+        self.stats
+            .acquire_read()
+            .estimate(&type_urls, self.config.min_samples)
+    }
+
+    /// Tracks a transaction broadcast by the relayer, whose gas usage is to be
+    /// learnt once it is confirmed.
+    pub fn track_tx(&self, hash: Hash) {
+        if !self.config.enabled {
+            return;
+        }
+
+        let mut submitted_txs = self.submitted_txs.acquire_write();
+
+        if submitted_txs.len() == MAX_SUBMITTED_TXS {
+            submitted_txs.pop_front();
+        }
+
+        submitted_txs.push_back(hash);
+    }
+
+    /// Records the gas used by a confirmed transaction, if it was submitted by the
+    /// relayer. Failed transactions are ignored, since they may have run out of gas
+    /// before executing all their messages.
+    pub fn observe_tx(&self, response: &TxResponse) {
+        if !self.config.enabled {
+            return;
+        }
+
+        let submitted = {
+            let mut submitted_txs = self.submitted_txs.acquire_write();
+
+            submitted_txs
+                .iter()
+                .position(|hash| *hash == response.hash)
+                .and_then(|index| submitted_txs.remove(index))
+                .is_some()
+        };
+
+        if !submitted || response.tx_result.code.is_err() {
+            return;
+        }
+
+        let Ok(gas_used) = u64::try_from(response.tx_result.gas_used) else {
+            return;
+        };
+
+        let Some(type_urls) = decode_type_urls(&response.tx) else {
+            trace!(
+                "failed to decode tx {}, ignoring its gas usage",
+                response.hash
+            );
+            return;
+        };
+
+        self.stats.acquire_write().record_tx(&type_urls, gas_used);
+
+        self.save_if_due();
+    }
+
+    /// Persists the observations which were not persisted yet, eg. on shutdown.
+    pub fn flush(&self) {
+        let mut saved = self.saved.acquire_write();
+
+        if saved.unsaved {
+            self.save(&mut saved);
+        }
+    }
+
+    fn save_if_due(&self) {
+        let mut saved = self.saved.acquire_write();
+        saved.unsaved = true;
+
+        if saved
+            .last_saved
+            .is_some_and(|instant| instant.elapsed() < SAVE_INTERVAL)
+        {
+            return;
+        }
+
+        self.save(&mut saved);
+    }
+
+    fn save(&self, saved: &mut SaveState) {
+        let Some(file) = &self.file else {
+            return;
+        };
+
+        if let Err(e) = self.stats.acquire_read().save(file) {
+            warn!("failed to persist the gas model: {e}");
+        }
+
+        saved.last_saved = Some(Instant::now());
+        saved.unsaved = false;
+    }
+}
+
+/// Returns the type URLs of the messages of the given encoded transaction.
+fn decode_type_urls(tx_bytes: &[u8]) -> Option<Vec<String>> {
+    let tx_raw = TxRaw::decode(tx_bytes).ok()?;
+    let body = TxBody::decode(tx_raw.body_bytes.as_slice()).ok()?;
+
+    Some(body.messages.into_iter().map(|msg| msg.type_url).collect())
+}
+
+/// Returns the path of the file in which the gas model of the given chain is persisted.
+pub fn gas_model_path(chain_id: &ChainId, config: &GasModelConfig) -> Result<PathBuf, Error> {
+    match &config.file {
+        Some(file) => Ok(file.clone()),
+        None => dirs_next::home_dir()
+            .map(|home| {
+                home.join(GAS_MODEL_DEFAULT_DIR)
+                    .join(format!("{chain_id}.json"))
+            })
+            .ok_or_else(Error::home_location_unavailable),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECV_PACKET: &str = "/ibc.core.channel.v1.MsgRecvPacket";
+    const UPDATE_CLIENT: &str = "/ibc.core.client.v1.MsgUpdateClient";
+
+    fn type_urls(urls: &[&str]) -> Vec<String> {
+        urls.iter().map(|url| url.to_string()).collect()
+    }
+
+    #[test]
+    fn learn_gas_per_message_type() {
+        let mut stats = GasModelStats::default();
+
+        stats.record_tx(&type_urls(&[UPDATE_CLIENT]), 90_000);
+        stats.record_tx(&type_urls(&[RECV_PACKET, RECV_PACKET]), 120_000);
+
+        // The base gas is learnt once txs with different numbers of messages are observed
+        assert_eq!(stats.txs.base_gas(), Some(60_000));
+        assert_eq!(stats.messages[UPDATE_CLIENT].average, 90_000);
+        assert_eq!(stats.messages[RECV_PACKET].average, 30_000);
+        assert_eq!(stats.messages[RECV_PACKET].samples, 2);
+
+        // The gas is split proportionally to the amounts learnt so far
+        stats.record_tx(&type_urls(&[UPDATE_CLIENT, RECV_PACKET]), 120_000);
+
+        assert_eq!(stats.txs.base_gas(), Some(60_000));
+        assert_eq!(stats.messages[UPDATE_CLIENT].average, 67_500);
+        assert_eq!(stats.messages[UPDATE_CLIENT].max, 90_000);
+        assert_eq!(stats.messages[RECV_PACKET].average, 25_000);
+
+        assert_eq!(
+            stats.estimate(&type_urls(&[UPDATE_CLIENT, RECV_PACKET]), 2),
+            Some(152_500)
+        );
+        assert_eq!(
+            stats.estimate(&type_urls(&[UPDATE_CLIENT, RECV_PACKET]), 3),
+            None
+        );
+        assert_eq!(stats.estimate(&type_urls(&["/unknown"]), 1), None);
+    }
+
+    #[test]
+    fn estimate_single_message_tx_from_batches() {
+        // Each tx uses 50_000 gas, plus 30_000 gas per message
+        let mut stats = GasModelStats::default();
+
+        for _ in 0..5 {
+            stats.record_tx(&type_urls(&[RECV_PACKET; 10]), 350_000);
+        }
+
+        // The base gas cannot be told apart from the gas of the messages yet,
+        // so single-message txs are estimated conservatively
+        assert_eq!(stats.txs.base_gas(), None);
+        assert_eq!(stats.messages[RECV_PACKET].average, 35_000);
+        assert_eq!(stats.estimate(&type_urls(&[RECV_PACKET]), 1), Some(350_000));
+
+        stats.record_tx(&type_urls(&[RECV_PACKET]), 80_000);
+
+        assert_eq!(stats.txs.base_gas(), Some(50_000));
+
+        let estimate = stats.estimate(&type_urls(&[RECV_PACKET]), 1).unwrap();
+        assert!((80_000..90_000).contains(&estimate), "{estimate}");
+
+        let estimate = stats.estimate(&type_urls(&[RECV_PACKET; 10]), 1).unwrap();
+        assert!(estimate >= 350_000, "{estimate}");
+    }
+
+    fn tx_response(hash: Hash, gas_used: i64) -> TxResponse {
+        let body = TxBody {
+            messages: vec![Any {
+                type_url: RECV_PACKET.to_owned(),
+                value: vec![],
+            }],
+            ..Default::default()
+        };
+
+        let tx = TxRaw {
+            body_bytes: body.encode_to_vec(),
+            ..Default::default()
+        };
+
+        TxResponse {
+            hash,
+            height: 1u32.into(),
+            index: 0,
+            tx_result: tendermint::abci::types::ExecTxResult {
+                gas_used,
+                ..Default::default()
+            },
+            tx: tx.encode_to_vec(),
+            proof: None,
+        }
+    }
+
+    #[test]
+    fn learn_gas_from_submitted_txs_only() {
+        let gas_model = GasModel {
+            config: GasModelConfig {
+                enabled: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let submitted = Hash::Sha256([1; 32]);
+        let other = Hash::Sha256([2; 32]);
+
+        gas_model.track_tx(submitted);
+        gas_model.observe_tx(&tx_response(other, 50_000));
+        gas_model.observe_tx(&tx_response(submitted, 80_000));
+        // A transaction is only learnt from once
+        gas_model.observe_tx(&tx_response(submitted, 80_000));
+
+        let stats = gas_model.stats();
+        assert_eq!(stats.messages[RECV_PACKET].samples, 1);
+        assert_eq!(stats.messages[RECV_PACKET].average, 80_000);
+    }
+}
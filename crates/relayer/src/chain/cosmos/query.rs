@@ -1,9 +1,13 @@
 use ibc_proto::cosmos::base::tendermint::v1beta1::GetNodeInfoResponse;
 use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics04_channel::v2::events::{
+    PKT_DST_CLIENT_ATTRIBUTE_KEY, PKT_SRC_CLIENT_ATTRIBUTE_KEY,
+};
 use ibc_relayer_types::core::ics23_commitment::merkle::{
     convert_tm_to_ics_merkle_proof, MerkleProof,
 };
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::events::WithBlockDataType;
 use prost::Message;
 use tendermint::block::Height;
 use tendermint_rpc::query::Query;
@@ -11,16 +15,22 @@ use tendermint_rpc::{Client, HttpClient, Url};
 
 use crate::chain::cosmos::version::Specs;
 use crate::chain::requests::QueryHeight;
-use crate::chain::requests::{QueryClientEventRequest, QueryPacketEventDataRequest, QueryTxHash};
+use crate::chain::requests::{
+    QueryClientEventRequest, QueryPacketEventDataRequest, QueryPacketEventDataV2Request,
+    QueryTxHash,
+};
 use crate::error::Error;
 
 pub mod account;
 pub mod balance;
+pub mod class_trace;
 pub mod connection;
 pub mod consensus_state;
 pub mod custom;
 pub mod denom_trace;
 pub mod fee;
+pub mod fee_grant;
+pub mod ica;
 pub mod status;
 pub mod tx;
 
@@ -55,6 +65,24 @@ pub fn packet_query(request: &QueryPacketEventDataRequest, seq: Sequence) -> Que
     )
 }
 
+/// The client of an IBC v2 packet event is its source client for `send_packet`
+/// events, and its destination client for `write_acknowledgement` events.
+pub fn packet_v2_query(request: &QueryPacketEventDataV2Request, seq: Sequence) -> Query {
+    let client_attribute = match request.event_id {
+        WithBlockDataType::WriteAck => PKT_DST_CLIENT_ATTRIBUTE_KEY,
+        _ => PKT_SRC_CLIENT_ATTRIBUTE_KEY,
+    };
+
+    Query::eq(
+        format!("{}.{client_attribute}", request.event_id.as_str()),
+        request.client_id.to_string(),
+    )
+    .and_eq(
+        format!("{}.packet_sequence", request.event_id.as_str()),
+        seq.to_string(),
+    )
+}
+
 pub fn header_query(request: &QueryClientEventRequest) -> Query {
     Query::eq(
         format!("{}.client_id", request.event_id.as_str()),
@@ -79,7 +107,7 @@ pub async fn abci_query(
     rpc_client: &HttpClient,
     rpc_address: &Url,
     path: String,
-    data: String,
+    data: impl Into<Vec<u8>> + Send,
     height: Height,
     prove: bool,
 ) -> Result<QueryResponse, Error> {
@@ -91,7 +119,7 @@ pub async fn abci_query(
 
     // Use the Tendermint-rs RPC client to do the query.
     let response = rpc_client
-        .abci_query(Some(path), data.into_bytes(), height, prove)
+        .abci_query(Some(path), data, height, prove)
         .await
         .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
 
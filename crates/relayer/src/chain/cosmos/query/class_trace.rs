@@ -0,0 +1,36 @@
+use http::uri::Uri;
+
+use ibc_proto::ibc::applications::nft_transfer::v1::{
+    query_client::QueryClient, QueryClassTraceRequest,
+};
+
+use crate::config::default::max_grpc_decoding_size;
+use crate::error::Error;
+use crate::nft_transfer::ClassTrace;
+use crate::util::create_grpc_client;
+
+// Uses the GRPC client to retrieve the ICS721 class trace for a specific hash
+pub async fn query_class_trace(grpc_address: &Uri, hash: &str) -> Result<ClassTrace, Error> {
+    let mut client = create_grpc_client(grpc_address, QueryClient::new).await?;
+
+    client = client.max_decoding_message_size(max_grpc_decoding_size().get_bytes() as usize);
+
+    let request = tonic::Request::new(QueryClassTraceRequest {
+        hash: hash.to_owned(),
+    });
+
+    let response = client
+        .class_trace(request)
+        .await
+        .map(|r| r.into_inner())
+        .map_err(|e| Error::grpc_status(e, "query_class_trace".to_owned()))?;
+
+    let class_trace = response
+        .class_trace
+        .ok_or_else(|| Error::empty_class_trace(hash.to_owned()))?;
+
+    Ok(ClassTrace {
+        path: class_trace.path,
+        base_class_id: class_trace.base_class_id,
+    })
+}
@@ -1,4 +1,5 @@
 use http::uri::Uri;
+use ibc_proto::cosmos::base::query::v1beta1::PageRequest;
 use ibc_proto::ibc::applications::fee::v1::query_client::QueryClient;
 use ibc_proto::ibc::applications::fee::v1::{
     QueryCounterpartyPayeeRequest, QueryIncentivizedPacketsForChannelRequest,
@@ -47,6 +48,8 @@ pub async fn query_counterparty_payee(
     }
 }
 
+/// Query the fees escrowed for all the incentivized packets of the given channel,
+/// going through all the pages of the response.
 pub async fn query_incentivized_packets(
     grpc_address: &Uri,
     channel_id: &ChannelId,
@@ -56,25 +59,37 @@ pub async fn query_incentivized_packets(
 
     client = client.max_decoding_message_size(max_grpc_decoding_size().get_bytes() as usize);
 
-    let request = QueryIncentivizedPacketsForChannelRequest {
-        channel_id: channel_id.to_string(),
-        port_id: port_id.to_string(),
-        pagination: None,
-        query_height: 0,
-    };
-
-    let response = client
-        .incentivized_packets_for_channel(request)
-        .await
-        .map_err(|e| Error::grpc_status(e, "query_incentivized_packets".to_owned()))?;
-
-    let raw_packets = response.into_inner().incentivized_packets;
+    let mut packets = Vec::new();
+    let mut page_key = Vec::new();
+
+    loop {
+        let request = QueryIncentivizedPacketsForChannelRequest {
+            channel_id: channel_id.to_string(),
+            port_id: port_id.to_string(),
+            pagination: Some(PageRequest {
+                key: page_key,
+                ..Default::default()
+            }),
+            query_height: 0,
+        };
+
+        let response = client
+            .incentivized_packets_for_channel(request)
+            .await
+            .map_err(|e| Error::grpc_status(e, "query_incentivized_packets".to_owned()))?
+            .into_inner();
+
+        for raw_packet in response.incentivized_packets {
+            packets.push(IdentifiedPacketFees::try_from(raw_packet).map_err(Error::ics29)?);
+        }
 
-    let packets = raw_packets
-        .into_iter()
-        .map(IdentifiedPacketFees::try_from)
-        .collect::<Result<_, _>>()
-        .map_err(Error::ics29)?;
+        match response.pagination {
+            Some(pagination) if !pagination.next_key.is_empty() => {
+                page_key = pagination.next_key;
+            }
+            _ => break,
+        }
+    }
 
     Ok(packets)
 }
@@ -0,0 +1,41 @@
+use http::uri::Uri;
+use ibc_proto::cosmos::feegrant::v1beta1::query_client::QueryClient;
+use ibc_proto::cosmos::feegrant::v1beta1::QueryAllowanceRequest;
+use ibc_proto::google::protobuf::Any;
+use tonic::Code;
+
+use crate::config::default::max_grpc_decoding_size;
+use crate::error::Error;
+use crate::util::create_grpc_client;
+
+/// Uses the GRPC client to retrieve the fee allowance granted by `granter` to `grantee`,
+/// or `None` if there is no such allowance, eg. because it was revoked or has expired.
+pub async fn query_allowance(
+    grpc_address: &Uri,
+    granter: &str,
+    grantee: &str,
+) -> Result<Option<Any>, Error> {
+    let mut client = create_grpc_client(grpc_address, QueryClient::new).await?;
+
+    client = client.max_decoding_message_size(max_grpc_decoding_size().get_bytes() as usize);
+
+    let request = tonic::Request::new(QueryAllowanceRequest {
+        granter: granter.to_string(),
+        grantee: grantee.to_string(),
+    });
+
+    match client.allowance(request).await {
+        Ok(response) => Ok(response
+            .into_inner()
+            .allowance
+            .and_then(|grant| grant.allowance)),
+        // The SDK reports missing allowances with its `ErrNoAllowance` error
+        Err(status)
+            if status.code() == Code::NotFound
+                || status.message().contains("fee-grant not found") =>
+        {
+            Ok(None)
+        }
+        Err(status) => Err(Error::grpc_status(status, "query_allowance".to_owned())),
+    }
+}
@@ -0,0 +1,36 @@
+use http::uri::Uri;
+
+use ibc_proto::ibc::applications::interchain_accounts::controller::v1::{
+    query_client::QueryClient, QueryInterchainAccountRequest,
+};
+use ibc_relayer_types::core::ics24_host::identifier::ConnectionId;
+use ibc_relayer_types::signer::Signer;
+
+use crate::config::default::max_grpc_decoding_size;
+use crate::error::Error;
+use crate::util::create_grpc_client;
+
+/// Uses the GRPC client to retrieve the address of the interchain account
+/// registered by `owner` over the given controller connection.
+pub async fn query_interchain_account(
+    grpc_address: &Uri,
+    owner: &Signer,
+    connection_id: &ConnectionId,
+) -> Result<String, Error> {
+    let mut client = create_grpc_client(grpc_address, QueryClient::new).await?;
+
+    client = client.max_decoding_message_size(max_grpc_decoding_size().get_bytes() as usize);
+
+    let request = tonic::Request::new(QueryInterchainAccountRequest {
+        owner: owner.to_string(),
+        connection_id: connection_id.to_string(),
+    });
+
+    let response = client
+        .interchain_account(request)
+        .await
+        .map(|r| r.into_inner())
+        .map_err(|e| Error::grpc_status(e, "query_interchain_account".to_owned()))?;
+
+    Ok(response.address)
+}
@@ -1,18 +1,24 @@
+use ibc_proto::cosmos::tx::v1beta1::{AuthInfo, TxRaw};
+use ibc_relayer_types::applications::transfer::RawCoin;
 use ibc_relayer_types::core::ics02_client::height::Height;
 use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use ibc_relayer_types::events::IbcEvent;
 use ibc_relayer_types::Height as ICSHeight;
+use prost::Message;
 use tendermint::abci::Event;
 use tendermint::Hash as TxHash;
 use tendermint_rpc::endpoint::tx::Response as TxResponse;
 use tendermint_rpc::{Client, HttpClient, Order, Url};
 use tracing::warn;
 
-use crate::chain::cosmos::query::{header_query, packet_query, tx_hash_query};
+use crate::chain::cosmos::backend::{decode_events, Backend};
+use crate::chain::cosmos::gas_model::GasModel;
+use crate::chain::cosmos::query::{header_query, packet_query, packet_v2_query, tx_hash_query};
 use crate::chain::cosmos::types::events;
 use crate::chain::requests::{
-    QueryClientEventRequest, QueryHeight, QueryPacketEventDataRequest, QueryTxHash, QueryTxRequest,
+    QueryClientEventRequest, QueryHeight, QueryPacketEventDataRequest,
+    QueryPacketEventDataV2Request, QueryTxHash, QueryTxRequest,
 };
 use crate::error::Error;
 use crate::event::{ibc_event_try_from_abci_event, IbcEventWithHeight};
@@ -24,7 +30,9 @@ pub async fn query_txs(
     chain_id: &ChainId,
     rpc_client: &HttpClient,
     rpc_address: &Url,
+    backend: &dyn Backend,
     request: QueryTxRequest,
+    gas_model: Option<&GasModel>,
 ) -> Result<Vec<IbcEventWithHeight>, Error> {
     crate::time!("query_txs",
     {
@@ -68,7 +76,7 @@ pub async fn query_txs(
                 "packet_from_tx_search_response: unexpected number of txs"
             );
 
-            let tx = response.txs.remove(0);
+            let tx = decode_tx_events(backend, response.txs.remove(0));
             let event = update_client_from_tx_search_response(chain_id, &request, tx)?;
 
             Ok(event.into_iter().collect())
@@ -96,7 +104,16 @@ pub async fn query_txs(
                 Ok(vec![])
             } else {
                 let tx = response.txs.remove(0);
-                Ok(all_ibc_events_from_tx_search_response(chain_id, tx))
+
+                // The txs submitted by the relayer are confirmed by querying them by hash,
+                // which gives an opportunity to learn the gas used by their messages.
+                if let Some(gas_model) = gas_model {
+                    gas_model.observe_tx(&tx);
+                }
+
+                Ok(all_ibc_events_from_tx_search_response(
+                    chain_id, backend, tx,
+                ))
             }
         }
     }
@@ -116,6 +133,7 @@ pub async fn query_packets_from_txs(
     chain_id: &ChainId,
     rpc_client: &HttpClient,
     rpc_address: &Url,
+    backend: &dyn Backend,
     request: &QueryPacketEventDataRequest,
 ) -> Result<Vec<IbcEventWithHeight>, Error> {
     crate::time!(
@@ -143,6 +161,8 @@ pub async fn query_packets_from_txs(
 
         // Process each tx in descending order
         for tx in response.txs {
+            let tx = decode_tx_events(backend, tx);
+
             // Check if the tx contains and event which matches the query
             if let Some(event) = packet_from_tx_search_response(chain_id, request, *seq, &tx)? {
                 // We found the event
@@ -172,6 +192,82 @@ pub async fn query_packets_from_txs(
     Ok(result)
 }
 
+/// This function queries transactions for the IBC v2 packet events matching the request.
+///
+/// As for [`query_packets_from_txs`], one `tx_search` is performed for each sequence,
+/// and at most one packet event is returned for each of them.
+pub async fn query_packets_v2_from_txs(
+    chain_id: &ChainId,
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+    backend: &dyn Backend,
+    request: &QueryPacketEventDataV2Request,
+) -> Result<Vec<IbcEventWithHeight>, Error> {
+    crate::time!(
+        "query_packets_v2_from_txs",
+        {
+            "src_chain": chain_id,
+        }
+    );
+    crate::telemetry!(query, chain_id, "query_packets_v2_from_txs");
+
+    let mut result: Vec<IbcEventWithHeight> = vec![];
+
+    for seq in &request.sequences {
+        // Query the latest 10 txs which include the event specified in the query request
+        let response = rpc_client
+            .tx_search(
+                packet_v2_query(request, *seq),
+                false,
+                1,
+                10,
+                Order::Descending,
+            )
+            .await
+            .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
+
+        // Use the latest event found for this sequence
+        let event = response.txs.into_iter().find_map(|tx| {
+            let tx = decode_tx_events(backend, tx);
+            let height = ICSHeight::new(chain_id.version(), u64::from(tx.height)).ok()?;
+
+            tx.tx_result
+                .events
+                .iter()
+                .find_map(|ev| filter_matching_event_v2(ev, request, *seq))
+                .map(|ibc_event| IbcEventWithHeight::new(ibc_event, height))
+        });
+
+        result.extend(event);
+    }
+
+    Ok(result)
+}
+
+/// Returns the given event wrapped in `Some` if it is an IBC v2 packet
+/// event consistent with the request parameters, `None` otherwise.
+fn filter_matching_event_v2(
+    event: &Event,
+    request: &QueryPacketEventDataV2Request,
+    seq: Sequence,
+) -> Option<IbcEvent> {
+    if event.kind != request.event_id.as_str() {
+        return None;
+    }
+
+    let ibc_event = ibc_event_try_from_abci_event(event).ok()?;
+
+    let matches = match &ibc_event {
+        IbcEvent::SendPacketV2(e) => e.src_client_id() == &request.client_id,
+        IbcEvent::WriteAcknowledgementV2(e) => e.dst_client_id() == &request.client_id,
+        _ => false,
+    };
+
+    let packet = ibc_event.packet_v2()?;
+
+    (matches && packet.sequence == seq).then_some(ibc_event)
+}
+
 /// This function queries packet events from a block at a specific height.
 /// It returns packet events that match certain criteria (see [`filter_matching_event`]).
 /// It returns at most one packet event for each sequence specified in the request.
@@ -179,6 +275,7 @@ pub async fn query_packets_from_block(
     chain_id: &ChainId,
     rpc_client: &HttpClient,
     rpc_address: &Url,
+    backend: &dyn Backend,
     request: &QueryPacketEventDataRequest,
 ) -> Result<Vec<IbcEventWithHeight>, Error> {
     crate::time!(
@@ -204,18 +301,19 @@ pub async fn query_packets_from_block(
         .await
         .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
 
-    let mut events: Vec<_> = block_results
-        .begin_block_events
-        .unwrap_or_default()
-        .iter()
-        .filter_map(|ev| filter_matching_event(ev, request, &request.sequences))
-        .map(|ev| IbcEventWithHeight::new(ev, height))
-        .collect();
+    let mut events: Vec<_> = decode_events(
+        backend,
+        block_results.begin_block_events.unwrap_or_default(),
+    )
+    .iter()
+    .filter_map(|ev| filter_matching_event(ev, request, &request.sequences))
+    .map(|ev| IbcEventWithHeight::new(ev, height))
+    .collect();
 
     if let Some(txs) = block_results.txs_results {
         for tx in txs {
             events.extend(
-                tx.events
+                decode_events(backend, tx.events)
                     .iter()
                     .filter_map(|ev| filter_matching_event(ev, request, &request.sequences))
                     .map(|ev| IbcEventWithHeight::new(ev, height)),
@@ -224,9 +322,7 @@ pub async fn query_packets_from_block(
     }
 
     events.extend(
-        block_results
-            .end_block_events
-            .unwrap_or_default()
+        decode_events(backend, block_results.end_block_events.unwrap_or_default())
             .iter()
             .filter_map(|ev| filter_matching_event(ev, request, &request.sequences))
             .map(|ev| IbcEventWithHeight::new(ev, height)),
@@ -237,8 +333,7 @@ pub async fn query_packets_from_block(
     // are no longer present. We put these in place of the end_block_events
     // in older protocol.
     events.extend(
-        block_results
-            .finalize_block_events
+        decode_events(backend, block_results.finalize_block_events)
             .iter()
             .filter_map(|ev| filter_matching_event(ev, request, &request.sequences))
             .map(|ev| IbcEventWithHeight::new(ev, height)),
@@ -376,8 +471,46 @@ pub async fn query_tx_response(
     Ok(response.txs.into_iter().next())
 }
 
+/// Returns the fees paid for the committed transaction with the given hash,
+/// which are the fees declared in the transaction, as the Cosmos SDK deducts
+/// them in full from the fee payer whether the transaction succeeds or not.
+pub async fn query_tx_fee(
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+    tx_hash: &TxHash,
+) -> Result<Vec<RawCoin>, Error> {
+    let response = query_tx_response(rpc_client, rpc_address, tx_hash)
+        .await?
+        .ok_or_else(|| Error::tx_not_found(tx_hash.to_string()))?;
+
+    let tx_raw = TxRaw::decode(response.tx.as_slice())
+        .map_err(|e| Error::protobuf_decode("TxRaw".to_string(), e))?;
+
+    let auth_info = AuthInfo::decode(tx_raw.auth_info_bytes.as_slice())
+        .map_err(|e| Error::protobuf_decode("AuthInfo".to_string(), e))?;
+
+    auth_info
+        .fee
+        .map(|fee| fee.amount)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|coin| {
+            let fee = format!("{}{}", coin.amount, coin.denom);
+            RawCoin::try_from(coin).map_err(|e| Error::invalid_tx_fee(fee, e))
+        })
+        .collect()
+}
+
+/// Apply the event hook of the backend to the events of a transaction
+/// found by a `tx_search`.
+fn decode_tx_events(backend: &dyn Backend, mut response: TxResponse) -> TxResponse {
+    response.tx_result.events = decode_events(backend, response.tx_result.events);
+    response
+}
+
 pub fn all_ibc_events_from_tx_search_response(
     chain_id: &ChainId,
+    backend: &dyn Backend,
     response: TxResponse,
 ) -> Vec<IbcEventWithHeight> {
     let height = ICSHeight::new(chain_id.version(), u64::from(response.height)).unwrap();
@@ -394,8 +527,7 @@ pub fn all_ibc_events_from_tx_search_response(
             height,
         )]
     } else {
-        let result = deliver_tx_result
-            .events
+        let result = decode_events(backend, deliver_tx_result.events)
             .iter()
             .flat_map(|event| events::from_tx_response_event(height, event).into_iter())
             .collect::<Vec<_>>();
@@ -403,3 +535,68 @@ pub fn all_ibc_events_from_tx_search_response(
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics04_channel::events::{
+        Attributes as ChannelAttributes, OpenInit,
+    };
+    use tendermint::abci::types::ExecTxResult;
+
+    use crate::chain::cosmos::backend::EncodeTxRequest;
+
+    #[derive(Debug)]
+    struct RenamingBackend;
+
+    impl Backend for RenamingBackend {
+        fn encode_tx(&self, _request: EncodeTxRequest<'_>) -> Result<Vec<u8>, Error> {
+            Ok(vec![])
+        }
+
+        fn decode_event(&self, mut event: Event) -> Option<Event> {
+            event.kind = event.kind.strip_prefix("app_")?.to_string();
+            Some(event)
+        }
+    }
+
+    fn tx_response(events: Vec<Event>) -> TxResponse {
+        TxResponse {
+            hash: TxHash::None,
+            height: 5u32.into(),
+            index: 0,
+            tx_result: ExecTxResult {
+                events,
+                ..Default::default()
+            },
+            tx: vec![],
+            proof: None,
+        }
+    }
+
+    #[test]
+    fn tx_search_events_are_decoded_by_the_backend() {
+        let attributes = ChannelAttributes {
+            port_id: "transfer".parse().unwrap(),
+            channel_id: Some("channel-0".parse().unwrap()),
+            connection_id: "connection-0".parse().unwrap(),
+            counterparty_port_id: "transfer".parse().unwrap(),
+            counterparty_channel_id: None,
+        };
+        let mut open_init: Event = OpenInit::try_from(attributes).unwrap().into();
+        open_init.kind = format!("app_{}", open_init.kind);
+
+        let message = Event::new("message", Vec::<tendermint::abci::EventAttribute>::new());
+        let response = tx_response(vec![open_init, message]);
+
+        let events = all_ibc_events_from_tx_search_response(
+            &ChainId::from_string("chain-0"),
+            &RenamingBackend,
+            response,
+        );
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0].event, IbcEvent::OpenInitChannel(_)));
+    }
+}
@@ -0,0 +1,232 @@
+//! Replacement of the transactions which get stuck in the mempool.
+//!
+//! A transaction which is not committed within the configured `stuck_timeout`
+//! is signed again at the same account sequence, with its original fee bumped by
+//! the configured `fee_bump`, and rebroadcast. On chains whose mempool supports it,
+//! the new transaction replaces the stuck one. Once the fee reaches the configured
+//! `max_fee_multiplier`, or if the chain rejects the new transaction, Hermes stops
+//! replacing the transaction and waits for it to be committed until it times out.
+//!
+//! A chain which rejects a replacement because of its account sequence, while the
+//! stuck transaction is still not committed, does not support replacing transactions,
+//! in which case Hermes stops replacing any transaction on this chain.
+
+use std::time::Instant;
+
+use ibc_proto::cosmos::base::v1beta1::Coin;
+use ibc_proto::cosmos::tx::v1beta1::Fee;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use tendermint::abci::Code;
+use tendermint::Hash as TxHash;
+use tendermint_rpc::{Client, HttpClient};
+use tracing::{debug, info, warn};
+
+use crate::chain::cosmos::backend::EncodeTxRequest;
+use crate::chain::cosmos::retry::INCORRECT_ACCOUNT_SEQUENCE_ERR;
+use crate::chain::cosmos::tx::broadcast_tx_sync;
+use crate::chain::cosmos::types::config::TxConfig;
+use crate::chain::cosmos::types::tx::TxSyncResult;
+use crate::config::types::Memo;
+use crate::error::Error;
+use crate::keyring::Secp256k1KeyPair;
+use crate::util::lock::{LockExt, RwArc};
+
+/// Whether the mempool of a chain accepts replacing a Tx by another one at the
+/// same account sequence, as learnt from the replacements attempted so far.
+#[derive(Clone, Debug, Default)]
+pub struct ReplacementSupport {
+    supported: RwArc<Option<bool>>,
+}
+
+impl ReplacementSupport {
+    /// Returns false once the chain is known to reject replacements.
+    pub fn may_be_supported(&self) -> bool {
+        *self.supported.acquire_read() != Some(false)
+    }
+
+    fn record(&self, supported: bool) {
+        *self.supported.acquire_write() = Some(supported);
+    }
+}
+
+/// Rebroadcasts the given stuck Tx at the same account sequence with a bumped fee.
+///
+/// The fee of the original Tx is bumped rather than estimated again, since the
+/// simulation of a Tx at the sequence of a Tx which is already in the mempool fails.
+///
+/// Returns `true` if a replacement Tx was broadcast, or `false` if the Tx cannot
+/// be replaced anymore, either because its fee cannot be bumped any further or
+/// because the chain rejected the replacement.
+pub async fn replace_stuck_tx(
+    rpc_client: &HttpClient,
+    config: &TxConfig,
+    key_pair: &Secp256k1KeyPair,
+    tx_memo: &Memo,
+    tx_sync_result: &mut TxSyncResult,
+) -> Result<bool, Error> {
+    let hash = tx_sync_result.response.hash;
+
+    let Some(replacement) = tx_sync_result.replacement.as_mut() else {
+        return Ok(false);
+    };
+
+    if !config.tx_replacement_support.may_be_supported() {
+        tx_sync_result.replacement = None;
+        return Ok(false);
+    }
+
+    let Some(fee_multiplier) = config
+        .tx_replacement
+        .next_fee_multiplier(replacement.fee_multiplier)
+    else {
+        warn!(
+            "tx {hash} is still not committed after bumping its fee by {}x, giving up on replacing it",
+            replacement.fee_multiplier
+        );
+
+        tx_sync_result.replacement = None;
+        return Ok(false);
+    };
+
+    // Wait for another `stuck_timeout` before retrying if the replacement fails
+    replacement.broadcast_at = Instant::now();
+
+    info!("tx {hash} is not committed yet, replacing it with a fee bumped by {fee_multiplier}x");
+
+    let fee = bump_fee(&replacement.fee, fee_multiplier);
+
+    let tx_bytes = config.backend.encode_tx(EncodeTxRequest {
+        config,
+        key_pair,
+        account: &replacement.account,
+        tx_memo,
+        messages: &replacement.messages,
+        fee: &fee,
+    })?;
+
+    let response = broadcast_tx_sync(rpc_client, &config.rpc_address, tx_bytes).await?;
+
+    if response.code.is_err() {
+        let stuck_hashes = replacement
+            .replaced_hashes
+            .iter()
+            .chain(core::iter::once(&hash));
+
+        if response.code == Code::from(INCORRECT_ACCOUNT_SEQUENCE_ERR)
+            && !is_any_committed(rpc_client, stuck_hashes).await
+        {
+            warn!(
+                ?response,
+                "chain {} rejected the replacement of tx {hash} at the same account sequence, \
+                its mempool does not support replacing txs, giving up on replacing txs on this chain",
+                config.chain_id
+            );
+
+            config.tx_replacement_support.record(false);
+        } else {
+            // The original Tx was most likely committed in the meantime
+            warn!(
+                ?response,
+                "failed to replace tx {hash}, waiting for it to be committed instead"
+            );
+        }
+
+        tx_sync_result.replacement = None;
+        return Ok(false);
+    }
+
+    debug!("tx {hash} was replaced by tx {}", response.hash);
+
+    config.tx_replacement_support.record(true);
+    config.gas_model.track_tx(response.hash);
+
+    replacement.replaced_hashes.push(hash);
+    replacement.fee_multiplier = fee_multiplier;
+    tx_sync_result.response = response;
+
+    Ok(true)
+}
+
+/// Returns true if any of the Txs with the given hashes was committed.
+async fn is_any_committed<'a>(
+    rpc_client: &HttpClient,
+    hashes: impl Iterator<Item = &'a TxHash>,
+) -> bool {
+    for hash in hashes {
+        if rpc_client.tx(*hash, false).await.is_ok() {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Multiplies the amounts of the given fee by the given multiplier, rounding up.
+fn bump_fee(fee: &Fee, multiplier: f64) -> Fee {
+    let multiplier =
+        BigRational::from_float(multiplier).unwrap_or_else(|| BigRational::from_integer(1.into()));
+
+    let amount = fee
+        .amount
+        .iter()
+        .map(|coin| {
+            let amount = match coin.amount.parse::<BigInt>() {
+                Ok(amount) => (BigRational::from_integer(amount) * &multiplier)
+                    .ceil()
+                    .to_integer()
+                    .to_string(),
+                Err(_) => coin.amount.clone(),
+            };
+
+            Coin {
+                denom: coin.denom.clone(),
+                amount,
+            }
+        })
+        .collect();
+
+    Fee {
+        amount,
+        ..fee.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bump_fee_amounts() {
+        let fee = Fee {
+            amount: vec![Coin {
+                denom: "uatom".to_string(),
+                amount: "1001".to_string(),
+            }],
+            gas_limit: 200_000,
+            payer: String::new(),
+            granter: "cosmos1granter".to_string(),
+        };
+
+        let bumped = bump_fee(&fee, 1.5);
+
+        assert_eq!(bumped.amount[0].amount, "1502");
+        assert_eq!(bumped.amount[0].denom, "uatom");
+        assert_eq!(bumped.gas_limit, 200_000);
+        assert_eq!(bumped.granter, "cosmos1granter");
+    }
+
+    #[test]
+    fn replacement_support_is_shared() {
+        let support = ReplacementSupport::default();
+        let cloned = support.clone();
+
+        assert!(support.may_be_supported());
+
+        cloned.record(true);
+        assert!(support.may_be_supported());
+
+        cloned.record(false);
+        assert!(!support.may_be_supported());
+    }
+}
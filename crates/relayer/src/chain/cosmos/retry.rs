@@ -3,6 +3,7 @@ use std::thread;
 
 use tracing::{debug, error, info, instrument, warn};
 
+use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_proto::google::protobuf::Any;
 use tendermint::abci::Code;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
@@ -23,7 +24,7 @@ const ACCOUNT_SEQUENCE_RETRY_DELAY: u64 = 300;
 
 // The error "incorrect account sequence" is defined as the unique error code 32 in cosmos-sdk:
 // https://github.com/cosmos/cosmos-sdk/blob/v0.44.0/types/errors/errors.go#L115-L117
-const INCORRECT_ACCOUNT_SEQUENCE_ERR: u32 = 32;
+pub(crate) const INCORRECT_ACCOUNT_SEQUENCE_ERR: u32 = 32;
 
 /// Try to `send_tx` and retry on account sequence error with re-cached account s.n.
 /// An account sequence error can occur if the account sequence that
@@ -36,6 +37,8 @@ const INCORRECT_ACCOUNT_SEQUENCE_ERR: u32 = 32;
 ///
 /// We treat both cases by re-fetching the account sequence number
 /// from the full node and retrying once with the new account s.n.
+///
+/// Returns the response to the broadcast along with the fee paid by the Tx.
 #[instrument(
     name = "send_tx_with_account_sequence_retry",
     level = "error",
@@ -52,7 +55,7 @@ pub async fn send_tx_with_account_sequence_retry(
     account: &mut Account,
     tx_memo: &Memo,
     messages: &[Any],
-) -> Result<Response, Error> {
+) -> Result<(Response, Fee), Error> {
     time!(
         "send_tx_with_account_sequence_retry",
         {
@@ -81,7 +84,7 @@ async fn do_send_tx_with_account_sequence_retry(
     account: &mut Account,
     tx_memo: &Memo,
     messages: &[Any],
-) -> Result<Response, Error> {
+) -> Result<(Response, Fee), Error> {
     match estimate_fee_and_send_tx(rpc_client, config, key_pair, account, tx_memo, messages).await {
         // Gas estimation failed with account sequence mismatch during gas estimation.
         // It indicates that the account sequence cached by hermes is stale (got < expected).
@@ -103,7 +106,7 @@ async fn do_send_tx_with_account_sequence_retry(
         // NOTE: The error code could potentially overlap between Cosmos SDK and Ibc-go channel
         // error codes. This is currently not the case of incorrect account sequence error
         //which is the Cosmos SDK code 32 and Ibc-go channel errors only go up to 25.
-        Ok((ref response, _)) if response.code == Code::from(INCORRECT_ACCOUNT_SEQUENCE_ERR) => {
+        Ok((ref response, _, _)) if response.code == Code::from(INCORRECT_ACCOUNT_SEQUENCE_ERR) => {
             warn!(
                 ?response,
                 "failed to broadcast tx because of a mismatched account sequence number, \
@@ -125,7 +128,7 @@ async fn do_send_tx_with_account_sequence_retry(
 
         // Gas estimation succeeded and broadcast_tx_sync was either successful or has failed with
         // an unrecoverable error.
-        Ok((response, estimated_gas)) => {
+        Ok((response, fee, estimated_gas)) => {
             debug!("gas estimation succeeded");
 
             // Gas estimation and broadcast_tx_sync were successful.
@@ -146,7 +149,7 @@ async fn do_send_tx_with_account_sequence_retry(
                         increasing account sequence number"
                     );
 
-                    Ok(response)
+                    Ok((response, fee))
                 }
 
                 Code::Err(code) if response.log.contains("packet messages are redundant") => {
@@ -157,7 +160,7 @@ async fn do_send_tx_with_account_sequence_retry(
                         "broadcast tx was not completed, all packets in tx have been relayed already, no fees were consumed"
                     );
 
-                    Ok(response)
+                    Ok((response, fee))
                 }
 
                 // Gas estimation succeeded, but broadcast_tx_sync failed with unrecoverable error.
@@ -177,7 +180,7 @@ async fn do_send_tx_with_account_sequence_retry(
                         &response.log
                     );
 
-                    Ok(response)
+                    Ok((response, fee))
                 }
             }
         }
@@ -198,7 +201,7 @@ async fn refresh_account_and_retry_send_tx_with_account_sequence(
     account: &mut Account,
     tx_memo: &Memo,
     messages: &[Any],
-) -> Result<Response, Error> {
+) -> Result<(Response, Fee), Error> {
     let key_account = key_pair.account();
     // Re-fetch the account sequence number
     refresh_account(&config.grpc_address, &key_account, account).await?;
@@ -206,10 +209,10 @@ async fn refresh_account_and_retry_send_tx_with_account_sequence(
     // Retry after delay
     thread::sleep(Duration::from_millis(ACCOUNT_SEQUENCE_RETRY_DELAY));
 
-    let (estimate_result, _) =
+    let (estimate_result, fee, _) =
         estimate_fee_and_send_tx(rpc_client, config, key_pair, account, tx_memo, messages).await?;
 
-    Ok(estimate_result)
+    Ok((estimate_result, fee))
 }
 
 /// Determine whether the given error yielded by `tx_simulate`
@@ -1,12 +1,14 @@
 use ibc_proto::cosmos::tx::v1beta1::service_client::ServiceClient;
-use ibc_proto::cosmos::tx::v1beta1::{SimulateRequest, SimulateResponse, Tx};
+use ibc_proto::cosmos::tx::v1beta1::{SimulateRequest, SimulateResponse, TxRaw};
 use tonic::codegen::http::Uri;
 
 use crate::config::default::max_grpc_decoding_size;
 use crate::error::Error;
 use crate::util::create_grpc_client;
 
-pub async fn send_tx_simulate(grpc_address: &Uri, tx: Tx) -> Result<SimulateResponse, Error> {
+/// Simulates the given Tx. The raw Tx is encoded as-is, rather than from its decoded
+/// body, so that the fields of the body unknown to `TxBody`, if any, are preserved.
+pub async fn send_tx_simulate(grpc_address: &Uri, tx: TxRaw) -> Result<SimulateResponse, Error> {
     let mut tx_bytes = vec![];
     prost::Message::encode(&tx, &mut tx_bytes)
         .map_err(|e| Error::protobuf_encode(String::from("Transaction"), e))?;
@@ -1,10 +1,12 @@
 use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_proto::google::protobuf::Any;
 use ibc_relayer_types::events::IbcEvent;
+use tendermint::abci::Code;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 use tendermint_rpc::{Client, HttpClient, Url};
+use tracing::error;
 
-use crate::chain::cosmos::encode::sign_and_encode_tx;
+use crate::chain::cosmos::backend::EncodeTxRequest;
 use crate::chain::cosmos::estimate::estimate_tx_fees;
 use crate::chain::cosmos::query::account::query_account;
 use crate::chain::cosmos::query::tx::all_ibc_events_from_tx_search_response;
@@ -15,6 +17,8 @@ use crate::config::types::Memo;
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
 use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
+use crate::sdk_error::sdk_error_from_tx_sync_error_code;
+use crate::telemetry;
 
 use super::batch::send_batched_messages_and_wait_commit;
 use super::estimate::EstimatedGas;
@@ -26,7 +30,7 @@ pub async fn estimate_fee_and_send_tx(
     account: &Account,
     tx_memo: &Memo,
     messages: &[Any],
-) -> Result<(Response, EstimatedGas), Error> {
+) -> Result<(Response, Fee, EstimatedGas), Error> {
     let (fee, estimated_gas) =
         estimate_tx_fees(config, key_pair, account, tx_memo, messages).await?;
 
@@ -35,7 +39,44 @@ pub async fn estimate_fee_and_send_tx(
     )
     .await?;
 
-    Ok((tx_result, estimated_gas))
+    Ok((tx_result, fee, estimated_gas))
+}
+
+/// Estimate the fee of an unordered Tx made of the given messages, and broadcast it.
+/// Since unordered Txs do not use the account sequence, there is neither any need
+/// to retry on account sequence mismatch, nor to increment the account sequence.
+pub async fn send_unordered_tx(
+    rpc_client: &HttpClient,
+    config: &TxConfig,
+    key_pair: &Secp256k1KeyPair,
+    account: &Account,
+    tx_memo: &Memo,
+    messages: &[Any],
+) -> Result<(Response, Fee), Error> {
+    let (response, fee, estimated_gas) =
+        estimate_fee_and_send_tx(rpc_client, config, key_pair, account, tx_memo, messages).await?;
+
+    match response.code {
+        Code::Ok => {
+            telemetry!(messages_submitted, &config.chain_id, messages.len() as u64);
+        }
+        Code::Err(code) => {
+            error!(
+                ?response,
+                diagnostic = ?sdk_error_from_tx_sync_error_code(code.into(), estimated_gas),
+                "failed to broadcast unordered tx with unrecoverable error"
+            );
+
+            telemetry!(
+                broadcast_errors,
+                &account.address.to_string(),
+                code.into(),
+                &response.log
+            );
+        }
+    }
+
+    Ok((response, fee))
 }
 
 async fn send_tx_with_fee(
@@ -47,10 +88,21 @@ async fn send_tx_with_fee(
     messages: &[Any],
     fee: &Fee,
 ) -> Result<Response, Error> {
-    let tx_bytes = sign_and_encode_tx(config, key_pair, account, tx_memo, messages, fee)?;
+    let tx_bytes = config.backend.encode_tx(EncodeTxRequest {
+        config,
+        key_pair,
+        account,
+        tx_memo,
+        messages,
+        fee,
+    })?;
 
     let response = broadcast_tx_sync(rpc_client, &config.rpc_address, tx_bytes).await?;
 
+    if response.code.is_ok() {
+        config.gas_model.track_tx(response.hash);
+    }
+
     Ok(response)
 }
 
@@ -91,7 +143,7 @@ pub async fn simple_send_tx(
         .await?
         .into();
 
-    let (response, _) = estimate_fee_and_send_tx(
+    let (response, _, _) = estimate_fee_and_send_tx(
         rpc_client,
         config,
         key_pair,
@@ -113,7 +165,8 @@ pub async fn simple_send_tx(
     )
     .await?;
 
-    let events = all_ibc_events_from_tx_search_response(&config.chain_id, response);
+    let events =
+        all_ibc_events_from_tx_search_response(&config.chain_id, config.backend.as_ref(), response);
 
     Ok(events)
 }
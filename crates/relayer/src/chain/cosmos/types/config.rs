@@ -1,3 +1,4 @@
+use alloc::sync::Arc;
 use core::str::FromStr;
 use core::time::Duration;
 use http::Uri;
@@ -5,16 +6,21 @@ use ibc_proto::google::protobuf::Any;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use tendermint_rpc::Url;
 
+use crate::chain::cosmos::backend::{find_backend, Backend};
 use crate::chain::cosmos::config::CosmosSdkConfig;
+use crate::chain::cosmos::gas_model::GasModel;
+use crate::chain::cosmos::replace::ReplacementSupport;
 use crate::chain::cosmos::types::gas::GasConfig;
 use crate::config::types::{MaxMsgNum, MaxTxSize};
-use crate::config::AddressType;
+use crate::config::{AddressType, FeeGranters, TxReplacementConfig};
 use crate::error::Error;
 
 #[derive(Debug, Clone)]
 pub struct TxConfig {
     pub chain_id: ChainId,
     pub gas_config: GasConfig,
+    pub fee_granters: FeeGranters,
+    pub gas_model: GasModel,
     pub rpc_address: Url,
     pub grpc_address: Uri,
     pub rpc_timeout: Duration,
@@ -22,6 +28,13 @@ pub struct TxConfig {
     pub max_msg_num: MaxMsgNum,
     pub max_tx_size: MaxTxSize,
     pub extension_options: Vec<Any>,
+    pub tx_replacement: TxReplacementConfig,
+    /// Whether the mempool of the chain accepts replacing Txs, as learnt so far
+    pub tx_replacement_support: ReplacementSupport,
+    /// The timeout of the unordered Txs, or `None` if Txs are ordered by account sequence.
+    pub unordered_tx_timeout: Option<Duration>,
+    /// The hooks encoding the Txs and decoding their events
+    pub backend: Arc<dyn Backend>,
 }
 
 impl<'a> TryFrom<&'a CosmosSdkConfig> for TxConfig {
@@ -33,6 +46,8 @@ impl<'a> TryFrom<&'a CosmosSdkConfig> for TxConfig {
 
         let gas_config = GasConfig::from(config);
 
+        let gas_model = GasModel::from_config(&config.id, &config.gas_model);
+
         let extension_options = config
             .extension_options
             .iter()
@@ -42,6 +57,8 @@ impl<'a> TryFrom<&'a CosmosSdkConfig> for TxConfig {
         Ok(Self {
             chain_id: config.id.clone(),
             gas_config,
+            fee_granters: config.fee_granters.clone(),
+            gas_model,
             rpc_address: config.rpc_addr.clone(),
             grpc_address,
             rpc_timeout: config.rpc_timeout,
@@ -49,6 +66,11 @@ impl<'a> TryFrom<&'a CosmosSdkConfig> for TxConfig {
             max_msg_num: config.max_msg_num,
             max_tx_size: config.max_tx_size,
             extension_options,
+            tx_replacement: config.tx_replacement.clone(),
+            tx_replacement_support: ReplacementSupport::default(),
+            // Only enabled once the chain is known to support unordered Txs
+            unordered_tx_timeout: None,
+            backend: find_backend(config.backend.as_ref())?,
         })
     }
 }
@@ -30,7 +30,7 @@ impl<'a> From<&'a CosmosSdkConfig> for GasConfig {
             gas_price: config.gas_price.clone(),
             max_fee: max_fee_from_config(config),
             fee_granter: fee_granter_from_config(config),
-            dynamic_gas_price: config.dynamic_gas_price,
+            dynamic_gas_price: config.dynamic_gas_price.clone(),
         }
     }
 }
@@ -1,6 +1,11 @@
-use ibc_proto::cosmos::tx::v1beta1::{AuthInfo, TxBody};
+use std::time::Instant;
+
+use ibc_proto::cosmos::tx::v1beta1::{AuthInfo, Fee, TxBody};
+use ibc_proto::google::protobuf::Any;
+use tendermint::Hash as TxHash;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 
+use crate::chain::cosmos::types::account::Account;
 use crate::event::IbcEventWithHeight;
 
 pub struct SignedTx {
@@ -22,4 +27,21 @@ pub struct TxSyncResult {
     // the events generated by a Tx once executed
     pub events: Vec<IbcEventWithHeight>,
     pub status: TxStatus,
+    // the data needed to replace the Tx if it gets stuck in the mempool
+    pub replacement: Option<ReplaceableTx>,
+}
+
+/// A Tx which can be rebroadcast at the same account sequence with a
+/// bumped fee if it is not committed in time.
+pub struct ReplaceableTx {
+    pub messages: Vec<Any>,
+    // the fee of the original Tx, including its gas limit, which the replacements bump
+    pub fee: Fee,
+    // the account, with the sequence used by the Tx
+    pub account: Account,
+    pub broadcast_at: Instant,
+    // the multiplier applied to the estimated fee of the Tx
+    pub fee_multiplier: f64,
+    // the hashes of the Txs replaced by the current one, any of which may still be committed
+    pub replaced_hashes: Vec<TxHash>,
 }
@@ -37,6 +37,16 @@ pub struct Specs {
     pub consensus: Option<ConsensusVersion>,
 }
 
+impl Specs {
+    /// Returns true if the chain supports unordered transactions,
+    /// which were introduced in Cosmos SDK v0.53.
+    pub fn supports_unordered_txs(&self) -> bool {
+        self.cosmos_sdk
+            .as_ref()
+            .is_some_and(|v| v.major > 0 || v.minor >= 53)
+    }
+}
+
 impl Display for Specs {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         let cosmos_sdk = self
@@ -292,6 +302,8 @@ mod tests {
                 ))
             }
         );
+
+        assert!(!specs.supports_unordered_txs());
     }
 
     #[test]
@@ -11,28 +11,40 @@ use tendermint_rpc::{HttpClient, Url};
 use tokio::time::sleep;
 use tracing::{debug, debug_span, trace};
 
+use crate::chain::cosmos::backend::{decode_events, Backend};
+use crate::chain::cosmos::gas_model::GasModel;
 use crate::chain::cosmos::query::tx::query_tx_response;
+use crate::chain::cosmos::replace::replace_stuck_tx;
+use crate::chain::cosmos::types::config::TxConfig;
 use crate::chain::cosmos::types::events::from_tx_response_event;
 use crate::chain::cosmos::types::tx::{TxStatus, TxSyncResult};
+use crate::config::types::Memo;
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
+use crate::keyring::Secp256k1KeyPair;
 
 const WAIT_BACKOFF: Duration = Duration::from_millis(300);
 
 /// Given a vector of `TxSyncResult` elements,
 /// each including a transaction response hash for one or more messages, periodically queries the chain
 /// with the transaction hashes to get the list of IbcEvents included in those transactions.
+/// The gas used by the committed transactions is recorded in the gas model of the chain.
+///
+/// If Tx replacement is enabled, the transactions which are not committed within the
+/// configured `stuck_timeout` are rebroadcast with a bumped fee, and the wait is extended.
 pub async fn wait_for_block_commits(
-    chain_id: &ChainId,
     rpc_client: &HttpClient,
-    rpc_address: &Url,
-    rpc_timeout: &Duration,
+    config: &TxConfig,
+    key_pair: &Secp256k1KeyPair,
+    tx_memo: &Memo,
     tx_sync_results: &mut [TxSyncResult],
 ) -> Result<(), Error> {
     if all_tx_results_found(tx_sync_results) {
         return Ok(());
     }
 
+    let chain_id = &config.chain_id;
+
     crate::time!(
         "wait_for_block_commits",
         {
@@ -41,7 +53,7 @@ pub async fn wait_for_block_commits(
     );
     let _span = debug_span!("wait_for_block_commits", id = %chain_id).entered();
 
-    let start_time = Instant::now();
+    let mut start_time = Instant::now();
 
     let hashes = tx_sync_results
         .iter()
@@ -61,36 +73,84 @@ pub async fn wait_for_block_commits(
             );
 
             return Ok(());
-        } else if &elapsed > rpc_timeout {
+        } else if elapsed > config.rpc_timeout {
             debug!("timed out after {} ms", elapsed.as_millis());
             return Err(Error::tx_no_confirmation());
         } else {
             thread::sleep(WAIT_BACKOFF);
 
             for tx_sync_result in tx_sync_results.iter_mut() {
-                let res =
-                    update_tx_sync_result(chain_id, rpc_client, rpc_address, tx_sync_result).await;
+                let res = update_tx_sync_result(
+                    chain_id,
+                    rpc_client,
+                    &config.rpc_address,
+                    &config.gas_model,
+                    config.backend.as_ref(),
+                    tx_sync_result,
+                )
+                .await;
                 if let Err(e) = res {
                     debug!("update_tx_sync_result failed: {e}");
                 }
+
+                if is_stuck(config, tx_sync_result) {
+                    match replace_stuck_tx(rpc_client, config, key_pair, tx_memo, tx_sync_result)
+                        .await
+                    {
+                        // Give the replacement Tx as much time to be committed as the original one
+                        Ok(true) => start_time = Instant::now(),
+                        Ok(false) => {}
+                        Err(e) => debug!("replace_stuck_tx failed: {e}"),
+                    }
+                }
             }
         }
     }
 }
 
+/// Returns true if the given Tx is pending and was broadcast longer
+/// than the configured `stuck_timeout` ago, if Tx replacement is enabled.
+fn is_stuck(config: &TxConfig, tx_sync_result: &TxSyncResult) -> bool {
+    matches!(tx_sync_result.status, TxStatus::Pending { .. })
+        && tx_sync_result
+            .replacement
+            .as_ref()
+            .is_some_and(|replacement| {
+                replacement.broadcast_at.elapsed() > config.tx_replacement.stuck_timeout
+            })
+}
+
 async fn update_tx_sync_result(
     chain_id: &ChainId,
     rpc_client: &HttpClient,
     rpc_address: &Url,
+    gas_model: &GasModel,
+    backend: &dyn Backend,
     tx_sync_result: &mut TxSyncResult,
 ) -> Result<(), Error> {
     if let TxStatus::Pending { message_count } = tx_sync_result.status {
-        let response =
-            query_tx_response(rpc_client, rpc_address, &tx_sync_result.response.hash).await?;
+        // If the Tx was replaced, any of the replaced Txs may have been committed instead
+        let replaced_hashes = tx_sync_result
+            .replacement
+            .as_ref()
+            .map(|replacement| replacement.replaced_hashes.as_slice())
+            .unwrap_or_default();
+
+        let mut response = None;
+
+        for hash in core::iter::once(&tx_sync_result.response.hash).chain(replaced_hashes) {
+            response = query_tx_response(rpc_client, rpc_address, hash).await?;
+
+            if response.is_some() {
+                break;
+            }
+        }
 
         if let Some(response) = response {
             tx_sync_result.status = TxStatus::ReceivedResponse;
 
+            gas_model.observe_tx(&response);
+
             let height = Height::new(chain_id.version(), u64::from(response.height)).unwrap();
             if response.tx_result.code.is_err() {
                 tx_sync_result.events = vec![
@@ -104,9 +164,7 @@ async fn update_tx_sync_result(
                     message_count
                 ];
             } else {
-                tx_sync_result.events = response
-                    .tx_result
-                    .events
+                tx_sync_result.events = decode_events(backend, response.tx_result.events)
                     .iter()
                     .flat_map(|event| from_tx_response_event(height, event))
                     .collect::<Vec<_>>();
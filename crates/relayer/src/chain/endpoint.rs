@@ -2,6 +2,7 @@ use alloc::sync::Arc;
 
 use ibc_proto::ibc::core::channel::v1::{QueryUpgradeErrorRequest, QueryUpgradeRequest};
 use ibc_relayer_types::applications::ics28_ccv::msgs::{ConsumerChain, ConsumerId};
+use ibc_relayer_types::applications::ics29_fee::packet_fee::IdentifiedPacketFees;
 use ibc_relayer_types::core::ics02_client::height::Height;
 use tokio::runtime::Runtime as TokioRuntime;
 
@@ -9,6 +10,7 @@ use ibc_proto::ibc::apps::fee::v1::{
     QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
 };
 use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
+use ibc_relayer_types::applications::transfer::RawCoin;
 use ibc_relayer_types::core::ics02_client::client_state::ClientState;
 use ibc_relayer_types::core::ics02_client::consensus_state::ConsensusState;
 use ibc_relayer_types::core::ics02_client::events::UpdateClient;
@@ -20,6 +22,7 @@ use ibc_relayer_types::core::ics03_connection::version::{get_compatible_versions
 use ibc_relayer_types::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
 use ibc_relayer_types::core::ics04_channel::packet::{PacketMsgType, Sequence};
 use ibc_relayer_types::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
+use ibc_relayer_types::core::ics04_channel::v2::packet::PacketMsgType as PacketMsgTypeV2;
 use ibc_relayer_types::core::ics23_commitment::commitment::{
     CommitmentPrefix, CommitmentProofBytes,
 };
@@ -35,6 +38,7 @@ use ibc_relayer_types::Height as ICSHeight;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response as TxResponse;
 
 use crate::account::Balance;
+use crate::chain::capabilities::ChainCapabilities;
 use crate::chain::client::ClientSettings;
 use crate::chain::handle::Subscription;
 use crate::chain::requests::*;
@@ -49,6 +53,8 @@ use crate::error::Error;
 use crate::event::IbcEventWithHeight;
 use crate::keyring::{AnySigningKeyPair, KeyRing, SigningKeyPairSized};
 use crate::misbehaviour::MisbehaviourEvidence;
+use crate::nft_transfer::ClassTrace;
+use crate::transfer::{IbcShielding, IbcShieldingRequest, ShieldedTransferRequest};
 
 /// The result of a health check.
 #[derive(Debug)]
@@ -130,6 +136,9 @@ pub trait ChainEndpoint: Sized {
     /// Return the version of the IBC protocol that this chain is running, if known.
     fn version_specs(&self) -> Result<Specs, Error>;
 
+    /// Return the features supported by this chain backend.
+    fn capabilities(&self) -> ChainCapabilities;
+
     // Send transactions
 
     /// Sends one or more transactions with `msgs` to chain and
@@ -146,6 +155,24 @@ pub trait ChainEndpoint: Sized {
         tracked_msgs: TrackedMsgs,
     ) -> Result<Vec<TxResponse>, Error>;
 
+    /// Estimates the fees which would be paid for submitting a transaction
+    /// with the given `msgs` to the chain, without broadcasting it.
+    fn estimate_fee(&mut self, tracked_msgs: TrackedMsgs) -> Result<Vec<RawCoin>, Error>;
+
+    /// Returns the fees paid for the committed transaction with the given hash.
+    fn query_tx_fee(&self, tx_hash: QueryTxHash) -> Result<Vec<RawCoin>, Error>;
+
+    /// Generates the receiver and memo of an IBC transfer which shields the
+    /// tokens received by this chain to the given MASP payment address.
+    fn build_ibc_shielding(&self, request: IbcShieldingRequest) -> Result<IbcShielding, Error>;
+
+    /// Sends an IBC transfer unshielding tokens from a MASP spending key,
+    /// and synchronously wait for it to be committed.
+    fn send_shielded_transfer(
+        &mut self,
+        request: ShieldedTransferRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error>;
+
     /// Fetch a header from the chain at the given height and verify it.
     fn verify_header(
         &mut self,
@@ -176,6 +203,17 @@ pub trait ChainEndpoint: Sized {
     /// Query the denomination trace given a trace hash.
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error>;
 
+    /// Query the ICS721 class trace given a trace hash.
+    fn query_class_trace(&self, hash: String) -> Result<ClassTrace, Error>;
+
+    /// Query the address of the interchain account registered by `owner`
+    /// over the given controller connection.
+    fn query_interchain_account(
+        &self,
+        owner: Signer,
+        connection_id: ConnectionId,
+    ) -> Result<String, Error>;
+
     fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error>;
 
     fn query_compatible_versions(&self) -> Result<Vec<Version>, Error> {
@@ -325,6 +363,46 @@ pub trait ChainEndpoint: Sized {
         request: QueryPacketAcknowledgementsRequest,
     ) -> Result<(Vec<Sequence>, ICSHeight), Error>;
 
+    /// Performs a query to retrieve the commitment of an IBC v2 packet,
+    /// stored on the chain at path `path::CommitmentsV2Path`. A proof can
+    /// optionally be returned along with the result.
+    fn query_packet_commitment_v2(
+        &self,
+        request: QueryPacketCommitmentV2Request,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error>;
+
+    /// Performs a query to retrieve the receipt of an IBC v2 packet,
+    /// stored on the chain at path `path::ReceiptsV2Path`. A proof can
+    /// optionally be returned along with the result.
+    fn query_packet_receipt_v2(
+        &self,
+        request: QueryPacketReceiptV2Request,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error>;
+
+    /// Performs a query to retrieve the acknowledgement of an IBC v2 packet,
+    /// stored on the chain at path `path::AcksV2Path`. A proof can
+    /// optionally be returned along with the result.
+    fn query_packet_acknowledgement_v2(
+        &self,
+        request: QueryPacketAcknowledgementV2Request,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error>;
+
+    /// Performs a query to retrieve the sequences of the IBC v2 packets sent
+    /// over the given client whose commitment is still stored on the chain.
+    fn query_packet_commitments_v2(
+        &self,
+        request: QueryPacketCommitmentsV2Request,
+    ) -> Result<(Vec<Sequence>, ICSHeight), Error>;
+
+    /// Performs a query to retrieve the IBC v2 packet events matching the given request.
+    fn query_packet_events_v2(
+        &self,
+        request: QueryPacketEventDataV2Request,
+    ) -> Result<Vec<IbcEventWithHeight>, Error>;
+
     /// Performs a query about which IBC packets in the specified list has not
     /// been acknowledged. Returns the sequence numbers of the packets that were not
     /// acknowledged.
@@ -672,6 +750,60 @@ pub trait ChainEndpoint: Sized {
         Ok(proofs)
     }
 
+    /// Builds the proof for IBC v2 packet messages.
+    ///
+    /// The `client_id` is the identifier of the client on this chain
+    /// over which the packet was sent (for `Recv`) or received
+    /// (for `Ack` and `Timeout`).
+    fn build_packet_v2_proofs(
+        &self,
+        packet_type: PacketMsgTypeV2,
+        client_id: ClientId,
+        sequence: Sequence,
+        height: ICSHeight,
+    ) -> Result<Proofs, Error> {
+        let (_, maybe_packet_proof) = match packet_type {
+            PacketMsgTypeV2::Recv => self.query_packet_commitment_v2(
+                QueryPacketCommitmentV2Request {
+                    client_id,
+                    sequence,
+                    height: QueryHeight::Specific(height),
+                },
+                IncludeProof::Yes,
+            )?,
+            PacketMsgTypeV2::Ack => self.query_packet_acknowledgement_v2(
+                QueryPacketAcknowledgementV2Request {
+                    client_id,
+                    sequence,
+                    height: QueryHeight::Specific(height),
+                },
+                IncludeProof::Yes,
+            )?,
+            PacketMsgTypeV2::Timeout => self.query_packet_receipt_v2(
+                QueryPacketReceiptV2Request {
+                    client_id,
+                    sequence,
+                    height: QueryHeight::Specific(height),
+                },
+                IncludeProof::Yes,
+            )?,
+        };
+
+        let Some(packet_proof) = maybe_packet_proof else {
+            return Err(Error::queried_proof_not_found());
+        };
+
+        Proofs::new(
+            CommitmentProofBytes::try_from(packet_proof).map_err(Error::malformed_proof)?,
+            None,
+            None,
+            None,
+            None,
+            height.increment(),
+        )
+        .map_err(Error::malformed_proof)
+    }
+
     fn maybe_register_counterparty_payee(
         &mut self,
         channel_id: &ChannelId,
@@ -689,6 +821,13 @@ pub trait ChainEndpoint: Sized {
         request: QueryIncentivizedPacketRequest,
     ) -> Result<QueryIncentivizedPacketResponse, Error>;
 
+    /// Return the fees escrowed for all the incentivized packets of the given channel.
+    fn query_incentivized_packets(
+        &self,
+        channel_id: ChannelId,
+        port_id: PortId,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error>;
+
     fn query_consumer_chains(&self) -> Result<Vec<ConsumerChain>, Error>;
 
     fn query_upgrade(
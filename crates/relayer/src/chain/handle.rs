@@ -9,8 +9,10 @@ use ibc_proto::ibc::apps::fee::v1::{
 };
 use ibc_proto::ibc::core::channel::v1::{QueryUpgradeErrorRequest, QueryUpgradeRequest};
 use ibc_relayer_types::applications::ics28_ccv::msgs::{ConsumerChain, ConsumerId};
+use ibc_relayer_types::applications::ics29_fee::packet_fee::IdentifiedPacketFees;
 use ibc_relayer_types::{
     applications::ics31_icq::response::CrossChainQueryResponse,
+    applications::transfer::RawCoin,
     core::{
         ics02_client::{events::UpdateClient, header::AnyHeader},
         ics03_connection::{
@@ -21,6 +23,7 @@ use ibc_relayer_types::{
             channel::{ChannelEnd, IdentifiedChannelEnd},
             packet::{PacketMsgType, Sequence},
             upgrade::{ErrorReceipt, Upgrade},
+            v2::packet::PacketMsgType as PacketMsgTypeV2,
         },
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
         ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
@@ -44,9 +47,12 @@ use crate::{
     },
     keyring::AnySigningKeyPair,
     misbehaviour::MisbehaviourEvidence,
+    nft_transfer::ClassTrace,
+    transfer::{IbcShielding, IbcShieldingRequest, ShieldedTransferRequest},
 };
 
 use super::{
+    capabilities::ChainCapabilities,
     client::ClientSettings,
     endpoint::{ChainStatus, HealthCheck},
     requests::*,
@@ -126,6 +132,26 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>>,
     },
 
+    EstimateFee {
+        tracked_msgs: TrackedMsgs,
+        reply_to: ReplyTo<Vec<RawCoin>>,
+    },
+
+    QueryTxFee {
+        tx_hash: QueryTxHash,
+        reply_to: ReplyTo<Vec<RawCoin>>,
+    },
+
+    BuildIbcShielding {
+        request: IbcShieldingRequest,
+        reply_to: ReplyTo<IbcShielding>,
+    },
+
+    SendShieldedTransfer {
+        request: ShieldedTransferRequest,
+        reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
+    },
+
     Config {
         reply_to: ReplyTo<ChainConfig>,
     },
@@ -148,6 +174,10 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Specs>,
     },
 
+    Capabilities {
+        reply_to: ReplyTo<ChainCapabilities>,
+    },
+
     QueryBalance {
         key_name: Option<String>,
         denom: Option<String>,
@@ -164,6 +194,17 @@ pub enum ChainRequest {
         reply_to: ReplyTo<DenomTrace>,
     },
 
+    QueryClassTrace {
+        hash: String,
+        reply_to: ReplyTo<ClassTrace>,
+    },
+
+    QueryInterchainAccount {
+        owner: Signer,
+        connection_id: ConnectionId,
+        reply_to: ReplyTo<String>,
+    },
+
     QueryApplicationStatus {
         reply_to: ReplyTo<ChainStatus>,
     },
@@ -334,6 +375,42 @@ pub enum ChainRequest {
         reply_to: ReplyTo<(Vec<Sequence>, Height)>,
     },
 
+    BuildPacketV2Proofs {
+        packet_type: PacketMsgTypeV2,
+        client_id: ClientId,
+        sequence: Sequence,
+        height: Height,
+        reply_to: ReplyTo<Proofs>,
+    },
+
+    QueryPacketCommitmentV2 {
+        request: QueryPacketCommitmentV2Request,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(Vec<u8>, Option<MerkleProof>)>,
+    },
+
+    QueryPacketReceiptV2 {
+        request: QueryPacketReceiptV2Request,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(Vec<u8>, Option<MerkleProof>)>,
+    },
+
+    QueryPacketAcknowledgementV2 {
+        request: QueryPacketAcknowledgementV2Request,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(Vec<u8>, Option<MerkleProof>)>,
+    },
+
+    QueryPacketCommitmentsV2 {
+        request: QueryPacketCommitmentsV2Request,
+        reply_to: ReplyTo<(Vec<Sequence>, Height)>,
+    },
+
+    QueryPacketEventDataV2 {
+        request: QueryPacketEventDataV2Request,
+        reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
+    },
+
     QueryUnreceivedAcknowledgement {
         request: QueryUnreceivedAcksRequest,
         reply_to: ReplyTo<Vec<Sequence>>,
@@ -371,6 +448,12 @@ pub enum ChainRequest {
         reply_to: ReplyTo<QueryIncentivizedPacketResponse>,
     },
 
+    QueryIncentivizedPackets {
+        channel_id: ChannelId,
+        port_id: PortId,
+        reply_to: ReplyTo<Vec<IdentifiedPacketFees>>,
+    },
+
     QueryConsumerChains {
         reply_to: ReplyTo<Vec<ConsumerChain>>,
     },
@@ -426,6 +509,24 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
         tracked_msgs: TrackedMsgs,
     ) -> Result<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>, Error>;
 
+    /// Estimates the fees which would be paid for submitting a transaction
+    /// with the given `msgs` to the chain, without broadcasting it.
+    fn estimate_fee(&self, tracked_msgs: TrackedMsgs) -> Result<Vec<RawCoin>, Error>;
+
+    /// Return the fees paid for the committed transaction with the given hash.
+    fn query_tx_fee(&self, tx_hash: QueryTxHash) -> Result<Vec<RawCoin>, Error>;
+
+    /// Generate the receiver and memo of an IBC transfer which shields the
+    /// tokens received by this chain to the given MASP payment address.
+    fn build_ibc_shielding(&self, request: IbcShieldingRequest) -> Result<IbcShielding, Error>;
+
+    /// Send an IBC transfer unshielding tokens from a MASP spending key,
+    /// and return the events emitted by the chain once the transaction was committed.
+    fn send_shielded_transfer(
+        &self,
+        request: ShieldedTransferRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error>;
+
     fn get_signer(&self) -> Result<Signer, Error>;
 
     fn config(&self) -> Result<ChainConfig, Error>;
@@ -437,6 +538,9 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
     /// Return the version of the IBC protocol that this chain is running, if known.
     fn version_specs(&self) -> Result<Specs, Error>;
 
+    /// Return the features supported by this chain.
+    fn capabilities(&self) -> Result<ChainCapabilities, Error>;
+
     /// Query the balance of the given account for the given denom.
     /// If no account is given, behavior must be specified, e.g. retrieve it from configuration file.
     /// If no denom is given, behavior must be specified, e.g. using the denom used to pay tx fees
@@ -453,6 +557,17 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
     /// Query the denomination trace given a trace hash.
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error>;
 
+    /// Query the ICS721 class trace given a trace hash.
+    fn query_class_trace(&self, hash: String) -> Result<ClassTrace, Error>;
+
+    /// Query the address of the interchain account registered by `owner`
+    /// over the given controller connection.
+    fn query_interchain_account(
+        &self,
+        owner: Signer,
+        connection_id: ConnectionId,
+    ) -> Result<String, Error>;
+
     /// Query the latest height and timestamp the application is at
     fn query_application_status(&self) -> Result<ChainStatus, Error>;
 
@@ -664,6 +779,54 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
         request: QueryPacketAcknowledgementsRequest,
     ) -> Result<(Vec<Sequence>, Height), Error>;
 
+    fn build_packet_v2_proofs(
+        &self,
+        packet_type: PacketMsgTypeV2,
+        client_id: &ClientId,
+        sequence: Sequence,
+        height: Height,
+    ) -> Result<Proofs, Error>;
+
+    /// Performs a query to retrieve the commitment of an IBC v2 packet,
+    /// stored on the chain at path `path::CommitmentsV2Path`. A proof can
+    /// optionally be returned along with the result.
+    fn query_packet_commitment_v2(
+        &self,
+        request: QueryPacketCommitmentV2Request,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error>;
+
+    /// Performs a query to retrieve the receipt of an IBC v2 packet,
+    /// stored on the chain at path `path::ReceiptsV2Path`. A proof can
+    /// optionally be returned along with the result.
+    fn query_packet_receipt_v2(
+        &self,
+        request: QueryPacketReceiptV2Request,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error>;
+
+    /// Performs a query to retrieve the acknowledgement of an IBC v2 packet,
+    /// stored on the chain at path `path::AcksV2Path`. A proof can
+    /// optionally be returned along with the result.
+    fn query_packet_acknowledgement_v2(
+        &self,
+        request: QueryPacketAcknowledgementV2Request,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error>;
+
+    /// Performs a query to retrieve the sequences of the IBC v2 packets sent
+    /// over the given client whose commitment is still stored on the chain.
+    fn query_packet_commitments_v2(
+        &self,
+        request: QueryPacketCommitmentsV2Request,
+    ) -> Result<(Vec<Sequence>, Height), Error>;
+
+    /// Performs a query to retrieve the IBC v2 packet events matching the given request.
+    fn query_packet_events_v2(
+        &self,
+        request: QueryPacketEventDataV2Request,
+    ) -> Result<Vec<IbcEventWithHeight>, Error>;
+
     /// Performs a query about which IBC packets in the specified list has not
     /// been acknowledged. Returns the sequence numbers of the packets that were not
     /// acknowledged.
@@ -705,6 +868,13 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
         request: QueryIncentivizedPacketRequest,
     ) -> Result<QueryIncentivizedPacketResponse, Error>;
 
+    /// Return the fees escrowed for all the incentivized packets of the given channel.
+    fn query_incentivized_packets(
+        &self,
+        channel_id: ChannelId,
+        port_id: PortId,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error>;
+
     fn query_consumer_chains(&self) -> Result<Vec<ConsumerChain>, Error>;
 
     fn query_upgrade(
@@ -7,10 +7,12 @@ use ibc_proto::ibc::{
     apps::fee::v1::{QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse},
     core::channel::v1::{QueryUpgradeErrorRequest, QueryUpgradeRequest},
 };
+use ibc_relayer_types::applications::ics29_fee::packet_fee::IdentifiedPacketFees;
 use ibc_relayer_types::{
     applications::{
         ics28_ccv::msgs::{ConsumerChain, ConsumerId},
         ics31_icq::response::CrossChainQueryResponse,
+        transfer::RawCoin,
     },
     core::{
         ics02_client::{events::UpdateClient, header::AnyHeader},
@@ -22,6 +24,7 @@ use ibc_relayer_types::{
             channel::{ChannelEnd, IdentifiedChannelEnd},
             packet::{PacketMsgType, Sequence},
             upgrade::{ErrorReceipt, Upgrade},
+            v2::packet::PacketMsgType as PacketMsgTypeV2,
         },
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
         ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
@@ -34,8 +37,8 @@ use ibc_relayer_types::{
 use crate::{
     account::Balance,
     chain::{
-        client::ClientSettings, endpoint::ChainStatus, requests::*, tracking::TrackedMsgs,
-        version::Specs,
+        capabilities::ChainCapabilities, client::ClientSettings, endpoint::ChainStatus,
+        requests::*, tracking::TrackedMsgs, version::Specs,
     },
     client_state::{AnyClientState, IdentifiedAnyClientState},
     config::ChainConfig,
@@ -46,6 +49,8 @@ use crate::{
     event::IbcEventWithHeight,
     keyring::AnySigningKeyPair,
     misbehaviour::MisbehaviourEvidence,
+    nft_transfer::ClassTrace,
+    transfer::{IbcShielding, IbcShieldingRequest, ShieldedTransferRequest},
 };
 
 use super::{reply_channel, ChainHandle, ChainRequest, HealthCheck, ReplyTo, Subscription};
@@ -124,6 +129,10 @@ impl ChainHandle for BaseChainHandle {
         })
     }
 
+    fn query_tx_fee(&self, tx_hash: QueryTxHash) -> Result<Vec<RawCoin>, Error> {
+        self.send(|reply_to| ChainRequest::QueryTxFee { tx_hash, reply_to })
+    }
+
     fn send_messages_and_wait_check_tx(
         &self,
         tracked_msgs: TrackedMsgs,
@@ -134,6 +143,24 @@ impl ChainHandle for BaseChainHandle {
         })
     }
 
+    fn estimate_fee(&self, tracked_msgs: TrackedMsgs) -> Result<Vec<RawCoin>, Error> {
+        self.send(|reply_to| ChainRequest::EstimateFee {
+            tracked_msgs,
+            reply_to,
+        })
+    }
+
+    fn build_ibc_shielding(&self, request: IbcShieldingRequest) -> Result<IbcShielding, Error> {
+        self.send(|reply_to| ChainRequest::BuildIbcShielding { request, reply_to })
+    }
+
+    fn send_shielded_transfer(
+        &self,
+        request: ShieldedTransferRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.send(|reply_to| ChainRequest::SendShieldedTransfer { request, reply_to })
+    }
+
     fn get_signer(&self) -> Result<Signer, Error> {
         self.send(|reply_to| ChainRequest::Signer { reply_to })
     }
@@ -158,6 +185,10 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::VersionSpecs { reply_to })
     }
 
+    fn capabilities(&self) -> Result<ChainCapabilities, Error> {
+        self.send(|reply_to| ChainRequest::Capabilities { reply_to })
+    }
+
     fn query_balance(
         &self,
         key_name: Option<String>,
@@ -178,6 +209,22 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::QueryDenomTrace { hash, reply_to })
     }
 
+    fn query_class_trace(&self, hash: String) -> Result<ClassTrace, Error> {
+        self.send(|reply_to| ChainRequest::QueryClassTrace { hash, reply_to })
+    }
+
+    fn query_interchain_account(
+        &self,
+        owner: Signer,
+        connection_id: ConnectionId,
+    ) -> Result<String, Error> {
+        self.send(|reply_to| ChainRequest::QueryInterchainAccount {
+            owner,
+            connection_id,
+            reply_to,
+        })
+    }
+
     fn query_application_status(&self) -> Result<ChainStatus, Error> {
         self.send(|reply_to| ChainRequest::QueryApplicationStatus { reply_to })
     }
@@ -472,6 +519,72 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::QueryPacketAcknowledgements { request, reply_to })
     }
 
+    fn build_packet_v2_proofs(
+        &self,
+        packet_type: PacketMsgTypeV2,
+        client_id: &ClientId,
+        sequence: Sequence,
+        height: Height,
+    ) -> Result<Proofs, Error> {
+        self.send(|reply_to| ChainRequest::BuildPacketV2Proofs {
+            packet_type,
+            client_id: client_id.clone(),
+            sequence,
+            height,
+            reply_to,
+        })
+    }
+
+    fn query_packet_commitment_v2(
+        &self,
+        request: QueryPacketCommitmentV2Request,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.send(|reply_to| ChainRequest::QueryPacketCommitmentV2 {
+            request,
+            include_proof,
+            reply_to,
+        })
+    }
+
+    fn query_packet_receipt_v2(
+        &self,
+        request: QueryPacketReceiptV2Request,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.send(|reply_to| ChainRequest::QueryPacketReceiptV2 {
+            request,
+            include_proof,
+            reply_to,
+        })
+    }
+
+    fn query_packet_acknowledgement_v2(
+        &self,
+        request: QueryPacketAcknowledgementV2Request,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.send(|reply_to| ChainRequest::QueryPacketAcknowledgementV2 {
+            request,
+            include_proof,
+            reply_to,
+        })
+    }
+
+    fn query_packet_commitments_v2(
+        &self,
+        request: QueryPacketCommitmentsV2Request,
+    ) -> Result<(Vec<Sequence>, Height), Error> {
+        self.send(|reply_to| ChainRequest::QueryPacketCommitmentsV2 { request, reply_to })
+    }
+
+    fn query_packet_events_v2(
+        &self,
+        request: QueryPacketEventDataV2Request,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.send(|reply_to| ChainRequest::QueryPacketEventDataV2 { request, reply_to })
+    }
+
     fn query_unreceived_acknowledgements(
         &self,
         request: QueryUnreceivedAcksRequest,
@@ -525,6 +638,18 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::QueryIncentivizedPacket { request, reply_to })
     }
 
+    fn query_incentivized_packets(
+        &self,
+        channel_id: ChannelId,
+        port_id: PortId,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error> {
+        self.send(|reply_to| ChainRequest::QueryIncentivizedPackets {
+            channel_id,
+            port_id,
+            reply_to,
+        })
+    }
+
     fn query_consumer_chains(&self) -> Result<Vec<ConsumerChain>, Error> {
         self.send(|reply_to| ChainRequest::QueryConsumerChains { reply_to })
     }
@@ -1,6 +1,7 @@
 use core::fmt::{Display, Error as FmtError, Formatter};
 use crossbeam_channel as channel;
 use ibc_relayer_types::applications::ics28_ccv::msgs::ConsumerId;
+use ibc_relayer_types::applications::ics29_fee::packet_fee::IdentifiedPacketFees;
 use tracing::Span;
 
 use ibc_proto::ibc::apps::fee::v1::QueryIncentivizedPacketRequest;
@@ -8,6 +9,7 @@ use ibc_proto::ibc::apps::fee::v1::QueryIncentivizedPacketResponse;
 use ibc_proto::ibc::core::channel::v1::{QueryUpgradeErrorRequest, QueryUpgradeRequest};
 use ibc_relayer_types::applications::ics28_ccv::msgs::ConsumerChain;
 use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
+use ibc_relayer_types::applications::transfer::RawCoin;
 use ibc_relayer_types::core::ics02_client::events::UpdateClient;
 use ibc_relayer_types::core::ics02_client::header::AnyHeader;
 use ibc_relayer_types::core::ics03_connection::connection::ConnectionEnd;
@@ -18,6 +20,7 @@ use ibc_relayer_types::core::ics04_channel::channel::IdentifiedChannelEnd;
 use ibc_relayer_types::core::ics04_channel::packet::{PacketMsgType, Sequence};
 use ibc_relayer_types::core::ics04_channel::upgrade::ErrorReceipt;
 use ibc_relayer_types::core::ics04_channel::upgrade::Upgrade;
+use ibc_relayer_types::core::ics04_channel::v2::packet::PacketMsgType as PacketMsgTypeV2;
 use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentPrefix;
 use ibc_relayer_types::core::ics23_commitment::merkle::MerkleProof;
 use ibc_relayer_types::core::ics24_host::identifier::{
@@ -29,6 +32,7 @@ use ibc_relayer_types::Height;
 
 use crate::account::Balance;
 use crate::cache::{Cache, CacheStatus};
+use crate::chain::capabilities::ChainCapabilities;
 use crate::chain::client::ClientSettings;
 use crate::chain::endpoint::{ChainStatus, HealthCheck};
 use crate::chain::handle::{ChainHandle, ChainRequest, Subscription};
@@ -44,7 +48,9 @@ use crate::error::Error;
 use crate::event::IbcEventWithHeight;
 use crate::keyring::AnySigningKeyPair;
 use crate::misbehaviour::MisbehaviourEvidence;
+use crate::nft_transfer::ClassTrace;
 use crate::telemetry;
+use crate::transfer::{IbcShielding, IbcShieldingRequest, ShieldedTransferRequest};
 
 /// A chain handle with support for caching.
 /// To be used for the passive relaying mode (i.e., `start` CLI).
@@ -112,6 +118,25 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().send_messages_and_wait_check_tx(tracked_msgs)
     }
 
+    fn estimate_fee(&self, tracked_msgs: TrackedMsgs) -> Result<Vec<RawCoin>, Error> {
+        self.inner().estimate_fee(tracked_msgs)
+    }
+
+    fn query_tx_fee(&self, tx_hash: QueryTxHash) -> Result<Vec<RawCoin>, Error> {
+        self.inner().query_tx_fee(tx_hash)
+    }
+
+    fn build_ibc_shielding(&self, request: IbcShieldingRequest) -> Result<IbcShielding, Error> {
+        self.inner().build_ibc_shielding(request)
+    }
+
+    fn send_shielded_transfer(
+        &self,
+        request: ShieldedTransferRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.inner().send_shielded_transfer(request)
+    }
+
     fn get_signer(&self) -> Result<Signer, Error> {
         self.inner().get_signer()
     }
@@ -132,6 +157,10 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().version_specs()
     }
 
+    fn capabilities(&self) -> Result<ChainCapabilities, Error> {
+        self.inner().capabilities()
+    }
+
     fn query_balance(
         &self,
         key_name: Option<String>,
@@ -148,6 +177,18 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().query_denom_trace(hash)
     }
 
+    fn query_class_trace(&self, hash: String) -> Result<ClassTrace, Error> {
+        self.inner().query_class_trace(hash)
+    }
+
+    fn query_interchain_account(
+        &self,
+        owner: Signer,
+        connection_id: ConnectionId,
+    ) -> Result<String, Error> {
+        self.inner().query_interchain_account(owner, connection_id)
+    }
+
     fn query_application_status(&self) -> Result<ChainStatus, Error> {
         self.inner().query_application_status()
     }
@@ -468,6 +509,57 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().query_packet_acknowledgements(request)
     }
 
+    fn build_packet_v2_proofs(
+        &self,
+        packet_type: PacketMsgTypeV2,
+        client_id: &ClientId,
+        sequence: Sequence,
+        height: Height,
+    ) -> Result<Proofs, Error> {
+        self.inner()
+            .build_packet_v2_proofs(packet_type, client_id, sequence, height)
+    }
+
+    fn query_packet_commitment_v2(
+        &self,
+        request: QueryPacketCommitmentV2Request,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.inner()
+            .query_packet_commitment_v2(request, include_proof)
+    }
+
+    fn query_packet_receipt_v2(
+        &self,
+        request: QueryPacketReceiptV2Request,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.inner().query_packet_receipt_v2(request, include_proof)
+    }
+
+    fn query_packet_acknowledgement_v2(
+        &self,
+        request: QueryPacketAcknowledgementV2Request,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.inner()
+            .query_packet_acknowledgement_v2(request, include_proof)
+    }
+
+    fn query_packet_commitments_v2(
+        &self,
+        request: QueryPacketCommitmentsV2Request,
+    ) -> Result<(Vec<Sequence>, Height), Error> {
+        self.inner().query_packet_commitments_v2(request)
+    }
+
+    fn query_packet_events_v2(
+        &self,
+        request: QueryPacketEventDataV2Request,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.inner().query_packet_events_v2(request)
+    }
+
     fn query_unreceived_acknowledgements(
         &self,
         request: QueryUnreceivedAcksRequest,
@@ -517,6 +609,14 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner.query_incentivized_packet(request)
     }
 
+    fn query_incentivized_packets(
+        &self,
+        channel_id: ChannelId,
+        port_id: PortId,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error> {
+        self.inner.query_incentivized_packets(channel_id, port_id)
+    }
+
     fn query_consumer_chains(&self) -> Result<Vec<ConsumerChain>, Error> {
         self.inner.query_consumer_chains()
     }
@@ -0,0 +1,1798 @@
+//! An in-memory chain for testing the relayer without running any node.
+//!
+//! A [`MockChain`] keeps a simulated IBC store, produces blocks signed by a
+//! fixed validator set and emits the IBC events of each block to its
+//! subscribers. Since it implements [`ChainEndpoint`], it can be spawned with
+//! [`ChainRuntime::spawn_endpoint`](crate::chain::runtime::ChainRuntime::spawn_endpoint)
+//! and driven through a regular [`ChainHandle`](crate::chain::handle::ChainHandle),
+//! so that clients, connections, channels, links and the supervisor can be
+//! exercised in plain unit tests.
+//!
+//! The [`MockController`] of a chain injects latency and faults into the
+//! requests made by the relayer: dropped transactions, account sequence
+//! mismatches and RPC timeouts.
+
+use alloc::collections::BTreeMap;
+use alloc::sync::{Arc, Weak};
+use core::str::FromStr;
+use core::time::Duration;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::thread;
+
+use hdpath::StandardHDPath;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::applications::fee::v1::{
+    QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
+};
+use ibc_proto::ibc::core::channel::v1::{QueryUpgradeErrorRequest, QueryUpgradeRequest};
+use ibc_proto::ics23::commitment_proof::Proof;
+use ibc_proto::ics23::{CommitmentProof, ExistenceProof};
+use ibc_relayer_types::applications::ics28_ccv::msgs::{ConsumerChain, ConsumerId};
+use ibc_relayer_types::applications::ics29_fee::packet_fee::IdentifiedPacketFees;
+use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
+use ibc_relayer_types::applications::transfer::RawCoin;
+use ibc_relayer_types::clients::ics07_tendermint::client_state::{
+    AllowUpdate, ClientState as TmClientState,
+};
+use ibc_relayer_types::clients::ics07_tendermint::consensus_state::ConsensusState as TmConsensusState;
+use ibc_relayer_types::clients::ics07_tendermint::header::Header as TmHeader;
+use ibc_relayer_types::core::ics02_client::events::{NewBlock, UpdateClient};
+use ibc_relayer_types::core::ics03_connection::connection::{
+    ConnectionEnd, IdentifiedConnectionEnd,
+};
+use ibc_relayer_types::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd, Ordering};
+use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+use ibc_relayer_types::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
+use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentPrefix;
+use ibc_relayer_types::core::ics23_commitment::merkle::MerkleProof;
+use ibc_relayer_types::core::ics24_host::identifier::{
+    ChainId, ChannelId, ClientId, ConnectionId, PortId,
+};
+use ibc_relayer_types::events::{IbcEvent, WithBlockDataType};
+use ibc_relayer_types::signer::Signer;
+use ibc_relayer_types::Height as ICSHeight;
+use sha2::{Digest, Sha256};
+use tendermint::abci::Code;
+use tendermint::{Hash as TxHash, Time};
+use tendermint_light_client::types::LightBlock;
+use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
+use tendermint_testgen::{Generator, LightBlock as TestgenLightBlock};
+use tokio::runtime::Runtime as TokioRuntime;
+use tracing::debug;
+
+use crate::account::Balance;
+use crate::chain::capabilities::{Capability, ChainCapabilities};
+use crate::chain::client::ClientSettings;
+use crate::chain::cosmos::batch::response_to_tx_sync_result;
+use crate::chain::cosmos::config::CosmosSdkConfig;
+use crate::chain::cosmos::version::Specs as CosmosSpecs;
+use crate::chain::endpoint::{ChainEndpoint, ChainStatus, HealthCheck};
+use crate::chain::handle::Subscription;
+use crate::chain::requests::*;
+use crate::chain::tracking::{TrackedMsgs, TrackingId};
+use crate::chain::version::Specs;
+use crate::client_state::{AnyClientState, IdentifiedAnyClientState};
+use crate::config::error::Error as ConfigError;
+use crate::config::ChainConfig;
+use crate::consensus_state::AnyConsensusState;
+use crate::denom::DenomTrace;
+use crate::error::Error;
+use crate::event::bus::EventBus;
+use crate::event::source::{EventBatch, Result as MonitorResult};
+use crate::event::IbcEventWithHeight;
+use crate::keyring::{KeyRing, Secp256k1KeyPair, SigningKeyPair, Store};
+use crate::misbehaviour::MisbehaviourEvidence;
+use crate::nft_transfer::ClassTrace;
+use crate::transfer::{IbcShielding, IbcShieldingRequest, ShieldedTransferRequest};
+use crate::util::lock::LockExt;
+
+use self::store::{commitment_prefix, HostBlock, MockStore};
+
+pub mod store;
+
+/// The mnemonic of the key the relayer signs its transactions with
+const MNEMONIC: &str =
+    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+const HD_PATH: &str = "m/44'/118'/0'/0/0";
+
+/// The unbonding period reported to the clients of the chain
+const UNBONDING_PERIOD: Duration = Duration::from_secs(21 * 24 * 60 * 60);
+
+/// The ABCI code returned by the Cosmos SDK on an account sequence mismatch
+const SEQUENCE_MISMATCH_CODE: u32 = 32;
+
+/// Settings of a [`MockChain`] which are not part of the chain configuration
+#[derive(Copy, Clone, Debug)]
+pub struct MockConfig {
+    /// Interval at which empty blocks are produced in the background,
+    /// or `None` to only produce blocks when transactions are submitted
+    /// or when requested through the [`MockController`].
+    pub block_time: Option<Duration>,
+
+    /// Delay applied to every request made to the chain
+    pub latency: Duration,
+}
+
+impl Default for MockConfig {
+    fn default() -> Self {
+        Self {
+            block_time: Some(Duration::from_millis(10)),
+            latency: Duration::ZERO,
+        }
+    }
+}
+
+/// Faults injected into the next requests made to the chain
+#[derive(Clone, Debug, Default)]
+struct Faults {
+    dropped_txs: usize,
+    sequence_mismatches: usize,
+    rpc_timeouts: usize,
+}
+
+struct Block {
+    light_block: LightBlock,
+    store: Arc<MockStore>,
+    events: Vec<IbcEventWithHeight>,
+}
+
+struct MockState {
+    chain_id: ChainId,
+    blocks: BTreeMap<u64, Block>,
+    /// The result of the transactions included in a block, by hash
+    txs: HashMap<TxHash, (ICSHeight, Result<Vec<IbcEvent>, String>)>,
+    tx_count: u64,
+    event_bus: EventBus<Arc<MonitorResult<EventBatch>>>,
+    faults: Faults,
+    latency: Duration,
+    halted: bool,
+}
+
+impl MockState {
+    fn new(chain_id: ChainId, latency: Duration) -> Self {
+        let mut state = Self {
+            chain_id,
+            blocks: BTreeMap::new(),
+            txs: HashMap::new(),
+            tx_count: 0,
+            event_bus: EventBus::new(),
+            faults: Faults::default(),
+            latency,
+            halted: false,
+        };
+
+        // Genesis block
+        state.produce_block(None);
+        state
+    }
+
+    fn latest_block(&self) -> &Block {
+        let (_, block) = self
+            .blocks
+            .last_key_value()
+            .expect("the genesis block is always present");
+
+        block
+    }
+
+    fn latest_height(&self) -> ICSHeight {
+        let (height, _) = self
+            .blocks
+            .last_key_value()
+            .expect("the genesis block is always present");
+
+        self.height(*height)
+    }
+
+    fn height(&self, height: u64) -> ICSHeight {
+        ICSHeight::new(self.chain_id.version(), height).expect("block heights start at 1")
+    }
+
+    fn block(&self, height: ICSHeight) -> Result<&Block, Error> {
+        self.blocks
+            .get(&height.revision_height())
+            .ok_or_else(|| Error::query(format!("block at height {height}")))
+    }
+
+    fn store(&self, height: QueryHeight) -> Result<&MockStore, Error> {
+        match height {
+            QueryHeight::Latest => Ok(&self.latest_block().store),
+            QueryHeight::Specific(height) => Ok(&self.block(height)?.store),
+        }
+    }
+
+    /// Produce a new block including the given transaction, if any, and
+    /// broadcast its events to the subscribers of the chain.
+    fn produce_block(&mut self, tx: Option<(TxHash, &[Any])>) -> ICSHeight {
+        let height = self.height(self.blocks.len() as u64 + 1);
+        let time = Time::now();
+        let host = HostBlock {
+            height,
+            timestamp: time.into(),
+        };
+
+        let mut store = self
+            .blocks
+            .last_key_value()
+            .map(|(_, block)| block.store.clone())
+            .unwrap_or_default();
+        let mut events = vec![IbcEventWithHeight::new(
+            IbcEvent::NewBlock(NewBlock::new(height)),
+            height,
+        )];
+
+        if let Some((hash, msgs)) = tx {
+            // Messages of a transaction are applied atomically
+            let mut tx_store = MockStore::clone(&store);
+            let result = msgs.iter().try_fold(vec![], |mut tx_events, msg| {
+                tx_events.extend(tx_store.apply(msg, host)?);
+                Ok(tx_events)
+            });
+
+            if let Ok(tx_events) = &result {
+                store = Arc::new(tx_store);
+                events.extend(
+                    tx_events
+                        .iter()
+                        .map(|event| IbcEventWithHeight::new(event.clone(), height)),
+                );
+            }
+
+            self.txs.insert(hash, (height, result));
+        }
+
+        let light_block = TestgenLightBlock::new_default_with_time_and_chain_id(
+            self.chain_id.to_string(),
+            time,
+            height.revision_height(),
+        )
+        .generate()
+        .expect("failed to generate a light block");
+        let light_block = LightBlock::new(
+            light_block.signed_header,
+            light_block.validators,
+            light_block.next_validators,
+            light_block.provider,
+        );
+
+        self.event_bus.broadcast(Arc::new(Ok(EventBatch {
+            chain_id: self.chain_id.clone(),
+            tracking_id: TrackingId::new_uuid(),
+            height,
+            events: events.clone(),
+        })));
+
+        self.blocks.insert(
+            height.revision_height(),
+            Block {
+                light_block,
+                store,
+                events,
+            },
+        );
+
+        height
+    }
+
+    /// Broadcast a transaction, which is included in a new block unless
+    /// it is dropped or rejected because of an injected fault.
+    fn broadcast_tx(&mut self, msgs: &[Any]) -> Response {
+        self.tx_count += 1;
+
+        let mut hasher = Sha256::new();
+        hasher.update(self.chain_id.as_str());
+        hasher.update(self.tx_count.to_be_bytes());
+        for msg in msgs {
+            hasher.update(&msg.type_url);
+            hasher.update(&msg.value);
+        }
+        let hash = TxHash::Sha256(hasher.finalize().into());
+
+        let mut response = Response {
+            codespace: String::new(),
+            code: Code::Ok,
+            data: Default::default(),
+            log: String::new(),
+            hash,
+        };
+
+        if self.faults.dropped_txs > 0 {
+            self.faults.dropped_txs -= 1;
+            debug!(chain = %self.chain_id, tx_hash = %hash, "dropping tx");
+        } else if self.faults.sequence_mismatches > 0 {
+            self.faults.sequence_mismatches -= 1;
+            response.codespace = "sdk".to_string();
+            response.code = Code::from(SEQUENCE_MISMATCH_CODE);
+            response.log = "account sequence mismatch: incorrect account sequence".to_string();
+        } else {
+            self.produce_block(Some((hash, msgs)));
+        }
+
+        response
+    }
+}
+
+/// Handle to the state of a [`MockChain`], for producing blocks and
+/// injecting faults while the chain is driven by the relayer.
+#[derive(Clone)]
+pub struct MockController {
+    state: Arc<RwLock<MockState>>,
+}
+
+impl MockController {
+    /// Produce an empty block and return its height.
+    pub fn produce_block(&self) -> ICSHeight {
+        self.state.acquire_write().produce_block(None)
+    }
+
+    /// The height of the latest block of the chain.
+    pub fn latest_height(&self) -> ICSHeight {
+        self.state.acquire_read().latest_height()
+    }
+
+    /// A snapshot of the IBC store at the latest height.
+    pub fn store(&self) -> MockStore {
+        MockStore::clone(&self.state.acquire_read().latest_block().store)
+    }
+
+    /// Delay every subsequent request to the chain by `latency`.
+    pub fn set_latency(&self, latency: Duration) {
+        self.state.acquire_write().latency = latency;
+    }
+
+    /// Silently drop the next `count` transactions, which are accepted
+    /// by the chain but never included in a block.
+    pub fn drop_txs(&self, count: usize) {
+        self.state.acquire_write().faults.dropped_txs = count;
+    }
+
+    /// Reject the next `count` transactions with an account sequence mismatch.
+    pub fn mismatch_sequences(&self, count: usize) {
+        self.state.acquire_write().faults.sequence_mismatches = count;
+    }
+
+    /// Fail the next `count` requests to the chain with an RPC timeout.
+    pub fn time_out_requests(&self, count: usize) {
+        self.state.acquire_write().faults.rpc_timeouts = count;
+    }
+}
+
+pub struct MockChain {
+    config: CosmosSdkConfig,
+    keybase: KeyRing<Secp256k1KeyPair>,
+    state: Arc<RwLock<MockState>>,
+}
+
+impl MockChain {
+    /// Create a new chain from the given configuration, starting at a
+    /// genesis block with an empty IBC store.
+    ///
+    /// Only the chain identifier, the key and the client settings are taken
+    /// from `config`, the chain never connects to the configured endpoints.
+    /// When blocks are produced in the background, the maximum block time of
+    /// the configuration is set to the block time, so that the relayer does
+    /// not wait longer than needed between retries.
+    pub fn new(mut config: CosmosSdkConfig, mock_config: MockConfig) -> Result<Self, Error> {
+        if let Some(block_time) = mock_config.block_time {
+            config.max_block_time = block_time;
+        }
+
+        let mut keybase = KeyRing::new_secp256k1(
+            Store::Memory,
+            &config.account_prefix,
+            &config.id,
+            &config.key_store_folder,
+        )
+        .map_err(Error::key_base)?;
+
+        let hd_path = StandardHDPath::from_str(HD_PATH).expect("the HD path is valid");
+        let key = Secp256k1KeyPair::from_mnemonic(
+            MNEMONIC,
+            &hd_path,
+            &config.address_type,
+            &config.account_prefix,
+        )
+        .map_err(Error::key_base)?;
+        keybase
+            .add_key(&config.key_name, key)
+            .map_err(Error::key_base)?;
+
+        let state = Arc::new(RwLock::new(MockState::new(
+            config.id.clone(),
+            mock_config.latency,
+        )));
+
+        if let Some(block_time) = mock_config.block_time {
+            let state = Arc::downgrade(&state);
+            thread::spawn(move || produce_blocks(state, block_time));
+        }
+
+        Ok(Self {
+            config,
+            keybase,
+            state,
+        })
+    }
+
+    pub fn controller(&self) -> MockController {
+        MockController {
+            state: self.state.clone(),
+        }
+    }
+
+    /// Wait for the configured latency, then fail the request if an RPC
+    /// timeout was injected.
+    fn request(&self) -> Result<(), Error> {
+        let latency = self.state.acquire_read().latency;
+        if !latency.is_zero() {
+            thread::sleep(latency);
+        }
+
+        let mut state = self.state.acquire_write();
+        if state.faults.rpc_timeouts > 0 {
+            state.faults.rpc_timeouts -= 1;
+
+            return Err(Error::rpc(
+                self.config.rpc_addr.clone(),
+                tendermint_rpc::Error::timeout(latency),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn query_store<T>(
+        &self,
+        height: QueryHeight,
+        query: impl FnOnce(&MockStore) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        self.request()?;
+
+        let state = self.state.acquire_read();
+        query(state.store(height)?)
+    }
+}
+
+/// Produce an empty block every `block_time`, until the chain is dropped or shut down.
+fn produce_blocks(state: Weak<RwLock<MockState>>, block_time: Duration) {
+    loop {
+        thread::sleep(block_time);
+
+        let Some(state) = state.upgrade() else {
+            return;
+        };
+
+        let mut state = state.acquire_write();
+        if state.halted {
+            return;
+        }
+
+        state.produce_block(None);
+    }
+}
+
+/// A proof for the value at the given path, which is never verified by the counterparty chain.
+fn proof(include_proof: IncludeProof, path: String, value: Vec<u8>) -> Option<MerkleProof> {
+    match include_proof {
+        IncludeProof::Yes => Some(MerkleProof {
+            proofs: vec![CommitmentProof {
+                proof: Some(Proof::Exist(ExistenceProof {
+                    key: path.into_bytes(),
+                    value,
+                    leaf: None,
+                    path: vec![],
+                })),
+            }],
+        }),
+        IncludeProof::No => None,
+    }
+}
+
+fn matches_packet(request: &QueryPacketEventDataRequest, packet: &Packet) -> bool {
+    packet.source_port == request.source_port_id
+        && packet.source_channel == request.source_channel_id
+        && packet.destination_port == request.destination_port_id
+        && packet.destination_channel == request.destination_channel_id
+        && request.sequences.contains(&packet.sequence)
+}
+
+impl ChainEndpoint for MockChain {
+    type LightBlock = LightBlock;
+    type Header = TmHeader;
+    type ConsensusState = TmConsensusState;
+    type ClientState = TmClientState;
+    type Time = Time;
+    type SigningKeyPair = Secp256k1KeyPair;
+
+    fn id(&self) -> &ChainId {
+        &self.config.id
+    }
+
+    fn config(&self) -> ChainConfig {
+        ChainConfig::CosmosSdk(self.config.clone())
+    }
+
+    fn bootstrap(config: ChainConfig, _rt: Arc<TokioRuntime>) -> Result<Self, Error> {
+        #[allow(irrefutable_let_patterns)]
+        let ChainConfig::CosmosSdk(config) = config
+        else {
+            return Err(Error::config(ConfigError::wrong_type()));
+        };
+
+        Self::new(config, MockConfig::default())
+    }
+
+    fn shutdown(self) -> Result<(), Error> {
+        self.state.acquire_write().halted = true;
+
+        Ok(())
+    }
+
+    fn health_check(&mut self) -> Result<HealthCheck, Error> {
+        Ok(HealthCheck::Healthy)
+    }
+
+    fn subscribe(&mut self) -> Result<Subscription, Error> {
+        Ok(self.state.acquire_write().event_bus.subscribe())
+    }
+
+    fn keybase(&self) -> &KeyRing<Self::SigningKeyPair> {
+        &self.keybase
+    }
+
+    fn keybase_mut(&mut self) -> &mut KeyRing<Self::SigningKeyPair> {
+        &mut self.keybase
+    }
+
+    fn get_signer(&self) -> Result<Signer, Error> {
+        let key = self.get_key()?;
+        Ok(Signer::from_str(&key.account()).expect("the account is not empty"))
+    }
+
+    fn get_key(&self) -> Result<Self::SigningKeyPair, Error> {
+        self.keybase
+            .get_key(&self.config.key_name)
+            .map_err(|e| Error::key_not_found(self.config.key_name.clone(), e))
+    }
+
+    fn version_specs(&self) -> Result<Specs, Error> {
+        Ok(Specs::Cosmos(CosmosSpecs {
+            cosmos_sdk: None,
+            ibc_go: None,
+            consensus: None,
+        }))
+    }
+
+    fn capabilities(&self) -> ChainCapabilities {
+        ChainCapabilities {
+            fees: false,
+            cross_chain_queries: false,
+            channel_upgrades: false,
+            ccv: false,
+            balances: false,
+            push_events: true,
+            denom_traces: false,
+            interchain_accounts: false,
+            nft_transfers: false,
+            ibc_v2: false,
+            fee_estimation: false,
+            shielded_transfers: false,
+        }
+    }
+
+    fn send_messages_and_wait_commit(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.request()?;
+
+        let msgs = tracked_msgs.msgs;
+        if msgs.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut state = self.state.acquire_write();
+        let response = state.broadcast_tx(&msgs);
+        if response.code.is_err() {
+            return Ok(response_to_tx_sync_result(self.id(), msgs.len(), response).events);
+        }
+
+        match state.txs.get(&response.hash) {
+            None => Err(Error::tx_no_confirmation()),
+            Some((height, Ok(events))) => Ok(events
+                .iter()
+                .map(|event| IbcEventWithHeight::new(event.clone(), *height))
+                .collect()),
+            Some((height, Err(log))) => {
+                let event = IbcEvent::ChainError(format!(
+                    "deliver_tx for {} reports error: code={:?}, log={:?}",
+                    response.hash, response.code, log
+                ));
+                Ok(vec![IbcEventWithHeight::new(event, *height); msgs.len()])
+            }
+        }
+    }
+
+    fn send_messages_and_wait_check_tx(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<Response>, Error> {
+        self.request()?;
+
+        if tracked_msgs.msgs.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let response = self.state.acquire_write().broadcast_tx(&tracked_msgs.msgs);
+
+        Ok(vec![response])
+    }
+
+    fn estimate_fee(&mut self, _tracked_msgs: TrackedMsgs) -> Result<Vec<RawCoin>, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::FeeEstimation,
+        ))
+    }
+
+    fn query_tx_fee(&self, _tx_hash: QueryTxHash) -> Result<Vec<RawCoin>, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::FeeEstimation,
+        ))
+    }
+
+    fn build_ibc_shielding(&self, _request: IbcShieldingRequest) -> Result<IbcShielding, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::ShieldedTransfers,
+        ))
+    }
+
+    fn send_shielded_transfer(
+        &mut self,
+        _request: ShieldedTransferRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::ShieldedTransfers,
+        ))
+    }
+
+    fn verify_header(
+        &mut self,
+        _trusted: ICSHeight,
+        target: ICSHeight,
+        _client_state: &AnyClientState,
+    ) -> Result<Self::LightBlock, Error> {
+        self.request()?;
+
+        Ok(self.state.acquire_read().block(target)?.light_block.clone())
+    }
+
+    fn check_misbehaviour(
+        &mut self,
+        _update: &UpdateClient,
+        _client_state: &AnyClientState,
+    ) -> Result<Option<MisbehaviourEvidence>, Error> {
+        Ok(None)
+    }
+
+    fn query_balance(
+        &self,
+        _key_name: Option<&str>,
+        _denom: Option<&str>,
+    ) -> Result<Balance, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::Balances,
+        ))
+    }
+
+    fn query_all_balances(&self, _key_name: Option<&str>) -> Result<Vec<Balance>, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::Balances,
+        ))
+    }
+
+    fn query_denom_trace(&self, _hash: String) -> Result<DenomTrace, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::DenomTraces,
+        ))
+    }
+
+    fn query_class_trace(&self, _hash: String) -> Result<ClassTrace, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::NftTransfers,
+        ))
+    }
+
+    fn query_interchain_account(
+        &self,
+        _owner: Signer,
+        _connection_id: ConnectionId,
+    ) -> Result<String, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::InterchainAccounts,
+        ))
+    }
+
+    fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error> {
+        Ok(commitment_prefix())
+    }
+
+    fn query_application_status(&self) -> Result<ChainStatus, Error> {
+        self.request()?;
+
+        let state = self.state.acquire_read();
+        let header = &state.latest_block().light_block.signed_header.header;
+
+        Ok(ChainStatus {
+            height: state.latest_height(),
+            timestamp: header.time.into(),
+        })
+    }
+
+    fn query_clients(
+        &self,
+        _request: QueryClientStatesRequest,
+    ) -> Result<Vec<IdentifiedAnyClientState>, Error> {
+        self.query_store(QueryHeight::Latest, |store| {
+            Ok(store
+                .clients
+                .iter()
+                .map(|(client_id, client_state)| IdentifiedAnyClientState {
+                    client_id: client_id.clone(),
+                    client_state: client_state.clone(),
+                })
+                .collect())
+        })
+    }
+
+    fn query_client_state(
+        &self,
+        request: QueryClientStateRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(AnyClientState, Option<MerkleProof>), Error> {
+        self.query_store(request.height, |store| {
+            let client_state = store
+                .clients
+                .get(&request.client_id)
+                .ok_or_else(|| Error::query(format!("client state of {}", request.client_id)))?;
+            let path = format!("clients/{}/clientState", request.client_id);
+
+            Ok((client_state.clone(), proof(include_proof, path, vec![])))
+        })
+    }
+
+    fn query_consensus_state(
+        &self,
+        request: QueryConsensusStateRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(AnyConsensusState, Option<MerkleProof>), Error> {
+        self.query_store(request.query_height, |store| {
+            let consensus_state = store
+                .consensus_states
+                .get(&(request.client_id.clone(), request.consensus_height))
+                .ok_or_else(|| {
+                    Error::query(format!(
+                        "consensus state of {} at height {}",
+                        request.client_id, request.consensus_height
+                    ))
+                })?;
+            let path = format!(
+                "clients/{}/consensusStates/{}",
+                request.client_id, request.consensus_height
+            );
+
+            Ok((consensus_state.clone(), proof(include_proof, path, vec![])))
+        })
+    }
+
+    fn query_consensus_state_heights(
+        &self,
+        request: QueryConsensusStateHeightsRequest,
+    ) -> Result<Vec<ICSHeight>, Error> {
+        self.query_store(QueryHeight::Latest, |store| {
+            Ok(store
+                .consensus_states
+                .keys()
+                .filter(|(client_id, _)| client_id == &request.client_id)
+                .map(|(_, height)| *height)
+                .collect())
+        })
+    }
+
+    fn query_upgraded_client_state(
+        &self,
+        _request: QueryUpgradedClientStateRequest,
+    ) -> Result<(AnyClientState, MerkleProof), Error> {
+        Err(Error::empty_upgraded_client_state())
+    }
+
+    fn query_upgraded_consensus_state(
+        &self,
+        _request: QueryUpgradedConsensusStateRequest,
+    ) -> Result<(AnyConsensusState, MerkleProof), Error> {
+        Err(Error::empty_upgraded_client_state())
+    }
+
+    fn query_connections(
+        &self,
+        _request: QueryConnectionsRequest,
+    ) -> Result<Vec<IdentifiedConnectionEnd>, Error> {
+        self.query_store(QueryHeight::Latest, |store| {
+            Ok(store
+                .connections
+                .iter()
+                .map(|(connection_id, connection_end)| {
+                    IdentifiedConnectionEnd::new(connection_id.clone(), connection_end.clone())
+                })
+                .collect())
+        })
+    }
+
+    fn query_client_connections(
+        &self,
+        request: QueryClientConnectionsRequest,
+    ) -> Result<Vec<ConnectionId>, Error> {
+        self.query_store(QueryHeight::Latest, |store| {
+            Ok(store
+                .connections
+                .iter()
+                .filter(|(_, connection_end)| connection_end.client_id() == &request.client_id)
+                .map(|(connection_id, _)| connection_id.clone())
+                .collect())
+        })
+    }
+
+    fn query_connection(
+        &self,
+        request: QueryConnectionRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ConnectionEnd, Option<MerkleProof>), Error> {
+        self.query_store(request.height, |store| {
+            let connection_end = store
+                .connections
+                .get(&request.connection_id)
+                .ok_or_else(|| Error::connection_not_found(request.connection_id.clone()))?;
+            let path = format!("connections/{}", request.connection_id);
+
+            Ok((connection_end.clone(), proof(include_proof, path, vec![])))
+        })
+    }
+
+    fn query_connection_channels(
+        &self,
+        request: QueryConnectionChannelsRequest,
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+        self.query_store(QueryHeight::Latest, |store| {
+            Ok(store
+                .channels
+                .iter()
+                .filter(|(_, channel_end)| {
+                    channel_end.connection_hops().first() == Some(&request.connection_id)
+                })
+                .map(|((port_id, channel_id), channel_end)| {
+                    IdentifiedChannelEnd::new(
+                        port_id.clone(),
+                        channel_id.clone(),
+                        channel_end.clone(),
+                    )
+                })
+                .collect())
+        })
+    }
+
+    fn query_channels(
+        &self,
+        _request: QueryChannelsRequest,
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+        self.query_store(QueryHeight::Latest, |store| {
+            Ok(store
+                .channels
+                .iter()
+                .map(|((port_id, channel_id), channel_end)| {
+                    IdentifiedChannelEnd::new(
+                        port_id.clone(),
+                        channel_id.clone(),
+                        channel_end.clone(),
+                    )
+                })
+                .collect())
+        })
+    }
+
+    fn query_channel(
+        &self,
+        request: QueryChannelRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ChannelEnd, Option<MerkleProof>), Error> {
+        self.query_store(request.height, |store| {
+            let channel_end = store
+                .channels
+                .get(&(request.port_id.clone(), request.channel_id.clone()))
+                .ok_or_else(|| {
+                    Error::query(format!(
+                        "channel {} on port {}",
+                        request.channel_id, request.port_id
+                    ))
+                })?;
+            let path = format!(
+                "channelEnds/ports/{}/channels/{}",
+                request.port_id, request.channel_id
+            );
+
+            Ok((channel_end.clone(), proof(include_proof, path, vec![])))
+        })
+    }
+
+    fn query_channel_client_state(
+        &self,
+        request: QueryChannelClientStateRequest,
+    ) -> Result<Option<IdentifiedAnyClientState>, Error> {
+        self.query_store(QueryHeight::Latest, |store| {
+            let client_state = store
+                .channels
+                .get(&(request.port_id, request.channel_id))
+                .and_then(|channel_end| channel_end.connection_hops().first())
+                .and_then(|connection_id| store.connections.get(connection_id))
+                .and_then(|connection_end| {
+                    let client_id = connection_end.client_id();
+                    let client_state = store.clients.get(client_id)?;
+
+                    Some(IdentifiedAnyClientState {
+                        client_id: client_id.clone(),
+                        client_state: client_state.clone(),
+                    })
+                });
+
+            Ok(client_state)
+        })
+    }
+
+    fn query_packet_commitment(
+        &self,
+        request: QueryPacketCommitmentRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.query_store(request.height, |store| {
+            let commitment = store
+                .commitments
+                .get(&(
+                    request.port_id.clone(),
+                    request.channel_id.clone(),
+                    request.sequence,
+                ))
+                .cloned()
+                .unwrap_or_default();
+            let path = format!(
+                "commitments/ports/{}/channels/{}/sequences/{}",
+                request.port_id, request.channel_id, request.sequence
+            );
+
+            Ok((commitment.clone(), proof(include_proof, path, commitment)))
+        })
+    }
+
+    fn query_packet_commitments(
+        &self,
+        request: QueryPacketCommitmentsRequest,
+    ) -> Result<(Vec<Sequence>, ICSHeight), Error> {
+        self.request()?;
+
+        let state = self.state.acquire_read();
+        let store = state.store(request.query_height)?;
+        let sequences = store
+            .commitments
+            .keys()
+            .filter(|(port_id, channel_id, _)| {
+                port_id == &request.port_id && channel_id == &request.channel_id
+            })
+            .map(|(_, _, sequence)| *sequence)
+            .collect();
+        let height = match request.query_height {
+            QueryHeight::Latest => state.latest_height(),
+            QueryHeight::Specific(height) => height,
+        };
+
+        Ok((sequences, height))
+    }
+
+    fn query_packet_receipt(
+        &self,
+        request: QueryPacketReceiptRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.query_store(request.height, |store| {
+            let key = (
+                request.port_id.clone(),
+                request.channel_id.clone(),
+                request.sequence,
+            );
+            let receipt = if store.receipts.contains(&key) {
+                vec![1]
+            } else {
+                vec![]
+            };
+            let path = format!(
+                "receipts/ports/{}/channels/{}/sequences/{}",
+                request.port_id, request.channel_id, request.sequence
+            );
+
+            Ok((receipt.clone(), proof(include_proof, path, receipt)))
+        })
+    }
+
+    fn query_unreceived_packets(
+        &self,
+        request: QueryUnreceivedPacketsRequest,
+    ) -> Result<Vec<Sequence>, Error> {
+        self.query_store(QueryHeight::Latest, |store| {
+            let channel_key = (request.port_id, request.channel_id);
+            let next_sequence_recv = store.next_sequence_recv.get(&channel_key);
+            let ordered = store
+                .channels
+                .get(&channel_key)
+                .is_some_and(|channel_end| channel_end.ordering() == &Ordering::Ordered);
+            let (port_id, channel_id) = channel_key;
+
+            Ok(request
+                .packet_commitment_sequences
+                .into_iter()
+                .filter(|sequence| {
+                    if ordered {
+                        next_sequence_recv.map_or(true, |next| sequence >= next)
+                    } else {
+                        !store
+                            .receipts
+                            .contains(&(port_id.clone(), channel_id.clone(), *sequence))
+                    }
+                })
+                .collect())
+        })
+    }
+
+    fn query_packet_acknowledgement(
+        &self,
+        request: QueryPacketAcknowledgementRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.query_store(request.height, |store| {
+            let ack = store
+                .acks
+                .get(&(
+                    request.port_id.clone(),
+                    request.channel_id.clone(),
+                    request.sequence,
+                ))
+                .cloned()
+                .unwrap_or_default();
+            let path = format!(
+                "acks/ports/{}/channels/{}/sequences/{}",
+                request.port_id, request.channel_id, request.sequence
+            );
+
+            Ok((ack.clone(), proof(include_proof, path, ack)))
+        })
+    }
+
+    fn query_packet_acknowledgements(
+        &self,
+        request: QueryPacketAcknowledgementsRequest,
+    ) -> Result<(Vec<Sequence>, ICSHeight), Error> {
+        self.request()?;
+
+        let state = self.state.acquire_read();
+        let store = state.store(QueryHeight::Latest)?;
+        let sequences = request
+            .packet_commitment_sequences
+            .into_iter()
+            .filter(|sequence| {
+                store.acks.contains_key(&(
+                    request.port_id.clone(),
+                    request.channel_id.clone(),
+                    *sequence,
+                ))
+            })
+            .collect();
+
+        Ok((sequences, state.latest_height()))
+    }
+
+    fn query_packet_commitment_v2(
+        &self,
+        _request: QueryPacketCommitmentV2Request,
+        _include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::IbcV2,
+        ))
+    }
+
+    fn query_packet_receipt_v2(
+        &self,
+        _request: QueryPacketReceiptV2Request,
+        _include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::IbcV2,
+        ))
+    }
+
+    fn query_packet_acknowledgement_v2(
+        &self,
+        _request: QueryPacketAcknowledgementV2Request,
+        _include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::IbcV2,
+        ))
+    }
+
+    fn query_packet_commitments_v2(
+        &self,
+        _request: QueryPacketCommitmentsV2Request,
+    ) -> Result<(Vec<Sequence>, ICSHeight), Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::IbcV2,
+        ))
+    }
+
+    fn query_packet_events_v2(
+        &self,
+        _request: QueryPacketEventDataV2Request,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::IbcV2,
+        ))
+    }
+
+    fn query_unreceived_acknowledgements(
+        &self,
+        request: QueryUnreceivedAcksRequest,
+    ) -> Result<Vec<Sequence>, Error> {
+        self.query_store(QueryHeight::Latest, |store| {
+            Ok(request
+                .packet_ack_sequences
+                .into_iter()
+                .filter(|sequence| {
+                    store.commitments.contains_key(&(
+                        request.port_id.clone(),
+                        request.channel_id.clone(),
+                        *sequence,
+                    ))
+                })
+                .collect())
+        })
+    }
+
+    fn query_next_sequence_receive(
+        &self,
+        request: QueryNextSequenceReceiveRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Sequence, Option<MerkleProof>), Error> {
+        self.query_store(request.height, |store| {
+            let sequence = store
+                .next_sequence_recv
+                .get(&(request.port_id.clone(), request.channel_id.clone()))
+                .copied()
+                .unwrap_or_else(|| Sequence::from(1));
+            let path = format!(
+                "nextSequenceRecv/ports/{}/channels/{}",
+                request.port_id, request.channel_id
+            );
+            let value = sequence.as_u64().to_be_bytes().to_vec();
+
+            Ok((sequence, proof(include_proof, path, value)))
+        })
+    }
+
+    fn query_txs(&self, request: QueryTxRequest) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.request()?;
+
+        let state = self.state.acquire_read();
+
+        match request {
+            QueryTxRequest::Client(request) => {
+                let max_height = match request.query_height {
+                    QueryHeight::Latest => state.latest_height(),
+                    QueryHeight::Specific(height) => height,
+                };
+
+                let event = state
+                    .blocks
+                    .range(..=max_height.revision_height())
+                    .flat_map(|(_, block)| block.events.iter())
+                    .find(|event| match (&event.event, &request.event_id) {
+                        (IbcEvent::CreateClient(create), &WithBlockDataType::CreateClient) => {
+                            create.client_id() == &request.client_id
+                                && create.0.consensus_height == request.consensus_height
+                        }
+                        (IbcEvent::UpdateClient(update), &WithBlockDataType::UpdateClient) => {
+                            update.client_id() == &request.client_id
+                                && update.consensus_height() == request.consensus_height
+                        }
+                        _ => false,
+                    });
+
+                Ok(event.cloned().into_iter().collect())
+            }
+
+            QueryTxRequest::Transaction(QueryTxHash(hash)) => match state.txs.get(&hash) {
+                None => Ok(vec![]),
+                Some((height, Ok(events))) => Ok(events
+                    .iter()
+                    .map(|event| IbcEventWithHeight::new(event.clone(), *height))
+                    .collect()),
+                Some((height, Err(log))) => {
+                    let event = IbcEvent::ChainError(format!(
+                        "deliver_tx for {hash} reports error: code={:?}, log={log:?}",
+                        Code::from(1)
+                    ));
+                    Ok(vec![IbcEventWithHeight::new(event, *height)])
+                }
+            },
+        }
+    }
+
+    fn query_packet_events(
+        &self,
+        request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.request()?;
+
+        let state = self.state.acquire_read();
+        let blocks = match request.height {
+            Qualified::SmallerEqual(QueryHeight::Latest) => state.blocks.range(..),
+            Qualified::SmallerEqual(QueryHeight::Specific(height)) => {
+                state.blocks.range(..=height.revision_height())
+            }
+            Qualified::Equal(QueryHeight::Latest) => {
+                let height = state.latest_height().revision_height();
+                state.blocks.range(height..=height)
+            }
+            Qualified::Equal(QueryHeight::Specific(height)) => {
+                let height = height.revision_height();
+                state.blocks.range(height..=height)
+            }
+        };
+
+        // At most one event for each sequence, as when querying the events of a Cosmos chain
+        let mut events: Vec<IbcEventWithHeight> = vec![];
+        for event in blocks.flat_map(|(_, block)| block.events.iter()) {
+            let packet = match (&event.event, &request.event_id) {
+                (IbcEvent::SendPacket(send), &WithBlockDataType::SendPacket) => &send.packet,
+                (IbcEvent::WriteAcknowledgement(ack), &WithBlockDataType::WriteAck) => &ack.packet,
+                _ => continue,
+            };
+
+            let seen = events
+                .iter()
+                .any(|seen| seen.event.packet().map(|p| p.sequence) == Some(packet.sequence));
+
+            if matches_packet(&request, packet) && !seen {
+                events.push(event.clone());
+            }
+        }
+
+        Ok(events)
+    }
+
+    fn query_host_consensus_state(
+        &self,
+        request: QueryHostConsensusStateRequest,
+    ) -> Result<Self::ConsensusState, Error> {
+        self.request()?;
+
+        let state = self.state.acquire_read();
+        let block = match request.height {
+            QueryHeight::Latest => state.latest_block(),
+            QueryHeight::Specific(height) => state.block(height)?,
+        };
+
+        Ok(TmConsensusState::from(
+            block.light_block.signed_header.header.clone(),
+        ))
+    }
+
+    fn build_client_state(
+        &self,
+        height: ICSHeight,
+        settings: ClientSettings,
+    ) -> Result<Self::ClientState, Error> {
+        let ClientSettings::Tendermint(settings) = settings;
+        let trusting_period = settings.trusting_period.unwrap_or_else(|| {
+            self.config
+                .trusting_period
+                .unwrap_or(2 * UNBONDING_PERIOD / 3)
+        });
+
+        TmClientState::new(
+            self.id().clone(),
+            settings.trust_threshold,
+            trusting_period,
+            UNBONDING_PERIOD,
+            settings.max_clock_drift,
+            height,
+            self.config.proof_specs.clone().unwrap_or_default(),
+            vec!["upgrade".to_string(), "upgradedIBCState".to_string()],
+            AllowUpdate {
+                after_expiry: true,
+                after_misbehaviour: true,
+            },
+        )
+        .map_err(Error::ics07)
+    }
+
+    fn build_consensus_state(
+        &self,
+        light_block: Self::LightBlock,
+    ) -> Result<Self::ConsensusState, Error> {
+        Ok(TmConsensusState::from(light_block.signed_header.header))
+    }
+
+    fn build_header(
+        &mut self,
+        trusted_height: ICSHeight,
+        target_height: ICSHeight,
+        _client_state: &AnyClientState,
+    ) -> Result<(Self::Header, Vec<Self::Header>), Error> {
+        self.request()?;
+
+        let state = self.state.acquire_read();
+        let target = &state.block(target_height)?.light_block;
+        let trusted = &state.block(trusted_height)?.light_block;
+
+        let header = TmHeader {
+            signed_header: target.signed_header.clone(),
+            validator_set: target.validators.clone(),
+            trusted_height,
+            trusted_validator_set: trusted.next_validators.clone(),
+        };
+
+        Ok((header, vec![]))
+    }
+
+    fn maybe_register_counterparty_payee(
+        &mut self,
+        _channel_id: &ChannelId,
+        _port_id: &PortId,
+        _counterparty_payee: &Signer,
+    ) -> Result<(), Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::Fees,
+        ))
+    }
+
+    fn cross_chain_query(
+        &self,
+        _requests: Vec<CrossChainQueryRequest>,
+    ) -> Result<Vec<CrossChainQueryResponse>, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::CrossChainQueries,
+        ))
+    }
+
+    fn query_incentivized_packet(
+        &self,
+        _request: QueryIncentivizedPacketRequest,
+    ) -> Result<QueryIncentivizedPacketResponse, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::Fees,
+        ))
+    }
+
+    fn query_incentivized_packets(
+        &self,
+        _channel_id: ChannelId,
+        _port_id: PortId,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::Fees,
+        ))
+    }
+
+    fn query_consumer_chains(&self) -> Result<Vec<ConsumerChain>, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::Ccv,
+        ))
+    }
+
+    fn query_upgrade(
+        &self,
+        _request: QueryUpgradeRequest,
+        _height: ICSHeight,
+        _include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::ChannelUpgrades,
+        ))
+    }
+
+    fn query_upgrade_error(
+        &self,
+        _request: QueryUpgradeErrorRequest,
+        _height: ICSHeight,
+        _include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::ChannelUpgrades,
+        ))
+    }
+
+    fn query_ccv_consumer_id(&self, _client_id: ClientId) -> Result<ConsumerId, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::Ccv,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Instant;
+
+    use ibc_proto::cosmos::base::v1beta1::Coin;
+    use ibc_relayer_types::applications::transfer::msgs::transfer::MsgTransfer;
+    use ibc_relayer_types::core::ics02_client::msgs::create_client::MsgCreateClient;
+    use ibc_relayer_types::core::ics04_channel::timeout::TimeoutHeight;
+    use ibc_relayer_types::core::ics04_channel::version::Version;
+    use ibc_relayer_types::timestamp::Timestamp;
+    use ibc_relayer_types::tx_msg::Msg;
+
+    use crate::chain::handle::{BaseChainHandle, ChainHandle};
+    use crate::chain::runtime::ChainRuntime;
+    use crate::channel::Channel;
+    use crate::config::default;
+    use crate::config::Config;
+    use crate::connection::Connection;
+    use crate::error::ErrorDetail;
+    use crate::foreign_client::{CreateOptions, ForeignClient};
+    use crate::link::{Link, LinkParameters};
+    use crate::registry::SharedRegistry;
+    use crate::supervisor::{spawn_supervisor, SupervisorOptions};
+
+    fn config(chain_id: &str) -> CosmosSdkConfig {
+        toml::from_str(&format!(
+            r#"
+            id = '{chain_id}'
+            rpc_addr = 'http://127.0.0.1:26657'
+            grpc_addr = 'http://127.0.0.1:9090'
+            event_source = {{ mode = 'push', url = 'ws://127.0.0.1:26657/websocket', batch_delay = '10ms' }}
+            account_prefix = 'cosmos'
+            key_name = 'relayer'
+            store_prefix = 'ibc'
+            gas_price = {{ price = 0.0, denom = 'stake' }}
+            "#
+        ))
+        .unwrap()
+    }
+
+    /// A chain which only produces blocks when transactions are submitted
+    fn manual_chain(chain_id: &str) -> MockChain {
+        let mock_config = MockConfig {
+            block_time: None,
+            ..MockConfig::default()
+        };
+
+        MockChain::new(config(chain_id), mock_config).unwrap()
+    }
+
+    fn spawn_chain(chain_id: &str, rt: &Arc<TokioRuntime>) -> BaseChainHandle {
+        let chain = MockChain::new(config(chain_id), MockConfig::default()).unwrap();
+        ChainRuntime::<MockChain>::spawn_endpoint(chain, rt.clone())
+    }
+
+    fn create_client_msg(host: &MockChain, src: &mut MockChain) -> TrackedMsgs {
+        let height = src.query_application_status().unwrap().height;
+        let settings = ClientSettings::for_create_command(
+            CreateOptions::default(),
+            &src.config(),
+            &host.config(),
+        );
+        let client_state = src.build_client_state(height, settings).unwrap();
+        let light_block = src
+            .verify_header(height, height, &client_state.clone().into())
+            .unwrap();
+        let consensus_state = src.build_consensus_state(light_block).unwrap();
+
+        let msg = MsgCreateClient::new(
+            AnyClientState::from(client_state).into(),
+            AnyConsensusState::from(consensus_state).into(),
+            host.get_signer().unwrap(),
+        )
+        .unwrap();
+
+        TrackedMsgs::new_static(vec![msg.to_any()], "create client")
+    }
+
+    #[test]
+    fn create_client() {
+        let mut host = manual_chain("ibc-0");
+        let mut src = manual_chain("ibc-1");
+        let msgs = create_client_msg(&host, &mut src);
+
+        let events = host.send_messages_and_wait_commit(msgs).unwrap();
+        assert!(matches!(
+            events.as_slice(),
+            [IbcEventWithHeight {
+                event: IbcEvent::CreateClient(_),
+                ..
+            }]
+        ));
+
+        let clients = host
+            .query_clients(QueryClientStatesRequest { pagination: None })
+            .unwrap();
+        assert_eq!(clients.len(), 1);
+        assert_eq!(clients[0].client_state.chain_id(), src.id().clone());
+    }
+
+    #[test]
+    fn dropped_tx_is_never_committed() {
+        let mut host = manual_chain("ibc-0");
+        let mut src = manual_chain("ibc-1");
+        let msgs = create_client_msg(&host, &mut src);
+        host.controller().drop_txs(2);
+
+        let error = host
+            .send_messages_and_wait_commit(msgs.clone())
+            .unwrap_err();
+        assert!(matches!(error.detail(), ErrorDetail::TxNoConfirmation(_)));
+
+        let responses = host.send_messages_and_wait_check_tx(msgs).unwrap();
+        assert!(responses[0].code.is_ok());
+
+        let events = host
+            .query_txs(QueryTxRequest::Transaction(QueryTxHash(responses[0].hash)))
+            .unwrap();
+        assert!(events.is_empty());
+        assert!(host.controller().store().clients.is_empty());
+    }
+
+    #[test]
+    fn sequence_mismatch_is_reported_as_chain_error() {
+        let mut host = manual_chain("ibc-0");
+        let mut src = manual_chain("ibc-1");
+        let msgs = create_client_msg(&host, &mut src);
+        host.controller().mismatch_sequences(1);
+
+        let events = host.send_messages_and_wait_commit(msgs.clone()).unwrap();
+        assert!(matches!(
+            events.as_slice(),
+            [IbcEventWithHeight {
+                event: IbcEvent::ChainError(_),
+                ..
+            }]
+        ));
+
+        // Only the first transaction is rejected
+        let events = host.send_messages_and_wait_commit(msgs).unwrap();
+        assert!(matches!(
+            events.as_slice(),
+            [IbcEventWithHeight {
+                event: IbcEvent::CreateClient(_),
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn failed_tx_does_not_change_the_store() {
+        let mut host = manual_chain("ibc-0");
+        let mut src = manual_chain("ibc-1");
+        let mut msgs = create_client_msg(&host, &mut src);
+        msgs.msgs.push(Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+            value: vec![],
+        });
+
+        let events = host.send_messages_and_wait_commit(msgs).unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(events
+            .iter()
+            .all(|event| matches!(event.event, IbcEvent::ChainError(_))));
+        assert!(host.controller().store().clients.is_empty());
+    }
+
+    #[test]
+    fn rpc_timeout_fails_the_next_request() {
+        let chain = manual_chain("ibc-0");
+        chain.controller().time_out_requests(1);
+
+        let error = chain.query_application_status().unwrap_err();
+        assert!(matches!(error.detail(), ErrorDetail::Rpc(_)));
+
+        let status = chain.query_application_status().unwrap();
+        assert_eq!(status.height, chain.controller().latest_height());
+    }
+
+    #[test]
+    fn latency_delays_requests() {
+        let chain = manual_chain("ibc-0");
+        chain.controller().set_latency(Duration::from_millis(20));
+
+        let start = Instant::now();
+        chain.query_application_status().unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    /// Open a transfer channel between the two chains and return its identifiers
+    fn open_channel(
+        chain_a: &BaseChainHandle,
+        chain_b: &BaseChainHandle,
+    ) -> (ChannelId, ChannelId) {
+        let client_on_a = ForeignClient::new(chain_a.clone(), chain_b.clone()).unwrap();
+        let client_on_b = ForeignClient::new(chain_b.clone(), chain_a.clone()).unwrap();
+        let connection = Connection::new(client_on_a, client_on_b, Duration::ZERO).unwrap();
+        let channel = Channel::new(
+            connection,
+            Ordering::Unordered,
+            PortId::transfer(),
+            PortId::transfer(),
+            Some(Version::ics20()),
+        )
+        .unwrap();
+
+        (
+            channel.a_channel_id().unwrap().clone(),
+            channel.b_channel_id().unwrap().clone(),
+        )
+    }
+
+    fn transfer(src: &BaseChainHandle, dst: &BaseChainHandle, src_channel_id: &ChannelId) {
+        let msg = MsgTransfer {
+            source_port: PortId::transfer(),
+            source_channel: src_channel_id.clone(),
+            token: Coin {
+                denom: "samoleans".to_string(),
+                amount: "100".to_string(),
+            },
+            tokens: vec![],
+            sender: src.get_signer().unwrap(),
+            receiver: dst.get_signer().unwrap(),
+            timeout_height: TimeoutHeight::no_timeout(),
+            timeout_timestamp: Timestamp::none(),
+            memo: None,
+            forwarding: None,
+        };
+
+        let events = src
+            .send_messages_and_wait_commit(TrackedMsgs::new_static(vec![msg.to_any()], "transfer"))
+            .unwrap();
+        assert!(matches!(events[0].event, IbcEvent::SendPacket(_)));
+    }
+
+    fn link_parameters(src_channel_id: ChannelId) -> LinkParameters {
+        LinkParameters {
+            src_port_id: PortId::transfer(),
+            src_channel_id,
+            max_memo_size: default::ics20_max_memo_size(),
+            max_receiver_size: default::ics20_max_receiver_size(),
+            exclude_src_sequences: vec![],
+        }
+    }
+
+    #[test]
+    fn relay_transfer_with_link() {
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+        let chain_a = spawn_chain("ibc-0", &rt);
+        let chain_b = spawn_chain("ibc-1", &rt);
+        let (channel_a, channel_b) = open_channel(&chain_a, &chain_b);
+
+        transfer(&chain_a, &chain_b, &channel_a);
+
+        let a_to_b = Link::new_from_opts(
+            chain_a.clone(),
+            chain_b.clone(),
+            link_parameters(channel_a.clone()),
+            false,
+            false,
+        )
+        .unwrap();
+        let events = a_to_b
+            .relay_recv_packet_and_timeout_messages(vec![])
+            .unwrap();
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, IbcEvent::WriteAcknowledgement(_))));
+
+        let b_to_a = Link::new_from_opts(
+            chain_b.clone(),
+            chain_a.clone(),
+            link_parameters(channel_b.clone()),
+            false,
+            false,
+        )
+        .unwrap();
+        let events = b_to_a.relay_ack_packet_messages(vec![]).unwrap();
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, IbcEvent::AcknowledgePacket(_))));
+
+        let unreceived = chain_b
+            .query_unreceived_packets(QueryUnreceivedPacketsRequest {
+                port_id: PortId::transfer(),
+                channel_id: channel_b,
+                packet_commitment_sequences: vec![Sequence::from(1)],
+            })
+            .unwrap();
+        assert!(unreceived.is_empty());
+
+        let (commitments, _) = chain_a
+            .query_packet_commitments(QueryPacketCommitmentsRequest {
+                query_height: QueryHeight::Latest,
+                port_id: PortId::transfer(),
+                channel_id: channel_a,
+                pagination: Paginate::All,
+            })
+            .unwrap();
+        assert!(commitments.is_empty());
+
+        chain_a.shutdown().unwrap();
+        chain_b.shutdown().unwrap();
+    }
+
+    #[test]
+    fn relay_transfer_with_supervisor() {
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+        let chain_a = spawn_chain("ibc-0", &rt);
+        let chain_b = spawn_chain("ibc-1", &rt);
+        let (channel_a, _) = open_channel(&chain_a, &chain_b);
+
+        let config = Config {
+            chains: vec![chain_a.config().unwrap(), chain_b.config().unwrap()],
+            ..Config::default()
+        };
+        let registry = SharedRegistry::new(config.clone());
+        registry.write().insert(chain_a.clone());
+        registry.write().insert(chain_b.clone());
+
+        let options = SupervisorOptions {
+            health_check: false,
+            force_full_scan: false,
+        };
+        let supervisor = spawn_supervisor(config, registry, None, options).unwrap();
+
+        transfer(&chain_a, &chain_b, &channel_a);
+
+        // Wait for the packet to be received and acknowledged by the workers
+        let request = QueryPacketCommitmentsRequest {
+            query_height: QueryHeight::Latest,
+            port_id: PortId::transfer(),
+            channel_id: channel_a,
+            pagination: Paginate::All,
+        };
+        let acknowledged = (0..300).any(|_| {
+            thread::sleep(Duration::from_millis(20));
+            let (commitments, _) = chain_a.query_packet_commitments(request.clone()).unwrap();
+            commitments.is_empty()
+        });
+        assert!(acknowledged);
+
+        supervisor.shutdown();
+        chain_a.shutdown().unwrap();
+        chain_b.shutdown().unwrap();
+    }
+}
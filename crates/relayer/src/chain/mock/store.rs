@@ -0,0 +1,683 @@
+//! The IBC store of a [`MockChain`](super::MockChain), along with the handlers
+//! applying the IBC messages submitted to the chain.
+//!
+//! The handlers only check what is needed to keep the store consistent:
+//! proofs, signatures and headers are not verified.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::fmt::Display;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::applications::transfer::v2::FungibleTokenPacketData as RawPacketData;
+use ibc_proto::Protobuf;
+use sha2::{Digest, Sha256};
+
+use ibc_relayer_types::applications::transfer::msgs::transfer::{self, MsgTransfer};
+use ibc_relayer_types::clients::ics07_tendermint::consensus_state::ConsensusState as TmConsensusState;
+use ibc_relayer_types::core::ics02_client::client_state::ClientState;
+use ibc_relayer_types::core::ics02_client::events::{
+    Attributes as ClientAttributes, CreateClient, UpdateClient,
+};
+use ibc_relayer_types::core::ics02_client::header::{AnyHeader, Header};
+use ibc_relayer_types::core::ics02_client::msgs::create_client::{self, MsgCreateClient};
+use ibc_relayer_types::core::ics02_client::msgs::update_client::{self, MsgUpdateClient};
+use ibc_relayer_types::core::ics03_connection::connection::{
+    ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+};
+use ibc_relayer_types::core::ics03_connection::events::{
+    self as connection_events, Attributes as ConnectionAttributes,
+};
+use ibc_relayer_types::core::ics03_connection::msgs::conn_open_ack::{self, MsgConnectionOpenAck};
+use ibc_relayer_types::core::ics03_connection::msgs::conn_open_confirm::{
+    self, MsgConnectionOpenConfirm,
+};
+use ibc_relayer_types::core::ics03_connection::msgs::conn_open_init::{
+    self, MsgConnectionOpenInit,
+};
+use ibc_relayer_types::core::ics03_connection::msgs::conn_open_try::{self, MsgConnectionOpenTry};
+use ibc_relayer_types::core::ics03_connection::version::get_compatible_versions;
+use ibc_relayer_types::core::ics04_channel::channel::{
+    ChannelEnd, Ordering, State as ChannelState, UpgradeState,
+};
+use ibc_relayer_types::core::ics04_channel::events as channel_events;
+use ibc_relayer_types::core::ics04_channel::msgs::acknowledgement::{self, MsgAcknowledgement};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_close_confirm::{
+    self, MsgChannelCloseConfirm,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_close_init::{self, MsgChannelCloseInit};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_open_ack::{self, MsgChannelOpenAck};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_open_confirm::{
+    self, MsgChannelOpenConfirm,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_open_init::{self, MsgChannelOpenInit};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_open_try::{self, MsgChannelOpenTry};
+use ibc_relayer_types::core::ics04_channel::msgs::recv_packet::{self, MsgRecvPacket};
+use ibc_relayer_types::core::ics04_channel::msgs::timeout::{self, MsgTimeout};
+use ibc_relayer_types::core::ics04_channel::msgs::timeout_on_close::{self, MsgTimeoutOnClose};
+use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentPrefix;
+use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::timestamp::Timestamp;
+use ibc_relayer_types::tx_msg::Msg;
+use ibc_relayer_types::Height;
+
+use crate::client_state::AnyClientState;
+use crate::consensus_state::AnyConsensusState;
+
+/// The acknowledgement written for every packet received by the chain
+pub const SUCCESS_ACK: &[u8] = br#"{"result":"AQ=="}"#;
+
+pub type ChannelKey = (PortId, ChannelId);
+pub type PacketKey = (PortId, ChannelId, Sequence);
+
+/// The height and timestamp of the block the messages are applied in
+#[derive(Copy, Clone, Debug)]
+pub struct HostBlock {
+    pub height: Height,
+    pub timestamp: Timestamp,
+}
+
+/// The IBC state of the chain at a given height
+#[derive(Clone, Debug, Default)]
+pub struct MockStore {
+    pub clients: BTreeMap<ClientId, AnyClientState>,
+    pub consensus_states: BTreeMap<(ClientId, Height), AnyConsensusState>,
+    pub connections: BTreeMap<ConnectionId, ConnectionEnd>,
+    pub channels: BTreeMap<ChannelKey, ChannelEnd>,
+    pub next_sequence_send: BTreeMap<ChannelKey, Sequence>,
+    pub next_sequence_recv: BTreeMap<ChannelKey, Sequence>,
+    pub commitments: BTreeMap<PacketKey, Vec<u8>>,
+    pub receipts: BTreeSet<PacketKey>,
+    pub acks: BTreeMap<PacketKey, Vec<u8>>,
+    client_counter: u64,
+    connection_counter: u64,
+    channel_counter: u64,
+}
+
+impl MockStore {
+    /// Apply a message to the store and return the events it emitted.
+    pub fn apply(&mut self, msg: &Any, host: HostBlock) -> Result<Vec<IbcEvent>, String> {
+        match msg.type_url.as_str() {
+            create_client::TYPE_URL => self.create_client(decode(msg)?),
+            update_client::TYPE_URL => self.update_client(decode(msg)?),
+            conn_open_init::TYPE_URL => self.conn_open_init(decode(msg)?),
+            conn_open_try::TYPE_URL => self.conn_open_try(decode(msg)?),
+            conn_open_ack::TYPE_URL => self.conn_open_ack(decode(msg)?),
+            conn_open_confirm::TYPE_URL => self.conn_open_confirm(decode(msg)?),
+            chan_open_init::TYPE_URL => self.chan_open_init(decode(msg)?),
+            chan_open_try::TYPE_URL => self.chan_open_try(decode(msg)?),
+            chan_open_ack::TYPE_URL => self.chan_open_ack(decode(msg)?),
+            chan_open_confirm::TYPE_URL => self.chan_open_confirm(decode(msg)?),
+            chan_close_init::TYPE_URL => self.chan_close_init(decode(msg)?),
+            chan_close_confirm::TYPE_URL => self.chan_close_confirm(decode(msg)?),
+            transfer::TYPE_URL => self.transfer(decode(msg)?),
+            recv_packet::TYPE_URL => self.recv_packet(decode(msg)?, host),
+            acknowledgement::TYPE_URL => self.acknowledge_packet(decode(msg)?),
+            timeout::TYPE_URL => self.timeout_packet(decode::<MsgTimeout>(msg)?.packet, false),
+            timeout_on_close::TYPE_URL => {
+                self.timeout_packet(decode::<MsgTimeoutOnClose>(msg)?.packet, true)
+            }
+            type_url => Err(format!("unsupported message type `{type_url}`")),
+        }
+    }
+
+    fn create_client(&mut self, msg: MsgCreateClient) -> Result<Vec<IbcEvent>, String> {
+        let client_state = AnyClientState::try_from(msg.client_state).map_err(|e| e.to_string())?;
+        let consensus_state =
+            AnyConsensusState::try_from(msg.consensus_state).map_err(|e| e.to_string())?;
+
+        let client_type = client_state.client_type();
+        let consensus_height = client_state.latest_height();
+        let client_id =
+            ClientId::new(client_type, self.client_counter).map_err(|e| e.to_string())?;
+        self.client_counter += 1;
+
+        self.clients.insert(client_id.clone(), client_state);
+        self.consensus_states
+            .insert((client_id.clone(), consensus_height), consensus_state);
+
+        Ok(vec![IbcEvent::CreateClient(CreateClient(
+            ClientAttributes {
+                client_id,
+                client_type,
+                consensus_height,
+            },
+        ))])
+    }
+
+    fn update_client(&mut self, msg: MsgUpdateClient) -> Result<Vec<IbcEvent>, String> {
+        let header = AnyHeader::try_from(msg.header).map_err(|e| e.to_string())?;
+        let AnyClientState::Tendermint(client_state) = self.client(&msg.client_id)?.clone();
+        let AnyHeader::Tendermint(tm_header) = &header;
+
+        let consensus_height = header.height();
+        let client_state = if consensus_height > client_state.latest_height() {
+            client_state
+                .with_header(tm_header.clone())
+                .map_err(|e| e.to_string())?
+        } else {
+            client_state
+        };
+        let client_type = client_state.client_type();
+        let consensus_state = TmConsensusState::from(tm_header.signed_header.header.clone());
+
+        self.clients
+            .insert(msg.client_id.clone(), client_state.into());
+        self.consensus_states.insert(
+            (msg.client_id.clone(), consensus_height),
+            consensus_state.into(),
+        );
+
+        Ok(vec![IbcEvent::UpdateClient(UpdateClient {
+            common: ClientAttributes {
+                client_id: msg.client_id,
+                client_type,
+                consensus_height,
+            },
+            header: Some(header),
+        })])
+    }
+
+    fn conn_open_init(&mut self, msg: MsgConnectionOpenInit) -> Result<Vec<IbcEvent>, String> {
+        self.client(&msg.client_id)?;
+
+        let versions = msg
+            .version
+            .map_or_else(get_compatible_versions, |version| vec![version]);
+        let connection_end = ConnectionEnd::new(
+            ConnectionState::Init,
+            msg.client_id,
+            msg.counterparty,
+            versions,
+            msg.delay_period,
+        );
+        let connection_id = self.insert_connection(connection_end.clone());
+
+        Ok(vec![IbcEvent::OpenInitConnection(
+            connection_events::OpenInit(connection_attributes(connection_id, &connection_end)),
+        )])
+    }
+
+    fn conn_open_try(&mut self, msg: MsgConnectionOpenTry) -> Result<Vec<IbcEvent>, String> {
+        self.client(&msg.client_id)?;
+
+        let connection_end = ConnectionEnd::new(
+            ConnectionState::TryOpen,
+            msg.client_id,
+            msg.counterparty,
+            msg.counterparty_versions,
+            msg.delay_period,
+        );
+        let connection_id = self.insert_connection(connection_end.clone());
+
+        Ok(vec![IbcEvent::OpenTryConnection(
+            connection_events::OpenTry(connection_attributes(connection_id, &connection_end)),
+        )])
+    }
+
+    fn conn_open_ack(&mut self, msg: MsgConnectionOpenAck) -> Result<Vec<IbcEvent>, String> {
+        let connection_end = self.connection_mut(&msg.connection_id)?;
+        if !connection_end.state_matches(&ConnectionState::Init) {
+            return Err(format!("connection {} is not in Init", msg.connection_id));
+        }
+
+        let counterparty = ConnectionCounterparty::new(
+            connection_end.counterparty().client_id().clone(),
+            Some(msg.counterparty_connection_id),
+            connection_end.counterparty().prefix().clone(),
+        );
+        connection_end.set_state(ConnectionState::Open);
+        connection_end.set_counterparty(counterparty);
+        connection_end.set_version(msg.version);
+
+        let attributes = connection_attributes(msg.connection_id, connection_end);
+        Ok(vec![IbcEvent::OpenAckConnection(
+            connection_events::OpenAck(attributes),
+        )])
+    }
+
+    fn conn_open_confirm(
+        &mut self,
+        msg: MsgConnectionOpenConfirm,
+    ) -> Result<Vec<IbcEvent>, String> {
+        let connection_end = self.connection_mut(&msg.connection_id)?;
+        if !connection_end.state_matches(&ConnectionState::TryOpen) {
+            return Err(format!(
+                "connection {} is not in TryOpen",
+                msg.connection_id
+            ));
+        }
+
+        connection_end.set_state(ConnectionState::Open);
+
+        let attributes = connection_attributes(msg.connection_id, connection_end);
+        Ok(vec![IbcEvent::OpenConfirmConnection(
+            connection_events::OpenConfirm(attributes),
+        )])
+    }
+
+    fn chan_open_init(&mut self, msg: MsgChannelOpenInit) -> Result<Vec<IbcEvent>, String> {
+        let connection_id = self.channel_connection(&msg.channel)?;
+        let counterparty = msg.channel.counterparty().clone();
+        let channel_id = self.insert_channel(&msg.port_id, msg.channel);
+
+        Ok(vec![IbcEvent::OpenInitChannel(channel_events::OpenInit {
+            port_id: msg.port_id,
+            channel_id: Some(channel_id),
+            connection_id,
+            counterparty_port_id: counterparty.port_id,
+            counterparty_channel_id: counterparty.channel_id,
+        })])
+    }
+
+    fn chan_open_try(&mut self, msg: MsgChannelOpenTry) -> Result<Vec<IbcEvent>, String> {
+        let connection_id = self.channel_connection(&msg.channel)?;
+        let counterparty = msg.channel.counterparty().clone();
+
+        let mut channel_end = msg.channel;
+        channel_end.set_state(ChannelState::TryOpen);
+        let channel_id = self.insert_channel(&msg.port_id, channel_end);
+
+        Ok(vec![IbcEvent::OpenTryChannel(channel_events::OpenTry {
+            port_id: msg.port_id,
+            channel_id: Some(channel_id),
+            connection_id,
+            counterparty_port_id: counterparty.port_id,
+            counterparty_channel_id: counterparty.channel_id,
+        })])
+    }
+
+    fn chan_open_ack(&mut self, msg: MsgChannelOpenAck) -> Result<Vec<IbcEvent>, String> {
+        let channel_end = self.channel_mut(&msg.port_id, &msg.channel_id)?;
+        if !channel_end.state_matches(&ChannelState::Init) {
+            return Err(format!("channel {} is not in Init", msg.channel_id));
+        }
+
+        channel_end.set_state(ChannelState::Open(UpgradeState::NotUpgrading));
+        channel_end.set_counterparty_channel_id(msg.counterparty_channel_id.clone());
+        channel_end.set_version(msg.counterparty_version);
+        let connection_id = channel_end.connection_hops()[0].clone();
+        let counterparty_port_id = channel_end.counterparty().port_id.clone();
+
+        Ok(vec![IbcEvent::OpenAckChannel(channel_events::OpenAck {
+            port_id: msg.port_id,
+            channel_id: Some(msg.channel_id),
+            counterparty_channel_id: Some(msg.counterparty_channel_id),
+            connection_id,
+            counterparty_port_id,
+        })])
+    }
+
+    fn chan_open_confirm(&mut self, msg: MsgChannelOpenConfirm) -> Result<Vec<IbcEvent>, String> {
+        let channel_end = self.channel_mut(&msg.port_id, &msg.channel_id)?;
+        if !channel_end.state_matches(&ChannelState::TryOpen) {
+            return Err(format!("channel {} is not in TryOpen", msg.channel_id));
+        }
+
+        channel_end.set_state(ChannelState::Open(UpgradeState::NotUpgrading));
+        let connection_id = channel_end.connection_hops()[0].clone();
+        let counterparty = channel_end.counterparty().clone();
+
+        Ok(vec![IbcEvent::OpenConfirmChannel(
+            channel_events::OpenConfirm {
+                port_id: msg.port_id,
+                channel_id: Some(msg.channel_id),
+                connection_id,
+                counterparty_port_id: counterparty.port_id,
+                counterparty_channel_id: counterparty.channel_id,
+            },
+        )])
+    }
+
+    fn chan_close_init(&mut self, msg: MsgChannelCloseInit) -> Result<Vec<IbcEvent>, String> {
+        let channel_end = self.channel_mut(&msg.port_id, &msg.channel_id)?;
+        channel_end.set_state(ChannelState::Closed);
+        let connection_id = channel_end.connection_hops()[0].clone();
+        let counterparty = channel_end.counterparty().clone();
+
+        Ok(vec![IbcEvent::CloseInitChannel(
+            channel_events::CloseInit {
+                port_id: msg.port_id,
+                channel_id: msg.channel_id,
+                connection_id,
+                counterparty_port_id: counterparty.port_id,
+                counterparty_channel_id: counterparty.channel_id,
+            },
+        )])
+    }
+
+    fn chan_close_confirm(&mut self, msg: MsgChannelCloseConfirm) -> Result<Vec<IbcEvent>, String> {
+        let channel_end = self.channel_mut(&msg.port_id, &msg.channel_id)?;
+        channel_end.set_state(ChannelState::Closed);
+        let connection_id = channel_end.connection_hops()[0].clone();
+        let counterparty = channel_end.counterparty().clone();
+
+        Ok(vec![IbcEvent::CloseConfirmChannel(
+            channel_events::CloseConfirm {
+                channel_id: Some(msg.channel_id),
+                port_id: msg.port_id,
+                connection_id,
+                counterparty_port_id: counterparty.port_id,
+                counterparty_channel_id: counterparty.channel_id,
+            },
+        )])
+    }
+
+    fn transfer(&mut self, msg: MsgTransfer) -> Result<Vec<IbcEvent>, String> {
+        let channel_key = (msg.source_port.clone(), msg.source_channel.clone());
+        let channel_end = self.open_channel(&channel_key)?;
+        let counterparty = channel_end.counterparty().clone();
+        let destination_channel = counterparty
+            .channel_id
+            .ok_or_else(|| format!("channel {} has no counterparty", msg.source_channel))?;
+
+        let data = RawPacketData {
+            denom: msg.token.denom,
+            amount: msg.token.amount,
+            sender: msg.sender.to_string(),
+            receiver: msg.receiver.to_string(),
+            memo: msg.memo.unwrap_or_default(),
+        };
+        let data = serde_json::to_vec(&data).map_err(|e| e.to_string())?;
+
+        let next_sequence = self
+            .next_sequence_send
+            .entry(channel_key)
+            .or_insert_with(|| Sequence::from(1));
+        let sequence = *next_sequence;
+        *next_sequence = sequence.increment();
+
+        let packet = Packet {
+            sequence,
+            source_port: msg.source_port,
+            source_channel: msg.source_channel,
+            destination_port: counterparty.port_id,
+            destination_channel,
+            data,
+            timeout_height: msg.timeout_height,
+            timeout_timestamp: msg.timeout_timestamp,
+        };
+        self.commitments
+            .insert(packet_key(&packet), packet_commitment(&packet));
+
+        Ok(vec![IbcEvent::SendPacket(channel_events::SendPacket {
+            packet,
+        })])
+    }
+
+    fn recv_packet(
+        &mut self,
+        msg: MsgRecvPacket,
+        host: HostBlock,
+    ) -> Result<Vec<IbcEvent>, String> {
+        let packet = msg.packet;
+        let channel_key = (
+            packet.destination_port.clone(),
+            packet.destination_channel.clone(),
+        );
+        let ordering = *self.open_channel(&channel_key)?.ordering();
+
+        if packet.timed_out(&host.timestamp, host.height) {
+            return Err(format!("packet {} has timed out", packet.sequence));
+        }
+
+        let key = (
+            packet.destination_port.clone(),
+            packet.destination_channel.clone(),
+            packet.sequence,
+        );
+        if ordering == Ordering::Ordered {
+            let next_sequence = self
+                .next_sequence_recv
+                .entry(channel_key)
+                .or_insert_with(|| Sequence::from(1));
+            if packet.sequence < *next_sequence {
+                // Already received, as ibc-go this is a no-op
+                return Ok(vec![]);
+            }
+            if packet.sequence != *next_sequence {
+                return Err(format!(
+                    "packet sequence {} does not match the next sequence to receive {}",
+                    packet.sequence, next_sequence
+                ));
+            }
+            *next_sequence = next_sequence.increment();
+        } else if !self.receipts.insert(key.clone()) {
+            // Already received, as ibc-go this is a no-op
+            return Ok(vec![]);
+        }
+
+        self.acks.insert(key, ack_commitment(SUCCESS_ACK));
+
+        Ok(vec![
+            IbcEvent::ReceivePacket(channel_events::ReceivePacket {
+                packet: packet.clone(),
+            }),
+            IbcEvent::WriteAcknowledgement(channel_events::WriteAcknowledgement {
+                packet,
+                ack: SUCCESS_ACK.to_vec(),
+            }),
+        ])
+    }
+
+    fn acknowledge_packet(&mut self, msg: MsgAcknowledgement) -> Result<Vec<IbcEvent>, String> {
+        let packet = msg.packet;
+        self.open_channel(&(packet.source_port.clone(), packet.source_channel.clone()))?;
+
+        if self.commitments.remove(&packet_key(&packet)).is_none() {
+            // Already acknowledged, as ibc-go this is a no-op
+            return Ok(vec![]);
+        }
+
+        Ok(vec![IbcEvent::AcknowledgePacket(
+            channel_events::AcknowledgePacket { packet },
+        )])
+    }
+
+    fn timeout_packet(&mut self, packet: Packet, on_close: bool) -> Result<Vec<IbcEvent>, String> {
+        let channel_key = (packet.source_port.clone(), packet.source_channel.clone());
+        let channel_end = self
+            .channels
+            .get_mut(&channel_key)
+            .ok_or_else(|| format!("channel {} not found", packet.source_channel))?;
+
+        if self.commitments.remove(&packet_key(&packet)).is_none() {
+            // Already timed out or acknowledged, as ibc-go this is a no-op
+            return Ok(vec![]);
+        }
+
+        // A timeout closes ordered channels
+        if channel_end.ordering() == &Ordering::Ordered {
+            channel_end.set_state(ChannelState::Closed);
+        }
+
+        let event = if on_close {
+            IbcEvent::TimeoutOnClosePacket(channel_events::TimeoutOnClosePacket { packet })
+        } else {
+            IbcEvent::TimeoutPacket(channel_events::TimeoutPacket { packet })
+        };
+        Ok(vec![event])
+    }
+
+    fn client(&self, client_id: &ClientId) -> Result<&AnyClientState, String> {
+        self.clients
+            .get(client_id)
+            .ok_or_else(|| format!("client {client_id} not found"))
+    }
+
+    fn connection_mut(
+        &mut self,
+        connection_id: &ConnectionId,
+    ) -> Result<&mut ConnectionEnd, String> {
+        self.connections
+            .get_mut(connection_id)
+            .ok_or_else(|| format!("connection {connection_id} not found"))
+    }
+
+    fn channel_mut(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<&mut ChannelEnd, String> {
+        self.channels
+            .get_mut(&(port_id.clone(), channel_id.clone()))
+            .ok_or_else(|| format!("channel {channel_id} not found on port {port_id}"))
+    }
+
+    fn open_channel(&self, channel_key: &ChannelKey) -> Result<&ChannelEnd, String> {
+        let (port_id, channel_id) = channel_key;
+        let channel_end = self
+            .channels
+            .get(channel_key)
+            .ok_or_else(|| format!("channel {channel_id} not found on port {port_id}"))?;
+
+        if !channel_end.is_open() {
+            return Err(format!("channel {channel_id} is not open"));
+        }
+
+        Ok(channel_end)
+    }
+
+    /// Return the connection of a new channel end, which must exist
+    fn channel_connection(&self, channel_end: &ChannelEnd) -> Result<ConnectionId, String> {
+        let connection_id = channel_end
+            .connection_hops()
+            .first()
+            .ok_or_else(|| "channel has no connection hop".to_string())?;
+
+        if !self.connections.contains_key(connection_id) {
+            return Err(format!("connection {connection_id} not found"));
+        }
+
+        Ok(connection_id.clone())
+    }
+
+    fn insert_connection(&mut self, connection_end: ConnectionEnd) -> ConnectionId {
+        let connection_id = ConnectionId::new(self.connection_counter);
+        self.connection_counter += 1;
+        self.connections
+            .insert(connection_id.clone(), connection_end);
+        connection_id
+    }
+
+    fn insert_channel(&mut self, port_id: &PortId, channel_end: ChannelEnd) -> ChannelId {
+        let channel_id = ChannelId::new(self.channel_counter);
+        self.channel_counter += 1;
+
+        let channel_key = (port_id.clone(), channel_id.clone());
+        self.next_sequence_send
+            .insert(channel_key.clone(), Sequence::from(1));
+        self.next_sequence_recv
+            .insert(channel_key.clone(), Sequence::from(1));
+        self.channels.insert(channel_key, channel_end);
+
+        channel_id
+    }
+}
+
+/// The prefix under which the IBC store is committed
+pub fn commitment_prefix() -> CommitmentPrefix {
+    CommitmentPrefix::try_from(b"ibc".to_vec()).expect("prefix is not empty")
+}
+
+fn decode<M>(msg: &Any) -> Result<M, String>
+where
+    M: Msg + Protobuf<<M as Msg>::Raw>,
+    <M as Msg>::Raw: Default,
+    <M as TryFrom<<M as Msg>::Raw>>::Error: Display,
+{
+    M::decode_vec(&msg.value).map_err(|e| format!("failed to decode `{}`: {e}", msg.type_url))
+}
+
+fn connection_attributes(
+    connection_id: ConnectionId,
+    connection_end: &ConnectionEnd,
+) -> ConnectionAttributes {
+    ConnectionAttributes {
+        connection_id: Some(connection_id),
+        client_id: connection_end.client_id().clone(),
+        counterparty_connection_id: connection_end.counterparty().connection_id().cloned(),
+        counterparty_client_id: connection_end.counterparty().client_id().clone(),
+    }
+}
+
+fn packet_key(packet: &Packet) -> PacketKey {
+    (
+        packet.source_port.clone(),
+        packet.source_channel.clone(),
+        packet.sequence,
+    )
+}
+
+/// The commitment of a packet, computed as by ibc-go
+fn packet_commitment(packet: &Packet) -> Vec<u8> {
+    let mut input = Vec::with_capacity(56);
+    input.extend(packet.timeout_timestamp.nanoseconds().to_be_bytes());
+    input.extend(
+        packet
+            .timeout_height
+            .commitment_revision_number()
+            .to_be_bytes(),
+    );
+    input.extend(
+        packet
+            .timeout_height
+            .commitment_revision_height()
+            .to_be_bytes(),
+    );
+    input.extend(Sha256::digest(&packet.data));
+
+    Sha256::digest(input).to_vec()
+}
+
+fn ack_commitment(ack: &[u8]) -> Vec<u8> {
+    Sha256::digest(ack).to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics04_channel::timeout::TimeoutHeight;
+
+    fn packet(sequence: u64) -> Packet {
+        Packet {
+            sequence: Sequence::from(sequence),
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::new(0),
+            destination_port: PortId::transfer(),
+            destination_channel: ChannelId::new(1),
+            data: vec![1, 2, 3],
+            timeout_height: TimeoutHeight::no_timeout(),
+            timeout_timestamp: Timestamp::none(),
+        }
+    }
+
+    #[test]
+    fn packet_commitment_depends_on_the_packet_data() {
+        let mut other = packet(1);
+        other.data = vec![4, 5, 6];
+
+        assert_eq!(packet_commitment(&packet(1)), packet_commitment(&packet(2)));
+        assert_ne!(packet_commitment(&packet(1)), packet_commitment(&other));
+    }
+
+    #[test]
+    fn unsupported_message() {
+        let msg = Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+            value: vec![],
+        };
+        let host = HostBlock {
+            height: Height::new(0, 1).unwrap(),
+            timestamp: Timestamp::now(),
+        };
+
+        let error = MockStore::default().apply(&msg, host).unwrap_err();
+        assert_eq!(
+            error,
+            "unsupported message type `/cosmos.bank.v1beta1.MsgSend`"
+        );
+    }
+}
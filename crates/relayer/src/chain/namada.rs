@@ -11,7 +11,9 @@ use ibc_proto::ibc::applications::fee::v1::{
 };
 use ibc_proto::ibc::core::channel::v1::{QueryUpgradeErrorRequest, QueryUpgradeRequest};
 use ibc_relayer_types::applications::ics28_ccv::msgs::{ConsumerChain, ConsumerId};
+use ibc_relayer_types::applications::ics29_fee::packet_fee::IdentifiedPacketFees;
 use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
+use ibc_relayer_types::applications::transfer::RawCoin;
 use ibc_relayer_types::clients::ics07_tendermint::client_state::{
     AllowUpdate, ClientState as TmClientState,
 };
@@ -63,6 +65,7 @@ use tendermint_rpc::{HttpClient, Url};
 use tokio::runtime::Runtime as TokioRuntime;
 
 use crate::account::Balance;
+use crate::chain::capabilities::{Capability, ChainCapabilities};
 use crate::chain::client::ClientSettings;
 use crate::chain::cosmos::batch::response_to_tx_sync_result;
 use crate::chain::cosmos::config::CosmosSdkConfig;
@@ -83,16 +86,22 @@ use crate::keyring::{KeyRing, NamadaKeyPair, SigningKeyPair};
 use crate::light_client::tendermint::LightClient as TmLightClient;
 use crate::light_client::{LightClient, Verified};
 use crate::misbehaviour::MisbehaviourEvidence;
+use crate::nft_transfer::ClassTrace;
+use crate::transfer::{IbcShielding, IbcShieldingRequest, ShieldedTransferRequest};
 
 use self::error::Error as NamadaError;
 
 pub mod error;
+mod fee;
 pub mod key;
+mod masp;
 mod query;
 mod tx;
 pub mod version;
 pub mod wallet;
 
+pub use masp::set_masp_params_dir;
+
 pub struct NamadaChain {
     /// Reuse CosmosSdkConfig for tendermint's light clients
     config: CosmosSdkConfig,
@@ -331,6 +340,20 @@ impl ChainEndpoint for NamadaChain {
         Ok(Signer::from_str(&key.account()).expect("The key name shouldn't be empty"))
     }
 
+    fn capabilities(&self) -> ChainCapabilities {
+        ChainCapabilities {
+            fees: false,
+            cross_chain_queries: false,
+            channel_upgrades: false,
+            ccv: false,
+            interchain_accounts: false,
+            nft_transfers: false,
+            ibc_v2: false,
+            fee_estimation: false,
+            ..ChainCapabilities::all()
+        }
+    }
+
     fn version_specs(&self) -> Result<Specs, Error> {
         let status = self
             .rt
@@ -426,6 +449,42 @@ impl ChainEndpoint for NamadaChain {
         Ok(responses)
     }
 
+    fn estimate_fee(&mut self, _tracked_msgs: TrackedMsgs) -> Result<Vec<RawCoin>, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::FeeEstimation,
+        ))
+    }
+
+    fn query_tx_fee(&self, _tx_hash: QueryTxHash) -> Result<Vec<RawCoin>, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::FeeEstimation,
+        ))
+    }
+
+    fn build_ibc_shielding(&self, request: IbcShieldingRequest) -> Result<IbcShielding, Error> {
+        self.gen_ibc_shielding(request)
+    }
+
+    fn send_shielded_transfer(
+        &mut self,
+        request: ShieldedTransferRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        let response = self.submit_shielded_transfer(request)?;
+        let mut tx_sync_results = vec![response_to_tx_sync_result(
+            &self.config.id,
+            1,
+            into_tm_response(response),
+        )];
+        self.wait_for_block_commits(&mut tx_sync_results)?;
+
+        Ok(tx_sync_results
+            .into_iter()
+            .flat_map(|el| el.events)
+            .collect())
+    }
+
     fn verify_header(
         &mut self,
         trusted: ICSHeight,
@@ -548,6 +607,24 @@ impl ChainEndpoint for NamadaChain {
         }
     }
 
+    fn query_class_trace(&self, _hash: String) -> Result<ClassTrace, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::NftTransfers,
+        ))
+    }
+
+    fn query_interchain_account(
+        &self,
+        _owner: Signer,
+        _connection_id: ConnectionId,
+    ) -> Result<String, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::InterchainAccounts,
+        ))
+    }
+
     fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error> {
         crate::time!(
             "query_commitment_prefix",
@@ -1092,6 +1169,59 @@ impl ChainEndpoint for NamadaChain {
         Ok((sequences, status.height))
     }
 
+    fn query_packet_commitment_v2(
+        &self,
+        _request: QueryPacketCommitmentV2Request,
+        _include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::IbcV2,
+        ))
+    }
+
+    fn query_packet_receipt_v2(
+        &self,
+        _request: QueryPacketReceiptV2Request,
+        _include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::IbcV2,
+        ))
+    }
+
+    fn query_packet_acknowledgement_v2(
+        &self,
+        _request: QueryPacketAcknowledgementV2Request,
+        _include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::IbcV2,
+        ))
+    }
+
+    fn query_packet_commitments_v2(
+        &self,
+        _request: QueryPacketCommitmentsV2Request,
+    ) -> Result<(Vec<Sequence>, ICSHeight), Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::IbcV2,
+        ))
+    }
+
+    fn query_packet_events_v2(
+        &self,
+        _request: QueryPacketEventDataV2Request,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::IbcV2,
+        ))
+    }
+
     fn query_unreceived_acknowledgements(
         &self,
         request: QueryUnreceivedAcksRequest,
@@ -1297,29 +1427,48 @@ impl ChainEndpoint for NamadaChain {
         _port_id: &PortId,
         _counterparty_payee: &Signer,
     ) -> Result<(), Error> {
-        // not supported
-        unimplemented!()
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::Fees,
+        ))
     }
 
     fn cross_chain_query(
         &self,
         _requests: Vec<CrossChainQueryRequest>,
     ) -> Result<Vec<CrossChainQueryResponse>, Error> {
-        // not supported
-        unimplemented!()
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::CrossChainQueries,
+        ))
     }
 
     fn query_incentivized_packet(
         &self,
         _request: QueryIncentivizedPacketRequest,
     ) -> Result<QueryIncentivizedPacketResponse, Error> {
-        // not supported
-        unimplemented!()
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::Fees,
+        ))
+    }
+
+    fn query_incentivized_packets(
+        &self,
+        _channel_id: ChannelId,
+        _port_id: PortId,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error> {
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::Fees,
+        ))
     }
 
     fn query_consumer_chains(&self) -> Result<Vec<ConsumerChain>, Error> {
-        // not supported
-        unimplemented!()
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::Ccv,
+        ))
     }
 
     fn query_upgrade(
@@ -1367,8 +1516,10 @@ impl ChainEndpoint for NamadaChain {
     }
 
     fn query_ccv_consumer_id(&self, _client_id: ClientId) -> Result<ConsumerId, Error> {
-        // not supported
-        unimplemented!()
+        Err(Error::unsupported_capability(
+            self.id().clone(),
+            Capability::Ccv,
+        ))
     }
 }
 
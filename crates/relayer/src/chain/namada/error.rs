@@ -61,6 +61,34 @@ define_error! {
         Version
             { version: String }
             |e| { format!("Parsing the version string failed: {}", e.version) },
+
+        SpendingKeyDecode
+            |_| { "MASP spending key decoding failed" },
+
+        PaymentAddressDecode
+            { raw: String }
+            |e| { format!("MASP payment address decoding failed for {}", e.raw) },
+
+        ShieldedSync
+            { reason: String }
+            |e| { format!("MASP shielded sync failed: {}", e.reason) },
+
+        NoFeeToken
+            { chain_id: ChainId }
+            |e| { format!("no token is whitelisted to pay the fees on chain {}", e.chain_id) },
+
+        InsufficientFeeBalance
+            {
+                chain_id: ChainId,
+                gas_limit: u64,
+            }
+            |e| {
+                format!("no whitelisted fee token has enough balance to pay for {} gas on chain {}",
+                    e.gas_limit, e.chain_id)
+            },
+
+        EmptyShielding
+            |_| { "no MASP transaction was generated for the IBC shielding transfer" },
     }
 }
 
@@ -0,0 +1,202 @@
+use alloc::collections::BTreeMap;
+use core::str::FromStr;
+
+use namada_sdk::address::Address;
+use namada_sdk::io::NamadaIo;
+use namada_sdk::token::Amount;
+use namada_sdk::{rpc, Namada};
+use tracing::{debug, warn};
+
+use crate::chain::endpoint::ChainEndpoint;
+use crate::error::Error;
+
+use super::error::Error as NamadaError;
+use super::NamadaChain;
+
+/// A token whitelisted to pay the fees, along with the gas price paid in it
+#[derive(Clone, Debug)]
+pub struct FeeToken {
+    pub token: Address,
+    pub gas_price: f64,
+}
+
+impl NamadaChain {
+    /// Query the gas cost table of the chain and return the tokens the fees can be
+    /// paid with, in order of preference: the configured `gas_price.denom` first,
+    /// then the configured `fallback_fee_tokens`, or all the other whitelisted
+    /// tokens if there are none.
+    pub(super) fn fee_token_candidates(&self) -> Result<Vec<FeeToken>, Error> {
+        let preferred_str = &self.config.gas_price.denom;
+        let preferred = Address::from_str(preferred_str)
+            .map_err(|_| NamadaError::address_decode(preferred_str.clone()))?;
+
+        let fallback = self
+            .config
+            .fallback_fee_tokens
+            .iter()
+            .map(|token| {
+                Address::from_str(token).map_err(|_| NamadaError::address_decode(token.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let gas_cost_key = namada_sdk::parameters::storage::get_gas_cost_key();
+        let gas_costs: BTreeMap<Address, Amount> = self
+            .rt
+            .block_on(rpc::query_storage_value(self.ctx.client(), &gas_cost_key))
+            .map_err(NamadaError::namada)?;
+
+        let mut gas_prices = Vec::with_capacity(gas_costs.len());
+        for (token, minimum_gas_price) in gas_costs {
+            let minimum_gas_price = self.to_f64(&token, minimum_gas_price);
+            gas_prices.push((token, minimum_gas_price));
+        }
+
+        if !gas_prices.iter().any(|(token, _)| *token == preferred) {
+            warn!(
+                id = %self.config.id,
+                "the configured fee token {preferred} is not whitelisted to pay the fees, \
+                 falling back to the other fee tokens",
+            );
+        }
+
+        let candidates = order_fee_tokens(
+            gas_prices,
+            &preferred,
+            self.config.gas_price.price,
+            &fallback,
+        )
+        .into_iter()
+        .map(|(token, gas_price)| FeeToken { token, gas_price })
+        .collect::<Vec<_>>();
+
+        if candidates.is_empty() {
+            return Err(NamadaError::no_fee_token(self.config.id.clone()).into());
+        }
+
+        Ok(candidates)
+    }
+
+    /// Select the first of the given tokens whose balance in the relayer account
+    /// covers the fees of a transaction with the given gas limit.
+    pub(super) fn select_fee_token(
+        &self,
+        candidates: &[FeeToken],
+        gas_limit: u64,
+    ) -> Result<FeeToken, Error> {
+        let payer = self.get_key()?.address;
+
+        for candidate in candidates {
+            let balance = self
+                .rt
+                .block_on(rpc::get_token_balance(
+                    self.ctx.client(),
+                    &candidate.token,
+                    &payer,
+                    None,
+                ))
+                .map_err(NamadaError::namada)?;
+            let balance = self.to_f64(&candidate.token, balance);
+            let fee = candidate.gas_price * gas_limit as f64;
+
+            if balance >= fee {
+                return Ok(candidate.clone());
+            }
+
+            debug!(
+                id = %self.config.id,
+                "insufficient balance of fee token {} to pay {fee}: {balance}",
+                candidate.token,
+            );
+        }
+
+        Err(NamadaError::insufficient_fee_balance(self.config.id.clone(), gas_limit).into())
+    }
+
+    fn to_f64(&self, token: &Address, amount: Amount) -> f64 {
+        self.rt
+            .block_on(self.ctx.denominate_amount(token, amount))
+            .to_string()
+            .parse()
+            .expect("Denominated amount should be parsable")
+    }
+}
+
+/// Put the preferred token first, paid at the highest of the configured and the
+/// minimum gas prices, followed by the fallback tokens in the given order, or by
+/// all the other tokens in the order of the gas cost table if there are none.
+///
+/// The minimum gas prices are denominated in their own token, so they tell
+/// nothing about which token is the cheapest to pay with.
+fn order_fee_tokens<T: PartialEq>(
+    mut gas_prices: Vec<(T, f64)>,
+    preferred: &T,
+    preferred_gas_price: f64,
+    fallback: &[T],
+) -> Vec<(T, f64)> {
+    let mut ordered = Vec::with_capacity(gas_prices.len());
+
+    if let Some(index) = gas_prices.iter().position(|(token, _)| token == preferred) {
+        let (token, minimum_gas_price) = gas_prices.remove(index);
+        ordered.push((token, preferred_gas_price.max(minimum_gas_price)));
+    }
+
+    if fallback.is_empty() {
+        ordered.extend(gas_prices);
+    } else {
+        for fallback_token in fallback {
+            if let Some(index) = gas_prices
+                .iter()
+                .position(|(token, _)| token == fallback_token)
+            {
+                ordered.push(gas_prices.remove(index));
+            }
+        }
+    }
+
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::order_fee_tokens;
+
+    #[test]
+    fn preferred_token_comes_first() {
+        let gas_prices = vec![("nam", 0.001), ("btc", 0.0001), ("eth", 0.01)];
+
+        assert_eq!(
+            order_fee_tokens(gas_prices, &"eth", 0.02, &[]),
+            vec![("eth", 0.02), ("nam", 0.001), ("btc", 0.0001)]
+        );
+    }
+
+    #[test]
+    fn preferred_gas_price_is_raised_to_the_minimum() {
+        let gas_prices = vec![("nam", 0.001), ("btc", 0.0001)];
+
+        assert_eq!(
+            order_fee_tokens(gas_prices, &"nam", 0.0005, &[]),
+            vec![("nam", 0.001), ("btc", 0.0001)]
+        );
+    }
+
+    #[test]
+    fn fallback_tokens_in_configured_order() {
+        let gas_prices = vec![("nam", 0.001), ("btc", 0.0001), ("eth", 0.01)];
+
+        assert_eq!(
+            order_fee_tokens(gas_prices, &"nam", 0.002, &["eth", "osmo", "btc"]),
+            vec![("nam", 0.002), ("eth", 0.01), ("btc", 0.0001)]
+        );
+    }
+
+    #[test]
+    fn only_fallback_tokens_without_preferred_token() {
+        let gas_prices = vec![("nam", 0.001), ("btc", 0.0001), ("eth", 0.01)];
+
+        assert_eq!(
+            order_fee_tokens(gas_prices, &"osmo", 0.02, &["btc"]),
+            vec![("btc", 0.0001)]
+        );
+    }
+}
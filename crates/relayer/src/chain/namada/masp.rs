@@ -0,0 +1,141 @@
+use core::str::FromStr;
+use core::time::Duration;
+use std::path::Path;
+
+use namada_sdk::address::MASP;
+use namada_sdk::args::{
+    GenIbcShieldingTransfer, IbcShieldingTransferAsset, InputAmount, Query, TxExpiration,
+};
+use namada_sdk::control_flow::ShutdownSignal;
+use namada_sdk::ibc::convert_masp_tx_to_ibc_memo;
+use namada_sdk::ibc::core::host::types::identifiers::{
+    ChannelId as NamadaChannelId, PortId as NamadaPortId,
+};
+use namada_sdk::io::{DevNullProgressBar, NamadaIo};
+use namada_sdk::masp::{
+    LedgerMaspClient, MaspLocalTaskEnv, ShieldedSyncConfig, ENV_VAR_MASP_PARAMS_DIR,
+};
+use namada_sdk::storage::BlockHeight;
+use namada_sdk::token::DenominatedAmount;
+use namada_sdk::wallet::DatedSpendingKey;
+use namada_sdk::{tx, ExtendedSpendingKey, Namada, PaymentAddress, TransferTarget};
+use tendermint_rpc::Url;
+
+use crate::error::Error;
+use crate::transfer::{IbcShielding, IbcShieldingRequest};
+
+use super::error::Error as NamadaError;
+use super::NamadaChain;
+
+/// Number of threads scanning the MASP notes during a shielded sync
+const SYNC_THREADS: usize = 4;
+/// Maximum number of concurrent block fetches during a shielded sync
+const MAX_CONCURRENT_FETCHES: usize = 100;
+/// Backoff between the retries of a failed block fetch
+const FETCH_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Point the Namada SDK to the folder holding the MASP parameters.
+///
+/// The SDK only looks this folder up through an environment variable,
+/// so this must be called once at startup, before any chain runtime is spawned.
+pub fn set_masp_params_dir(dir: &Path) {
+    std::env::set_var(ENV_VAR_MASP_PARAMS_DIR, dir);
+}
+
+/// The shielded sync of the relayer is never interrupted
+struct NoShutdown;
+
+impl ShutdownSignal for NoShutdown {
+    async fn wait_for_shutdown(&mut self) {
+        core::future::pending::<()>().await
+    }
+
+    fn received(&mut self) -> bool {
+        false
+    }
+}
+
+impl NamadaChain {
+    /// Generate the MASP transaction shielding the tokens received over IBC,
+    /// and return the receiver and the memo the sending chain should set
+    /// in its `MsgTransfer`.
+    pub fn gen_ibc_shielding(&self, request: IbcShieldingRequest) -> Result<IbcShielding, Error> {
+        let target = PaymentAddress::from_str(&request.target)
+            .map_err(|_| NamadaError::payment_address_decode(request.target.clone()))?;
+        let amount = DenominatedAmount::from_str(&request.token.amount.to_string())
+            .map_err(|e| NamadaError::namada(namada_sdk::error::Error::Other(e.to_string())))?;
+        let port_id = NamadaPortId::from_str(request.port_id.as_str())
+            .expect("port ID should be convertible");
+        let channel_id = NamadaChannelId::from_str(request.channel_id.as_str())
+            .expect("channel ID should be convertible");
+        let ledger_address = Url::from_str(&self.config.rpc_addr.to_string())
+            .map_err(|e| NamadaError::rpc(self.config.rpc_addr.clone(), e))?;
+
+        let args = GenIbcShieldingTransfer {
+            query: Query { ledger_address },
+            output_folder: None,
+            target: TransferTarget::PaymentAddress(target),
+            amount: InputAmount::Validated(amount),
+            expiration: TxExpiration::Default,
+            asset: IbcShieldingTransferAsset::LookupNamadaAddress {
+                token: request.token.denom,
+                port_id,
+                channel_id,
+            },
+        };
+        let masp_tx = self
+            .rt
+            .block_on(tx::gen_ibc_shielding_transfer(&self.ctx, args))
+            .map_err(NamadaError::namada)?
+            .ok_or_else(NamadaError::empty_shielding)?;
+
+        Ok(IbcShielding {
+            receiver: MASP.to_string(),
+            memo: convert_masp_tx_to_ibc_memo(&masp_tx),
+        })
+    }
+
+    /// Fetch the MASP notes owned by the given spending key so that they
+    /// can be spent, and persist the shielded context.
+    ///
+    /// The blocks are scanned from the birthday of the key if given,
+    /// and from the genesis of the chain otherwise.
+    pub(super) async fn sync_shielded_context(
+        &self,
+        spending_key: ExtendedSpendingKey,
+        birthday: Option<u64>,
+    ) -> Result<(), Error> {
+        let env = MaspLocalTaskEnv::new(SYNC_THREADS)
+            .map_err(|e| NamadaError::shielded_sync(e.to_string()))?;
+        let client = LedgerMaspClient::new(
+            self.ctx.client().clone(),
+            MAX_CONCURRENT_FETCHES,
+            FETCH_BACKOFF,
+        );
+        let config = ShieldedSyncConfig::builder()
+            .client(client)
+            .fetched_tracker(DevNullProgressBar)
+            .scanned_tracker(DevNullProgressBar)
+            .applied_tracker(DevNullProgressBar)
+            .shutdown_signal(NoShutdown)
+            .build();
+
+        let mut shielded = self.ctx.shielded_mut().await;
+        // Nothing has been saved before the first sync
+        let _ = shielded.load().await;
+        shielded
+            .sync(
+                env,
+                config,
+                None,
+                &[DatedSpendingKey::new(
+                    spending_key,
+                    birthday.map(BlockHeight),
+                )],
+                &[],
+            )
+            .await
+            .map_err(|e| NamadaError::shielded_sync(e.to_string()))?;
+        shielded.save().await.map_err(Error::io)
+    }
+}
@@ -5,14 +5,25 @@ use std::thread;
 use std::time::Instant;
 
 use ibc_proto::google::protobuf::Any;
+use ibc_relayer_types::core::ics04_channel::timeout::TimeoutHeight;
 use itertools::Itertools;
 use namada_sdk::address::{Address, ImplicitAddress};
 use namada_sdk::args::TxBuilder;
-use namada_sdk::args::{Tx as TxArgs, TxCustom};
+use namada_sdk::args::{InputAmount, Tx as TxArgs, TxCustom, TxIbcTransfer};
 use namada_sdk::chain::ChainId;
+use namada_sdk::ibc::core::host::types::identifiers::{
+    ChannelId as NamadaChannelId, PortId as NamadaPortId,
+};
+use namada_sdk::ibc::trace::convert_to_address;
 use namada_sdk::io::NamadaIo;
+use namada_sdk::masp_primitives::transaction::components::sapling::builder::RngBuildParams;
+use namada_sdk::masp_primitives::zip32::{
+    ExtendedSpendingKey as MaspExtendedSpendingKey, PseudoExtendedKey,
+};
+use namada_sdk::token::DenominatedAmount;
 use namada_sdk::tx::{prepare_tx, ProcessTxResponse};
-use namada_sdk::{rpc, signing, tx, Namada};
+use namada_sdk::{rpc, signing, tx, ExtendedSpendingKey, Namada, TransferSource, TransferTarget};
+use rand::rngs::OsRng;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 use tracing::{debug, debug_span, trace, warn};
 
@@ -22,6 +33,7 @@ use crate::chain::cosmos::types::tx::{TxStatus, TxSyncResult};
 use crate::chain::cosmos::wait::all_tx_results_found;
 use crate::chain::endpoint::ChainEndpoint;
 use crate::error::{Error, ErrorDetail};
+use crate::transfer::ShieldedTransferRequest;
 
 use super::error::{Error as NamadaError, ErrorDetail as NamadaErrorDetail};
 use super::NamadaChain;
@@ -57,18 +69,88 @@ impl NamadaChain {
             self.set_tx_data(&mut tx, msg)?;
             txs.push((tx, signing_data.expect("signing_data should exist")));
         }
-        let (mut tx, signing_data) = tx::build_batch(txs).map_err(NamadaError::namada)?;
+        let (tx, signing_data) = tx::build_batch(txs).map_err(NamadaError::namada)?;
         // This is fine, as only the relayers is signing the transactions
         let signing_data = signing_data.first().expect("SigningData should exist");
 
+        self.estimate_sign_and_submit(tx, &tx_args, signing_data)
+    }
+
+    /// Submit a MASP transfer over IBC, spending the notes of the given spending key.
+    pub fn submit_shielded_transfer(
+        &mut self,
+        request: ShieldedTransferRequest,
+    ) -> Result<Response, Error> {
+        let spending_key = ExtendedSpendingKey::from_str(&request.spending_key)
+            .map_err(|_| NamadaError::spending_key_decode())?;
+        let token = Address::from_str(&request.token.denom)
+            .or_else(|_| convert_to_address(&request.token.denom))
+            .map_err(|_| NamadaError::address_decode(request.token.denom.clone()))?;
+        let amount = DenominatedAmount::from_str(&request.token.amount.to_string())
+            .map_err(|e| NamadaError::namada(namada_sdk::error::Error::Other(e.to_string())))?;
+        let port_id = NamadaPortId::from_str(request.src_port_id.as_str())
+            .expect("port ID should be convertible");
+        let channel_id = NamadaChannelId::from_str(request.src_channel_id.as_str())
+            .expect("channel ID should be convertible");
+        let timeout_height = match request.timeout_height {
+            TimeoutHeight::At(height) => Some(height.revision_height()),
+            TimeoutHeight::Never => None,
+        };
+        let timeout_sec_offset =
+            (!request.timeout_duration.is_zero()).then_some(request.timeout_duration.as_secs());
+
+        let tx_args = self.make_tx_args()?;
+
+        let relayer_key = self.get_key()?;
+        let relayer_addr = relayer_key.address;
+
+        let rt = self.rt.clone();
+        rt.block_on(self.submit_reveal_aux(&tx_args, &relayer_addr))?;
+        rt.block_on(self.sync_shielded_context(spending_key, request.birthday))?;
+
+        let args = TxIbcTransfer {
+            tx: tx_args.clone(),
+            source: TransferSource::ExtendedKey(PseudoExtendedKey::from(
+                MaspExtendedSpendingKey::from(spending_key),
+            )),
+            receiver: request.receiver,
+            token,
+            amount: InputAmount::Validated(amount),
+            port_id,
+            channel_id,
+            timeout_height,
+            timeout_sec_offset,
+            // Refund to the relayer account rather than to a disposable address
+            refund_target: Some(TransferTarget::Address(relayer_addr)),
+            ibc_shielding_data: None,
+            ibc_memo: request.memo,
+            gas_spending_key: None,
+            disposable_signing_key: false,
+            tx_code_path: PathBuf::from(tx::TX_IBC_WASM),
+        };
+        let (tx, signing_data, _) = rt
+            .block_on(args.build(&self.ctx, &mut RngBuildParams::new(OsRng)))
+            .map_err(NamadaError::namada)?;
+
+        self.estimate_sign_and_submit(tx, &tx_args, &signing_data)
+    }
+
+    fn estimate_sign_and_submit(
+        &mut self,
+        mut tx: tx::Tx,
+        tx_args: &TxArgs,
+        signing_data: &signing::SigningTxData,
+    ) -> Result<Response, Error> {
         // Estimate the fee with dry-run
-        match self.estimate_fee(tx.clone(), &tx_args, signing_data) {
+        let fee_token = match self.estimate_tx_fee(tx.clone(), tx_args, signing_data) {
             // Set the estimated fee
             Ok(Some((fee_token, gas_limit, fee_amount))) => {
-                self.prepare_tx_with_gas(&mut tx, &tx_args, &fee_token, gas_limit, fee_amount)?
+                self.prepare_tx_with_gas(&mut tx, tx_args, &fee_token, gas_limit, fee_amount)?;
+                Some(fee_token)
             }
             Ok(None) => {
                 // the default gas limit will be used
+                None
             }
             Err(err) => match err.detail() {
                 ErrorDetail::Namada(namada_err) => {
@@ -93,11 +175,12 @@ impl NamadaChain {
                 }
                 _ => return Err(err),
             },
-        }
+        };
 
+        let rt = self.rt.clone();
         rt.block_on(self.ctx.sign(
             &mut tx,
-            &tx_args,
+            tx_args,
             signing_data.clone(),
             signing::default_sign,
             (),
@@ -106,13 +189,18 @@ impl NamadaChain {
 
         let tx_header_hash = tx.header_hash().to_string();
         let response = rt
-            .block_on(self.ctx.submit(tx, &tx_args))
+            .block_on(self.ctx.submit(tx, tx_args))
             .map_err(NamadaError::namada)?;
 
         match response {
             tx::ProcessTxResponse::Broadcast(mut response) => {
                 // overwrite the tx decrypted hash for the tx query
                 response.hash = tx_header_hash.parse().expect("invalid hash");
+
+                if let Some(fee_token) = fee_token.filter(|_| response.code.is_ok()) {
+                    crate::telemetry!(fee_token_selected, &self.config.id, &fee_token.to_string());
+                }
+
                 Ok(response)
             }
             _ => unreachable!("The response type was unexpected"),
@@ -154,17 +242,15 @@ impl NamadaChain {
         Ok(())
     }
 
-    fn estimate_fee(
+    fn estimate_tx_fee(
         &self,
         mut tx: tx::Tx,
         args: &TxArgs,
         signing_data: &signing::SigningTxData,
     ) -> Result<Option<(Address, u64, f64)>, Error> {
         let chain_id = self.config.id.clone();
-        let fee_token_str = self.config.gas_price.denom.clone();
-        let fee_token = Address::from_str(&fee_token_str)
-            .map_err(|_| NamadaError::address_decode(fee_token_str.clone()))?;
-        let gas_price = self.config.gas_price.price;
+        let fee_tokens = self.fee_token_candidates()?;
+        let simulation_fee_token = &fee_tokens[0];
 
         let max_block_gas_key = namada_sdk::parameters::storage::get_max_block_gas_key();
         let max_block_gas: u64 = self
@@ -178,7 +264,13 @@ impl NamadaChain {
 
         let args = args.clone().dry_run_wrapper(true);
         // Set the max gas to the gas limit for the simulation
-        self.prepare_tx_with_gas(&mut tx, &args, &fee_token, max_block_gas, gas_price)?;
+        self.prepare_tx_with_gas(
+            &mut tx,
+            &args,
+            &simulation_fee_token.token,
+            max_block_gas,
+            simulation_fee_token.gas_price,
+        )?;
 
         self.rt
             .block_on(self.ctx.sign(
@@ -238,14 +330,17 @@ impl NamadaChain {
             gas_amount: estimated_gas,
         });
 
+        let fee_token = self.select_fee_token(&fee_tokens, adjusted_gas)?;
+
         debug!(
             id = %chain_id,
-            "send_tx: using {} gas, gas_price {:?}",
+            "send_tx: using {} gas, fee token {}, gas_price {:?}",
             estimated_gas,
-            gas_price,
+            fee_token.token,
+            fee_token.gas_price,
         );
 
-        Ok(Some((fee_token, adjusted_gas, gas_price)))
+        Ok(Some((fee_token.token, adjusted_gas, fee_token.gas_price)))
     }
 
     fn prepare_tx_with_gas(
@@ -6,7 +6,9 @@ use anyhow::Context;
 use futures::{FutureExt, StreamExt, TryStreamExt};
 use http::Uri;
 use ibc_proto::ics23;
+use ibc_proto::Protobuf;
 use ibc_relayer_types::applications::ics28_ccv::msgs::ConsumerChain;
+use ibc_relayer_types::applications::ics29_fee::packet_fee::IdentifiedPacketFees;
 use ibc_relayer_types::core::ics02_client;
 use ibc_relayer_types::core::ics04_channel;
 use pbjson_types;
@@ -35,6 +37,7 @@ use std::thread;
 use std::time::Duration;
 use tracing::info;
 
+use crate::chain::capabilities::{Capability, ChainCapabilities};
 use crate::chain::client::ClientSettings;
 use crate::chain::endpoint::ChainStatus;
 use crate::chain::requests::*;
@@ -65,7 +68,10 @@ use ibc_relayer_types::core::ics03_connection::connection::{
 use ibc_relayer_types::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
 use ibc_relayer_types::core::ics04_channel::packet::Sequence;
 use ibc_relayer_types::core::ics23_commitment::merkle::MerkleProof;
-use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
+use ibc_relayer_types::core::ics24_host::path::{
+    ChannelUpgradeErrorPath, ChannelUpgradePath, ClientUpgradePath, Path,
+};
 use ibc_relayer_types::Height as ICSHeight;
 use penumbra_sdk_fee::FeeTier;
 use penumbra_sdk_ibc::IbcRelay;
@@ -88,7 +94,7 @@ use signature::rand_core::OsRng;
 
 use tendermint::time::Time as TmTime;
 use tendermint_light_client::verifier::types::LightBlock as TmLightBlock;
-use tendermint_rpc::{Client as _, HttpClient};
+use tendermint_rpc::{client::CompatMode, Client as _, HttpClient};
 use tokio::runtime::Runtime as TokioRuntime;
 use tokio::sync::Mutex;
 use tonic::IntoRequest;
@@ -106,6 +112,7 @@ use crate::{
 };
 
 use crate::chain::penumbra::config::PenumbraConfig;
+use crate::chain::penumbra::util::compat_mode_from_version;
 
 pub struct PenumbraChain {
     config: PenumbraConfig,
@@ -120,6 +127,7 @@ pub struct PenumbraChain {
 
     tendermint_rpc_client: HttpClient,
     tendermint_light_client: TmLightClient,
+    compat_mode: CompatMode,
 
     tx_monitor_cmd: Option<TxEventSourceCmd>,
 
@@ -148,7 +156,14 @@ impl PenumbraChain {
                 *max_retries,
                 self.rt.clone(),
             ),
-            _ => unimplemented!(),
+            Mode::Push { url, batch_delay } => EventSource::penumbra_websocket(
+                self.config.id.clone(),
+                url.clone(),
+                self.compat_mode,
+                *batch_delay,
+                self.tendermint_rpc_client.clone(),
+                self.rt.clone(),
+            ),
         }
         .map_err(Error::event_source)?;
 
@@ -441,6 +456,34 @@ impl PenumbraChain {
         Ok(id)
     }
 
+    /// Query the value of the given key of the Penumbra state at the given height,
+    /// along with its proof, through the `state/key` ABCI query.
+    fn query_state_key(
+        &self,
+        key: String,
+        query_height: ICSHeight,
+    ) -> Result<(Vec<u8>, MerkleProof), Error> {
+        use crate::chain::cosmos::query::abci_query;
+
+        let response = self.rt.block_on(abci_query(
+            &self.tendermint_rpc_client,
+            &self.config.rpc_addr,
+            STATE_KEY_QUERY_PATH.to_string(),
+            hex::encode(key),
+            query_height.into(),
+            true,
+        ))?;
+
+        // Penumbra answers with an empty value and a proof of non-existence for missing keys
+        if response.value.is_empty() {
+            return Err(Error::empty_response_value());
+        }
+
+        let proof = response.proof.ok_or_else(Error::empty_response_proof)?;
+
+        Ok((response.value, proof))
+    }
+
     async fn query_balance(
         &self,
         address_index: AddressIndex,
@@ -469,6 +512,60 @@ impl PenumbraChain {
 
         Err(anyhow::anyhow!("denom not found"))
     }
+
+    async fn query_all_balances(
+        &self,
+        address_index: AddressIndex,
+    ) -> Result<Vec<crate::account::Balance>, anyhow::Error> {
+        let mut view_client = self.view_client.lock().await.clone();
+        let assets = ViewClient::assets(&mut view_client).await?;
+        let balances = ViewClient::balances(&mut view_client, address_index, None).await?;
+
+        let balances = balances
+            .into_iter()
+            .map(|(id, amount)| {
+                // Fall back on the asset id for assets without known metadata
+                let denom = assets
+                    .get(&id)
+                    .map(|metadata| metadata.base_denom().denom)
+                    .unwrap_or_else(|| id.to_string());
+
+                crate::account::Balance {
+                    amount: amount.to_string(),
+                    denom,
+                }
+            })
+            .collect();
+
+        Ok(balances)
+    }
+
+    /// Look up the metadata of the given asset, identified either by its
+    /// asset id or by its base denom, and map it onto a denom trace.
+    async fn query_denom_trace(
+        &self,
+        denom: &str,
+    ) -> Result<crate::denom::DenomTrace, anyhow::Error> {
+        let mut view_client = self.view_client.lock().await.clone();
+        let assets = ViewClient::assets(&mut view_client).await?;
+
+        let metadata = assets
+            .values()
+            .find(|metadata| {
+                metadata.id().to_string() == denom || metadata.base_denom().denom == denom
+            })
+            .ok_or_else(|| anyhow::anyhow!("denom not found"))?;
+
+        let denom_trace = match metadata.ibc_transfer_path()? {
+            Some((path, base_denom)) => crate::denom::DenomTrace { path, base_denom },
+            None => crate::denom::DenomTrace {
+                path: String::new(),
+                base_denom: metadata.base_denom().denom,
+            },
+        };
+
+        Ok(denom_trace)
+    }
 }
 
 impl ChainEndpoint for PenumbraChain {
@@ -493,11 +590,14 @@ impl ChainEndpoint for PenumbraChain {
             return Err(Error::config(ConfigError::wrong_type()));
         };
 
-        let rpc_client = HttpClient::new(config.rpc_addr.clone())
+        let mut rpc_client = HttpClient::new(config.rpc_addr.clone())
             .map_err(|e| Error::rpc(config.rpc_addr.clone(), e))?;
 
         let node_info = rt.block_on(fetch_node_info(&rpc_client, &config))?;
 
+        let compat_mode = compat_mode_from_version(&config.compat_mode, node_info.version.clone())?;
+        rpc_client.set_compat_mode(compat_mode);
+
         let fvk = config.kms_config.spend_key.full_viewing_key();
 
         // Identify filepath for storing Penumbra view database locally.
@@ -597,6 +697,7 @@ impl ChainEndpoint for PenumbraChain {
             custody_client,
             tendermint_rpc_client: rpc_client,
             tendermint_light_client,
+            compat_mode,
             tx_monitor_cmd: None,
 
             ibc_client_grpc_client,
@@ -677,6 +778,20 @@ impl ChainEndpoint for PenumbraChain {
         }))
     }
 
+    fn capabilities(&self) -> ChainCapabilities {
+        ChainCapabilities {
+            fees: false,
+            cross_chain_queries: false,
+            ccv: false,
+            interchain_accounts: false,
+            nft_transfers: false,
+            ibc_v2: false,
+            fee_estimation: false,
+            shielded_transfers: false,
+            ..ChainCapabilities::all()
+        }
+    }
+
     fn send_messages_and_wait_commit(
         &mut self,
         tracked_msgs: TrackedMsgs,
@@ -709,6 +824,46 @@ impl ChainEndpoint for PenumbraChain {
         Ok(vec![res])
     }
 
+    fn estimate_fee(
+        &mut self,
+        _tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<ibc_relayer_types::applications::transfer::RawCoin>, Error> {
+        Err(Error::unsupported_capability(
+            self.config.id.clone(),
+            Capability::FeeEstimation,
+        ))
+    }
+
+    fn query_tx_fee(
+        &self,
+        _tx_hash: QueryTxHash,
+    ) -> Result<Vec<ibc_relayer_types::applications::transfer::RawCoin>, Error> {
+        Err(Error::unsupported_capability(
+            self.config.id.clone(),
+            Capability::FeeEstimation,
+        ))
+    }
+
+    fn build_ibc_shielding(
+        &self,
+        _request: crate::transfer::IbcShieldingRequest,
+    ) -> Result<crate::transfer::IbcShielding, Error> {
+        Err(Error::unsupported_capability(
+            self.config.id.clone(),
+            Capability::ShieldedTransfers,
+        ))
+    }
+
+    fn send_shielded_transfer(
+        &mut self,
+        _request: crate::transfer::ShieldedTransferRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        Err(Error::unsupported_capability(
+            self.config.id.clone(),
+            Capability::ShieldedTransfers,
+        ))
+    }
+
     fn verify_header(
         &mut self,
         trusted: ibc_relayer_types::Height,
@@ -763,11 +918,33 @@ impl ChainEndpoint for PenumbraChain {
         &self,
         _key_name: Option<&str>,
     ) -> Result<Vec<crate::account::Balance>, Error> {
-        unimplemented!("cannot query balance of a shielded chain >:}}")
+        self.rt
+            .block_on(self.query_all_balances(AddressIndex::new(0)))
+            .map_err(|e| Error::temp_penumbra_error(e.to_string()))
+    }
+
+    fn query_denom_trace(&self, hash: String) -> Result<crate::denom::DenomTrace, Error> {
+        self.rt
+            .block_on(self.query_denom_trace(&hash))
+            .map_err(|e| Error::temp_penumbra_error(e.to_string()))
     }
 
-    fn query_denom_trace(&self, _hash: String) -> Result<crate::denom::DenomTrace, Error> {
-        todo!("penumbra doesn't support denom trace querying yet")
+    fn query_class_trace(&self, _hash: String) -> Result<crate::nft_transfer::ClassTrace, Error> {
+        Err(Error::unsupported_capability(
+            self.config.id.clone(),
+            Capability::NftTransfers,
+        ))
+    }
+
+    fn query_interchain_account(
+        &self,
+        _owner: ibc_relayer_types::signer::Signer,
+        _connection_id: ibc_relayer_types::core::ics24_host::identifier::ConnectionId,
+    ) -> Result<String, Error> {
+        Err(Error::unsupported_capability(
+            self.config.id.clone(),
+            Capability::InterchainAccounts,
+        ))
     }
 
     fn query_commitment_prefix(
@@ -1002,16 +1179,62 @@ impl ChainEndpoint for PenumbraChain {
 
     fn query_upgraded_client_state(
         &self,
-        _request: QueryUpgradedClientStateRequest,
+        request: QueryUpgradedClientStateRequest,
     ) -> Result<(AnyClientState, MerkleProof), Error> {
-        todo!("need to implement corresponding state query in penumbra")
+        crate::time!(
+            "query_upgraded_client_state",
+            {
+                "src_chain": self.config().id().to_string(),
+            }
+        );
+        crate::telemetry!(query, self.id(), "query_upgraded_client_state");
+
+        let upgrade_height = request.upgrade_height;
+        let query_height = upgrade_height
+            .decrement()
+            .map_err(|_| Error::invalid_height_no_source())?;
+
+        let (upgraded_client_state_raw, proof) = self.query_state_key(
+            upgrade_key(ClientUpgradePath::UpgradedClientState(
+                upgrade_height.revision_height(),
+            )),
+            query_height,
+        )?;
+
+        let client_state = AnyClientState::decode_vec(&upgraded_client_state_raw)
+            .map_err(Error::conversion_from_any)?;
+
+        Ok((client_state, proof))
     }
 
     fn query_upgraded_consensus_state(
         &self,
-        _request: QueryUpgradedConsensusStateRequest,
+        request: QueryUpgradedConsensusStateRequest,
     ) -> Result<(AnyConsensusState, MerkleProof), Error> {
-        todo!("need to implement corresponding state query in penumbra")
+        crate::time!(
+            "query_upgraded_consensus_state",
+            {
+                "src_chain": self.config().id().to_string(),
+            }
+        );
+        crate::telemetry!(query, self.id(), "query_upgraded_consensus_state");
+
+        let upgrade_height = request.upgrade_height;
+        let query_height = upgrade_height
+            .decrement()
+            .map_err(|_| Error::invalid_height_no_source())?;
+
+        let (upgraded_consensus_state_raw, proof) = self.query_state_key(
+            upgrade_key(ClientUpgradePath::UpgradedClientConsensusState(
+                upgrade_height.revision_height(),
+            )),
+            query_height,
+        )?;
+
+        let consensus_state = AnyConsensusState::decode_vec(&upgraded_consensus_state_raw)
+            .map_err(Error::conversion_from_any)?;
+
+        Ok((consensus_state, proof))
     }
 
     fn query_connections(
@@ -1487,6 +1710,59 @@ impl ChainEndpoint for PenumbraChain {
         Ok((acks_sequences, height))
     }
 
+    fn query_packet_commitment_v2(
+        &self,
+        _request: QueryPacketCommitmentV2Request,
+        _include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        Err(Error::unsupported_capability(
+            self.config.id.clone(),
+            Capability::IbcV2,
+        ))
+    }
+
+    fn query_packet_receipt_v2(
+        &self,
+        _request: QueryPacketReceiptV2Request,
+        _include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        Err(Error::unsupported_capability(
+            self.config.id.clone(),
+            Capability::IbcV2,
+        ))
+    }
+
+    fn query_packet_acknowledgement_v2(
+        &self,
+        _request: QueryPacketAcknowledgementV2Request,
+        _include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        Err(Error::unsupported_capability(
+            self.config.id.clone(),
+            Capability::IbcV2,
+        ))
+    }
+
+    fn query_packet_commitments_v2(
+        &self,
+        _request: QueryPacketCommitmentsV2Request,
+    ) -> Result<(Vec<Sequence>, ICSHeight), Error> {
+        Err(Error::unsupported_capability(
+            self.config.id.clone(),
+            Capability::IbcV2,
+        ))
+    }
+
+    fn query_packet_events_v2(
+        &self,
+        _request: QueryPacketEventDataV2Request,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        Err(Error::unsupported_capability(
+            self.config.id.clone(),
+            Capability::IbcV2,
+        ))
+    }
+
     fn query_unreceived_acknowledgements(
         &self,
         request: QueryUnreceivedAcksRequest,
@@ -1568,13 +1844,16 @@ impl ChainEndpoint for PenumbraChain {
     }
 
     fn query_txs(&self, request: QueryTxRequest) -> Result<Vec<IbcEventWithHeight>, Error> {
+        use crate::chain::cosmos::backend::CosmosSdkBackend;
         use crate::chain::cosmos::query::tx::query_txs;
 
         self.rt.block_on(query_txs(
             self.id(),
             &self.tendermint_rpc_client,
             &self.config.rpc_addr,
+            &CosmosSdkBackend,
             request,
+            None,
         ))
     }
 
@@ -1582,6 +1861,7 @@ impl ChainEndpoint for PenumbraChain {
         &self,
         mut request: QueryPacketEventDataRequest,
     ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        use crate::chain::cosmos::backend::CosmosSdkBackend;
         use crate::chain::cosmos::query::tx::{query_packets_from_block, query_packets_from_txs};
 
         match request.height {
@@ -1592,6 +1872,7 @@ impl ChainEndpoint for PenumbraChain {
                 self.id(),
                 &self.tendermint_rpc_client,
                 &self.config.rpc_addr,
+                &CosmosSdkBackend,
                 &request,
             )),
             Qualified::SmallerEqual(_) => {
@@ -1599,6 +1880,7 @@ impl ChainEndpoint for PenumbraChain {
                     self.id(),
                     &self.tendermint_rpc_client,
                     &self.config.rpc_addr,
+                    &CosmosSdkBackend,
                     &request,
                 ))?;
 
@@ -1642,9 +1924,32 @@ impl ChainEndpoint for PenumbraChain {
 
     fn query_host_consensus_state(
         &self,
-        _request: QueryHostConsensusStateRequest,
+        request: QueryHostConsensusStateRequest,
     ) -> Result<Self::ConsensusState, Error> {
-        todo!("need to implement corresponding state query on penumbra")
+        crate::time!(
+            "query_host_consensus_state",
+            {
+                "src_chain": self.config().id().to_string(),
+            }
+        );
+
+        let header = match request.height {
+            QueryHeight::Latest => self.rt.block_on(async {
+                self.tendermint_rpc_client
+                    .latest_block()
+                    .await
+                    .map(|response| response.block.header)
+            }),
+            QueryHeight::Specific(height) => self.rt.block_on(async {
+                self.tendermint_rpc_client
+                    .header(tendermint::block::Height::from(height))
+                    .await
+                    .map(|response| response.header)
+            }),
+        };
+
+        let header = header.map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+        Ok(header.into())
     }
 
     fn build_client_state(
@@ -1667,7 +1972,10 @@ impl ChainEndpoint for PenumbraChain {
             settings.max_clock_drift,
             height,
             proof_specs.into(),
-            vec!["upgrade".to_string(), "upgradedIBCState".to_string()],
+            vec![
+                UPGRADE_SUBSTORE_PREFIX.to_string(),
+                "upgradedIBCState".to_string(),
+            ],
             AllowUpdate {
                 after_expiry: true,
                 after_misbehaviour: true,
@@ -1711,7 +2019,10 @@ impl ChainEndpoint for PenumbraChain {
     ) -> Result<(), Error> {
         // the payee is an optional payee to which reverse and timeout relayer packet
         // fees will be paid out.
-        todo!("currently unimplemented in penumbra")
+        Err(Error::unsupported_capability(
+            self.config.id.clone(),
+            Capability::Fees,
+        ))
     }
 
     fn cross_chain_query(
@@ -1722,7 +2033,10 @@ impl ChainEndpoint for PenumbraChain {
         Error,
     > {
         // https://github.com/cosmos/ibc/blob/main/spec/app/ics-031-crosschain-queries/README.md
-        todo!("not currently implemented in penumbra")
+        Err(Error::unsupported_capability(
+            self.config.id.clone(),
+            Capability::CrossChainQueries,
+        ))
     }
 
     fn query_incentivized_packet(
@@ -1730,18 +2044,35 @@ impl ChainEndpoint for PenumbraChain {
         _request: ibc_proto::ibc::apps::fee::v1::QueryIncentivizedPacketRequest,
     ) -> Result<ibc_proto::ibc::apps::fee::v1::QueryIncentivizedPacketResponse, Error> {
         // https://buf.build/cosmos/ibc/docs/e769eb46d1e742e8b0368510479161df:ibc.applications.fee.v1#ibc.applications.fee.v1.Query.IncentivizedPackets
-        unimplemented!("not implemented in penumbra")
+        Err(Error::unsupported_capability(
+            self.config.id.clone(),
+            Capability::Fees,
+        ))
+    }
+
+    fn query_incentivized_packets(
+        &self,
+        _channel_id: ChannelId,
+        _port_id: PortId,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error> {
+        Err(Error::unsupported_capability(
+            self.config.id.clone(),
+            Capability::Fees,
+        ))
     }
 
     fn query_consumer_chains(&self) -> Result<Vec<ConsumerChain>, Error> {
-        unimplemented!("not currently implemented in penumbra")
+        Err(Error::unsupported_capability(
+            self.config.id.clone(),
+            Capability::Ccv,
+        ))
     }
 
     fn query_upgrade(
         &self,
-        _request: ibc_proto::ibc::core::channel::v1::QueryUpgradeRequest,
-        _height: ibc_relayer_types::core::ics02_client::height::Height,
-        _include_proof: IncludeProof,
+        request: ibc_proto::ibc::core::channel::v1::QueryUpgradeRequest,
+        height: ibc_relayer_types::core::ics02_client::height::Height,
+        include_proof: IncludeProof,
     ) -> Result<
         (
             ibc_relayer_types::core::ics04_channel::upgrade::Upgrade,
@@ -1749,14 +2080,35 @@ impl ChainEndpoint for PenumbraChain {
         ),
         Error,
     > {
-        unimplemented!("not currently implemented in penumbra")
+        crate::telemetry!(query, self.id(), "query_upgrade");
+
+        let port_id = PortId::from_str(&request.port_id)
+            .map_err(|_| Error::invalid_port_string(request.port_id))?;
+        let channel_id = ChannelId::from_str(&request.channel_id)
+            .map_err(|_| Error::invalid_channel_string(request.channel_id))?;
+
+        let (value, proof) = self.query_state_key(
+            ibc_key(ChannelUpgradePath {
+                port_id,
+                channel_id,
+            }),
+            height,
+        )?;
+
+        let upgrade = ibc_relayer_types::core::ics04_channel::upgrade::Upgrade::decode_vec(&value)
+            .map_err(Error::decode)?;
+
+        match include_proof {
+            IncludeProof::Yes => Ok((upgrade, Some(proof))),
+            IncludeProof::No => Ok((upgrade, None)),
+        }
     }
 
     fn query_upgrade_error(
         &self,
-        _request: ibc_proto::ibc::core::channel::v1::QueryUpgradeErrorRequest,
-        _height: ibc_relayer_types::core::ics02_client::height::Height,
-        _include_proof: IncludeProof,
+        request: ibc_proto::ibc::core::channel::v1::QueryUpgradeErrorRequest,
+        height: ibc_relayer_types::core::ics02_client::height::Height,
+        include_proof: IncludeProof,
     ) -> Result<
         (
             ibc_relayer_types::core::ics04_channel::upgrade::ErrorReceipt,
@@ -1764,14 +2116,39 @@ impl ChainEndpoint for PenumbraChain {
         ),
         Error,
     > {
-        unimplemented!("not currently implemented in penumbra")
+        crate::telemetry!(query, self.id(), "query_upgrade_error");
+
+        let port_id = PortId::from_str(&request.port_id)
+            .map_err(|_| Error::invalid_port_string(request.port_id))?;
+        let channel_id = ChannelId::from_str(&request.channel_id)
+            .map_err(|_| Error::invalid_channel_string(request.channel_id))?;
+
+        let (value, proof) = self.query_state_key(
+            ibc_key(ChannelUpgradeErrorPath {
+                port_id,
+                channel_id,
+            }),
+            height,
+        )?;
+
+        let error_receipt =
+            ibc_relayer_types::core::ics04_channel::upgrade::ErrorReceipt::decode_vec(&value)
+                .map_err(Error::decode)?;
+
+        match include_proof {
+            IncludeProof::Yes => Ok((error_receipt, Some(proof))),
+            IncludeProof::No => Ok((error_receipt, None)),
+        }
     }
 
     fn query_ccv_consumer_id(
         &self,
         _client_id: ClientId,
     ) -> Result<ibc_relayer_types::applications::ics28_ccv::msgs::ConsumerId, Error> {
-        unimplemented!("not currently implemented in penumbra")
+        Err(Error::unsupported_capability(
+            self.config.id.clone(),
+            Capability::Ccv,
+        ))
     }
 }
 
@@ -1799,6 +2176,23 @@ fn decode_merkle_proof(proof_bytes: Vec<u8>) -> Result<MerkleProof, Error> {
     Ok(proof)
 }
 
+/// ABCI query path for the keys of the Penumbra state
+const STATE_KEY_QUERY_PATH: &str = "state/key";
+
+/// Prefix of the keys under which the upgraded IBC client and consensus states are
+/// stored, as per the upgrade path of the client states built for Penumbra.
+const UPGRADE_SUBSTORE_PREFIX: &str = "upgrade";
+
+/// The key of the given IBC path in the IBC substore of the Penumbra state
+fn ibc_key(path: impl Into<Path>) -> String {
+    format!("{}/{}", penumbra_sdk_ibc::IBC_SUBSTORE_PREFIX, path.into())
+}
+
+/// The key of the given upgraded IBC state in the Penumbra state
+fn upgrade_key(path: ClientUpgradePath) -> String {
+    format!("{UPGRADE_SUBSTORE_PREFIX}/{}", Path::Upgrade(path))
+}
+
 const LEAF_DOMAIN_SEPARATOR: &[u8] = b"JMT::LeafNode";
 const INTERNAL_DOMAIN_SEPARATOR: &[u8] = b"JMT::IntrnalNode";
 
@@ -1857,3 +2251,65 @@ fn sort_events_by_sequence(events: &mut [IbcEventWithHeight]) {
             .unwrap_or(Ordering::Equal)
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use penumbra_sdk_ibc::{MerklePrefixExt, IBC_COMMITMENT_PREFIX};
+
+    fn port_and_channel() -> (PortId, ChannelId) {
+        (
+            PortId::from_str("transfer").unwrap(),
+            ChannelId::from_str("channel-0").unwrap(),
+        )
+    }
+
+    #[test]
+    fn channel_upgrade_keys_are_in_the_ibc_substore() {
+        let (port_id, channel_id) = port_and_channel();
+
+        let upgrade_path = ChannelUpgradePath {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+        };
+        let error_path = ChannelUpgradeErrorPath {
+            port_id,
+            channel_id,
+        };
+
+        assert_eq!(
+            ibc_key(upgrade_path.clone()),
+            "ibc-data/channelUpgrades/upgrades/ports/transfer/channels/channel-0"
+        );
+        assert_eq!(
+            ibc_key(error_path),
+            "ibc-data/channelUpgrades/upgradeError/ports/transfer/channels/channel-0"
+        );
+
+        // Same key as the one the Penumbra IBC component commits to
+        assert_eq!(
+            ibc_key(upgrade_path.clone()),
+            IBC_COMMITMENT_PREFIX.apply_string(Path::from(upgrade_path).to_string())
+        );
+    }
+
+    #[test]
+    fn upgraded_client_keys_follow_the_upgrade_path() {
+        // The upgrade path set in the client states built for Penumbra, whose
+        // last element is replaced by the upgrade path of each state
+        let upgrade_path = [UPGRADE_SUBSTORE_PREFIX, "upgradedIBCState"];
+
+        assert_eq!(
+            upgrade_key(ClientUpgradePath::UpgradedClientState(42)),
+            format!("{}/{}/42/upgradedClient", upgrade_path[0], upgrade_path[1])
+        );
+        assert_eq!(
+            upgrade_key(ClientUpgradePath::UpgradedClientConsensusState(42)),
+            format!(
+                "{}/{}/42/upgradedConsState",
+                upgrade_path[0], upgrade_path[1]
+            )
+        );
+    }
+}
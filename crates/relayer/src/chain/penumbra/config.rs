@@ -2,6 +2,7 @@ use core::time::Duration;
 
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use penumbra_sdk_custody::soft_kms;
+use penumbra_sdk_keys::keys::AddressIndex;
 use serde_derive::{Deserialize, Serialize};
 use tendermint_rpc::Url;
 
@@ -86,3 +87,12 @@ pub struct PenumbraConfig {
     /// this with the Hermes keyring, but it's a low-priority item.
     pub kms_config: soft_kms::Config,
 }
+
+impl PenumbraConfig {
+    /// The address of the relayer's account, i.e. the address with index 0 of the
+    /// configured spend key, which holds the notes used to pay for the fees.
+    pub fn address(&self) -> String {
+        let fvk = self.kms_config.spend_key.full_viewing_key();
+        fvk.payment_address(AddressIndex::new(0)).0.to_string()
+    }
+}
@@ -34,6 +34,7 @@ use ibc_proto::{
     },
 };
 use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics04_channel::v2::raw::QueryPacketCommitmentsRequest as RawQueryPacketCommitmentsV2Request;
 use ibc_relayer_types::core::ics24_host::identifier::{
     ChainId, ChannelId, ClientId, ConnectionId, PortId,
 };
@@ -458,6 +459,63 @@ impl From<QueryPacketAcknowledgementRequest> for RawQueryPacketAcknowledgementRe
     }
 }
 
+/// ABCI query to fetch the commitment of an IBC v2 packet
+/// sent over the client with the given identifier.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueryPacketCommitmentV2Request {
+    pub client_id: ClientId,
+    pub sequence: Sequence,
+    pub height: QueryHeight,
+}
+
+/// ABCI query to fetch the receipt of an IBC v2 packet
+/// received over the client with the given identifier.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueryPacketReceiptV2Request {
+    pub client_id: ClientId,
+    pub sequence: Sequence,
+    pub height: QueryHeight,
+}
+
+/// ABCI query to fetch the acknowledgement of an IBC v2 packet
+/// received over the client with the given identifier.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueryPacketAcknowledgementV2Request {
+    pub client_id: ClientId,
+    pub sequence: Sequence,
+    pub height: QueryHeight,
+}
+
+/// gRPC query to fetch the sequences of the IBC v2 packets sent over the
+/// client with the given identifier whose commitment is still stored,
+/// ie. which were neither acknowledged nor timed out yet.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueryPacketCommitmentsV2Request {
+    pub client_id: ClientId,
+    pub pagination: Paginate,
+}
+
+impl From<QueryPacketCommitmentsV2Request> for RawQueryPacketCommitmentsV2Request {
+    fn from(request: QueryPacketCommitmentsV2Request) -> Self {
+        RawQueryPacketCommitmentsV2Request {
+            client_id: request.client_id.to_string(),
+            pagination: Some(PageRequest::from(request.pagination).into()),
+        }
+    }
+}
+
+/// Used to query the IBC v2 packet events of type `event_id` with sequences in `sequences`.
+///
+/// `client_id` is matched against the source client of `SendPacket` events
+/// and against the destination client of `WriteAck` events, ie. it is always
+/// the client on the chain which emitted the events.
+#[derive(Clone, Debug)]
+pub struct QueryPacketEventDataV2Request {
+    pub event_id: WithBlockDataType,
+    pub client_id: ClientId,
+    pub sequences: Vec<Sequence>,
+}
+
 /// gRPC query to fetch all packet acknowledgements associated with the specified channel.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct QueryPacketAcknowledgementsRequest {
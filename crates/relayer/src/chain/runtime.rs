@@ -9,10 +9,12 @@ use ibc_proto::ibc::{
     apps::fee::v1::{QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse},
     core::channel::v1::{QueryUpgradeErrorRequest, QueryUpgradeRequest},
 };
+use ibc_relayer_types::applications::ics29_fee::packet_fee::IdentifiedPacketFees;
 use ibc_relayer_types::{
     applications::{
         ics28_ccv::msgs::{ConsumerChain, ConsumerId},
         ics31_icq::response::CrossChainQueryResponse,
+        transfer::RawCoin,
     },
     core::{
         ics02_client::{events::UpdateClient, header::AnyHeader},
@@ -24,6 +26,7 @@ use ibc_relayer_types::{
             channel::{ChannelEnd, IdentifiedChannelEnd},
             packet::{PacketMsgType, Sequence},
             upgrade::{ErrorReceipt, Upgrade},
+            v2::packet::PacketMsgType as PacketMsgTypeV2,
         },
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
         ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
@@ -44,9 +47,12 @@ use crate::{
     event::IbcEventWithHeight,
     keyring::AnySigningKeyPair,
     misbehaviour::MisbehaviourEvidence,
+    nft_transfer::ClassTrace,
+    transfer::{IbcShielding, IbcShieldingRequest, ShieldedTransferRequest},
 };
 
 use super::{
+    capabilities::ChainCapabilities,
     client::ClientSettings,
     endpoint::{ChainEndpoint, ChainStatus, HealthCheck},
     handle::{ChainHandle, ChainRequest, ReplyTo, Subscription},
@@ -94,6 +100,14 @@ where
         Ok(handle)
     }
 
+    /// Spawns a new runtime for an already bootstrapped chain, such as an
+    /// in-memory mock chain which is not described by a [`ChainConfig`].
+    pub fn spawn_endpoint<Handle: ChainHandle>(chain: Endpoint, rt: Arc<TokioRuntime>) -> Handle {
+        let (handle, _) = Self::init(chain, rt);
+
+        handle
+    }
+
     /// Initializes a runtime for a given chain, and spawns the associated thread
     fn init<Handle: ChainHandle>(
         chain: Endpoint,
@@ -174,6 +188,22 @@ where
                             self.send_messages_and_wait_check_tx(tracked_msgs, reply_to)?
                         },
 
+                        ChainRequest::EstimateFee { tracked_msgs, reply_to } => {
+                            self.estimate_fee(tracked_msgs, reply_to)?
+                        },
+
+                        ChainRequest::QueryTxFee { tx_hash, reply_to } => {
+                            self.query_tx_fee(tx_hash, reply_to)?
+                        },
+
+                        ChainRequest::BuildIbcShielding { request, reply_to } => {
+                            self.build_ibc_shielding(request, reply_to)?
+                        },
+
+                        ChainRequest::SendShieldedTransfer { request, reply_to } => {
+                            self.send_shielded_transfer(request, reply_to)?
+                        },
+
                         ChainRequest::Signer { reply_to } => {
                             self.get_signer(reply_to)?
                         },
@@ -194,6 +224,10 @@ where
                             self.version_specs(reply_to)?
                         },
 
+                        ChainRequest::Capabilities { reply_to } => {
+                            self.capabilities(reply_to)?
+                        },
+
                         ChainRequest::BuildHeader { trusted_height, target_height, client_state, reply_to } => {
                             self.build_header(trusted_height, target_height, client_state, reply_to)?
                         },
@@ -230,6 +264,14 @@ where
                             self.query_denom_trace(hash, reply_to)?
                         },
 
+                        ChainRequest::QueryClassTrace { hash, reply_to } => {
+                            self.query_class_trace(hash, reply_to)?
+                        },
+
+                        ChainRequest::QueryInterchainAccount { owner, connection_id, reply_to } => {
+                            self.query_interchain_account(owner, connection_id, reply_to)?
+                        },
+
                         ChainRequest::QueryApplicationStatus { reply_to } => {
                             self.query_application_status(reply_to)?
                         },
@@ -322,6 +364,30 @@ where
                             self.query_packet_acknowledgements(request, reply_to)?
                         },
 
+                        ChainRequest::BuildPacketV2Proofs { packet_type, client_id, sequence, height, reply_to } => {
+                            self.build_packet_v2_proofs(packet_type, client_id, sequence, height, reply_to)?
+                        },
+
+                        ChainRequest::QueryPacketCommitmentV2 { request, include_proof, reply_to } => {
+                            self.query_packet_commitment_v2(request, include_proof, reply_to)?
+                        },
+
+                        ChainRequest::QueryPacketReceiptV2 { request, include_proof, reply_to } => {
+                            self.query_packet_receipt_v2(request, include_proof, reply_to)?
+                        },
+
+                        ChainRequest::QueryPacketAcknowledgementV2 { request, include_proof, reply_to } => {
+                            self.query_packet_acknowledgement_v2(request, include_proof, reply_to)?
+                        },
+
+                        ChainRequest::QueryPacketCommitmentsV2 { request, reply_to } => {
+                            self.query_packet_commitments_v2(request, reply_to)?
+                        },
+
+                        ChainRequest::QueryPacketEventDataV2 { request, reply_to } => {
+                            self.query_packet_events_v2(request, reply_to)?
+                        },
+
                         ChainRequest::QueryUnreceivedAcknowledgement { request, reply_to } => {
                             self.query_unreceived_acknowledgement(request, reply_to)?
                         },
@@ -354,6 +420,10 @@ where
                             self.query_incentivized_packet(request, reply_to)?
                         },
 
+                        ChainRequest::QueryIncentivizedPackets { channel_id, port_id, reply_to } => {
+                            self.query_incentivized_packets(channel_id, port_id, reply_to)?
+                        },
+
                         ChainRequest::QueryConsumerChains { reply_to } => {
                             self.query_consumer_chains(reply_to)?
                         },
@@ -405,6 +475,42 @@ where
         reply_to.send(result).map_err(Error::send)
     }
 
+    fn estimate_fee(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+        reply_to: ReplyTo<Vec<RawCoin>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.estimate_fee(tracked_msgs);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn query_tx_fee(
+        &self,
+        tx_hash: QueryTxHash,
+        reply_to: ReplyTo<Vec<RawCoin>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_tx_fee(tx_hash);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn build_ibc_shielding(
+        &self,
+        request: IbcShieldingRequest,
+        reply_to: ReplyTo<IbcShielding>,
+    ) -> Result<(), Error> {
+        let result = self.chain.build_ibc_shielding(request);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn send_shielded_transfer(
+        &mut self,
+        request: ShieldedTransferRequest,
+        reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.send_shielded_transfer(request);
+        reply_to.send(result).map_err(Error::send)
+    }
+
     fn query_balance(
         &self,
         key_name: Option<String>,
@@ -432,6 +538,21 @@ where
         reply_to.send(denom_trace).map_err(Error::send)
     }
 
+    fn query_class_trace(&self, hash: String, reply_to: ReplyTo<ClassTrace>) -> Result<(), Error> {
+        let class_trace = self.chain.query_class_trace(hash);
+        reply_to.send(class_trace).map_err(Error::send)
+    }
+
+    fn query_interchain_account(
+        &self,
+        owner: Signer,
+        connection_id: ConnectionId,
+        reply_to: ReplyTo<String>,
+    ) -> Result<(), Error> {
+        let address = self.chain.query_interchain_account(owner, connection_id);
+        reply_to.send(address).map_err(Error::send)
+    }
+
     fn query_application_status(&self, reply_to: ReplyTo<ChainStatus>) -> Result<(), Error> {
         let latest_timestamp = self.chain.query_application_status();
         reply_to.send(latest_timestamp).map_err(Error::send)
@@ -470,6 +591,11 @@ where
         reply_to.send(result).map_err(Error::send)
     }
 
+    fn capabilities(&mut self, reply_to: ReplyTo<ChainCapabilities>) -> Result<(), Error> {
+        let result = Ok(self.chain.capabilities());
+        reply_to.send(result).map_err(Error::send)
+    }
+
     fn build_header(
         &mut self,
         trusted_height: Height,
@@ -776,6 +902,73 @@ where
         reply_to.send(result).map_err(Error::send)
     }
 
+    fn build_packet_v2_proofs(
+        &self,
+        packet_type: PacketMsgTypeV2,
+        client_id: ClientId,
+        sequence: Sequence,
+        height: Height,
+        reply_to: ReplyTo<Proofs>,
+    ) -> Result<(), Error> {
+        let result = self
+            .chain
+            .build_packet_v2_proofs(packet_type, client_id, sequence, height);
+
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn query_packet_commitment_v2(
+        &self,
+        request: QueryPacketCommitmentV2Request,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(Vec<u8>, Option<MerkleProof>)>,
+    ) -> Result<(), Error> {
+        let result = self
+            .chain
+            .query_packet_commitment_v2(request, include_proof);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn query_packet_receipt_v2(
+        &self,
+        request: QueryPacketReceiptV2Request,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(Vec<u8>, Option<MerkleProof>)>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_packet_receipt_v2(request, include_proof);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn query_packet_acknowledgement_v2(
+        &self,
+        request: QueryPacketAcknowledgementV2Request,
+        include_proof: IncludeProof,
+        reply_to: ReplyTo<(Vec<u8>, Option<MerkleProof>)>,
+    ) -> Result<(), Error> {
+        let result = self
+            .chain
+            .query_packet_acknowledgement_v2(request, include_proof);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn query_packet_commitments_v2(
+        &self,
+        request: QueryPacketCommitmentsV2Request,
+        reply_to: ReplyTo<(Vec<Sequence>, Height)>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_packet_commitments_v2(request);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn query_packet_events_v2(
+        &self,
+        request: QueryPacketEventDataV2Request,
+        reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_packet_events_v2(request);
+        reply_to.send(result).map_err(Error::send)
+    }
+
     fn query_unreceived_acknowledgement(
         &self,
         request: QueryUnreceivedAcksRequest,
@@ -871,6 +1064,18 @@ where
         Ok(())
     }
 
+    fn query_incentivized_packets(
+        &self,
+        channel_id: ChannelId,
+        port_id: PortId,
+        reply_to: ReplyTo<Vec<IdentifiedPacketFees>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_incentivized_packets(channel_id, port_id);
+        reply_to.send(result).map_err(Error::send)?;
+
+        Ok(())
+    }
+
     fn query_consumer_chains(&self, reply_to: ReplyTo<Vec<ConsumerChain>>) -> Result<(), Error> {
         let result = self.chain.query_consumer_chains();
         reply_to.send(result).map_err(Error::send)?;
@@ -3,11 +3,16 @@
 pub mod compat_mode;
 pub mod dynamic_gas;
 pub mod error;
+pub mod fee_granters;
 pub mod filter;
+pub mod gas_model;
 pub mod gas_multiplier;
+pub mod packet_priority;
 pub mod proof_specs;
 pub mod refresh_rate;
+pub mod tx_replacement;
 pub mod types;
+pub mod unordered_tx;
 
 use alloc::collections::BTreeMap;
 use core::cmp::Ordering;
@@ -16,7 +21,7 @@ use core::str::FromStr;
 use core::time::Duration;
 use ibc_relayer_types::core::ics04_channel::packet::Sequence;
 use std::borrow::Cow;
-use std::{fs, fs::File, io::Write, ops::Range, path::Path};
+use std::{fs, fs::File, io::Write, ops::Range, path::Path, path::PathBuf};
 
 use byte_unit::Byte;
 use serde::{Deserialize, Serialize};
@@ -35,14 +40,20 @@ use crate::config::types::TrustThreshold;
 use crate::error::Error as RelayerError;
 use crate::extension_options::ExtensionOptionDynamicFeeTx;
 use crate::keyring::{AnySigningKeyPair, KeyRing, Store};
+use crate::price::validate_prices;
 
 use crate::keyring;
 
 pub use crate::config::Error as ConfigError;
 pub use error::Error;
 
+pub use fee_granters::{ChannelFeeGranters, FeeGranters};
 pub use filter::PacketFilter;
+pub use gas_model::GasModelConfig;
+pub use packet_priority::PacketPriority;
 pub use refresh_rate::RefreshRate;
+pub use tx_replacement::TxReplacementConfig;
+pub use unordered_tx::UnorderedTxConfig;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GasPrice {
@@ -199,6 +210,10 @@ pub mod default {
         false
     }
 
+    pub fn min_witnesses() -> usize {
+        1
+    }
+
     pub fn connection_delay() -> Duration {
         ZERO_DURATION
     }
@@ -272,6 +287,10 @@ pub struct Config {
     pub chains: Vec<ChainConfig>,
     #[serde(default)]
     pub tracing_server: TracingServerConfig,
+    #[serde(default)]
+    pub prices: PricesConfig,
+    #[serde(default)]
+    pub fee_accounting: FeeAccountingConfig,
 }
 
 impl Config {
@@ -335,6 +354,9 @@ impl Config {
         // Check for invalid mode config
         self.mode.validate()?;
 
+        // Check for invalid prices
+        validate_prices(&self.prices.denoms).map_err(Diagnostic::Error)?;
+
         Ok(())
     }
 }
@@ -488,6 +510,32 @@ impl Display for LogLevel {
 #[serde(default, deny_unknown_fields)]
 pub struct GlobalConfig {
     pub log_level: LogLevel,
+    /// The folder holding the MASP parameters needed to build shielded
+    /// transfers on Namada chains. Defaults to the location used by the Namada SDK.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub masp_params_dir: Option<PathBuf>,
+}
+
+/// Prices of the denominations used for paying transaction fees and incentivizing
+/// packets, expressed in a common unit, eg. USD.
+///
+/// Prices listed in the configuration take precedence over the ones loaded from `file`.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PricesConfig {
+    pub file: Option<PathBuf>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub denoms: BTreeMap<String, f64>,
+}
+
+/// Accounting of the ICS-29 fees distributed to relayers on the incentivized channels.
+///
+/// The totals are persisted in `file`, which defaults to `$HOME/.hermes/fee_report.json`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeeAccountingConfig {
+    pub enabled: bool,
+    pub file: Option<PathBuf>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -680,6 +728,22 @@ impl ChainConfig {
         }
     }
 
+    pub fn packet_priority(&self) -> Cow<'_, PacketPriority> {
+        match self {
+            Self::CosmosSdk(config) | Self::Namada(config) => {
+                Cow::Borrowed(&config.packet_priority)
+            }
+            Self::Penumbra(_config) => Cow::Owned(PacketPriority::default()),
+        }
+    }
+
+    pub fn gas_model(&self) -> Cow<'_, GasModelConfig> {
+        match self {
+            Self::CosmosSdk(config) | Self::Namada(config) => Cow::Borrowed(&config.gas_model),
+            Self::Penumbra(_config) => Cow::Owned(GasModelConfig::default()),
+        }
+    }
+
     pub fn max_block_time(&self) -> Duration {
         match self {
             Self::CosmosSdk(config) => config.max_block_time,
@@ -994,6 +1058,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_witnesses() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let config = load(path).expect("could not parse config");
+
+        match (&config.chains[0], &config.chains[1]) {
+            (ChainConfig::CosmosSdk(chain_a), ChainConfig::CosmosSdk(chain_b)) => {
+                assert_eq!(chain_a.witnesses.len(), 2);
+                assert!(chain_b.witnesses.is_empty());
+            }
+            _ => panic!("expected cosmos chain configs"),
+        }
+    }
+
     #[test]
     fn serialize_valid_config() {
         let path = concat!(
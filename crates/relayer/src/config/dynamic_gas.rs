@@ -1,3 +1,5 @@
+use alloc::collections::BTreeMap;
+
 use serde::de::Error as DeserializeError;
 use serde::de::Unexpected;
 use serde::Deserialize;
@@ -15,11 +17,34 @@ flex_error::define_error! {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize)]
+/// The source from which the dynamic gas price of a chain is queried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DynamicGasPriceProvider {
+    /// Skip's `x/feemarket` module, which quotes the gas price in every accepted fee denom.
+    Feemarket,
+    /// Osmosis' `x/txfees` module, which quotes the EIP-1559 base fee in the native denom.
+    OsmosisTxfees,
+    /// Ethermint's (eg. Evmos) `x/feemarket` module, which quotes the EIP-1559 base fee
+    /// in the EVM denom.
+    EthermintFeemarket,
+    /// No query is performed, the configured `gas_price` is used as the base fee.
+    Static,
+}
+
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize)]
 pub struct DynamicGasPrice {
     pub enabled: bool,
     pub multiplier: f64,
     pub max: f64,
+    /// The provider of the gas price, which must be set when enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<DynamicGasPriceProvider>,
+    /// The denoms in which fees can be paid, mapped to their value in a common unit.
+    /// The fees are paid in whichever denom is the cheapest given its dynamic gas price.
+    /// If empty, the fees are paid in the denom of the configured `gas_price`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub denoms: BTreeMap<String, f64>,
 }
 
 impl DynamicGasPrice {
@@ -36,6 +61,8 @@ impl DynamicGasPrice {
             enabled: false,
             multiplier: Self::DEFAULT_MULTIPLIER,
             max: Self::DEFAULT_MAX,
+            provider: None,
+            denoms: BTreeMap::new(),
         }
     }
 
@@ -48,6 +75,8 @@ impl DynamicGasPrice {
             enabled,
             multiplier,
             max,
+            provider: None,
+            denoms: BTreeMap::new(),
         })
     }
 
@@ -57,8 +86,21 @@ impl DynamicGasPrice {
             enabled,
             multiplier,
             max,
+            provider: None,
+            denoms: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_provider(self, provider: DynamicGasPriceProvider) -> Self {
+        Self {
+            provider: Some(provider),
+            ..self
         }
     }
+
+    pub fn with_denoms(self, denoms: BTreeMap<String, f64>) -> Self {
+        Self { denoms, ..self }
+    }
 }
 
 impl Default for DynamicGasPrice {
@@ -77,24 +119,35 @@ impl<'de> Deserialize<'de> for DynamicGasPrice {
             enabled: bool,
             multiplier: f64,
             max: f64,
+            #[serde(default)]
+            provider: Option<DynamicGasPriceProvider>,
+            #[serde(default)]
+            denoms: BTreeMap<String, f64>,
         }
 
         let DynGas {
             enabled,
             multiplier,
             max,
+            provider,
+            denoms,
         } = DynGas::deserialize(deserializer)?;
 
-        DynamicGasPrice::new(enabled, multiplier, max).map_err(|e| match e.detail() {
-            ErrorDetail::MultiplierTooSmall(_) => D::Error::invalid_value(
-                Unexpected::Float(multiplier),
-                &format!(
-                    "a floating-point value greater than {}",
-                    Self::MIN_MULTIPLIER
-                )
-                .as_str(),
-            ),
-        })
+        DynamicGasPrice::new(enabled, multiplier, max)
+            .map(|dynamic_gas| DynamicGasPrice {
+                provider,
+                ..dynamic_gas.with_denoms(denoms)
+            })
+            .map_err(|e| match e.detail() {
+                ErrorDetail::MultiplierTooSmall(_) => D::Error::invalid_value(
+                    Unexpected::Float(multiplier),
+                    &format!(
+                        "a floating-point value greater than {}",
+                        Self::MIN_MULTIPLIER
+                    )
+                    .as_str(),
+                ),
+            })
     }
 }
 
@@ -124,6 +177,43 @@ mod tests {
         assert!(err.contains("expected a floating-point value greater than"));
     }
 
+    #[test]
+    fn parse_gas_price_provider() {
+        #[derive(Debug, Deserialize)]
+        struct DummyConfig {
+            dynamic_gas: DynamicGasPrice,
+        }
+
+        let config = toml::from_str::<DummyConfig>(
+            "dynamic_gas = { enabled = true, multiplier = 1.1, max = 0.6 }",
+        )
+        .unwrap();
+
+        assert_eq!(config.dynamic_gas.provider, None);
+        assert!(config.dynamic_gas.denoms.is_empty());
+
+        let config = toml::from_str::<DummyConfig>(
+            "dynamic_gas = { enabled = true, multiplier = 1.1, max = 0.6, provider = 'osmosis_txfees', denoms = { uosmo = 0.5, uatom = 8.0 } }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.dynamic_gas.provider,
+            Some(DynamicGasPriceProvider::OsmosisTxfees)
+        );
+        assert_eq!(config.dynamic_gas.denoms.get("uatom"), Some(&8.0));
+
+        let config = toml::from_str::<DummyConfig>(
+            "dynamic_gas = { enabled = true, multiplier = 1.1, max = 0.6, provider = 'feemarket' }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.dynamic_gas.provider,
+            Some(DynamicGasPriceProvider::Feemarket)
+        );
+    }
+
     #[test]
     fn safe_gas_multiplier() {
         let dynamic_gas = DynamicGasPrice::new(true, 0.6, 0.6);
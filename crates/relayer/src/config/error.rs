@@ -55,6 +55,12 @@ define_error! {
             format!("invalid gas price: {}", e.price)
         },
 
+        InvalidPrice
+            { denom: String, price: f64 }
+        |e| {
+            format!("invalid price {} for denomination '{}', prices must be finite and non-negative", e.price, e.denom)
+        },
+
         CosmosConfigError { reason: String }
         |e| {
             format!("invalid cosmos config: {}", e.reason)
@@ -0,0 +1,118 @@
+//! Configuration of the fee granters paying for the transactions
+//! which relay the packets of specific channels.
+
+use serde::{Deserialize, Serialize};
+
+use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
+
+use crate::config::filter::{ChannelFilterMatch, PortFilterMatch};
+
+/// The fee granters of the channels whose relaying is sponsored, as a list of
+/// port/channel filters along with their granters. The first entry matching a
+/// channel is used, so entries for specific channels must be listed before the
+/// wildcards also matching them. When several granters are configured for a
+/// channel, they are used in order, skipping those whose allowance is exhausted.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FeeGranters(Vec<ChannelFeeGranters>);
+
+/// The fee granters of the channels matching a port/channel filter.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelFeeGranters {
+    pub port: PortFilterMatch,
+    pub channel: ChannelFilterMatch,
+    pub granters: Vec<String>,
+}
+
+impl FeeGranters {
+    pub fn new(entries: Vec<ChannelFeeGranters>) -> Self {
+        Self(entries)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the fee granters of the first entry matching the given channel, if any.
+    pub fn granters(&self, port_id: &PortId, channel_id: &ChannelId) -> &[String] {
+        self.0
+            .iter()
+            .find(|entry| entry.port.matches(port_id) && entry.channel.matches(channel_id))
+            .map_or(&[], |entry| entry.granters.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_fee_granters() {
+        let toml_content = r#"
+            fee_granters = [
+                { port = 'transfer', channel = 'channel-0', granters = ['cosmos1exact'] },
+                { port = 'transfer', channel = 'channel-*', granters = ['cosmos1first', 'cosmos1second'] },
+            ]
+            "#;
+
+        #[derive(Deserialize)]
+        struct Config {
+            fee_granters: FeeGranters,
+        }
+
+        let granters = toml::from_str::<Config>(toml_content)
+            .expect("could not parse fee granters")
+            .fee_granters;
+
+        let transfer = PortId::transfer();
+        let channel = |id: &str| id.parse::<ChannelId>().unwrap();
+
+        assert_eq!(
+            granters.granters(&transfer, &channel("channel-0")),
+            ["cosmos1exact"]
+        );
+        assert_eq!(
+            granters.granters(&transfer, &channel("channel-12")),
+            ["cosmos1first", "cosmos1second"]
+        );
+        assert!(granters
+            .granters(&"icahost".parse().unwrap(), &channel("channel-0"))
+            .is_empty());
+        assert!(FeeGranters::default()
+            .granters(&transfer, &channel("channel-0"))
+            .is_empty());
+    }
+
+    #[test]
+    fn first_matching_fee_granters_are_used() {
+        let entry = |port: &str, channel: &str, granter: &str| ChannelFeeGranters {
+            port: toml::Value::String(port.to_string()).try_into().unwrap(),
+            channel: toml::Value::String(channel.to_string()).try_into().unwrap(),
+            granters: vec![granter.to_string()],
+        };
+
+        let granters = FeeGranters::new(vec![
+            entry("transfer", "channel-1*", "cosmos1specific"),
+            entry("*", "channel-*", "cosmos1any"),
+            entry("transfer", "channel-12", "cosmos1shadowed"),
+        ]);
+
+        let transfer = PortId::transfer();
+        let channel = |id: &str| id.parse::<ChannelId>().unwrap();
+
+        // Overlapping entries resolve to the first one matching the channel
+        assert_eq!(
+            granters.granters(&transfer, &channel("channel-12")),
+            ["cosmos1specific"]
+        );
+        assert_eq!(
+            granters.granters(&transfer, &channel("channel-2")),
+            ["cosmos1any"]
+        );
+        assert_eq!(
+            granters.granters(&"icahost".parse().unwrap(), &channel("channel-12")),
+            ["cosmos1any"]
+        );
+    }
+}
@@ -9,7 +9,10 @@ use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
 use std::hash::Hash;
 
-use ibc_relayer_types::applications::transfer::RawCoin;
+use ibc_proto::ibc::applications::transfer::v2::FungibleTokenPacketData as RawPacketData;
+use ibc_relayer_types::applications::ics721_nft::packet::PacketData as NftPacketData;
+use ibc_relayer_types::applications::transfer::packet::PacketDataV2;
+use ibc_relayer_types::applications::transfer::{Amount, RawCoin};
 use ibc_relayer_types::bigint::U256;
 use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
 use ibc_relayer_types::events::IbcEventType;
@@ -21,6 +24,10 @@ pub struct PacketFilter {
     pub channel_policy: ChannelPolicy,
     #[serde(default)]
     pub min_fees: HashMap<ChannelFilterMatch, FeePolicy>,
+    #[serde(default, skip_serializing_if = "Ics20Rules::is_empty")]
+    pub ics20_rules: Ics20Rules,
+    #[serde(default, skip_serializing_if = "Ics721Rules::is_empty")]
+    pub ics721_rules: Ics721Rules,
 }
 
 impl Default for PacketFilter {
@@ -29,6 +36,8 @@ impl Default for PacketFilter {
         Self {
             channel_policy: ChannelPolicy::default(),
             min_fees: HashMap::new(),
+            ics20_rules: Ics20Rules::default(),
+            ics721_rules: Ics721Rules::default(),
         }
     }
 }
@@ -41,6 +50,8 @@ impl PacketFilter {
         Self {
             channel_policy,
             min_fees,
+            ics20_rules: Ics20Rules::default(),
+            ics721_rules: Ics721Rules::default(),
         }
     }
 
@@ -70,15 +81,32 @@ pub enum ChannelPolicy {
 }
 
 /// Represents the policy used to filter incentivized packets.
-/// Currently only filtering on `recv_fee` is authorized.
+/// Packets are filtered on their `recv_fee` and, optionally,
+/// on whether relaying them is profitable.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FeePolicy {
     recv: Vec<MinFee>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    profitability: Option<Profitability>,
 }
 
 impl FeePolicy {
     pub fn new(recv: Vec<MinFee>) -> Self {
-        Self { recv }
+        Self {
+            recv,
+            profitability: None,
+        }
+    }
+
+    pub fn with_profitability(self, profitability: Profitability) -> Self {
+        Self {
+            profitability: Some(profitability),
+            ..self
+        }
+    }
+
+    pub fn profitability(&self) -> Option<&Profitability> {
+        self.profitability.as_ref()
     }
 
     pub fn should_relay(&self, event_type: IbcEventType, fees: &[RawCoin]) -> bool {
@@ -91,6 +119,48 @@ impl FeePolicy {
     }
 }
 
+/// Requires the total fees of an incentivized packet to cover the estimated
+/// cost of relaying it, increased by a `margin` expressed as a fraction of
+/// that cost, eg. `0.1` for 10%.
+///
+/// Fees and costs are compared using the prices of their denominations.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profitability {
+    #[serde(default, deserialize_with = "finite_margin")]
+    margin: f64,
+}
+
+// Margins are finite, as non-finite margins are rejected when deserialized
+impl Eq for Profitability {}
+
+fn finite_margin<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    let margin = f64::deserialize(deserializer)?;
+
+    if margin.is_finite() && !margin.is_sign_negative() {
+        Ok(margin)
+    } else {
+        Err(de::Error::custom(format!(
+            "invalid profitability margin {margin}, must be finite and non-negative"
+        )))
+    }
+}
+
+impl Profitability {
+    pub fn new(margin: f64) -> Self {
+        Self { margin }
+    }
+
+    pub fn margin(&self) -> f64 {
+        self.margin
+    }
+
+    /// Returns true if the given fees cover the given cost and the margin.
+    pub fn is_profitable(&self, fees: f64, cost: f64) -> bool {
+        fees >= cost * (1.0 + self.margin)
+    }
+}
+
 /// Represents the minimum fee authorized when filtering.
 /// If no denom is specified, any denom is allowed.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -112,6 +182,338 @@ impl MinFee {
     }
 }
 
+/// An ordered list of rules matching on the decoded data of ICS-20 packets.
+///
+/// The first rule matching a packet decides whether it is relayed or not.
+/// Packets which are not matched by any rule, as well as packets which do
+/// not carry ICS-20 packet data, are relayed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Ics20Rules(Vec<Ics20Rule>);
+
+impl Ics20Rules {
+    pub fn new(rules: Vec<Ics20Rule>) -> Self {
+        Self(rules)
+    }
+
+    /// Returns true if there are no rules, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the reason for which a packet with the given data must not be relayed,
+    /// or `None` if the packet must be relayed.
+    pub fn filter_reason(&self, data: &[u8]) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let packet_data = Ics20PacketData::decode(data)?;
+
+        let (index, rule) = self
+            .0
+            .iter()
+            .enumerate()
+            .find(|(_, rule)| rule.matches(&packet_data))?;
+
+        match rule.action {
+            RuleAction::Allow => None,
+            RuleAction::Deny => Some(
+                rule.reason
+                    .clone()
+                    .unwrap_or_else(|| format!("ics20_rule_{index}")),
+            ),
+        }
+    }
+}
+
+/// Whether the packets matched by an [`Ics20Rule`] or an [`Ics721Rule`] are relayed or not.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleAction {
+    Allow,
+    Deny,
+}
+
+/// A rule matching ICS-20 packets on their decoded packet data.
+///
+/// A rule matches a packet if all of its conditions hold, unspecified conditions
+/// always holding. The `denom`, `min_amount` and `max_amount` conditions must hold
+/// for the same token when the packet carries several tokens.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Ics20Rule {
+    pub action: RuleAction,
+    /// The reason reported when a packet is filtered by this rule.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// The full denomination, including its trace prefixes, e.g. `transfer/channel-*/uatom`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub denom: Option<Wildcard>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "optional_amount"
+    )]
+    pub min_amount: Option<Amount>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "optional_amount"
+    )]
+    pub max_amount: Option<Amount>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub senders: Vec<AddressMatch>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub receivers: Vec<AddressMatch>,
+    /// Top-level keys of a JSON memo, e.g. `forward` for packet-forward-middleware
+    /// or `wasm` for IBC hooks. Matches if the memo contains any of them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub memo_keys: Vec<String>,
+}
+
+impl Ics20Rule {
+    fn matches(&self, packet_data: &Ics20PacketData) -> bool {
+        let token_matches = packet_data.tokens.iter().any(|(denom, amount)| {
+            let denom_matches = self
+                .denom
+                .as_ref()
+                .map_or(true, |pattern| pattern.is_match(denom));
+
+            let min_matches = self
+                .min_amount
+                .map_or(true, |min| amount.is_some_and(|amount| amount >= min));
+
+            let max_matches = self
+                .max_amount
+                .map_or(true, |max| amount.is_some_and(|amount| amount <= max));
+
+            denom_matches && min_matches && max_matches
+        });
+
+        let sender_matches =
+            self.senders.is_empty() || self.senders.iter().any(|s| s.matches(&packet_data.sender));
+
+        let receiver_matches = self.receivers.is_empty()
+            || self
+                .receivers
+                .iter()
+                .any(|r| r.matches(&packet_data.receiver));
+
+        let memo_matches = self.memo_keys.is_empty()
+            || packet_data.memos.iter().any(|memo| {
+                serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(memo)
+                    .map(|memo| self.memo_keys.iter().any(|key| memo.contains_key(key)))
+                    .unwrap_or(false)
+            });
+
+        token_matches && sender_matches && receiver_matches && memo_matches
+    }
+}
+
+/// An ordered list of rules matching on the decoded data of ICS-721 packets.
+///
+/// The first rule matching a packet decides whether it is relayed or not.
+/// Packets which are not matched by any rule, as well as packets which do
+/// not carry ICS-721 packet data, are relayed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Ics721Rules(Vec<Ics721Rule>);
+
+impl Ics721Rules {
+    pub fn new(rules: Vec<Ics721Rule>) -> Self {
+        Self(rules)
+    }
+
+    /// Returns true if there are no rules, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the reason for which a packet with the given data must not be relayed,
+    /// or `None` if the packet must be relayed.
+    pub fn filter_reason(&self, data: &[u8]) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let packet_data = NftPacketData::decode(data).ok()?;
+
+        let (index, rule) = self
+            .0
+            .iter()
+            .enumerate()
+            .find(|(_, rule)| rule.matches(&packet_data))?;
+
+        match rule.action {
+            RuleAction::Allow => None,
+            RuleAction::Deny => Some(
+                rule.reason
+                    .clone()
+                    .unwrap_or_else(|| format!("ics721_rule_{index}")),
+            ),
+        }
+    }
+}
+
+/// A rule matching ICS-721 packets on their decoded packet data.
+///
+/// A rule matches a packet if all of its conditions hold, unspecified conditions
+/// always holding.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Ics721Rule {
+    pub action: RuleAction,
+    /// The reason reported when a packet is filtered by this rule.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// The full class ID, including its trace prefixes, e.g. `nft-transfer/channel-*/class`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_id: Option<Wildcard>,
+    /// Matches if any of the tokens transferred by the packet matches any of these token IDs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub token_ids: Vec<Wildcard>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub senders: Vec<AddressMatch>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub receivers: Vec<AddressMatch>,
+}
+
+impl Ics721Rule {
+    fn matches(&self, packet_data: &NftPacketData) -> bool {
+        let class_matches = self.class_id.as_ref().map_or(true, |pattern| {
+            pattern.is_match(&packet_data.class_id.to_string())
+        });
+
+        let token_matches = self.token_ids.is_empty()
+            || packet_data.token_ids.iter().any(|token_id| {
+                let token_id = token_id.to_string();
+                self.token_ids
+                    .iter()
+                    .any(|pattern| pattern.is_match(&token_id))
+            });
+
+        let sender_matches = self.senders.is_empty()
+            || self
+                .senders
+                .iter()
+                .any(|s| s.matches(packet_data.sender.as_ref()));
+
+        let receiver_matches = self.receivers.is_empty()
+            || self
+                .receivers
+                .iter()
+                .any(|r| r.matches(packet_data.receiver.as_ref()));
+
+        class_matches && token_matches && sender_matches && receiver_matches
+    }
+}
+
+/// Matches an address either exactly, or by its bech32 human-readable part
+/// when given a prefix such as `osmo`. Bech32 addresses are case-insensitive.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AddressMatch(String);
+
+impl AddressMatch {
+    pub fn new(address_or_prefix: String) -> Self {
+        Self(address_or_prefix)
+    }
+
+    pub fn matches(&self, address: &str) -> bool {
+        if self.0.eq_ignore_ascii_case(address) {
+            return true;
+        }
+
+        match bech32::decode(address) {
+            Ok((hrp, _, _)) => self.0.eq_ignore_ascii_case(&hrp),
+            Err(_) => false,
+        }
+    }
+}
+
+/// The fields of ICS-20 packet data the rules match on, for either version of the application.
+struct Ics20PacketData {
+    /// The full denomination and amount of each token
+    tokens: Vec<(String, Option<Amount>)>,
+    sender: String,
+    receiver: String,
+    /// The memo of the packet, along with the memo to use on the final
+    /// destination of `ics20-2` packets which are forwarded
+    memos: Vec<String>,
+}
+
+impl Ics20PacketData {
+    fn decode(data: &[u8]) -> Option<Self> {
+        if let Ok(packet_data) = serde_json::from_slice::<RawPacketData>(data) {
+            return Some(Self {
+                tokens: vec![(
+                    packet_data.denom,
+                    Amount::from_str(&packet_data.amount).ok(),
+                )],
+                sender: packet_data.sender,
+                receiver: packet_data.receiver,
+                memos: vec![packet_data.memo],
+            });
+        }
+
+        let packet_data = PacketDataV2::decode_v2(data).ok()?;
+
+        let tokens = packet_data
+            .tokens
+            .into_iter()
+            .map(|token| (token.denom.to_string(), Some(token.amount)))
+            .collect();
+
+        let memos = packet_data
+            .memo
+            .into_iter()
+            .chain(packet_data.forwarding.destination_memo)
+            .collect();
+
+        Some(Self {
+            tokens,
+            sender: packet_data.sender.to_string(),
+            receiver: packet_data.receiver.to_string(),
+            memos,
+        })
+    }
+}
+
+/// (De)serializes an optional [`Amount`] from either a string or an integer.
+mod optional_amount {
+    use super::*;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawAmount {
+        Integer(u64),
+        String(String),
+    }
+
+    pub fn serialize<S: Serializer>(
+        amount: &Option<Amount>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match amount {
+            Some(amount) => serializer.serialize_str(&amount.to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Amount>, D::Error> {
+        match Option::<RawAmount>::deserialize(deserializer)? {
+            None => Ok(None),
+            Some(RawAmount::Integer(amount)) => Ok(Some(Amount::from(amount))),
+            Some(RawAmount::String(amount)) => Amount::from_str(&amount)
+                .map(Some)
+                .map_err(de::Error::custom),
+        }
+    }
+}
+
 impl Default for ChannelPolicy {
     /// By default, allows all channels & ports.
     fn default() -> Self {
@@ -276,6 +678,13 @@ impl Serialize for Wildcard {
     }
 }
 
+impl<'de> Deserialize<'de> for Wildcard {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Wildcard, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Wildcard::new(pattern.trim().to_string()).map_err(de::Error::custom)
+    }
+}
+
 impl PartialEq for Wildcard {
     fn eq(&self, other: &Self) -> bool {
         self.pattern == other.pattern
@@ -603,6 +1012,285 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn packet_filter_ics20_rules() {
+        let packet_filter = r#"
+            policy = 'allowall'
+
+            [[ics20_rules]]
+            action = 'allow'
+            senders = ['cosmos1trusted']
+
+            [[ics20_rules]]
+            action = 'deny'
+            reason = 'dust'
+            denom = 'transfer/channel-*/uatom'
+            max_amount = 999
+
+            [[ics20_rules]]
+            action = 'deny'
+            reason = 'pfm'
+            memo_keys = ['forward', 'wasm']
+
+            [[ics20_rules]]
+            action = 'deny'
+            reason = 'osmosis_receiver'
+            receivers = ['osmo']
+            min_amount = '1000000000000000000000'
+            "#;
+
+        let pf: PacketFilter = toml::from_str(packet_filter).expect("could not parse filter");
+
+        let data = |denom: &str, amount: &str, sender: &str, receiver: &str, memo: &str| {
+            serde_json::to_vec(&serde_json::json!({
+                "denom": denom,
+                "amount": amount,
+                "sender": sender,
+                "receiver": receiver,
+                "memo": memo,
+            }))
+            .unwrap()
+        };
+
+        let rules = &pf.ics20_rules;
+        let osmo_receiver = bech32::encode(
+            "osmo",
+            bech32::ToBase32::to_base32(&[1u8; 20]),
+            bech32::Variant::Bech32,
+        )
+        .unwrap();
+
+        assert_eq!(
+            rules.filter_reason(&data(
+                "transfer/channel-1/uatom",
+                "10",
+                "cosmos1a",
+                "cosmos1b",
+                ""
+            )),
+            Some("dust".to_owned())
+        );
+        assert_eq!(
+            rules.filter_reason(&data(
+                "transfer/channel-1/uatom",
+                "1000",
+                "cosmos1a",
+                "cosmos1b",
+                ""
+            )),
+            None
+        );
+        assert_eq!(
+            rules.filter_reason(&data("uatom", "10", "cosmos1a", "cosmos1b", "")),
+            None
+        );
+        assert_eq!(
+            rules.filter_reason(&data(
+                "transfer/channel-1/uatom",
+                "10",
+                "cosmos1trusted",
+                "cosmos1b",
+                ""
+            )),
+            None
+        );
+        assert_eq!(
+            rules.filter_reason(&data(
+                "uatom",
+                "1000",
+                "cosmos1a",
+                "cosmos1b",
+                r#"{"forward":{"receiver":"osmo1a","port":"transfer","channel":"channel-0"}}"#
+            )),
+            Some("pfm".to_owned())
+        );
+        assert_eq!(
+            rules.filter_reason(&data("uatom", "1000", "cosmos1a", "cosmos1b", "forward")),
+            None
+        );
+        assert_eq!(
+            rules.filter_reason(&data(
+                "uatom",
+                "1000000000000000000000",
+                "cosmos1a",
+                &osmo_receiver.to_uppercase(),
+                ""
+            )),
+            Some("osmosis_receiver".to_owned())
+        );
+        assert_eq!(
+            rules.filter_reason(&data("uatom", "1000", "cosmos1a", &osmo_receiver, "")),
+            None
+        );
+
+        // Packets which do not carry ICS-20 packet data are always relayed
+        assert_eq!(rules.filter_reason(b"not ics20"), None);
+    }
+
+    #[test]
+    fn packet_filter_ics20_rules_v2() {
+        use ibc_relayer_types::applications::transfer::raw::{
+            Denom as RawDenom, ForwardingPacketData as RawForwardingPacketData,
+            FungibleTokenPacketDataV2 as RawPacketDataV2, Hop as RawHop, Token as RawToken,
+        };
+        use prost::Message;
+
+        let rules: PacketFilter = toml::from_str(
+            r#"
+            policy = 'allowall'
+
+            [[ics20_rules]]
+            action = 'deny'
+            denom = 'transfer/channel-0/uatom'
+            memo_keys = ['wasm']
+            "#,
+        )
+        .expect("could not parse filter");
+
+        let mut packet_data = RawPacketDataV2 {
+            tokens: vec![
+                RawToken {
+                    denom: Some(RawDenom {
+                        base: "uosmo".to_owned(),
+                        trace: vec![],
+                    }),
+                    amount: "10".to_owned(),
+                },
+                RawToken {
+                    denom: Some(RawDenom {
+                        base: "uatom".to_owned(),
+                        trace: vec![RawHop {
+                            port_id: "transfer".to_owned(),
+                            channel_id: "channel-0".to_owned(),
+                        }],
+                    }),
+                    amount: "10".to_owned(),
+                },
+            ],
+            sender: "cosmos1a".to_owned(),
+            receiver: "cosmos1b".to_owned(),
+            memo: String::new(),
+            forwarding: None,
+        };
+
+        assert_eq!(
+            rules
+                .ics20_rules
+                .filter_reason(&packet_data.encode_to_vec()),
+            None
+        );
+
+        packet_data.forwarding = Some(RawForwardingPacketData {
+            destination_memo: r#"{"wasm":{}}"#.to_owned(),
+            hops: vec![],
+        });
+
+        assert_eq!(
+            rules
+                .ics20_rules
+                .filter_reason(&packet_data.encode_to_vec()),
+            Some("ics20_rule_0".to_owned())
+        );
+    }
+
+    #[test]
+    fn packet_filter_ics721_rules() {
+        let packet_filter = r#"
+            policy = 'allowall'
+
+            [[ics721_rules]]
+            action = 'allow'
+            senders = ['cosmos1trusted']
+
+            [[ics721_rules]]
+            action = 'deny'
+            reason = 'punks'
+            class_id = 'nft-transfer/channel-*/punks'
+
+            [[ics721_rules]]
+            action = 'deny'
+            token_ids = ['rare-*']
+            "#;
+
+        let pf: PacketFilter = toml::from_str(packet_filter).expect("could not parse filter");
+
+        let data = |class_id: &str, token_ids: &[&str], sender: &str| {
+            serde_json::to_vec(&serde_json::json!({
+                "classId": class_id,
+                "tokenIds": token_ids,
+                "sender": sender,
+                "receiver": "cosmos1b",
+            }))
+            .unwrap()
+        };
+
+        let rules = &pf.ics721_rules;
+
+        assert_eq!(
+            rules.filter_reason(&data("nft-transfer/channel-1/punks", &["1"], "cosmos1a")),
+            Some("punks".to_owned())
+        );
+        assert_eq!(
+            rules.filter_reason(&data(
+                "nft-transfer/channel-1/punks",
+                &["1"],
+                "cosmos1trusted"
+            )),
+            None
+        );
+        assert_eq!(
+            rules.filter_reason(&data("apes", &["1", "rare-2"], "cosmos1a")),
+            Some("ics721_rule_2".to_owned())
+        );
+        assert_eq!(
+            rules.filter_reason(&data("apes", &["1", "2"], "cosmos1a")),
+            None
+        );
+
+        // ICS-20 packet data is not matched by the ICS-721 rules
+        let ics20_data = serde_json::to_vec(&serde_json::json!({
+            "denom": "uatom",
+            "amount": "1",
+            "sender": "cosmos1a",
+            "receiver": "cosmos1b",
+        }))
+        .unwrap();
+        assert_eq!(rules.filter_reason(&ics20_data), None);
+    }
+
+    #[test]
+    fn packet_filter_profitability() {
+        let toml_content = r#"
+            policy = 'allowall'
+
+            [min_fees.'channel-0']
+            recv = [ { amount = 0 } ]
+            profitability = { margin = 0.25 }
+
+            [min_fees.'channel-1']
+            recv = [ { amount = 20, denom = 'stake' } ]
+            "#;
+
+        let filter: PacketFilter =
+            toml::from_str(toml_content).expect("could not parse packet filter");
+
+        let fee_policy = |channel: &str| {
+            filter
+                .min_fees
+                .iter()
+                .find(|(filter, _)| filter.matches(&channel.parse().unwrap()))
+                .map(|(_, policy)| policy)
+                .unwrap()
+        };
+
+        let profitability = fee_policy("channel-0").profitability().unwrap();
+        assert_eq!(profitability.margin(), 0.25);
+        assert!(profitability.is_profitable(125.0, 100.0));
+        assert!(!profitability.is_profitable(124.0, 100.0));
+
+        assert!(fee_policy("channel-1").profitability().is_none());
+    }
+
     #[test]
     fn to_string_wildcards() {
         let wildcard = "ica*".parse::<Wildcard>().unwrap();
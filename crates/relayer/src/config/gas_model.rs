@@ -0,0 +1,34 @@
+//! Configuration of the gas model learnt from the transactions submitted to a chain.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Specifies whether the gas used by each type of message submitted to a chain
+/// is learnt from the confirmed transactions, and how the learnt amounts are used
+/// when estimating the gas of new transactions.
+///
+/// The learnt amounts are persisted in `file`, which defaults to
+/// `$HOME/.hermes/gas_models/<chain-id>.json`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GasModelConfig {
+    pub enabled: bool,
+    /// Use the learnt amounts instead of simulating the transactions,
+    /// once enough samples have been observed for all their messages.
+    pub skip_simulation: bool,
+    /// The number of samples needed for the learnt amount of a message type to be used.
+    pub min_samples: u64,
+    pub file: Option<PathBuf>,
+}
+
+impl Default for GasModelConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            skip_simulation: false,
+            min_samples: 20,
+            file: None,
+        }
+    }
+}
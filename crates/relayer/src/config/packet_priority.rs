@@ -0,0 +1,298 @@
+//! Configuration of the order in which the pending packets of unordered
+//! channels are relayed, and of how many of them are batched per transaction.
+//!
+//! Each channel is relayed by its own worker, so the packets are ordered among
+//! the pending packets of the same channel. Channels are weighted against each
+//! other by the number of packets they batch in each transaction.
+
+use core::cmp::Ordering;
+use core::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use ibc_relayer_types::applications::ics29_fee::packet_fee::Fee;
+use ibc_relayer_types::core::ics24_host::identifier::ChannelId;
+
+use crate::config::filter::ChannelFilterMatch;
+
+/// A criterion by which pending packets can be ordered.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PriorityCriterion {
+    /// Packets with the highest ICS-29 fees, compared per denomination
+    /// in the order of `fee_denoms`, are relayed first.
+    Fee,
+    /// Packets which are the closest to time out are relayed first.
+    Timeout,
+}
+
+/// Specifies how the pending packets of unordered channels are ordered
+/// and batched. By default, packets are relayed in the order in which
+/// they are observed, ie. in sequence order when clearing packets.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PacketPriority {
+    /// The criteria by which packets are ordered, from the most to the least significant.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub order_by: Vec<PriorityCriterion>,
+    /// The denominations by which the ICS-29 fees of packets are compared,
+    /// from the most to the least significant.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fee_denoms: Vec<String>,
+    /// The maximum number of packets batched in a single transaction.
+    pub max_packets_per_tx: Option<usize>,
+    /// The weights of channels, scaling their `max_packets_per_tx`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub channel_weights: Vec<ChannelWeight>,
+}
+
+/// The weight of the channels matching a channel identifier or a wildcard.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelWeight {
+    pub channel: ChannelFilterMatch,
+    pub weight: f64,
+}
+
+impl PacketPriority {
+    /// Returns true if packets must be reordered or batched, false otherwise.
+    pub fn is_enabled(&self) -> bool {
+        !self.order_by.is_empty() || self.max_packets_per_tx.is_some()
+    }
+
+    /// Returns a description of the first invalid setting, if any.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.order_by.contains(&PriorityCriterion::Fee) && self.fee_denoms.is_empty() {
+            return Err("`fee_denoms` must not be empty when ordering by `fee`".to_owned());
+        }
+
+        if self.max_packets_per_tx == Some(0) {
+            return Err("`max_packets_per_tx` must be positive".to_owned());
+        }
+
+        if let Some(channel_weight) = self.channel_weights.iter().find(|channel_weight| {
+            !channel_weight.weight.is_finite() || channel_weight.weight <= 0.0
+        }) {
+            return Err(format!(
+                "the weight of `{}` must be positive, found {}",
+                channel_weight.channel, channel_weight.weight
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the weight of the given channel, which defaults to 1.
+    pub fn channel_weight(&self, channel_id: &ChannelId) -> f64 {
+        self.channel_weights
+            .iter()
+            .find(|channel_weight| channel_weight.channel.matches(channel_id))
+            .map_or(1.0, |channel_weight| channel_weight.weight)
+    }
+
+    /// Returns the amounts of the configured `fee_denoms` earned by relaying a packet
+    /// incentivized with the given fees, ie. their `recv_fee` and `ack_fee`.
+    pub fn fee_amounts<'a>(&self, fees: impl IntoIterator<Item = &'a Fee> + Clone) -> Vec<u128> {
+        self.fee_denoms
+            .iter()
+            .map(|denom| {
+                fees.clone()
+                    .into_iter()
+                    .flat_map(|fee| fee.recv_fee.iter().chain(&fee.ack_fee))
+                    .filter(|coin| &coin.denom == denom)
+                    .filter_map(|coin| coin.amount.to_string().parse::<u128>().ok())
+                    .fold(0u128, u128::saturating_add)
+            })
+            .collect()
+    }
+
+    /// Returns the maximum number of packets of the given channel batched in a single
+    /// transaction, ie. `max_packets_per_tx` scaled by the weight of the channel,
+    /// or `None` if the number of packets is not capped.
+    pub fn max_packets_per_tx(&self, channel_id: &ChannelId) -> Option<usize> {
+        self.max_packets_per_tx.map(|max| {
+            let scaled = (max as f64 * self.channel_weight(channel_id)).round();
+            (scaled as usize).max(1)
+        })
+    }
+
+    /// Compares two packets according to the configured criteria, such that
+    /// the packet which must be relayed first is ordered first.
+    pub fn compare(&self, a: &PriorityKey, b: &PriorityKey) -> Ordering {
+        self.order_by
+            .iter()
+            .fold(Ordering::Equal, |ordering, criterion| {
+                ordering.then_with(|| match criterion {
+                    PriorityCriterion::Fee => b.fee.cmp(&a.fee),
+                    PriorityCriterion::Timeout => match (a.time_to_timeout, b.time_to_timeout) {
+                        (Some(a), Some(b)) => a.cmp(&b),
+                        (Some(_), None) => Ordering::Less,
+                        (None, Some(_)) => Ordering::Greater,
+                        (None, None) => Ordering::Equal,
+                    },
+                })
+            })
+    }
+}
+
+/// The attributes of a pending packet by which it is prioritized.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PriorityKey {
+    /// The amounts of the ICS-29 fees escrowed for the packet, per configured `fee_denoms`.
+    pub fee: Vec<u128>,
+    /// The estimated time left until the packet times out on the destination chain,
+    /// or `None` if the packet never times out.
+    pub time_to_timeout: Option<Duration>,
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_relayer_types::applications::transfer::RawCoin;
+
+    use super::*;
+
+    #[test]
+    fn deserialize_packet_priority() {
+        let toml_content = r#"
+            order_by = ['timeout', 'fee']
+            fee_denoms = ['uatom']
+            max_packets_per_tx = 10
+            channel_weights = [
+                { channel = 'channel-0', weight = 2.0 },
+                { channel = 'channel-1*', weight = 0.5 },
+            ]
+            "#;
+
+        let priority: PacketPriority =
+            toml::from_str(toml_content).expect("could not parse packet priority");
+
+        assert!(priority.is_enabled());
+        assert!(priority.validate().is_ok());
+        assert_eq!(
+            priority.order_by,
+            vec![PriorityCriterion::Timeout, PriorityCriterion::Fee]
+        );
+
+        let channel = |id: &str| id.parse::<ChannelId>().unwrap();
+
+        assert_eq!(priority.max_packets_per_tx(&channel("channel-0")), Some(20));
+        assert_eq!(priority.max_packets_per_tx(&channel("channel-12")), Some(5));
+        assert_eq!(priority.max_packets_per_tx(&channel("channel-2")), Some(10));
+    }
+
+    #[test]
+    fn validate_packet_priority() {
+        let priority = PacketPriority {
+            order_by: vec![PriorityCriterion::Fee],
+            ..Default::default()
+        };
+        assert!(priority.validate().is_err());
+
+        let priority = PacketPriority {
+            max_packets_per_tx: Some(0),
+            ..Default::default()
+        };
+        assert!(priority.validate().is_err());
+
+        let priority = PacketPriority {
+            channel_weights: vec![ChannelWeight {
+                channel: ChannelFilterMatch::Exact("channel-0".parse().unwrap()),
+                weight: 0.0,
+            }],
+            ..Default::default()
+        };
+        assert!(priority.validate().is_err());
+    }
+
+    #[test]
+    fn packet_fee_amounts() {
+        let coins = |coins: &[(&str, u64)]| {
+            coins
+                .iter()
+                .map(|(denom, amount)| RawCoin::new(denom.to_string(), *amount))
+                .collect::<Vec<_>>()
+        };
+
+        let fees = [
+            Fee {
+                recv_fee: coins(&[("uatom", 10), ("stake", 100)]),
+                ack_fee: coins(&[("uatom", 5)]),
+                timeout_fee: coins(&[("uatom", 1000)]),
+            },
+            Fee {
+                recv_fee: coins(&[("uatom", 1)]),
+                ack_fee: vec![],
+                timeout_fee: vec![],
+            },
+        ];
+
+        let priority = PacketPriority {
+            order_by: vec![PriorityCriterion::Fee],
+            fee_denoms: vec!["uatom".to_owned(), "uosmo".to_owned()],
+            ..Default::default()
+        };
+
+        // Timeout fees are not earned by relaying the packet
+        assert_eq!(priority.fee_amounts(&fees), vec![16, 0]);
+    }
+
+    #[test]
+    fn compare_packet_priority() {
+        let key = |fee: &[u128], secs: Option<u64>| PriorityKey {
+            fee: fee.to_vec(),
+            time_to_timeout: secs.map(Duration::from_secs),
+        };
+
+        let mut keys = vec![
+            key(&[10, 0], None),
+            key(&[5, 50], Some(60)),
+            key(&[10, 0], Some(120)),
+            key(&[20, 0], Some(60)),
+        ];
+
+        let priority = PacketPriority {
+            order_by: vec![PriorityCriterion::Timeout, PriorityCriterion::Fee],
+            ..Default::default()
+        };
+
+        keys.sort_by(|a, b| priority.compare(a, b));
+        assert_eq!(
+            keys,
+            vec![
+                key(&[20, 0], Some(60)),
+                key(&[5, 50], Some(60)),
+                key(&[10, 0], Some(120)),
+                key(&[10, 0], None)
+            ]
+        );
+
+        let priority = PacketPriority {
+            order_by: vec![PriorityCriterion::Fee],
+            ..Default::default()
+        };
+
+        // Fees are compared per denomination, the amounts of other denominations only break ties
+        keys.sort_by(|a, b| priority.compare(a, b));
+        assert_eq!(
+            keys,
+            vec![
+                key(&[20, 0], Some(60)),
+                key(&[10, 0], Some(120)),
+                key(&[10, 0], None),
+                key(&[5, 50], Some(60))
+            ]
+        );
+    }
+
+    #[test]
+    fn default_packet_priority() {
+        let priority = PacketPriority::default();
+
+        assert!(!priority.is_enabled());
+        assert!(priority.validate().is_ok());
+        assert_eq!(
+            priority.max_packets_per_tx(&"channel-0".parse().unwrap()),
+            None
+        );
+    }
+}
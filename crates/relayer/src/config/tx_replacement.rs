@@ -0,0 +1,62 @@
+//! Configuration of the replacement of the transactions which get stuck in the mempool.
+
+use core::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Specifies whether the transactions which are not committed within `stuck_timeout`
+/// are rebroadcast at the same account sequence with a fee bumped by `fee_bump`,
+/// until the fee reaches `max_fee_multiplier` times the estimated fee.
+///
+/// This requires the mempool of the chain to support replacing a transaction by
+/// another one with the same account sequence and a higher fee.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TxReplacementConfig {
+    pub enabled: bool,
+    #[serde(with = "humantime_serde")]
+    pub stuck_timeout: Duration,
+    pub fee_bump: f64,
+    pub max_fee_multiplier: f64,
+}
+
+impl Default for TxReplacementConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            stuck_timeout: Duration::from_secs(15),
+            fee_bump: 1.2,
+            max_fee_multiplier: 2.0,
+        }
+    }
+}
+
+impl TxReplacementConfig {
+    /// Returns the fee multiplier to use when replacing a transaction which was
+    /// broadcast with the given fee multiplier, or `None` if the cap is reached.
+    pub fn next_fee_multiplier(&self, fee_multiplier: f64) -> Option<f64> {
+        if fee_multiplier >= self.max_fee_multiplier {
+            return None;
+        }
+
+        Some((fee_multiplier * self.fee_bump).min(self.max_fee_multiplier))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bump_fee_up_to_cap() {
+        let config = TxReplacementConfig {
+            fee_bump: 1.5,
+            max_fee_multiplier: 2.0,
+            ..Default::default()
+        };
+
+        assert_eq!(config.next_fee_multiplier(1.0), Some(1.5));
+        assert_eq!(config.next_fee_multiplier(1.5), Some(2.0));
+        assert_eq!(config.next_fee_multiplier(2.0), None);
+    }
+}
@@ -0,0 +1,27 @@
+//! Configuration of the unordered transactions submitted to a chain.
+
+use core::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Specifies whether transactions are submitted as unordered transactions, which
+/// do not use the account sequence and expire `timeout` after being signed.
+///
+/// Unordered transactions are only submitted to chains running Cosmos SDK v0.53
+/// or later. Hermes falls back on ordered transactions on the other chains.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UnorderedTxConfig {
+    pub enabled: bool,
+    #[serde(with = "humantime_serde")]
+    pub timeout: Duration,
+}
+
+impl Default for UnorderedTxConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            timeout: Duration::from_secs(300),
+        }
+    }
+}
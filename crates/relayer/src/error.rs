@@ -26,6 +26,7 @@ use tendermint_rpc::Error as TendermintRpcError;
 
 use ibc_relayer_types::applications::ics29_fee::error::Error as FeeError;
 use ibc_relayer_types::applications::ics31_icq::error::Error as CrossChainQueryError;
+use ibc_relayer_types::applications::transfer::error::Error as TransferError;
 use ibc_relayer_types::clients::ics07_tendermint::error as tendermint_error;
 use ibc_relayer_types::core::ics02_client::{client_type::ClientType, error as client_error};
 use ibc_relayer_types::core::ics03_connection::error as connection_error;
@@ -34,6 +35,7 @@ use ibc_relayer_types::core::ics24_host::error::ValidationError;
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, ConnectionId};
 use ibc_relayer_types::proofs::ProofError;
 
+use crate::chain::capabilities::Capability;
 use crate::chain::cosmos::version;
 use crate::chain::cosmos::BLOCK_MAX_BYTES_MAX_FRACTION;
 use crate::chain::namada::error::Error as NamadaError;
@@ -125,6 +127,40 @@ define_error! {
             [ LightClientIoError ]
             |e| { format!("light client error for RPC address {0}", e.address) },
 
+        LightClientAttack
+            {
+                chain_id: ChainId,
+                witness: tendermint_rpc::Url,
+                height: tendermint::block::Height,
+            }
+            |e| {
+                format!(
+                    "header at height {} from the primary node of chain {} conflicts with witness {}, refusing to use it",
+                    e.height, e.chain_id, e.witness
+                )
+            },
+
+        LightClientWitness
+            {
+                witness: tendermint_rpc::Url,
+                reason: String,
+            }
+            |e| { format!("error while cross-checking headers against witness {}: {}", e.witness, e.reason) },
+
+        InsufficientWitnesses
+            {
+                chain_id: ChainId,
+                height: tendermint::block::Height,
+                answered: usize,
+                required: usize,
+            }
+            |e| {
+                format!(
+                    "only {} witness(es) of chain {} could cross-check the header at height {}, while {} are required, refusing to use it",
+                    e.answered, e.chain_id, e.height, e.required
+                )
+            },
+
         ChainNotCaughtUp
             {
                 address: String,
@@ -571,6 +607,27 @@ define_error! {
                     "Query/DenomTrace RPC returned an empty denom trace for trace hash: {}", e.hash)
             },
 
+        EmptyClassTrace
+            { hash: String }
+            |e| {
+                format_args!(
+                    "Query/ClassTrace RPC returned an empty class trace for trace hash: {}", e.hash)
+            },
+
+        InvalidEstimatedFee
+            { fee: String }
+            [ TransferError ]
+            |e| { format!("invalid coin in estimated fee: {}", e.fee) },
+
+        InvalidTxFee
+            { fee: String }
+            [ TransferError ]
+            |e| { format!("invalid coin in the fee of a transaction: {}", e.fee) },
+
+        TxNotFound
+            { tx_hash: String }
+            |e| { format!("transaction {} was not found", e.tx_hash) },
+
         MessageTooBigForTx
             { len: usize }
             |e| {
@@ -655,6 +712,30 @@ define_error! {
         Namada
             [ NamadaError ]
             |_| { "Namada error" },
+
+        PersistedFileIo
+            { name: String, path: String }
+            [ TraceError<std::io::Error> ]
+            |e| { format!("failed to read or write the {} at `{}`", e.name, e.path) },
+
+        PersistedFileJson
+            { name: String, path: String }
+            [ TraceError<serde_json::Error> ]
+            |e| { format!("failed to decode or encode the {} at `{}`", e.name, e.path) },
+
+        HomeLocationUnavailable
+            |_| { "home location is unavailable" },
+
+        UnsupportedCapability
+            {
+                chain_id: ChainId,
+                capability: Capability,
+            }
+            |e| { format!("chain '{}' does not support {}", e.chain_id, e.capability) },
+
+        UnknownBackend
+            { name: String }
+            |e| { format!("no chain backend is registered under the name '{}'", e.name) },
     }
 }
 
@@ -672,6 +753,12 @@ impl Error {
             _ => false,
         }
     }
+
+    /// Check whether this error was returned because the chain does not support
+    /// the requested feature, in which case the caller can skip it gracefully.
+    pub fn is_unsupported_error(&self) -> bool {
+        matches!(self.detail(), ErrorDetail::UnsupportedCapability(_))
+    }
 }
 
 impl GrpcStatusSubdetail {
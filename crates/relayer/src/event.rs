@@ -28,6 +28,7 @@ use ibc_relayer_types::{
             },
             packet::{Packet, Sequence},
             timeout::TimeoutHeight,
+            v2::events::{self as channel_events_v2, is_v2_packet_event},
         },
     },
     events::{Error as IbcEventError, IbcEvent, IbcEventType},
@@ -146,6 +147,32 @@ pub fn ibc_event_try_from_abci_event(abci_event: &AbciEvent) -> Result<IbcEvent,
             channel_upgrade_error_try_from_abci_event(abci_event)
                 .map_err(IbcEventError::channel)?,
         )),
+        // IBC v2 packet events share their type with the ICS-04 packet events,
+        // hence these must be checked first.
+        Ok(IbcEventType::SendPacket) if is_v2_packet_event(&abci_event.attributes) => {
+            Ok(IbcEvent::SendPacketV2(
+                channel_events_v2::SendPacket::try_from(&abci_event.attributes[..])
+                    .map_err(IbcEventError::channel)?,
+            ))
+        }
+        Ok(IbcEventType::WriteAck) if is_v2_packet_event(&abci_event.attributes) => {
+            Ok(IbcEvent::WriteAcknowledgementV2(
+                channel_events_v2::WriteAcknowledgement::try_from(&abci_event.attributes[..])
+                    .map_err(IbcEventError::channel)?,
+            ))
+        }
+        Ok(IbcEventType::AckPacket) if is_v2_packet_event(&abci_event.attributes) => {
+            Ok(IbcEvent::AcknowledgePacketV2(
+                channel_events_v2::AcknowledgePacket::try_from(&abci_event.attributes[..])
+                    .map_err(IbcEventError::channel)?,
+            ))
+        }
+        Ok(IbcEventType::Timeout) if is_v2_packet_event(&abci_event.attributes) => {
+            Ok(IbcEvent::TimeoutPacketV2(
+                channel_events_v2::TimeoutPacket::try_from(&abci_event.attributes[..])
+                    .map_err(IbcEventError::channel)?,
+            ))
+        }
         Ok(IbcEventType::SendPacket) => Ok(IbcEvent::SendPacket(
             send_packet_try_from_abci_event(abci_event).map_err(IbcEventError::channel)?,
         )),
@@ -744,4 +771,49 @@ mod tests {
             }
         }
     }
+    #[test]
+    fn packet_v2_event_from_abci_event() {
+        use ibc_relayer_types::core::ics04_channel::v2::{
+            events::{
+                PKT_DST_CLIENT_ATTRIBUTE_KEY, PKT_ENCODED_HEX_ATTRIBUTE_KEY,
+                PKT_SRC_CLIENT_ATTRIBUTE_KEY,
+            },
+            raw::{Packet as RawPacket, Payload as RawPayload},
+        };
+        use prost::Message;
+
+        let raw_packet = RawPacket {
+            sequence: 1,
+            source_client: "07-tendermint-0".to_string(),
+            destination_client: "07-tendermint-1".to_string(),
+            timeout_timestamp: 1_700_000_000,
+            payloads: vec![RawPayload {
+                source_port: "transfer".to_string(),
+                destination_port: "transfer".to_string(),
+                version: "ics20-1".to_string(),
+                encoding: "application/json".to_string(),
+                value: b"{}".to_vec(),
+            }],
+        };
+
+        let encoded_packet = String::from_utf8(hex::encode(raw_packet.encode_to_vec())).unwrap();
+
+        let abci_event = AbciEvent::new(
+            IbcEventType::SendPacket.as_str(),
+            vec![
+                (PKT_SRC_CLIENT_ATTRIBUTE_KEY, "07-tendermint-0"),
+                (PKT_DST_CLIENT_ATTRIBUTE_KEY, "07-tendermint-1"),
+                (PKT_ENCODED_HEX_ATTRIBUTE_KEY, encoded_packet.as_str()),
+            ],
+        );
+
+        match ibc_event_try_from_abci_event(&abci_event) {
+            Ok(IbcEvent::SendPacketV2(e)) => {
+                assert_eq!(e.packet.sequence, Sequence::from(1));
+                assert_eq!(e.src_client_id().as_str(), "07-tendermint-0");
+                assert_eq!(e.dst_client_id().as_str(), "07-tendermint-1");
+            }
+            other => panic!("unexpected conversion result: {other:?}"),
+        }
+    }
 }
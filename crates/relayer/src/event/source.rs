@@ -18,7 +18,7 @@ use ibc_relayer_types::{
 pub use super::error::{Error, ErrorDetail};
 
 use super::IbcEventWithHeight;
-use crate::chain::{handle::Subscription, tracking::TrackingId};
+use crate::chain::{cosmos::backend::Backend, handle::Subscription, tracking::TrackingId};
 
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -43,6 +43,31 @@ impl EventSource {
         Ok((Self::WebSocket(source), tx))
     }
 
+    /// Create a WebSocket event source for a Penumbra chain, which backfills the
+    /// events of the blocks it missed while (re)connecting with the given RPC client.
+    pub fn penumbra_websocket(
+        chain_id: ChainId,
+        ws_url: WebSocketClientUrl,
+        rpc_compat: CompatMode,
+        batch_delay: Duration,
+        rpc_client: HttpClient,
+        rt: Arc<TokioRuntime>,
+    ) -> Result<(Self, TxEventSourceCmd)> {
+        let (mut source, tx) = websocket::EventSource::with_queries(
+            chain_id,
+            ws_url,
+            rpc_compat,
+            batch_delay,
+            rt,
+            queries::penumbra(),
+            websocket::extract::extract_penumbra_events,
+        )?;
+
+        source.init_subscriptions()?;
+
+        Ok((Self::WebSocket(source.with_backfill(rpc_client)), tx))
+    }
+
     pub fn rpc(
         chain_id: ChainId,
         rpc_client: HttpClient,
@@ -55,6 +80,15 @@ impl EventSource {
         Ok((Self::Rpc(source), tx))
     }
 
+    /// Decode the events of the chain with the hooks of the given backend
+    /// before extracting the IBC events from them.
+    pub fn with_backend(self, backend: Arc<dyn Backend>) -> Self {
+        match self {
+            Self::WebSocket(source) => Self::WebSocket(source.with_backend(backend)),
+            Self::Rpc(source) => Self::Rpc(source.with_backend(backend)),
+        }
+    }
+
     pub fn run(self) {
         match self {
             Self::WebSocket(source) => source.run(),
@@ -142,4 +176,14 @@ pub mod queries {
     pub fn ibc_query() -> Query {
         Query::eq("message.module", "interchainquery")
     }
+
+    /// Penumbra does not emit `message.module` events, so we subscribe
+    /// to all transactions and filter their IBC events when extracting them.
+    pub fn penumbra() -> Vec<Query> {
+        vec![new_block(), tx()]
+    }
+
+    pub fn tx() -> Query {
+        Query::from(EventType::Tx)
+    }
 }
@@ -22,7 +22,10 @@ use ibc_relayer_types::{
 };
 
 use crate::{
-    chain::tracking::TrackingId,
+    chain::{
+        cosmos::backend::{decode_events, Backend},
+        tracking::TrackingId,
+    },
     event::{bus::EventBus, error::ErrorDetail, source::Error, IbcEventWithHeight},
     telemetry,
     util::retry::ConstantGrowth,
@@ -59,6 +62,9 @@ pub struct EventSource {
 
     /// Last fetched block height
     last_fetched_height: BlockHeight,
+
+    /// Hooks decoding the events before their extraction
+    backend: Option<Arc<dyn Backend>>,
 }
 
 impl EventSource {
@@ -81,11 +87,19 @@ impl EventSource {
             event_bus,
             rx_cmd,
             last_fetched_height: BlockHeight::from(0_u32),
+            backend: None,
         };
 
         Ok((source, TxEventSourceCmd(tx_cmd)))
     }
 
+    /// Decode the events with the hooks of the given backend
+    /// before extracting the IBC events from them.
+    pub fn with_backend(mut self, backend: Arc<dyn Backend>) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
     pub fn run(mut self) {
         let _span = error_span!("event_source.rpc", chain.id = %self.chain_id).entered();
 
@@ -218,7 +232,14 @@ impl EventSource {
             loop {
                 attempts += 1;
 
-                match collect_events(&self.rpc_client, &self.chain_id, height).await {
+                match collect_events(
+                    &self.rpc_client,
+                    &self.chain_id,
+                    height,
+                    self.backend.as_deref(),
+                )
+                .await
+                {
                     Ok(batch) => {
                         if let Some(batch) = batch {
                             batches.push(batch);
@@ -321,12 +342,16 @@ fn dedupe(events: Vec<abci::Event>) -> Vec<abci::Event> {
 }
 
 /// Collect the IBC events from an RPC event
-async fn collect_events(
+pub(super) async fn collect_events(
     rpc_client: &HttpClient,
     chain_id: &ChainId,
     latest_block_height: BlockHeight,
+    backend: Option<&dyn Backend>,
 ) -> Result<Option<EventBatch>> {
-    let abci_events = fetch_all_events(rpc_client, latest_block_height).await?;
+    let mut abci_events = fetch_all_events(rpc_client, latest_block_height).await?;
+    if let Some(backend) = backend {
+        abci_events = decode_events(backend, abci_events);
+    }
     trace!("Found {} ABCI events before dedupe", abci_events.len());
 
     let abci_events = dedupe(abci_events);
@@ -386,7 +411,7 @@ async fn fetch_all_events(
     Ok(events)
 }
 
-async fn latest_height(rpc_client: &HttpClient) -> Result<BlockHeight> {
+pub(super) async fn latest_height(rpc_client: &HttpClient) -> Result<BlockHeight> {
     rpc_client
         .abci_info()
         .await
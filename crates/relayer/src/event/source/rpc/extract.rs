@@ -24,9 +24,9 @@ pub fn extract_events(
                     if let DistributionType::Reward = dist.distribution_type {
                         telemetry!(fees_amount, _chain_id, &dist.receiver, dist.fee.clone());
                     }
-                } else {
-                    events_with_height.push(IbcEventWithHeight { height, event });
                 }
+
+                events_with_height.push(IbcEventWithHeight { height, event });
             }
 
             _ => {}
@@ -54,6 +54,10 @@ fn event_is_type_packet(ev: &IbcEvent) -> bool {
             | IbcEvent::AcknowledgePacket(_)
             | IbcEvent::TimeoutPacket(_)
             | IbcEvent::TimeoutOnClosePacket(_)
+            | IbcEvent::SendPacketV2(_)
+            | IbcEvent::WriteAcknowledgementV2(_)
+            | IbcEvent::AcknowledgePacketV2(_)
+            | IbcEvent::TimeoutPacketV2(_)
     )
 }
 
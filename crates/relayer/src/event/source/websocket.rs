@@ -12,17 +12,23 @@ use futures::{
 };
 use tokio::task::JoinHandle;
 use tokio::{runtime::Runtime as TokioRuntime, sync::mpsc};
-use tracing::{debug, error, info, instrument, trace};
+use tracing::{debug, error, info, instrument, trace, warn};
 
+use tendermint::block::Height as BlockHeight;
 use tendermint_rpc::{
-    client::CompatMode, event::Event as RpcEvent, query::Query, SubscriptionClient,
-    WebSocketClient, WebSocketClientDriver, WebSocketClientUrl,
+    client::CompatMode,
+    event::{Event as RpcEvent, EventData as RpcEventData},
+    query::Query,
+    HttpClient, SubscriptionClient, WebSocketClient, WebSocketClientDriver, WebSocketClientUrl,
 };
 
 use ibc_relayer_types::{core::ics24_host::identifier::ChainId, events::IbcEvent};
 
 use crate::{
-    chain::tracking::TrackingId,
+    chain::{
+        cosmos::backend::{decode_events, Backend},
+        tracking::TrackingId,
+    },
     event::{bus::EventBus, error::*, IbcEventWithHeight},
     telemetry,
     util::{
@@ -31,10 +37,17 @@ use crate::{
     },
 };
 
-use super::{EventBatch, EventSourceCmd, Result, SubscriptionStream, TxEventSourceCmd};
+use super::{rpc, EventBatch, EventSourceCmd, Result, SubscriptionStream, TxEventSourceCmd};
 
 use self::extract::extract_events;
 
+/// Extracts the IBC events from an RPC event received over a subscription.
+pub type ExtractEvents =
+    fn(&ChainId, RpcEvent) -> core::result::Result<Vec<IbcEventWithHeight>, String>;
+
+/// The maximum number of blocks whose events are backfilled after a gap.
+const MAX_BACKFILLED_BLOCKS: u64 = 1000;
+
 mod retry_strategy {
     use crate::util::retry::clamp_total;
     use core::time::Duration;
@@ -82,6 +95,14 @@ pub struct EventSource {
     rpc_compat: CompatMode,
     /// Queries
     event_queries: Vec<Query>,
+    /// Extraction of the IBC events from the query results
+    extract: ExtractEvents,
+    /// Hooks decoding the events of the transactions before their extraction
+    backend: Option<Arc<dyn Backend>>,
+    /// RPC client used to backfill the events of the blocks missed while (re)connecting
+    backfill_client: Option<HttpClient>,
+    /// Height of the last block for which events were broadcast, if backfilling
+    last_height: Option<BlockHeight>,
     /// All subscriptions combined in a single stream
     subscriptions: Box<SubscriptionStream>,
     /// Tokio runtime
@@ -89,19 +110,44 @@ pub struct EventSource {
 }
 
 impl EventSource {
-    /// Create an event source, and connect to a node
+    /// Create an event source subscribing to the default queries, and connect to a node
+    pub fn new(
+        chain_id: ChainId,
+        ws_url: WebSocketClientUrl,
+        rpc_compat: CompatMode,
+        batch_delay: Duration,
+        rt: Arc<TokioRuntime>,
+    ) -> Result<(Self, TxEventSourceCmd)> {
+        // TODO: move them to config file(?)
+        let event_queries = super::queries::all();
+
+        Self::with_queries(
+            chain_id,
+            ws_url,
+            rpc_compat,
+            batch_delay,
+            rt,
+            event_queries,
+            extract_events,
+        )
+    }
+
+    /// Create an event source subscribing to the given queries, and extracting
+    /// the IBC events from their results with the given function, and connect to a node
     #[instrument(
         name = "event_source.create",
         level = "error",
         skip_all,
         fields(chain = %chain_id, url = %ws_url)
     )]
-    pub fn new(
+    pub fn with_queries(
         chain_id: ChainId,
         ws_url: WebSocketClientUrl,
         rpc_compat: CompatMode,
         batch_delay: Duration,
         rt: Arc<TokioRuntime>,
+        event_queries: Vec<Query>,
+        extract: ExtractEvents,
     ) -> Result<(Self, TxEventSourceCmd)> {
         let event_bus = EventBus::new();
         let (tx_cmd, rx_cmd) = channel::unbounded();
@@ -115,9 +161,6 @@ impl EventSource {
         let (tx_err, rx_err) = mpsc::unbounded_channel();
         let driver_handle = rt.spawn(run_driver(driver, tx_err.clone()));
 
-        // TODO: move them to config file(?)
-        let event_queries = super::queries::all();
-
         let source = Self {
             rt,
             chain_id,
@@ -125,6 +168,10 @@ impl EventSource {
             client,
             driver_handle,
             event_queries,
+            extract,
+            backend: None,
+            backfill_client: None,
+            last_height: None,
             event_bus,
             rx_err,
             tx_err,
@@ -137,6 +184,20 @@ impl EventSource {
         Ok((source, TxEventSourceCmd(tx_cmd)))
     }
 
+    /// Decode the events of the transactions with the hooks of the given backend
+    /// before extracting the IBC events from them.
+    pub fn with_backend(mut self, backend: Arc<dyn Backend>) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    /// Backfill the events of the blocks missed while connecting and reconnecting
+    /// to the WebSocket endpoint, by querying them with the given RPC client.
+    pub fn with_backfill(mut self, rpc_client: HttpClient) -> Self {
+        self.backfill_client = Some(rpc_client);
+        self
+    }
+
     /// The list of [`Query`] that this event source is subscribing for.
     pub fn queries(&self) -> &[Query] {
         &self.event_queries
@@ -269,6 +330,14 @@ impl EventSource {
         // work around double borrow
         let rt = self.rt.clone();
 
+        // Backfill the blocks committed since the event source connected
+        if let Some(rpc_client) = &self.backfill_client {
+            match rt.block_on(rpc::latest_height(rpc_client)) {
+                Ok(latest_height) => self.last_height = Some(latest_height),
+                Err(e) => warn!("failed to query the latest height, cannot backfill events: {e}"),
+            }
+        }
+
         // Continuously run the event loop, so that when it aborts
         // because of WebSocket client restart, we pick up the work again.
         loop {
@@ -300,7 +369,13 @@ impl EventSource {
         let subscriptions = core::mem::replace(&mut self.subscriptions, Box::new(stream::empty()));
 
         // Convert the stream of RPC events into a stream of event batches.
-        let batches = stream_batches(subscriptions, self.chain_id.clone(), self.batch_delay);
+        let batches = stream_batches(
+            subscriptions,
+            self.chain_id.clone(),
+            self.batch_delay,
+            self.extract,
+            self.backend.clone(),
+        );
 
         // Needed to be able to poll the stream
         pin_mut!(batches);
@@ -329,7 +404,10 @@ impl EventSource {
             }
 
             match result {
-                Ok(batch) => self.broadcast_batch(batch),
+                Ok(batch) => {
+                    self.backfill(batch.height.revision_height()).await;
+                    self.broadcast_batch(batch);
+                }
                 Err(e) => {
                     if let ErrorDetail::SubscriptionCancelled(reason) = e.detail() {
                         error!("subscription cancelled, reason: {}", reason);
@@ -349,6 +427,70 @@ impl EventSource {
         }
     }
 
+    /// Broadcast the events of the blocks between the last block for which events were
+    /// broadcast and the block at the given height, which were missed while connecting
+    /// or reconnecting to the WebSocket endpoint, if backfilling is enabled.
+    async fn backfill(&mut self, height: u64) {
+        let Some(rpc_client) = self.backfill_client.clone() else {
+            return;
+        };
+
+        let Ok(height) = BlockHeight::try_from(height) else {
+            return;
+        };
+
+        let last_height = match self.last_height {
+            Some(last_height) if last_height < height => last_height,
+            Some(_) => return,
+            None => {
+                self.last_height = Some(height);
+                return;
+            }
+        };
+
+        self.last_height = Some(height);
+
+        let missed = height.value() - last_height.value() - 1;
+
+        if missed == 0 {
+            return;
+        }
+
+        let start = if missed > MAX_BACKFILLED_BLOCKS {
+            warn!(
+                "missed the events of {missed} blocks, only backfilling the last {MAX_BACKFILLED_BLOCKS}"
+            );
+
+            height.value() - MAX_BACKFILLED_BLOCKS
+        } else {
+            last_height.value() + 1
+        };
+
+        debug!(
+            "backfilling the events of blocks {start} to {}",
+            height.value() - 1
+        );
+
+        for missed_height in start..height.value() {
+            let Ok(missed_height) = BlockHeight::try_from(missed_height) else {
+                continue;
+            };
+
+            match rpc::collect_events(
+                &rpc_client,
+                &self.chain_id,
+                missed_height,
+                self.backend.as_deref(),
+            )
+            .await
+            {
+                Ok(Some(batch)) => self.broadcast_batch(batch),
+                Ok(None) => {}
+                Err(e) => error!(height = %missed_height, "failed to backfill events: {e}"),
+            }
+        }
+    }
+
     /// Propagate error to subscribers.
     ///
     /// The main use case for propagating RPC errors is for the [`Supervisor`]
@@ -396,9 +538,16 @@ impl EventSource {
 /// Collect the IBC events from an RPC event
 fn collect_events(
     chain_id: &ChainId,
-    event: RpcEvent,
+    mut event: RpcEvent,
+    extract: ExtractEvents,
+    backend: Option<&dyn Backend>,
 ) -> impl Stream<Item = Result<IbcEventWithHeight>> {
-    let events = extract_events(chain_id, event).unwrap_or_default();
+    if let (Some(backend), RpcEventData::Tx { tx_result }) = (backend, &mut event.data) {
+        let abci_events = core::mem::take(&mut tx_result.result.events);
+        tx_result.result.events = decode_events(backend, abci_events);
+    }
+
+    let events = extract(chain_id, event).unwrap_or_default();
     stream::iter(events).map(Ok)
 }
 
@@ -407,6 +556,8 @@ fn stream_batches(
     subscriptions: Box<SubscriptionStream>,
     chain_id: ChainId,
     batch_delay: Duration,
+    extract: ExtractEvents,
+    backend: Option<Arc<dyn Backend>>,
 ) -> impl Stream<Item = Result<EventBatch>> {
     let id = chain_id.clone();
 
@@ -414,7 +565,7 @@ fn stream_batches(
     let events = subscriptions
         .map_ok(move |rpc_event| {
             trace!(chain = %id, "received an RPC event: {}", rpc_event.query);
-            collect_events(&id, rpc_event)
+            collect_events(&id, rpc_event, extract, backend.as_deref())
         })
         .map_err(Error::canceled_or_generic)
         .try_flatten();
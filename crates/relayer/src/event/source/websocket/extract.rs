@@ -133,11 +133,7 @@ pub fn extract_events(
         RpcEventData::NewBlock { block, .. } | RpcEventData::LegacyNewBlock { block, .. }
             if query == queries::new_block().to_string() =>
         {
-            let height = Height::new(
-                ChainId::chain_version(chain_id.to_string().as_str()),
-                u64::from(block.as_ref().ok_or("tx.height")?.header.height),
-            )
-            .map_err(|_| String::from("tx.height: invalid header height of 0"))?;
+            let height = block_height(chain_id, block.as_deref())?;
 
             events_with_height.push(IbcEventWithHeight::new(
                 ClientEvents::NewBlock::new(height).into(),
@@ -146,11 +142,7 @@ pub fn extract_events(
             events_with_height.append(&mut extract_block_events(height, &events));
         }
         RpcEventData::Tx { tx_result } => {
-            let height = Height::new(
-                ChainId::chain_version(chain_id.to_string().as_str()),
-                tx_result.height as u64,
-            )
-            .map_err(|_| String::from("tx_result.height: invalid header height of 0"))?;
+            let height = tx_height(chain_id, tx_result.height)?;
 
             for abci_event in &tx_result.result.events {
                 if let Ok(ibc_event) = ibc_event_try_from_abci_event(abci_event) {
@@ -191,12 +183,14 @@ pub fn extract_events(
                     } else if query == queries::ibc_channel().to_string()
                         && event_is_type_distribute_fee(&ibc_event)
                     {
-                        if let IbcEvent::DistributeFeePacket(dist) = ibc_event {
+                        if let IbcEvent::DistributeFeePacket(dist) = &ibc_event {
                             // Only record rewarded fees
                             if let DistributionType::Reward = dist.distribution_type {
-                                telemetry!(fees_amount, chain_id, &dist.receiver, dist.fee);
+                                telemetry!(fees_amount, chain_id, &dist.receiver, dist.fee.clone());
                             }
                         }
+
+                        events_with_height.push(IbcEventWithHeight::new(ibc_event, height));
                     }
                 }
             }
@@ -207,6 +201,74 @@ pub fn extract_events(
     Ok(events_with_height)
 }
 
+/// Extract IBC events from the Tendermint RPC events of a Penumbra chain
+///
+/// Penumbra does not emit the `message.module` events of the Cosmos SDK, so the
+/// event source subscribes to all transactions instead, see [`queries::penumbra`].
+/// The IBC events emitted by the IBC actions of these transactions are collected
+/// regardless of the query they were received for. Block events are extracted
+/// in the same way as for Cosmos SDK chains, see [`extract_events`].
+pub fn extract_penumbra_events(
+    chain_id: &ChainId,
+    result: RpcEvent,
+) -> Result<Vec<IbcEventWithHeight>, String> {
+    let mut events_with_height: Vec<IbcEventWithHeight> = vec![];
+    let RpcEvent {
+        data,
+        events,
+        query,
+    } = result;
+    let events = events.ok_or("missing events")?;
+
+    match data {
+        RpcEventData::NewBlock { block, .. } | RpcEventData::LegacyNewBlock { block, .. }
+            if query == queries::new_block().to_string() =>
+        {
+            let height = block_height(chain_id, block.as_deref())?;
+
+            events_with_height.push(IbcEventWithHeight::new(
+                ClientEvents::NewBlock::new(height).into(),
+                height,
+            ));
+            events_with_height.append(&mut extract_block_events(height, &events));
+        }
+        RpcEventData::Tx { tx_result } if query == queries::tx().to_string() => {
+            let height = tx_height(chain_id, tx_result.height)?;
+
+            for abci_event in &tx_result.result.events {
+                if let Ok(ibc_event) = ibc_event_try_from_abci_event(abci_event) {
+                    if event_is_type_client(&ibc_event)
+                        || event_is_type_connection(&ibc_event)
+                        || event_is_type_channel(&ibc_event)
+                    {
+                        tracing::trace!("extracted penumbra ibc event {}", ibc_event);
+                        events_with_height.push(IbcEventWithHeight::new(ibc_event, height));
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Ok(events_with_height)
+}
+
+fn block_height(chain_id: &ChainId, block: Option<&tendermint::Block>) -> Result<Height, String> {
+    Height::new(
+        ChainId::chain_version(chain_id.to_string().as_str()),
+        u64::from(block.ok_or("tx.height")?.header.height),
+    )
+    .map_err(|_| String::from("tx.height: invalid header height of 0"))
+}
+
+fn tx_height(chain_id: &ChainId, height: i64) -> Result<Height, String> {
+    Height::new(
+        ChainId::chain_version(chain_id.to_string().as_str()),
+        height as u64,
+    )
+    .map_err(|_| String::from("tx_result.height: invalid header height of 0"))
+}
+
 fn event_is_type_client(ev: &IbcEvent) -> bool {
     matches!(
         ev,
@@ -248,6 +310,10 @@ fn event_is_type_channel(ev: &IbcEvent) -> bool {
             | IbcEvent::AcknowledgePacket(_)
             | IbcEvent::TimeoutPacket(_)
             | IbcEvent::TimeoutOnClosePacket(_)
+            | IbcEvent::SendPacketV2(_)
+            | IbcEvent::WriteAcknowledgementV2(_)
+            | IbcEvent::AcknowledgePacketV2(_)
+            | IbcEvent::TimeoutPacketV2(_)
     )
 }
 
@@ -390,3 +456,129 @@ fn extract_block_events(
 
     events
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tendermint::abci;
+    use tendermint::block::{Block, Id as BlockId};
+    use tendermint::evidence::List as EvidenceList;
+    use tendermint_rpc::event::{TxInfo, TxResult};
+    use tendermint_testgen::{Generator, LightBlock as TestgenLightBlock};
+
+    use ibc_relayer_types::core::ics04_channel::events::Attributes as ChannelAttributes;
+
+    fn chain_id() -> ChainId {
+        ChainId::from_string("penumbra-1")
+    }
+
+    fn height(revision_height: u64) -> Height {
+        Height::new(chain_id().version(), revision_height).unwrap()
+    }
+
+    fn channel_open_init() -> abci::Event {
+        let attributes = ChannelAttributes {
+            port_id: "transfer".parse().unwrap(),
+            channel_id: Some("channel-0".parse().unwrap()),
+            connection_id: "connection-0".parse().unwrap(),
+            counterparty_port_id: "transfer".parse().unwrap(),
+            counterparty_channel_id: None,
+        };
+
+        ChannelEvents::OpenInit::try_from(attributes)
+            .unwrap()
+            .into()
+    }
+
+    fn tx_event(query: String, events: Vec<abci::Event>) -> RpcEvent {
+        RpcEvent {
+            query,
+            data: RpcEventData::Tx {
+                tx_result: TxInfo {
+                    height: 5,
+                    index: Some(0),
+                    tx: vec![],
+                    result: TxResult {
+                        log: None,
+                        gas_wanted: None,
+                        gas_used: None,
+                        events,
+                    },
+                },
+            },
+            events: Some(HashMap::new()),
+        }
+    }
+
+    fn new_block_event(block: Option<Block>) -> RpcEvent {
+        RpcEvent {
+            query: queries::new_block().to_string(),
+            data: RpcEventData::NewBlock {
+                block: block.map(Box::new),
+                block_id: BlockId::default(),
+                result_finalize_block: None,
+            },
+            events: Some(HashMap::new()),
+        }
+    }
+
+    #[test]
+    fn extract_penumbra_tx_events() {
+        let event = tx_event(
+            queries::tx().to_string(),
+            vec![
+                abci::Event::new(
+                    "penumbra.core.component.sct.v1.EventCommitment",
+                    Vec::<abci::EventAttribute>::new(),
+                ),
+                channel_open_init(),
+            ],
+        );
+
+        let events = extract_penumbra_events(&chain_id(), event).unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0].event, IbcEvent::OpenInitChannel(_)));
+        assert_eq!(events[0].height, height(5));
+    }
+
+    #[test]
+    fn ignore_penumbra_tx_events_of_other_queries() {
+        let event = tx_event(
+            queries::ibc_channel().to_string(),
+            vec![channel_open_init()],
+        );
+
+        let events = extract_penumbra_events(&chain_id(), event).unwrap();
+
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn extract_penumbra_new_block_events() {
+        let header = TestgenLightBlock::new_default(7)
+            .generate()
+            .unwrap()
+            .signed_header
+            .header;
+
+        let block = Block::new(header, vec![], EvidenceList::default(), None);
+
+        let events = extract_penumbra_events(&chain_id(), new_block_event(Some(block))).unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0].event, IbcEvent::NewBlock(_)));
+        assert_eq!(events[0].height, height(7));
+    }
+
+    #[test]
+    fn reject_incomplete_penumbra_events() {
+        assert!(extract_penumbra_events(&chain_id(), new_block_event(None)).is_err());
+
+        let mut event = tx_event(queries::tx().to_string(), vec![channel_open_init()]);
+        event.events = None;
+
+        assert!(extract_penumbra_events(&chain_id(), event).is_err());
+    }
+}
@@ -0,0 +1,529 @@
+//! Accounting of the ICS-29 fees distributed to relayers.
+//!
+//! The fee accounting consumes the `IncentivizedPacket` events, which record the fees
+//! escrowed for the packets sent on a channel, and the `DistributeFeePacket` events,
+//! which are emitted when the fees of a packet are distributed upon its acknowledgement
+//! or timeout. Each distribution is attributed to the channel of the packet being
+//! acknowledged or timed out, and to the address of the relayer being rewarded.
+//!
+//! The `IncentivizedPacket` events carry the cumulative fees of a packet, so the fees
+//! of each packet are tracked until they are distributed, in order to only account for
+//! the fees added by each event.
+
+use alloc::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use tracing::{error, warn};
+
+use ibc_relayer_types::applications::ics29_fee::events::{
+    DistributeFeePacket, DistributionType, IncentivizedPacket,
+};
+use ibc_relayer_types::applications::transfer::RawCoin;
+use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc_relayer_types::events::IbcEvent;
+
+use crate::config::FeeAccountingConfig;
+use crate::error::Error;
+use crate::event::IbcEventWithHeight;
+use crate::telemetry;
+use crate::util::lock::{LockExt, RwArc};
+
+/// The default location of the fee report, relative to the home directory.
+pub const FEE_REPORT_DEFAULT_PATH: &str = ".hermes/fee_report.json";
+
+/// Amounts of fees indexed by denomination.
+pub type Amounts = BTreeMap<String, u128>;
+
+/// The fees escrowed and distributed on the incentivized channels of each chain.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeReport {
+    pub chains: BTreeMap<ChainId, ChainFeeReport>,
+}
+
+impl FeeReport {
+    /// Loads the fee report persisted at the given path.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let report_json = fs::read_to_string(path).map_err(|e| io_error(path, e))?;
+
+        serde_json::from_str(&report_json).map_err(|e| json_error(path, e))
+    }
+
+    /// Loads the fee report persisted at the given path, or returns an
+    /// empty report if the file does not exist yet.
+    pub fn load_or_default(path: &Path) -> Result<Self, Error> {
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Persists the fee report at the given path.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let report_json = serde_json::to_string_pretty(self).map_err(|e| json_error(path, e))?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| io_error(path, e))?;
+        }
+
+        fs::write(path, report_json).map_err(|e| io_error(path, e))
+    }
+
+    /// Returns the part of the report which concerns the given chain.
+    pub fn for_chain(&self, chain_id: &ChainId) -> Self {
+        Self {
+            chains: self
+                .chains
+                .iter()
+                .filter(|(id, _)| *id == chain_id)
+                .map(|(id, report)| (id.clone(), report.clone()))
+                .collect(),
+        }
+    }
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> Error {
+    Error::persisted_file_io("fee report".to_string(), path.display().to_string(), e)
+}
+
+fn json_error(path: &Path, e: serde_json::Error) -> Error {
+    Error::persisted_file_json("fee report".to_string(), path.display().to_string(), e)
+}
+
+/// The fees escrowed and distributed on the incentivized channels of a chain,
+/// indexed by port and channel.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainFeeReport {
+    pub channels: BTreeMap<PortId, BTreeMap<ChannelId, ChannelFeeReport>>,
+}
+
+impl ChainFeeReport {
+    fn channel_mut(&mut self, port_id: &PortId, channel_id: &ChannelId) -> &mut ChannelFeeReport {
+        self.channels
+            .entry(port_id.clone())
+            .or_default()
+            .entry(channel_id.clone())
+            .or_default()
+    }
+}
+
+/// The fees escrowed and distributed on an incentivized channel.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelFeeReport {
+    /// Number of packets incentivized on the channel.
+    pub incentivized_packets: u64,
+    /// Total fees escrowed for the packets sent on the channel.
+    pub escrowed_fees: Amounts,
+    /// Number of packets whose fees were distributed.
+    pub rewarded_packets: u64,
+    /// Number of packets whose fees were distributed without crediting the relayer.
+    pub uncredited_packets: u64,
+    /// Total fees rewarded, indexed by the address of the relayer being credited.
+    pub rewards: BTreeMap<String, Amounts>,
+    /// Fees escrowed for the packets whose fees were not distributed yet, by sequence.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pending_fees: BTreeMap<Sequence, PacketFees>,
+}
+
+/// The cumulative fees escrowed for a packet.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PacketFees {
+    pub recv_fee: Amounts,
+    pub ack_fee: Amounts,
+    pub timeout_fee: Amounts,
+}
+
+impl PacketFees {
+    fn new(packet: &IncentivizedPacket) -> Self {
+        let amounts = |coins: &[RawCoin]| {
+            let mut amounts = Amounts::new();
+            add_coins(&mut amounts, coins);
+            amounts
+        };
+
+        Self {
+            recv_fee: amounts(&packet.total_recv_fee),
+            ack_fee: amounts(&packet.total_ack_fee),
+            timeout_fee: amounts(&packet.total_timeout_fee),
+        }
+    }
+
+    fn total(&self) -> Amounts {
+        let mut total = self.recv_fee.clone();
+        for amounts in [&self.ack_fee, &self.timeout_fee] {
+            for (denom, amount) in amounts {
+                let sum = total.entry(denom.clone()).or_default();
+                *sum = sum.saturating_add(*amount);
+            }
+        }
+        total
+    }
+}
+
+/// The fee rewards distributed for a packet being acknowledged or timed out.
+struct Distribution {
+    port_id: PortId,
+    channel_id: ChannelId,
+    sequence: Sequence,
+    /// Whether the packet was acknowledged, in which case its `recv_fee` is distributed
+    /// first, or timed out, in which case its `timeout_fee` is distributed first.
+    acknowledged: bool,
+    rewards: Vec<DistributeFeePacket>,
+}
+
+impl Distribution {
+    fn new(packet: &Packet, acknowledged: bool) -> Self {
+        Self {
+            port_id: packet.source_port.clone(),
+            channel_id: packet.source_channel.clone(),
+            sequence: packet.sequence,
+            acknowledged,
+            rewards: Vec::new(),
+        }
+    }
+}
+
+/// Keeps track of the fees escrowed and distributed on the incentivized
+/// channels, and persists the totals in the configured file, if any.
+#[derive(Clone, Debug, Default)]
+pub struct FeeAccounting {
+    report: RwArc<FeeReport>,
+    relayer_addresses: RwArc<BTreeMap<ChainId, String>>,
+    file: Option<PathBuf>,
+    /// The number of updates of the report, and the last one persisted to the file,
+    /// so that a stale report never overwrites a more recent one.
+    updates: RwArc<u64>,
+    saved_update: Arc<Mutex<u64>>,
+}
+
+impl FeeAccounting {
+    /// Sets up the fee accounting, resuming from the totals persisted in the
+    /// configured file, or returns `None` if fee accounting is disabled.
+    pub fn from_config(config: &FeeAccountingConfig) -> Result<Option<Self>, Error> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let file = report_path(config)?;
+        let report = FeeReport::load_or_default(&file)?;
+
+        Ok(Some(Self {
+            report: RwArc::new_lock(report),
+            relayer_addresses: RwArc::default(),
+            file: Some(file),
+            updates: RwArc::default(),
+            saved_update: Arc::default(),
+        }))
+    }
+
+    /// Registers the address of the relayer on the given chain, which is
+    /// expected to be credited with the fees of the packets it relays.
+    /// The relayer registers that address as its counterparty payee, to be
+    /// credited with the `recv_fee` of the packets sent from that chain.
+    pub fn set_relayer_address(&self, chain_id: ChainId, address: String) {
+        self.relayer_addresses
+            .acquire_write()
+            .insert(chain_id, address);
+    }
+
+    /// Returns a copy of the current fee report.
+    pub fn report(&self) -> FeeReport {
+        self.report.acquire_read().clone()
+    }
+
+    /// Records the incentivized packets and fee distributions of a batch of
+    /// events emitted by the given chain, and persists the updated totals.
+    pub fn record_events(&self, chain_id: &ChainId, events: &[IbcEventWithHeight]) {
+        let Some((report, update)) = self.update_report(chain_id, events) else {
+            return;
+        };
+
+        if let Some(file) = &self.file {
+            let mut saved_update = self.saved_update.lock().expect("poisoned lock");
+
+            if *saved_update >= update {
+                return;
+            }
+
+            match report.save(file) {
+                Ok(()) => *saved_update = update,
+                Err(e) => error!("failed to persist the fee report: {e}"),
+            }
+        }
+    }
+
+    /// Updates the report with the given events, and returns a copy of the updated
+    /// report along with its update number, or `None` if the report did not change.
+    fn update_report(
+        &self,
+        chain_id: &ChainId,
+        events: &[IbcEventWithHeight],
+    ) -> Option<(FeeReport, u64)> {
+        let relayer_address = self.relayer_addresses.acquire_read().get(chain_id).cloned();
+
+        let mut report = self.report.acquire_write();
+        let chain_report = report.chains.entry(chain_id.clone()).or_default();
+
+        let mut updated = false;
+        let mut distribution = None;
+
+        for event_with_height in events {
+            match &event_with_height.event {
+                IbcEvent::IncentivizedPacket(packet) => {
+                    let channel = chain_report.channel_mut(&packet.port_id, &packet.channel_id);
+                    let fees = PacketFees::new(packet);
+
+                    // Only the fees added since the previous event for the same packet are escrowed
+                    let previous_total = match channel.pending_fees.insert(packet.sequence, fees) {
+                        Some(previous_fees) => previous_fees.total(),
+                        None => {
+                            channel.incentivized_packets += 1;
+                            Amounts::new()
+                        }
+                    };
+
+                    for (denom, amount) in channel.pending_fees[&packet.sequence].total() {
+                        let previous = previous_total.get(&denom).copied().unwrap_or_default();
+                        let escrowed = channel.escrowed_fees.entry(denom).or_default();
+                        *escrowed = escrowed.saturating_add(amount.saturating_sub(previous));
+                    }
+
+                    updated = true;
+                }
+                // The fees of a packet are distributed right after it is acknowledged or timed out
+                IbcEvent::AcknowledgePacket(ev) => {
+                    updated |= record_distribution(
+                        chain_id,
+                        chain_report,
+                        distribution.replace(Distribution::new(&ev.packet, true)),
+                        relayer_address.as_deref(),
+                    );
+                }
+                IbcEvent::TimeoutPacket(ev) => {
+                    updated |= record_distribution(
+                        chain_id,
+                        chain_report,
+                        distribution.replace(Distribution::new(&ev.packet, false)),
+                        relayer_address.as_deref(),
+                    );
+                }
+                IbcEvent::TimeoutOnClosePacket(ev) => {
+                    updated |= record_distribution(
+                        chain_id,
+                        chain_report,
+                        distribution.replace(Distribution::new(&ev.packet, false)),
+                        relayer_address.as_deref(),
+                    );
+                }
+                IbcEvent::DistributeFeePacket(dist) => {
+                    // Only record rewarded fees
+                    if let (DistributionType::Reward, Some(distribution)) =
+                        (&dist.distribution_type, distribution.as_mut())
+                    {
+                        distribution.rewards.push(dist.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        updated |= record_distribution(
+            chain_id,
+            chain_report,
+            distribution,
+            relayer_address.as_deref(),
+        );
+
+        if !updated {
+            return None;
+        }
+
+        let mut updates = self.updates.acquire_write();
+        *updates += 1;
+
+        Some((report.clone(), *updates))
+    }
+}
+
+/// Attributes the rewards of the given distribution, if any, to the channel of the packet
+/// and to the credited relayers. Returns whether or not the report was updated.
+fn record_distribution(
+    chain_id: &ChainId,
+    chain_report: &mut ChainFeeReport,
+    distribution: Option<Distribution>,
+    relayer_address: Option<&str>,
+) -> bool {
+    let Some(distribution) = distribution.filter(|d| !d.rewards.is_empty()) else {
+        return false;
+    };
+
+    let channel = chain_report.channel_mut(&distribution.port_id, &distribution.channel_id);
+    channel.rewarded_packets += 1;
+
+    let fees = channel.pending_fees.remove(&distribution.sequence);
+
+    for reward in &distribution.rewards {
+        add_coins(
+            channel
+                .rewards
+                .entry(reward.receiver.to_string())
+                .or_default(),
+            [&reward.fee],
+        );
+    }
+
+    // The fee earned by relaying the packet, ie. its `recv_fee` if it was acknowledged or
+    // its `timeout_fee` if it timed out, is distributed first, unless it is known to be empty.
+    let earned_fee_is_empty = fees.is_some_and(|fees| {
+        if distribution.acknowledged {
+            fees.recv_fee.is_empty()
+        } else {
+            fees.timeout_fee.is_empty()
+        }
+    });
+
+    if let Some(address) = relayer_address.filter(|_| !earned_fee_is_empty) {
+        let credited = distribution.rewards[0].receiver.as_ref() == address;
+
+        if !credited {
+            channel.uncredited_packets += 1;
+
+            warn!(
+                chain = %chain_id,
+                port = %distribution.port_id,
+                channel = %distribution.channel_id,
+                sequence = %distribution.sequence,
+                "fees of incentivized packet were distributed without crediting the relayer address {address}"
+            );
+
+            telemetry!(
+                ics29_uncredited_packets,
+                chain_id,
+                &distribution.channel_id,
+                &distribution.port_id,
+                1
+            );
+        }
+    }
+
+    true
+}
+
+fn add_coins<'a>(amounts: &mut Amounts, coins: impl IntoIterator<Item = &'a RawCoin>) {
+    for coin in coins {
+        let Ok(amount) = coin.amount.to_string().parse::<u128>() else {
+            continue;
+        };
+
+        let total = amounts.entry(coin.denom.clone()).or_default();
+        *total = total.saturating_add(amount);
+    }
+}
+
+/// Returns the path of the fee report, either the configured one or the default one.
+pub fn report_path(config: &FeeAccountingConfig) -> Result<PathBuf, Error> {
+    match &config.file {
+        Some(file) => Ok(file.clone()),
+        None => dirs_next::home_dir()
+            .map(|home| home.join(FEE_REPORT_DEFAULT_PATH))
+            .ok_or_else(Error::home_location_unavailable),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+
+    use ibc_relayer_types::core::ics04_channel::events::AcknowledgePacket;
+    use ibc_relayer_types::signer::Signer;
+    use ibc_relayer_types::Height;
+
+    fn event(event: IbcEvent) -> IbcEventWithHeight {
+        IbcEventWithHeight::new(event, Height::new(0, 1).unwrap())
+    }
+
+    fn reward(receiver: &str, fee: &str) -> IbcEvent {
+        IbcEvent::DistributeFeePacket(DistributeFeePacket {
+            receiver: Signer::from_str(receiver).unwrap(),
+            fee: RawCoin::from_str(fee).unwrap(),
+            distribution_type: DistributionType::Reward,
+        })
+    }
+
+    fn ack(sequence: u64) -> IbcEvent {
+        let packet = Packet {
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::new(0),
+            sequence: sequence.into(),
+            ..Default::default()
+        };
+
+        IbcEvent::AcknowledgePacket(AcknowledgePacket { packet })
+    }
+
+    #[test]
+    fn record_fee_distributions() {
+        let chain_id = ChainId::from_string("ibc-0");
+        let accounting = FeeAccounting::default();
+        accounting.set_relayer_address(chain_id.clone(), "relayer".to_owned());
+
+        let incentivized = |sequence: u64, recv_fee: &str| {
+            IbcEvent::IncentivizedPacket(IncentivizedPacket {
+                port_id: PortId::transfer(),
+                channel_id: ChannelId::new(0),
+                sequence: sequence.into(),
+                total_recv_fee: vec![RawCoin::from_str(recv_fee).unwrap()],
+                total_ack_fee: vec![RawCoin::from_str("50stake").unwrap()],
+                total_timeout_fee: vec![RawCoin::from_str("10stake").unwrap()],
+            })
+        };
+
+        accounting.record_events(
+            &chain_id,
+            &[
+                event(incentivized(1, "100stake")),
+                event(incentivized(2, "100stake")),
+                // The fees of the packet 2 are topped up
+                event(incentivized(2, "200stake")),
+            ],
+        );
+
+        let report = accounting.report();
+        let channel = &report.chains[&chain_id].channels[&PortId::transfer()][&ChannelId::new(0)];
+
+        assert_eq!(channel.incentivized_packets, 2);
+        assert_eq!(channel.escrowed_fees["stake"], 420);
+        assert_eq!(channel.pending_fees.len(), 2);
+
+        accounting.record_events(
+            &chain_id,
+            &[
+                event(ack(1)),
+                event(reward("relayer", "100stake")),
+                event(reward("relayer", "50stake")),
+                // The recv fee of the packet 2 is credited to another relayer
+                event(ack(2)),
+                event(reward("competitor", "200stake")),
+                event(reward("relayer", "50stake")),
+                event(ack(3)),
+                event(reward("competitor", "100stake")),
+                event(reward("competitor", "50stake")),
+            ],
+        );
+
+        let report = accounting.report();
+        let channel = &report.chains[&chain_id].channels[&PortId::transfer()][&ChannelId::new(0)];
+
+        assert_eq!(channel.rewarded_packets, 3);
+        assert_eq!(channel.uncredited_packets, 2);
+        assert_eq!(channel.rewards["relayer"]["stake"], 200);
+        assert_eq!(channel.rewards["competitor"]["stake"], 350);
+        assert!(channel.pending_fees.is_empty());
+    }
+}
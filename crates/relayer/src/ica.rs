@@ -0,0 +1,339 @@
+//! Helpers for managing ICS-27 interchain accounts from a controller chain,
+//! which work similarly to `hermes tx ica register` and `hermes tx ica send`.
+
+use core::time::Duration;
+
+use flex_error::{define_error, TraceError};
+use ibc_proto::cosmos::bank::v1beta1::{MsgMultiSend, MsgSend};
+use ibc_proto::cosmos::distribution::v1beta1::{MsgSetWithdrawAddress, MsgWithdrawDelegatorReward};
+use ibc_proto::cosmos::gov::v1::MsgVote;
+use ibc_proto::cosmos::gov::v1beta1::MsgVote as LegacyMsgVote;
+use ibc_proto::cosmos::staking::v1beta1::{MsgBeginRedelegate, MsgDelegate, MsgUndelegate};
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::apps::transfer::v1::MsgTransfer;
+use ibc_proto::Protobuf;
+use ibc_relayer_types::applications::ics27_ica::cosmos_tx::CosmosTx;
+use ibc_relayer_types::applications::ics27_ica::msgs::register::{
+    LegacyMsgRegisterInterchainAccount, MsgRegisterInterchainAccount,
+};
+use ibc_relayer_types::applications::ics27_ica::msgs::send_tx::MsgSendTx;
+use ibc_relayer_types::applications::ics27_ica::packet_data::InterchainAccountPacketData;
+use ibc_relayer_types::core::ics04_channel::channel::Ordering;
+use ibc_relayer_types::core::ics04_channel::version::Version;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ConnectionId};
+use ibc_relayer_types::timestamp::Timestamp;
+use ibc_relayer_types::tx_msg::Msg;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::chain::handle::ChainHandle;
+use crate::error::Error;
+use crate::event::IbcEventWithHeight;
+use crate::transfer::{send_messages, TransferError};
+use crate::upgrade_chain::{requires_legacy_upgrade_proposal, UpgradeChainError};
+
+define_error! {
+    InterchainAccountError {
+        Key
+            [ Error ]
+            |_| { "key error" },
+
+        Send
+            [ TransferError ]
+            |_| { "failed to send the interchain account message" },
+
+        IbcGoVersion
+            [ UpgradeChainError ]
+            |_| { "failed to determine the ibc-go version of the controller chain" },
+
+        InvalidTimeout
+            { timeout: Duration }
+            |e| {
+                format!("invalid relative timeout for the interchain account transaction: {:?}",
+                    e.timeout)
+            },
+
+        UnsupportedOrdering
+            { chain_id: ChainId }
+            |e| {
+                format!("chain {} only supports unordered interchain account channels, \
+                    as its ibc-go version predates the `ordering` field of `MsgRegisterInterchainAccount`",
+                    e.chain_id)
+            },
+
+        EmptyMessages
+            |_| { "at least one message must be sent to the interchain account" },
+
+        InvalidMessages
+            [ TraceError<serde_json::Error> ]
+            |_| { "failed to parse the interchain account messages" },
+
+        MissingMessageType
+            |_| { "interchain account message is missing its `@type` field" },
+
+        UnsupportedMessageType
+            { type_url: String }
+            |e| {
+                format!("interchain account message type `{}` cannot be encoded from its JSON fields, \
+                    which is only supported for the types: {}; other types must be given as their \
+                    protobuf-encoded `value`, in base64 or in hex prefixed by `0x`",
+                    e.type_url, SUPPORTED_MESSAGE_TYPES.join(", "))
+            },
+
+        InvalidMessageValue
+            { type_url: String }
+            |e| {
+                format!("the `value` of the interchain account message of type `{}` is neither \
+                    valid base64 nor valid hex prefixed by `0x`", e.type_url)
+            },
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RegisterInterchainAccountOptions {
+    pub connection_id: ConnectionId,
+    /// The channel version to negotiate. When empty, the controller
+    /// chain uses its default interchain account metadata.
+    pub version: Version,
+    pub ordering: Ordering,
+}
+
+#[derive(Clone, Debug)]
+pub struct SendInterchainTxOptions {
+    pub connection_id: ConnectionId,
+    /// The messages to execute on the host chain, signed by the interchain account.
+    pub messages: Vec<Any>,
+    pub memo: Option<String>,
+    /// The timeout of the packet, relative to the time of the controller chain.
+    pub relative_timeout: Duration,
+}
+
+/// Registers an interchain account owned by the signer of `chain`, which
+/// initiates the opening of an interchain account channel over the given
+/// controller connection.
+pub fn register_interchain_account<Chain: ChainHandle>(
+    chain: &Chain,
+    opts: &RegisterInterchainAccountOptions,
+) -> Result<Vec<IbcEventWithHeight>, InterchainAccountError> {
+    let owner = chain.get_signer().map_err(InterchainAccountError::key)?;
+
+    // The `ordering` field of `MsgRegisterInterchainAccount` is only
+    // available from ibc-go v8 onwards
+    let legacy = requires_legacy_upgrade_proposal(chain.clone())
+        .map_err(InterchainAccountError::ibc_go_version)?;
+
+    let message = if legacy {
+        if opts.ordering == Ordering::Ordered {
+            return Err(InterchainAccountError::unsupported_ordering(chain.id()));
+        }
+
+        LegacyMsgRegisterInterchainAccount {
+            owner,
+            connection_id: opts.connection_id.clone(),
+            version: opts.version.clone(),
+        }
+        .to_any()
+    } else {
+        MsgRegisterInterchainAccount {
+            owner,
+            connection_id: opts.connection_id.clone(),
+            version: opts.version.clone(),
+            ordering: opts.ordering,
+        }
+        .to_any()
+    };
+
+    send_messages(chain, vec![message], "RegisterInterchainAccount")
+        .map_err(InterchainAccountError::send)
+}
+
+/// Sends a transaction containing the given messages to the interchain account
+/// owned by the signer of `chain` over the given controller connection.
+pub fn send_interchain_tx<Chain: ChainHandle>(
+    chain: &Chain,
+    opts: &SendInterchainTxOptions,
+) -> Result<Vec<IbcEventWithHeight>, InterchainAccountError> {
+    if opts.messages.is_empty() {
+        return Err(InterchainAccountError::empty_messages());
+    }
+
+    // A zero relative timeout is rejected by the controller chain
+    let relative_timeout = u64::try_from(opts.relative_timeout.as_nanos())
+        .ok()
+        .filter(|nanos| *nanos != 0)
+        .and_then(|nanos| Timestamp::from_nanoseconds(nanos).ok())
+        .ok_or_else(|| InterchainAccountError::invalid_timeout(opts.relative_timeout))?;
+
+    let owner = chain.get_signer().map_err(InterchainAccountError::key)?;
+
+    let cosmos_tx = CosmosTx {
+        messages: opts.messages.clone(),
+    };
+
+    let mut packet_data = InterchainAccountPacketData::new(cosmos_tx.encode_vec());
+    packet_data.memo = opts.memo.clone().unwrap_or_default();
+
+    let message = MsgSendTx {
+        owner,
+        connection_id: opts.connection_id.clone(),
+        packet_data,
+        relative_timeout,
+    }
+    .to_any();
+
+    send_messages(chain, vec![message], "SendInterchainTx").map_err(InterchainAccountError::send)
+}
+
+/// The type URLs of the messages which [`parse_messages`] knows how to encode
+/// from their JSON fields.
+const SUPPORTED_MESSAGE_TYPES: &[&str] = &[
+    "/cosmos.bank.v1beta1.MsgSend",
+    "/cosmos.bank.v1beta1.MsgMultiSend",
+    "/cosmos.staking.v1beta1.MsgDelegate",
+    "/cosmos.staking.v1beta1.MsgUndelegate",
+    "/cosmos.staking.v1beta1.MsgBeginRedelegate",
+    "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward",
+    "/cosmos.distribution.v1beta1.MsgSetWithdrawAddress",
+    "/cosmos.gov.v1beta1.MsgVote",
+    "/cosmos.gov.v1.MsgVote",
+    "/ibc.applications.transfer.v1.MsgTransfer",
+];
+
+/// Parses the messages to execute on the host chain from their JSON representation,
+/// which is either a single Cosmos message or an array of them, each tagged with its
+/// type URL as produced by `<chain binary> tx ... --generate-only`, e.g.
+/// `[{ "@type": "/cosmos.bank.v1beta1.MsgSend", "from_address": "...", "to_address": "...", "amount": [...] }]`.
+///
+/// Only the messages listed in [`SUPPORTED_MESSAGE_TYPES`] can be encoded from their
+/// JSON fields. Any message, including those of other types, can instead be given as
+/// its protobuf encoding, in base64 or in hex prefixed by `0x`, e.g.
+/// `{ "@type": "/cosmwasm.wasm.v1.MsgExecuteContract", "value": "CgR0ZXN0..." }`.
+/// The `type_url` key is accepted in place of `@type`.
+pub fn parse_messages(json: &str) -> Result<Vec<Any>, InterchainAccountError> {
+    let messages =
+        match serde_json::from_str(json).map_err(InterchainAccountError::invalid_messages)? {
+            Value::Array(messages) => messages,
+            message => vec![message],
+        };
+
+    messages.into_iter().map(encode_message).collect()
+}
+
+fn encode_message(mut message: Value) -> Result<Any, InterchainAccountError> {
+    let type_url = message
+        .as_object_mut()
+        .and_then(|fields| fields.remove("@type").or_else(|| fields.remove("type_url")))
+        .and_then(|type_url| type_url.as_str().map(str::to_string))
+        .ok_or_else(InterchainAccountError::missing_message_type)?;
+
+    if let Some(value) = raw_value(&message) {
+        return encode_raw(type_url, value);
+    }
+
+    match type_url.as_str() {
+        "/cosmos.bank.v1beta1.MsgSend" => encode_json::<MsgSend>(message),
+        "/cosmos.bank.v1beta1.MsgMultiSend" => encode_json::<MsgMultiSend>(message),
+        "/cosmos.staking.v1beta1.MsgDelegate" => encode_json::<MsgDelegate>(message),
+        "/cosmos.staking.v1beta1.MsgUndelegate" => encode_json::<MsgUndelegate>(message),
+        "/cosmos.staking.v1beta1.MsgBeginRedelegate" => encode_json::<MsgBeginRedelegate>(message),
+        "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward" => {
+            encode_json::<MsgWithdrawDelegatorReward>(message)
+        }
+        "/cosmos.distribution.v1beta1.MsgSetWithdrawAddress" => {
+            encode_json::<MsgSetWithdrawAddress>(message)
+        }
+        "/cosmos.gov.v1beta1.MsgVote" => encode_json::<LegacyMsgVote>(message),
+        "/cosmos.gov.v1.MsgVote" => encode_json::<MsgVote>(message),
+        "/ibc.applications.transfer.v1.MsgTransfer" => encode_json::<MsgTransfer>(message),
+        _ => Err(InterchainAccountError::unsupported_message_type(type_url)),
+    }
+}
+
+/// The protobuf-encoded `value` of a message, if it is its only field
+fn raw_value(message: &Value) -> Option<&str> {
+    match message.as_object() {
+        Some(fields) if fields.len() == 1 => fields.get("value")?.as_str(),
+        _ => None,
+    }
+}
+
+fn encode_raw(type_url: String, value: &str) -> Result<Any, InterchainAccountError> {
+    let value = match value.strip_prefix("0x") {
+        Some(hex_value) => hex::decode(hex_value).ok(),
+        None => subtle_encoding::base64::decode(value).ok(),
+    }
+    .ok_or_else(|| InterchainAccountError::invalid_message_value(type_url.clone()))?;
+
+    Ok(Any { type_url, value })
+}
+
+fn encode_json<M>(message: Value) -> Result<Any, InterchainAccountError>
+where
+    M: prost::Message + prost::Name + DeserializeOwned,
+{
+    let message: M =
+        serde_json::from_value(message).map_err(InterchainAccountError::invalid_messages)?;
+
+    Ok(Any {
+        type_url: format!("/{}", M::full_name()),
+        value: message.encode_to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_messages;
+
+    #[test]
+    fn parse_interchain_account_messages() {
+        let msgs = parse_messages(
+            r#"[{ "@type": "/cosmos.bank.v1beta1.MsgSend", "from_address": "test", "amount": [] }]"#,
+        )
+        .unwrap();
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].type_url, "/cosmos.bank.v1beta1.MsgSend");
+        assert_eq!(msgs[0].value, b"\n\x04test");
+
+        let msgs = parse_messages(
+            r#"{ "@type": "/cosmos.staking.v1beta1.MsgDelegate", "delegatorAddress": "test" }"#,
+        )
+        .unwrap();
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].type_url, "/cosmos.staking.v1beta1.MsgDelegate");
+
+        assert!(parse_messages("not json").is_err());
+        assert!(parse_messages(r#"[{ "from_address": "test" }]"#).is_err());
+        assert!(parse_messages(r#"[{ "@type": "/cosmos.bank.v1beta1.MsgUnknown" }]"#).is_err());
+        assert!(
+            parse_messages(r#"[{ "@type": "/cosmos.bank.v1beta1.MsgSend", "amount": 1 }]"#)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_raw_interchain_account_messages() {
+        // `MsgExecuteContract { sender: "test" }`, which has no JSON encoder
+        let msgs = parse_messages(
+            r#"[
+                { "@type": "/cosmwasm.wasm.v1.MsgExecuteContract", "value": "CgR0ZXN0" },
+                { "type_url": "/cosmwasm.wasm.v1.MsgExecuteContract", "value": "0x0a0474657374" }
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(msgs.len(), 2);
+
+        for msg in msgs {
+            assert_eq!(msg.type_url, "/cosmwasm.wasm.v1.MsgExecuteContract");
+            assert_eq!(msg.value, b"\n\x04test");
+        }
+
+        assert!(parse_messages(
+            r#"[{ "@type": "/cosmwasm.wasm.v1.MsgExecuteContract", "sender": "test" }]"#
+        )
+        .is_err());
+        assert!(parse_messages(
+            r#"[{ "@type": "/cosmwasm.wasm.v1.MsgExecuteContract", "value": "0xzz" }]"#
+        )
+        .is_err());
+    }
+}
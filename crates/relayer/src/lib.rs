@@ -6,6 +6,7 @@
     rust_2018_idioms
 )]
 #![allow(clippy::too_many_arguments)]
+#![recursion_limit = "256"]
 // TODO: disable unwraps:
 //  https://github.com/informalsystems/hermes/issues/987
 // #![cfg_attr(not(test), deny(clippy::unwrap_used))]
@@ -30,13 +31,17 @@ pub mod denom;
 pub mod error;
 pub mod event;
 pub mod extension_options;
+pub mod fee_accounting;
 pub mod foreign_client;
+pub mod ica;
 pub mod keyring;
 pub mod light_client;
 pub mod link;
 pub mod misbehaviour;
+pub mod nft_transfer;
 pub mod object;
 pub mod path;
+pub mod price;
 pub mod registry;
 pub mod rest;
 pub mod sdk_error;
@@ -1,5 +1,6 @@
 mod detector;
 
+use std::iter;
 use std::time::Duration;
 
 use itertools::Itertools;
@@ -46,6 +47,14 @@ pub struct LightClient {
     peer_id: PeerId,
     io: AnyIo,
     enable_verification: bool,
+    witnesses: Vec<Witness>,
+    min_witnesses: usize,
+}
+
+/// A full node against which the headers fetched from the primary node are cross-checked.
+struct Witness {
+    addr: rpc::Url,
+    rpc_client: rpc::HttpClient,
 }
 
 impl super::LightClient<CosmosSdkChain> for LightClient {
@@ -65,6 +74,9 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
         let Verified { target, supporting } =
             self.verify(trusted_height, target_height, client_state, now)?;
 
+        // Refuse to use the target header if any witness disagrees with the primary.
+        self.cross_check_with_witnesses(trusted_height, &target, &supporting, client_state, now)?;
+
         // Omit the trusted header from the minimal supporting set, as it is not
         // needed when submitting the update client message.
         let supporting = {
@@ -263,15 +275,19 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
     }
 }
 
+fn rpc_client_for_addr(addr: &rpc::Url) -> Result<rpc::HttpClient, Error> {
+    rpc::HttpClient::builder(addr.clone().try_into().unwrap())
+        .user_agent(format!("hermes/{}", HERMES_VERSION))
+        .build()
+        .map_err(|e| Error::rpc(addr.clone(), e))
+}
+
 fn io_for_addr(
     addr: &rpc::Url,
     peer_id: PeerId,
     timeout: Option<Duration>,
 ) -> Result<ProdIo, Error> {
-    let rpc_client = rpc::HttpClient::builder(addr.clone().try_into().unwrap())
-        .user_agent(format!("hermes/{}", HERMES_VERSION))
-        .build()
-        .map_err(|e| Error::rpc(addr.clone(), e))?;
+    let rpc_client = rpc_client_for_addr(addr)?;
     Ok(ProdIo::new(peer_id, rpc_client, timeout))
 }
 
@@ -307,6 +323,8 @@ impl LightClient {
             io,
 
             enable_verification,
+            witnesses: vec![],
+            min_witnesses: 0,
         })
     }
 
@@ -338,15 +356,139 @@ impl LightClient {
         // updates after significant change in validator sets.
         let enable_verification = !config.trusted_node;
 
+        let witnesses = config
+            .witnesses
+            .iter()
+            .map(|addr| {
+                Ok(Witness {
+                    addr: addr.clone(),
+                    rpc_client: rpc_client_for_addr(addr)?,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+
         Ok(Self {
             chain_id: config.id.clone(),
             peer_id,
             io,
 
             enable_verification,
+            witnesses,
+            min_witnesses: config.min_witnesses,
         })
     }
 
+    /// Cross-check the verification trace of the target block against every configured witness.
+    ///
+    /// If a witness returns a header which conflicts with the target block, the light client
+    /// attack evidence against the primary is reported to the witness and an error is returned,
+    /// so that the conflicting header is never submitted to the counterparty chain.
+    fn cross_check_with_witnesses(
+        &self,
+        trusted_height: ICSHeight,
+        target: &LightBlock,
+        supporting: &[LightBlock],
+        client_state: &AnyClientState,
+        now: Time,
+    ) -> Result<(), Error> {
+        if self.witnesses.is_empty() {
+            return Ok(());
+        }
+
+        let trusted_block = self.fetch_light_block(AtHeight::At(trusted_height.into()))?;
+
+        let primary_trace = iter::once(trusted_block)
+            .chain(supporting.iter().cloned())
+            .chain(iter::once(target.clone()))
+            .unique_by(LightBlock::height)
+            .collect_vec();
+
+        self.cross_check_trace(primary_trace, client_state, now)
+    }
+
+    /// Cross-check the given trace, which ends with the target block, against every configured
+    /// witness. Witnesses which cannot be reached or fail to answer are skipped, but an error is
+    /// returned if less than `min_witnesses` of them could cross-check the target block.
+    fn cross_check_trace(
+        &self,
+        primary_trace: Vec<LightBlock>,
+        client_state: &AnyClientState,
+        now: Time,
+    ) -> Result<(), Error> {
+        let client_state = match client_state {
+            AnyClientState::Tendermint(client_state) => Ok::<_, Error>(client_state),
+        }?;
+
+        let target_height = primary_trace
+            .last()
+            .map(LightBlock::height)
+            .unwrap_or_default();
+
+        let mut answered = 0;
+
+        for witness in &self.witnesses {
+            let divergence = detector::cross_check(
+                &witness.addr,
+                witness.rpc_client.clone(),
+                primary_trace.clone(),
+                client_state,
+                now,
+            );
+
+            match divergence {
+                Ok(None) => {
+                    trace!(witness = %witness.addr, height = %target_height, "header matches witness");
+                    answered += 1;
+                }
+                Ok(Some(Divergence {
+                    evidence,
+                    challenging_block,
+                })) => {
+                    error!(
+                        witness = %witness.addr,
+                        height = %target_height,
+                        "header from primary node conflicts with witness, possible light client attack"
+                    );
+                    debug!("evidence: {evidence:#?}");
+                    debug!("challenging block: {challenging_block:#?}");
+
+                    match detector::report_evidence(
+                        witness.rpc_client.clone(),
+                        evidence.against_primary,
+                    ) {
+                        Ok(hash) => warn!(
+                            "evidence reported to witness {} with hash: {hash}",
+                            witness.addr
+                        ),
+                        Err(e) => {
+                            error!("failed to report evidence to witness {}: {e}", witness.addr)
+                        }
+                    }
+
+                    return Err(Error::light_client_attack(
+                        self.chain_id.clone(),
+                        witness.addr.clone(),
+                        target_height,
+                    ));
+                }
+                Err(e) => {
+                    warn!(witness = %witness.addr, "skipping witness: {e}");
+                }
+            }
+        }
+
+        if answered < self.min_witnesses {
+            return Err(Error::insufficient_witnesses(
+                self.chain_id.clone(),
+                target_height,
+                answered,
+                self.min_witnesses,
+            ));
+        }
+
+        Ok(())
+    }
+
     fn prepare_client(
         &self,
         client_state: &AnyClientState,
@@ -450,3 +592,103 @@ impl LightClient {
         Ok((target_header, supporting_headers))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use tendermint_testgen::{Generator, LightBlock as TestgenLightBlock};
+
+    use ibc_relayer_types::clients::ics07_tendermint::client_state::{
+        AllowUpdate, ClientState as TmClientState,
+    };
+    use ibc_relayer_types::core::ics02_client::trust_threshold::TrustThreshold;
+
+    use super::*;
+    use crate::error::ErrorDetail;
+
+    fn light_client(chain_id: &ChainId, min_witnesses: usize) -> LightClient {
+        // Nothing listens on this port, so every request to the witness fails right away
+        let addr: rpc::Url = "http://127.0.0.1:1".parse().unwrap();
+        let peer_id: PeerId = "0000000000000000000000000000000000000000".parse().unwrap();
+
+        LightClient {
+            chain_id: chain_id.clone(),
+            peer_id,
+            io: AnyIo::Prod(io_for_addr(&addr, peer_id, Some(Duration::from_secs(1))).unwrap()),
+            enable_verification: true,
+            witnesses: vec![Witness {
+                addr: addr.clone(),
+                rpc_client: rpc_client_for_addr(&addr).unwrap(),
+            }],
+            min_witnesses,
+        }
+    }
+
+    fn light_block(chain_id: &ChainId, height: u64) -> LightBlock {
+        let block = TestgenLightBlock::new_default_with_time_and_chain_id(
+            chain_id.to_string(),
+            Time::now(),
+            height,
+        )
+        .generate()
+        .unwrap();
+
+        LightBlock::new(
+            block.signed_header,
+            block.validators,
+            block.next_validators,
+            block.provider,
+        )
+    }
+
+    fn client_state(chain_id: &ChainId, height: u64) -> AnyClientState {
+        TmClientState::new(
+            chain_id.clone(),
+            TrustThreshold::ONE_THIRD,
+            Duration::from_secs(14 * 24 * 3600),
+            Duration::from_secs(21 * 24 * 3600),
+            Duration::from_secs(40),
+            ICSHeight::new(chain_id.version(), height).unwrap(),
+            Default::default(),
+            vec!["upgrade".to_string(), "upgradedIBCState".to_string()],
+            AllowUpdate {
+                after_expiry: true,
+                after_misbehaviour: true,
+            },
+        )
+        .unwrap()
+        .into()
+    }
+
+    #[test]
+    fn refuses_header_when_no_witness_answers() {
+        let chain_id = ChainId::from_string("ibc-0");
+        let client = light_client(&chain_id, 1);
+        let trace = vec![light_block(&chain_id, 5), light_block(&chain_id, 10)];
+
+        let err = client
+            .cross_check_trace(trace, &client_state(&chain_id, 5), Time::now())
+            .unwrap_err();
+
+        match err.detail() {
+            ErrorDetail::InsufficientWitnesses(e) => {
+                assert_eq!(e.answered, 0);
+                assert_eq!(e.required, 1);
+                assert_eq!(e.height.value(), 10);
+            }
+            e => panic!("expected an insufficient witnesses error, got {e}"),
+        }
+    }
+
+    #[test]
+    fn accepts_header_when_no_witness_is_required() {
+        let chain_id = ChainId::from_string("ibc-0");
+        let client = light_client(&chain_id, 0);
+        let trace = vec![light_block(&chain_id, 5), light_block(&chain_id, 10)];
+
+        client
+            .cross_check_trace(trace, &client_state(&chain_id, 5), Time::now())
+            .unwrap();
+    }
+}
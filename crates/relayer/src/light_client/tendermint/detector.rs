@@ -13,7 +13,7 @@ use tendermint_light_client::{
     verifier::ProdVerifier,
 };
 use tendermint_light_client_detector::{detect_divergence, Divergence, Provider};
-use tendermint_rpc::{Client, HttpClient};
+use tendermint_rpc::{Client, HttpClient, Url};
 
 use ibc_relayer_types::clients::ics07_tendermint::client_state::ClientState;
 
@@ -69,6 +69,44 @@ pub fn detect(
     }
 }
 
+/// Cross-check the given trace obtained from the primary node against a witness node,
+/// by comparing the target block of the trace with the one returned by the witness.
+///
+/// Returns the divergence between the primary and the witness, if any.
+pub fn cross_check(
+    witness_addr: &Url,
+    witness_client: HttpClient,
+    primary_trace: Vec<LightBlock>,
+    client_state: &ClientState,
+    now: Time,
+) -> Result<Option<Divergence>, Error> {
+    let trusted_block = primary_trace
+        .first()
+        .cloned()
+        .ok_or_else(|| Error::light_client_witness(witness_addr.clone(), "empty trace".into()))?;
+
+    let status =
+        block_on(witness_client.status()).map_err(|e| Error::rpc(witness_addr.clone(), e))?;
+
+    let options = client_state.as_light_client_options();
+    let mut provider = make_provider(
+        status.node_info.id,
+        witness_client,
+        client_state,
+        trusted_block,
+        now,
+    )?;
+
+    block_on(detect_divergence::<Hasher>(
+        None,
+        &mut provider,
+        primary_trace,
+        options.clock_drift,
+        options.trusting_period,
+    ))
+    .map_err(|e| Error::light_client_witness(witness_addr.clone(), e.to_string()))
+}
+
 fn make_provider(
     peer_id: PeerId,
     rpc_client: HttpClient,
@@ -5,10 +5,10 @@ use ibc_relayer_types::core::{
     ics04_channel::packet::Sequence,
     ics24_host::identifier::{ChannelId, PortChannelId, PortId},
 };
-use tracing::info;
+use tracing::{info, warn};
 
+use crate::chain::{capabilities::Capability, handle::ChainHandle, requests::IncludeProof};
 use crate::chain::{counterparty::check_channel_counterparty, requests::QueryConnectionRequest};
-use crate::chain::{handle::ChainHandle, requests::IncludeProof};
 use crate::channel::{Channel, ChannelSide};
 use crate::link::error::LinkError;
 use crate::{
@@ -21,6 +21,7 @@ pub mod error;
 pub mod operational_data;
 pub mod packet_events;
 
+mod inclusion;
 mod pending;
 mod relay_path;
 mod relay_sender;
@@ -167,18 +168,28 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
         };
 
         if auto_register_counterparty_payee && a_channel.version.supports_fee() {
-            let address_a = a_chain.get_signer().map_err(LinkError::relayer)?;
-
-            info!(
-                "auto registering counterparty payee on chain {} as {} on chain {}",
-                b_chain.id(),
-                address_a,
-                a_chain.id()
-            );
-
-            b_chain
-                .maybe_register_counterparty_payee(b_channel_id.clone(), b_port_id, address_a)
-                .map_err(LinkError::relayer)?;
+            let b_capabilities = b_chain.capabilities().map_err(LinkError::relayer)?;
+
+            if b_capabilities.supports(Capability::Fees) {
+                let address_a = a_chain.get_signer().map_err(LinkError::relayer)?;
+
+                info!(
+                    "auto registering counterparty payee on chain {} as {} on chain {}",
+                    b_chain.id(),
+                    address_a,
+                    a_chain.id()
+                );
+
+                b_chain
+                    .maybe_register_counterparty_payee(b_channel_id.clone(), b_port_id, address_a)
+                    .map_err(LinkError::relayer)?;
+            } else {
+                warn!(
+                    "skipping the registration of the counterparty payee: chain {} does not support {}",
+                    b_chain.id(),
+                    Capability::Fees
+                );
+            }
         }
 
         Link::new(channel, with_tx_confirmation, opts)
@@ -0,0 +1,179 @@
+use alloc::collections::VecDeque;
+use core::time::Duration;
+
+use ibc_relayer_types::core::ics04_channel::packet::Packet;
+
+use crate::chain::endpoint::ChainStatus;
+
+/// The number of chain statuses used for estimating the block time.
+const MAX_SAMPLES: usize = 10;
+
+/// Predicts when a transaction submitted to a chain will be included in a block,
+/// based on the recently observed block times of the chain, falling back on its
+/// configured `max_block_time` until enough blocks have been observed, and on the
+/// number of transactions the relayer is still waiting to be included.
+#[derive(Clone, Debug)]
+pub struct InclusionPredictor {
+    max_block_time: Duration,
+    samples: VecDeque<ChainStatus>,
+}
+
+impl InclusionPredictor {
+    pub fn new(max_block_time: Duration) -> Self {
+        Self {
+            max_block_time,
+            samples: VecDeque::with_capacity(MAX_SAMPLES),
+        }
+    }
+
+    /// Records the latest status of the chain, ignoring statuses which are not
+    /// more recent than the previously observed ones.
+    pub fn observe(&mut self, status: &ChainStatus) {
+        if let Some(latest) = self.samples.back() {
+            // The samples taken before a chain upgrade are not relevant anymore
+            if latest.height.revision_number() != status.height.revision_number() {
+                self.samples.clear();
+            } else if latest.height >= status.height {
+                return;
+            }
+        }
+
+        if self.samples.len() == MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+
+        self.samples.push_back(status.clone());
+    }
+
+    /// The average time between the observed blocks, or the configured
+    /// `max_block_time` if less than two blocks have been observed.
+    pub fn block_time(&self) -> Duration {
+        let (Some(first), Some(last)) = (self.samples.front(), self.samples.back()) else {
+            return self.max_block_time;
+        };
+
+        let blocks = last.height.revision_height() - first.height.revision_height();
+
+        match last.timestamp.duration_since(&first.timestamp) {
+            Some(elapsed) if blocks > 0 => elapsed / u32::try_from(blocks).unwrap_or(u32::MAX),
+            _ => self.max_block_time,
+        }
+    }
+
+    /// Returns `true` if the given packet is likely to time out before a transaction
+    /// receiving it is included, given the current status of the chain and the number
+    /// of transactions submitted by the relayer to the chain which are still pending.
+    ///
+    /// The transaction is assumed to be included in the next block if there are no
+    /// pending transactions. Otherwise, the chain is assumed to be congested, and each
+    /// pending transaction to delay the inclusion by one more block.
+    pub fn predict_timeout(
+        &self,
+        packet: &Packet,
+        status: &ChainStatus,
+        pending_txs: usize,
+    ) -> bool {
+        let blocks = u32::try_from(pending_txs)
+            .unwrap_or(u32::MAX)
+            .saturating_add(1);
+
+        let inclusion_height = status.height + u64::from(blocks);
+
+        match status.timestamp + self.block_time().saturating_mul(blocks) {
+            Ok(inclusion_timestamp) => packet.timed_out(&inclusion_timestamp, inclusion_height),
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics04_channel::timeout::TimeoutHeight;
+    use ibc_relayer_types::timestamp::Timestamp;
+    use ibc_relayer_types::Height;
+
+    fn status(height: u64, secs: u64) -> ChainStatus {
+        ChainStatus {
+            height: Height::new(1, height).unwrap(),
+            timestamp: Timestamp::from_nanoseconds(secs * 1_000_000_000).unwrap(),
+        }
+    }
+
+    #[test]
+    fn estimate_block_time() {
+        let mut predictor = InclusionPredictor::new(Duration::from_secs(30));
+        assert_eq!(predictor.block_time(), Duration::from_secs(30));
+
+        predictor.observe(&status(10, 100));
+        predictor.observe(&status(12, 110));
+        predictor.observe(&status(11, 105));
+        predictor.observe(&status(14, 120));
+        assert_eq!(predictor.block_time(), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn predict_packet_timeout() {
+        let mut predictor = InclusionPredictor::new(Duration::from_secs(30));
+        predictor.observe(&status(10, 100));
+        predictor.observe(&status(20, 150));
+
+        let latest = status(20, 150);
+
+        let packet = |secs: u64| Packet {
+            timeout_timestamp: Timestamp::from_nanoseconds(secs * 1_000_000_000).unwrap(),
+            ..Default::default()
+        };
+
+        // The next block is expected at 155s
+        assert!(!predictor.predict_timeout(&packet(156), &latest, 0));
+        assert!(!predictor.predict_timeout(&packet(155), &latest, 0));
+        assert!(predictor.predict_timeout(&packet(154), &latest, 0));
+
+        let packet = |height: u64| Packet {
+            timeout_height: TimeoutHeight::At(Height::new(1, height).unwrap()),
+            ..Default::default()
+        };
+
+        // The next block is expected at height 21
+        assert!(!predictor.predict_timeout(&packet(21), &latest, 0));
+        assert!(predictor.predict_timeout(&packet(20), &latest, 0));
+    }
+
+    #[test]
+    fn predict_packet_timeout_with_pending_txs() {
+        let mut predictor = InclusionPredictor::new(Duration::from_secs(30));
+        predictor.observe(&status(10, 100));
+        predictor.observe(&status(20, 150));
+
+        let latest = status(20, 150);
+
+        let packet = |secs: u64| Packet {
+            timeout_timestamp: Timestamp::from_nanoseconds(secs * 1_000_000_000).unwrap(),
+            ..Default::default()
+        };
+
+        // With 2 pending txs, the tx is expected to be included 3 blocks later, at 165s
+        assert!(!predictor.predict_timeout(&packet(158), &latest, 0));
+        assert!(predictor.predict_timeout(&packet(158), &latest, 2));
+        assert!(!predictor.predict_timeout(&packet(165), &latest, 2));
+
+        let packet = |height: u64| Packet {
+            timeout_height: TimeoutHeight::At(Height::new(1, height).unwrap()),
+            ..Default::default()
+        };
+
+        // With 2 pending txs, the tx is expected to be included at height 23
+        assert!(!predictor.predict_timeout(&packet(22), &latest, 0));
+        assert!(predictor.predict_timeout(&packet(22), &latest, 2));
+        assert!(!predictor.predict_timeout(&packet(23), &latest, 2));
+    }
+
+    #[test]
+    fn predict_no_timeout() {
+        let predictor = InclusionPredictor::new(Duration::from_secs(30));
+
+        assert!(!predictor.predict_timeout(&Packet::default(), &status(20, 150), 0));
+    }
+}
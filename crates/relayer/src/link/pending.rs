@@ -72,6 +72,15 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
         self.chain.id()
     }
 
+    /// The number of transactions submitted to the chain which are still pending.
+    pub fn pending_tx_count(&self) -> usize {
+        self.pending_queue
+            .clone_vec()
+            .iter()
+            .map(|pending| pending.tx_hashes.0.len())
+            .sum()
+    }
+
     /// Insert a new pending transaction to the back of the queue.
     pub fn insert_new_pending_tx(&self, r: AsyncReply, od: OperationalData) {
         let mut tx_hashes = Vec::new();
@@ -250,6 +259,8 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                         &self.counterparty_chain_id
                     );
 
+                    relay_path.record_realized_profit(&self.chain, tx_hashes, &events);
+
                     // Append the events corresponding to errors from the pending tx.
                     events.extend(pending.error_events);
 
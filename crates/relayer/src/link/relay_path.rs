@@ -6,6 +6,11 @@ use std::time::{Duration, Instant};
 
 use ibc_proto::google::protobuf::Any;
 use ibc_proto::ibc::applications::transfer::v2::FungibleTokenPacketData as RawPacketData;
+use ibc_relayer_types::applications::ics29_fee::events::DistributionType;
+use ibc_relayer_types::applications::ics29_fee::packet_fee::Fee;
+use ibc_relayer_types::applications::ics721_nft::packet::PacketData as NftPacketData;
+use ibc_relayer_types::applications::transfer::packet::PacketDataV2;
+use ibc_relayer_types::applications::transfer::RawCoin;
 use itertools::Itertools;
 use tracing::{debug, error, info, span, trace, warn, Level};
 
@@ -19,6 +24,7 @@ use ibc_relayer_types::core::ics04_channel::msgs::{
     recv_packet::MsgRecvPacket, timeout::MsgTimeout, timeout_on_close::MsgTimeoutOnClose,
 };
 use ibc_relayer_types::core::ics04_channel::packet::{Packet, PacketMsgType};
+use ibc_relayer_types::core::ics04_channel::timeout::TimeoutHeight;
 use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
 use ibc_relayer_types::events::{IbcEvent, IbcEventType, WithBlockDataType};
 use ibc_relayer_types::signer::Signer;
@@ -37,6 +43,7 @@ use crate::chain::requests::QueryHeight;
 use crate::chain::requests::QueryHostConsensusStateRequest;
 use crate::chain::requests::QueryNextSequenceReceiveRequest;
 use crate::chain::requests::QueryPacketCommitmentRequest;
+use crate::chain::requests::QueryTxHash;
 use crate::chain::requests::QueryTxRequest;
 use crate::chain::requests::QueryUnreceivedAcksRequest;
 use crate::chain::requests::QueryUnreceivedPacketsRequest;
@@ -45,12 +52,16 @@ use crate::chain::tracking::TrackedMsgs;
 use crate::chain::tracking::TrackingId;
 use crate::channel::error::ChannelError;
 use crate::channel::Channel;
+use crate::config::filter::{Ics20Rules, Ics721Rules};
+use crate::config::packet_priority::{PriorityCriterion, PriorityKey};
 use crate::config::types::ics20_field_size_limit::Ics20FieldSizeLimit;
 use crate::config::types::ics20_field_size_limit::ValidationResult;
+use crate::config::PacketPriority;
 use crate::event::source::EventBatch;
 use crate::event::IbcEventWithHeight;
 use crate::foreign_client::{ForeignClient, ForeignClientError};
 use crate::link::error::{self, LinkError};
+use crate::link::inclusion::InclusionPredictor;
 use crate::link::operational_data::{
     OperationalData, OperationalDataTarget, TrackedEvents, TransitMessage,
 };
@@ -60,11 +71,14 @@ use crate::link::packet_events::query_write_ack_events;
 use crate::link::pending::PendingTxs;
 use crate::link::relay_sender::{AsyncReply, SubmitReply};
 use crate::link::relay_summary::RelaySummary;
+use crate::link::tx_hashes::TxHashes;
 use crate::link::LinkParameters;
 use crate::link::{pending, relay_sender};
 use crate::path::PathIdentifiers;
+use crate::price::PriceTable;
 use crate::telemetry;
 use crate::util::collate::CollatedIterExt;
+use crate::util::lock::{LockExt, RwArc};
 use crate::util::pretty::PrettyEvents;
 use crate::util::queue::Queue;
 
@@ -118,6 +132,27 @@ pub struct RelayPath<ChainA: ChainHandle, ChainB: ChainHandle> {
     pub max_memo_size: Ics20FieldSizeLimit,
     pub max_receiver_size: Ics20FieldSizeLimit,
     pub exclude_src_sequences: Vec<Sequence>,
+
+    // The rules of the source chain packet filter matching on ICS-20 and ICS-721 packet data.
+    ics20_rules: Ics20Rules,
+    ics721_rules: Ics721Rules,
+
+    // The ordering and batching of the pending packets of the source chain.
+    packet_priority: PacketPriority,
+
+    // Predicts when the transactions submitted to the destination chain will be included.
+    dst_inclusion: RwArc<InclusionPredictor>,
+
+    // Whether packets predicted to time out before being received are not relayed.
+    predict_timeouts: bool,
+
+    // `SendPacket` events of packets which were predicted to time out before being
+    // received, and for which a timeout must be relayed once they have timed out.
+    predicted_timeouts: Queue<IbcEventWithHeight>,
+
+    // The prices used to record the realized profit of relaying incentivized
+    // packets from the results of the confirmed transactions, if enabled.
+    realized_profit_prices: Option<PriceTable>,
 }
 
 impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
@@ -145,6 +180,16 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         let src_port_id = channel.src_port_id().clone();
         let dst_port_id = channel.dst_port_id().clone();
 
+        let src_config = src_chain.config().map_err(LinkError::relayer)?;
+        let ics20_rules = src_config.packet_filter().ics20_rules.clone();
+        let ics721_rules = src_config.packet_filter().ics721_rules.clone();
+        let packet_priority = src_config.packet_priority().into_owned();
+
+        let dst_max_block_time = dst_chain
+            .config()
+            .map_err(LinkError::relayer)?
+            .max_block_time();
+
         let path = PathIdentifiers {
             port_id: dst_port_id.clone(),
             channel_id: dst_channel_id.clone(),
@@ -168,6 +213,15 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             max_receiver_size: link_parameters.max_receiver_size,
 
             exclude_src_sequences: link_parameters.exclude_src_sequences,
+
+            ics20_rules,
+            ics721_rules,
+            packet_priority,
+
+            dst_inclusion: RwArc::new_lock(InclusionPredictor::new(dst_max_block_time)),
+            predict_timeouts: false,
+            predicted_timeouts: Queue::new(),
+            realized_profit_prices: None,
         })
     }
 
@@ -343,6 +397,121 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             .map_err(LinkError::client)
     }
 
+    /// Estimates the fees paid on the destination chain for relaying the given packet,
+    /// committed on the source chain at the given height, by simulating the transaction
+    /// which updates the client and receives the packet.
+    pub fn estimate_recv_packet_fee(
+        &self,
+        packet: &Packet,
+        height: Height,
+    ) -> Result<Vec<RawCoin>, LinkError> {
+        let mut msgs = self.build_update_client_on_dst(height.increment())?;
+        msgs.extend(self.build_recv_packet(packet, height)?);
+
+        self.dst_chain()
+            .estimate_fee(TrackedMsgs::new_static(msgs, "estimate-recv-packet"))
+            .map_err(LinkError::relayer)
+    }
+
+    /// Skips the `MsgRecvPacket` of the packets of unordered channels which are predicted to
+    /// time out before being received, and relays their timeout once they have timed out.
+    ///
+    /// The packets predicted to time out are only tracked in memory, so this must only be
+    /// enabled if the pending packets are cleared on start, which recovers them on restart.
+    pub fn predict_timeouts(&mut self) {
+        self.predict_timeouts = true;
+    }
+
+    /// Records the realized profit of relaying incentivized packets, using the given
+    /// prices, from the results of the transactions confirmed on either chain.
+    pub fn track_realized_profit(&mut self, prices: PriceTable) {
+        self.realized_profit_prices = Some(prices);
+    }
+
+    /// Records the realized profit, or loss if negative, of the given transactions
+    /// confirmed on `chain` with the given events: the ICS-29 fees they distributed
+    /// to the relayer minus the fees paid for them, valued using the configured prices.
+    ///
+    /// The value of the fees paid per packet message is also recorded, as an estimate
+    /// of the cost of the messages which cannot be simulated in advance.
+    pub(crate) fn record_realized_profit<Chain: ChainHandle>(
+        &self,
+        chain: &Chain,
+        tx_hashes: &TxHashes,
+        events: &[IbcEvent],
+    ) {
+        let Some(prices) = &self.realized_profit_prices else {
+            return;
+        };
+
+        let fees_paid = tx_hashes
+            .0
+            .iter()
+            .map(|tx_hash| chain.query_tx_fee(QueryTxHash(*tx_hash)))
+            .flatten_ok()
+            .collect::<Result<Vec<_>, _>>();
+
+        let (fees_paid, relayer) = match fees_paid.and_then(|fees| Ok((fees, chain.get_signer()?)))
+        {
+            Ok(result) => result,
+            Err(e) => {
+                warn!("failed to query the fees paid for {tx_hashes}, not recording their realized profit: {e}");
+                return;
+            }
+        };
+
+        let rewards = events
+            .iter()
+            .filter_map(|event| match event {
+                IbcEvent::DistributeFeePacket(distribution)
+                    if distribution.distribution_type == DistributionType::Reward
+                        && distribution.receiver == relayer =>
+                {
+                    Some(distribution.fee.clone())
+                }
+                _ => None,
+            })
+            .collect_vec();
+
+        let (Some(fees_paid), Some(rewards)) =
+            (prices.value_of(&fees_paid), prices.value_of(&rewards))
+        else {
+            warn!("missing price for the fees paid or earned by {tx_hashes}, not recording their realized profit");
+            return;
+        };
+
+        let packet_msgs = events
+            .iter()
+            .filter(|event| {
+                matches!(
+                    event,
+                    IbcEvent::ReceivePacket(_)
+                        | IbcEvent::AcknowledgePacket(_)
+                        | IbcEvent::TimeoutPacket(_)
+                        | IbcEvent::TimeoutOnClosePacket(_)
+                )
+            })
+            .count();
+
+        if packet_msgs > 0 {
+            prices.observe_packet_msg_cost(&chain.id(), fees_paid / packet_msgs as f64);
+        }
+
+        debug!(
+            fees_paid,
+            rewards, "recording the realized profit of {tx_hashes}"
+        );
+
+        telemetry!(
+            ics29_relaying_profit,
+            &self.src_chain().id(),
+            &self.dst_chain().id(),
+            self.src_channel_id(),
+            self.src_port_id(),
+            rewards - fees_paid
+        );
+    }
+
     fn build_chan_close_confirm_from_event(
         &self,
         event: &IbcEventWithHeight,
@@ -397,6 +566,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                 IbcEvent::SendPacket(send_packet_ev) => {
                     if src_channel_id == send_packet_ev.src_channel_id()
                         && self.src_port_id() == send_packet_ev.src_port_id()
+                        && self.allowed_by_packet_rules(&send_packet_ev.packet)
                     {
                         result.push(event_with_height);
                     }
@@ -430,6 +600,37 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         TrackedEvents::new(result, tracking_id)
     }
 
+    /// Evaluates the ICS-20 and ICS-721 rules of the source chain packet
+    /// filter against the data of the given packet.
+    fn allowed_by_packet_rules(&self, packet: &Packet) -> bool {
+        let Some(reason) = self
+            .ics20_rules
+            .filter_reason(&packet.data)
+            .or_else(|| self.ics721_rules.filter_reason(&packet.data))
+        else {
+            return true;
+        };
+
+        debug!(
+            %reason,
+            "packet {} is filtered out by the packet data rules, not relaying packet!", packet
+        );
+
+        telemetry!(
+            filtered_packets,
+            &self.src_chain().id(),
+            &self.dst_chain().id(),
+            &packet.source_channel,
+            &packet.destination_channel,
+            &packet.source_port,
+            &packet.destination_port,
+            &reason,
+            1
+        );
+
+        false
+    }
+
     fn relay_pending_packets(
         &self,
         height: Option<Height>,
@@ -511,10 +712,173 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     }
 
     /// Produces and schedules operational data for this relaying path based on the input events.
+    ///
+    /// On unordered channels with a configured packet priority, the events are first ordered
+    /// by priority and split into batches of at most `max_packets_per_tx` events.
     pub(crate) fn events_to_operational_data(
         &self,
         events: TrackedEvents,
     ) -> Result<(), LinkError> {
+        if !self.unordered_channel() || !self.packet_priority.is_enabled() || events.is_empty() {
+            return self.schedule_events(events);
+        }
+
+        let tracking_id = events.tracking_id();
+        let prioritized = self.prioritize_events(events.events())?;
+
+        let chunk_size = self
+            .packet_priority
+            .max_packets_per_tx(self.src_channel_id())
+            .unwrap_or(prioritized.len());
+
+        for chunk in prioritized.chunks(chunk_size) {
+            self.schedule_events(TrackedEvents::new(chunk.to_vec(), tracking_id))?;
+        }
+
+        Ok(())
+    }
+
+    /// Orders the given events according to the configured packet priority.
+    /// Events other than `SendPacket` events keep their relative order and come first.
+    fn prioritize_events(
+        &self,
+        events: &[IbcEventWithHeight],
+    ) -> Result<Vec<IbcEventWithHeight>, LinkError> {
+        let (send_packets, mut prioritized): (Vec<_>, Vec<_>) =
+            events.iter().cloned().partition(|event_with_height| {
+                matches!(event_with_height.event, IbcEvent::SendPacket(_))
+            });
+
+        if self.packet_priority.order_by.is_empty() {
+            prioritized.extend(send_packets);
+            return Ok(prioritized);
+        }
+
+        let dst_status = self
+            .dst_chain()
+            .query_application_status()
+            .map_err(|e| LinkError::query(self.dst_chain().id(), e))?;
+
+        let dst_max_block_time = self.dst_max_block_time()?;
+
+        let packet_fees = self.packet_fees();
+
+        let mut keyed_packets = send_packets
+            .into_iter()
+            .map(|event_with_height| {
+                let key = match &event_with_height.event {
+                    IbcEvent::SendPacket(event) => self.packet_priority_key(
+                        &event.packet,
+                        &packet_fees,
+                        &dst_status,
+                        dst_max_block_time,
+                    ),
+                    _ => PriorityKey::default(),
+                };
+
+                (key, event_with_height)
+            })
+            .collect::<Vec<_>>();
+
+        // Sorting is stable, packets with the same priority keep their relative order
+        keyed_packets.sort_by(|(a, _), (b, _)| self.packet_priority.compare(a, b));
+
+        prioritized.extend(keyed_packets.into_iter().map(|(_, event)| event));
+
+        Ok(prioritized)
+    }
+
+    /// Computes the attributes of a packet by which it is prioritized.
+    fn packet_priority_key(
+        &self,
+        packet: &Packet,
+        packet_fees: &HashMap<Sequence, Vec<Fee>>,
+        dst_status: &ChainStatus,
+        dst_max_block_time: Duration,
+    ) -> PriorityKey {
+        // Blocks are assumed to be produced at the maximum block time of the destination chain
+        let time_to_timeout_height = match packet.timeout_height {
+            TimeoutHeight::At(height)
+                if height.revision_number() == dst_status.height.revision_number() =>
+            {
+                let blocks = height
+                    .revision_height()
+                    .saturating_sub(dst_status.height.revision_height());
+
+                Some(dst_max_block_time.saturating_mul(blocks.try_into().unwrap_or(u32::MAX)))
+            }
+            _ => None,
+        };
+
+        let time_to_timeout_timestamp = if packet.timeout_timestamp == Timestamp::none() {
+            None
+        } else {
+            Some(
+                packet
+                    .timeout_timestamp
+                    .duration_since(&dst_status.timestamp)
+                    .unwrap_or_default(),
+            )
+        };
+
+        let time_to_timeout = match (time_to_timeout_height, time_to_timeout_timestamp) {
+            (Some(height), Some(timestamp)) => Some(height.min(timestamp)),
+            (height, timestamp) => height.or(timestamp),
+        };
+
+        let fee = packet_fees
+            .get(&packet.sequence)
+            .map(|fees| self.packet_priority.fee_amounts(fees))
+            .unwrap_or_default();
+
+        PriorityKey {
+            fee,
+            time_to_timeout,
+        }
+    }
+
+    /// Returns the ICS-29 fees escrowed for the incentivized packets of the source channel,
+    /// if the packets are ordered by fee.
+    fn packet_fees(&self) -> HashMap<Sequence, Vec<Fee>> {
+        if !self
+            .packet_priority
+            .order_by
+            .contains(&PriorityCriterion::Fee)
+        {
+            return HashMap::new();
+        }
+
+        // If the query failed it could mean that the channel is not incentivized.
+        let incentivized_packets = match self
+            .src_chain()
+            .query_incentivized_packets(self.src_channel_id().clone(), self.src_port_id().clone())
+        {
+            Ok(incentivized_packets) => incentivized_packets,
+            Err(e) => {
+                debug!(
+                    "query for the incentivized packets of channel {} failed: {e}",
+                    self.src_channel_id()
+                );
+                Vec::new()
+            }
+        };
+
+        incentivized_packets
+            .into_iter()
+            .map(|packet| {
+                let fees = packet
+                    .packet_fees
+                    .into_iter()
+                    .map(|packet_fee| packet_fee.fee)
+                    .collect();
+
+                (packet.packet_id.sequence, fees)
+            })
+            .collect()
+    }
+
+    /// Produces and schedules operational data for the input events, as a single batch.
+    fn schedule_events(&self, events: TrackedEvents) -> Result<(), LinkError> {
         // Obtain the operational data for the source chain (mostly timeout packets) and for the
         // destination chain (e.g., receive packet messages).
         let (src_opt, dst_opt) = self.generate_operational_data(events)?;
@@ -559,6 +923,8 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             .query_application_status()
             .map_err(|e| LinkError::query(self.dst_chain().id(), e))?;
 
+        self.dst_inclusion.acquire_write().observe(&dst_latest_info);
+
         let dst_latest_height = dst_latest_info.height;
 
         // Operational data targeting the source chain (e.g., Timeout packets)
@@ -596,10 +962,68 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                         &packet.destination_channel,
                         &packet.source_port,
                         &packet.destination_port,
+                        "ics20_field_size",
                         1
                     );
                     continue;
                 }
+
+                // If the packet contains ICS-721 packet data, surface the class
+                // and tokens being transferred instead of the opaque packet data.
+                if let Ok(nft_data) = NftPacketData::decode(&packet.data) {
+                    let token_ids = nft_data
+                        .token_ids
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(",");
+
+                    debug!(
+                        class_id = %nft_data.class_id,
+                        %token_ids,
+                        "processing ICS-721 packet {packet}"
+                    );
+
+                    if let IbcEvent::SendPacket(_) = event_with_height.event {
+                        telemetry!(
+                            ics721_packets,
+                            &self.src_chain().id(),
+                            &self.dst_chain().id(),
+                            &packet.source_channel,
+                            &packet.source_port,
+                            &nft_data.class_id.to_string(),
+                            1
+                        );
+                    }
+                }
+
+                // Likewise for `ics20-2` packet data, which is not human-readable.
+                if let Ok(transfer_data) = PacketDataV2::decode_v2(&packet.data) {
+                    let tokens = transfer_data
+                        .tokens
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(",");
+
+                    debug!(
+                        %tokens,
+                        forwarded = transfer_data.is_forwarded(),
+                        "processing ICS-20 v2 packet {packet}"
+                    );
+
+                    if let IbcEvent::SendPacket(_) = event_with_height.event {
+                        telemetry!(
+                            ics20_v2_packets,
+                            &self.src_chain().id(),
+                            &self.dst_chain().id(),
+                            &packet.source_channel,
+                            &packet.source_port,
+                            transfer_data.tokens.len() as u64,
+                            transfer_data.is_forwarded()
+                        );
+                    }
+                }
             }
 
             let (dst_msg, src_msg) = match &event_with_height.event {
@@ -1212,6 +1636,17 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                 }
             });
 
+            // Packets filtered out by the packet data rules must not be cleared either
+            let events_chunk = events_chunk
+                .into_iter()
+                .filter(|event_with_height| match &event_with_height.event {
+                    IbcEvent::SendPacket(e) => self.allowed_by_packet_rules(&e.packet),
+                    _ => true,
+                })
+                .collect::<Vec<_>>();
+
+            // The packets are prioritized within each chunk, so that the first chunks
+            // are scheduled without waiting for the events of the next ones.
             self.events_to_operational_data(TrackedEvents::new(events_chunk, tracking_id))?;
         }
 
@@ -1476,11 +1911,138 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         if timeout.is_some() {
             Ok((None, timeout))
+        } else if self.recv_predicted_to_time_out(&event.packet, dst_info) {
+            // Submitting a `MsgRecvPacket` would most likely fail on chain, relay a
+            // timeout instead once the packet has timed out on the destination chain.
+            self.track_predicted_timeout(IbcEventWithHeight::new(
+                IbcEvent::SendPacket(event.clone()),
+                height,
+            ));
+
+            Ok((None, None))
         } else {
             Ok((self.build_recv_packet(&event.packet, height)?, None))
         }
     }
 
+    /// Returns `true` if the given packet of an unordered channel is likely to time out
+    /// before a `MsgRecvPacket` submitted now is included on the destination chain, given
+    /// the recently observed block times of the destination chain and the transactions
+    /// submitted to it which are still pending.
+    ///
+    /// Packets of ordered channels are always relayed, as skipping one of them would
+    /// block the channel until it times out, which closes the channel.
+    fn recv_predicted_to_time_out(&self, packet: &Packet, dst_info: &ChainStatus) -> bool {
+        if !self.predict_timeouts || !self.unordered_channel() {
+            return false;
+        }
+
+        let predicted = self.dst_inclusion.acquire_read().predict_timeout(
+            packet,
+            dst_info,
+            self.pending_txs_dst.pending_tx_count(),
+        );
+
+        if predicted {
+            debug!(
+                "packet {packet} is predicted to time out before being received, not relaying MsgRecvPacket"
+            );
+        }
+
+        predicted
+    }
+
+    fn track_predicted_timeout(&self, event_with_height: IbcEventWithHeight) {
+        let sequence = event_with_height
+            .event
+            .packet()
+            .map(|packet| packet.sequence);
+
+        let already_tracked = self
+            .predicted_timeouts
+            .clone_vec()
+            .iter()
+            .any(|tracked| tracked.event.packet().map(|packet| packet.sequence) == sequence);
+
+        if !already_tracked {
+            self.predicted_timeouts.push_back(event_with_height);
+        }
+    }
+
+    /// Schedules the relaying of timeouts for the packets which were predicted to time
+    /// out before being received, once they have timed out on the destination chain.
+    fn schedule_predicted_timeouts(&self) -> Result<(), LinkError> {
+        if self.predicted_timeouts.is_empty() {
+            return Ok(());
+        }
+
+        let dst_status = self
+            .dst_chain()
+            .query_application_status()
+            .map_err(|e| LinkError::query(self.dst_chain().id(), e))?;
+
+        self.dst_inclusion.acquire_write().observe(&dst_status);
+
+        let (timed_out, pending): (VecDeque<_>, VecDeque<_>) = self
+            .predicted_timeouts
+            .take()
+            .into_iter()
+            .partition(|event_with_height| {
+                event_with_height.event.packet().map_or(true, |packet| {
+                    packet.timed_out(&dst_status.timestamp, dst_status.height)
+                })
+            });
+
+        self.predicted_timeouts.replace(pending);
+
+        if timed_out.is_empty() {
+            return Ok(());
+        }
+
+        // The packets which were received anyway, eg. by another relayer, were mispredicted
+        let unreceived_sequences = self
+            .dst_chain()
+            .query_unreceived_packets(QueryUnreceivedPacketsRequest {
+                port_id: self.dst_port_id().clone(),
+                channel_id: self.dst_channel_id().clone(),
+                packet_commitment_sequences: timed_out
+                    .iter()
+                    .filter_map(|event_with_height| event_with_height.event.packet())
+                    .map(|packet| packet.sequence)
+                    .collect(),
+            })
+            .map_err(LinkError::relayer)?;
+
+        let timed_out = timed_out
+            .into_iter()
+            .filter(|event_with_height| {
+                event_with_height
+                    .event
+                    .packet()
+                    .is_some_and(|packet| unreceived_sequences.contains(&packet.sequence))
+            })
+            .collect::<Vec<_>>();
+
+        if timed_out.is_empty() {
+            return Ok(());
+        }
+
+        // A `MsgRecvPacket` submitted for these packets would have failed
+        telemetry!(
+            predicted_timeouts,
+            &self.src_chain().id(),
+            &self.dst_chain().id(),
+            self.src_channel_id(),
+            self.src_port_id(),
+            timed_out.len() as u64
+        );
+
+        self.events_to_operational_data(TrackedEvents::new(
+            timed_out,
+            TrackingId::new_static("predicted-timeouts"),
+        ))
+    }
+
     /// Drives the relaying of elapsed operational data items meant for
     /// a specified target chain forward.
     ///
@@ -1664,6 +2226,8 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     pub fn refresh_schedule(&self) -> Result<(), LinkError> {
         let _span = span!(Level::ERROR, "refresh_schedule").entered();
 
+        self.schedule_predicted_timeouts()?;
+
         // Bail fast if no op. data to refresh
         if self.dst_operational_data.is_empty() {
             return Ok(());
@@ -1981,27 +2545,46 @@ fn check_ics20_fields_size(
     memo_limit: Ics20FieldSizeLimit,
     receiver_limit: Ics20FieldSizeLimit,
 ) -> bool {
+    let Some((memo, receiver)) = decode_ics20_memo_and_receiver(data) else {
+        return true;
+    };
+
+    match (
+        memo_limit.check_field_size(&memo),
+        receiver_limit.check_field_size(&receiver),
+    ) {
+        (ValidationResult::Valid, ValidationResult::Valid) => true,
+
+        (memo_validity, receiver_validity) => {
+            warn!("found invalid ICS-20 packet data, not relaying packet!");
+            warn!("    ICS-20 memo:     {memo_validity}");
+            warn!("    ICS-20 receiver: {receiver_validity}");
+
+            false
+        }
+    }
+}
+
+/// Extracts the memo and receiver fields of ICS-20 packet data.
+///
+/// `ics20-1` packet data is JSON-encoded, whereas `ics20-2` packet data is
+/// Protobuf-encoded. For the latter, the memo forwarded to the final destination
+/// is carried along with the packet and thus counts towards the memo size.
+fn decode_ics20_memo_and_receiver(data: &[u8]) -> Option<(String, String)> {
     match serde_json::from_slice::<RawPacketData>(data) {
-        Ok(packet_data) => {
-            match (
-                memo_limit.check_field_size(&packet_data.memo),
-                receiver_limit.check_field_size(&packet_data.receiver),
-            ) {
-                (ValidationResult::Valid, ValidationResult::Valid) => true,
-
-                (memo_validity, receiver_validity) => {
-                    warn!("found invalid ICS-20 packet data, not relaying packet!");
-                    warn!("    ICS-20 memo:     {memo_validity}");
-                    warn!("    ICS-20 receiver: {receiver_validity}");
-
-                    false
-                }
+        Ok(packet_data) => Some((packet_data.memo, packet_data.receiver)),
+        Err(e) => match PacketDataV2::decode_v2(data) {
+            Ok(packet_data) => {
+                let memo = packet_data.memo.unwrap_or_default()
+                    + &packet_data.forwarding.destination_memo.unwrap_or_default();
+
+                Some((memo, packet_data.receiver.to_string()))
             }
-        }
-        Err(e) => {
-            trace!("failed to decode ICS20 packet data with error `{e}`");
+            Err(_) => {
+                trace!("failed to decode ICS20 packet data with error `{e}`");
 
-            true
-        }
+                None
+            }
+        },
     }
 }
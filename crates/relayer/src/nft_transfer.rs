@@ -0,0 +1,113 @@
+//! Helpers for ICS721 non-fungible token transfers, which work similarly
+//! to the ICS20 helpers of the [`transfer`](crate::transfer) module.
+
+use std::str::FromStr;
+
+use core::time::Duration;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_relayer_types::applications::ics721_nft::msgs::transfer::MsgTransfer;
+use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
+use ibc_relayer_types::signer::Signer;
+use ibc_relayer_types::tx_msg::Msg;
+use serde::{Deserialize, Serialize};
+
+use crate::chain::handle::ChainHandle;
+use crate::event::IbcEventWithHeight;
+use crate::transfer::{send_messages, TransferError, TransferTimeout};
+
+/// The ICS721 class trace
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ClassTrace {
+    /// The chain of port/channel identifiers used for tracing the source of the class.
+    pub path: String,
+    /// The base class identifier for that class
+    pub base_class_id: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct NftTransferOptions {
+    pub src_port_id: PortId,
+    pub src_channel_id: ChannelId,
+    /// The class of the tokens to transfer, either as its full
+    /// trace path or as its `ibc/{hash}` identifier.
+    pub class_id: String,
+    pub token_ids: Vec<String>,
+    pub receiver: Option<String>,
+    pub timeout_height_offset: u64,
+    pub timeout_duration: Duration,
+    pub memo: Option<String>,
+}
+
+pub fn build_nft_transfer_message(
+    src_port_id: PortId,
+    src_channel_id: ChannelId,
+    class_id: String,
+    token_ids: Vec<String>,
+    sender: Signer,
+    receiver: Signer,
+    timeout: TransferTimeout,
+    memo: Option<String>,
+) -> Any {
+    let msg = MsgTransfer {
+        source_port: src_port_id,
+        source_channel: src_channel_id,
+        class_id,
+        token_ids,
+        sender,
+        receiver,
+        timeout_height: timeout.timeout_height,
+        timeout_timestamp: timeout.timeout_timestamp,
+        memo,
+    };
+
+    msg.to_any()
+}
+
+pub fn build_nft_transfer_messages<SrcChain: ChainHandle, DstChain: ChainHandle>(
+    src_chain: &SrcChain, // the chain whose tokens are sent
+    dst_chain: &DstChain, // the chain where the tokens are eventually received
+    opts: &NftTransferOptions,
+) -> Result<Vec<Any>, TransferError> {
+    let receiver = match &opts.receiver {
+        Some(receiver) => Signer::from_str(receiver).map_err(TransferError::receiver_address)?,
+        None => dst_chain.get_signer().map_err(TransferError::key)?,
+    };
+
+    let sender = src_chain.get_signer().map_err(TransferError::key)?;
+
+    let destination_chain_status = dst_chain
+        .query_application_status()
+        .map_err(TransferError::relayer)?;
+
+    let timeout = TransferTimeout::new(
+        opts.timeout_height_offset,
+        opts.timeout_duration,
+        &destination_chain_status,
+    )?;
+
+    let message = build_nft_transfer_message(
+        opts.src_port_id.clone(),
+        opts.src_channel_id.clone(),
+        opts.class_id.clone(),
+        opts.token_ids.clone(),
+        sender,
+        receiver,
+        timeout,
+        opts.memo.clone(),
+    );
+
+    Ok(vec![message])
+}
+
+pub fn build_and_send_nft_transfer_messages<SrcChain: ChainHandle, DstChain: ChainHandle>(
+    // the chain whose tokens are sent
+    src_chain: &SrcChain,
+    // the chain where the tokens are eventually received
+    dst_chain: &DstChain,
+    // options describing the transfer
+    opts: &NftTransferOptions,
+) -> Result<Vec<IbcEventWithHeight>, TransferError> {
+    let msgs = build_nft_transfer_messages(src_chain, dst_chain, opts)?;
+    send_messages(src_chain, msgs, "nft-transfer")
+}
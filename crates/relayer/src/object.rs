@@ -10,6 +10,9 @@ use ibc_relayer_types::core::{
     ics04_channel::events::{
         Attributes, CloseInit, SendPacket, TimeoutPacket, UpgradeAttributes, WriteAcknowledgement,
     },
+    ics04_channel::v2::events::{
+        SendPacket as SendPacketV2, WriteAcknowledgement as WriteAcknowledgementV2,
+    },
     ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
 };
 
@@ -120,6 +123,34 @@ impl Packet {
     }
 }
 
+/// An IBC v2 packet worker between a source and destination chain, and a specific pair of clients.
+///
+/// IBC v2 packets are not sent over a channel, but are routed between two clients,
+/// each one hosted on one end of the path and tracking the other end.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct PacketV2 {
+    /// Destination chain identifier.
+    pub dst_chain_id: ChainId,
+
+    /// Source chain identifier.
+    pub src_chain_id: ChainId,
+
+    /// Source client identifier (allocated on the source chain `src_chain_id`).
+    pub src_client_id: ClientId,
+
+    /// Destination client identifier (allocated on the destination chain `dst_chain_id`).
+    pub dst_client_id: ClientId,
+}
+
+impl PacketV2 {
+    pub fn short_name(&self) -> String {
+        format!(
+            "packet_v2::{}:{}->{}:{}",
+            self.src_client_id, self.src_chain_id, self.dst_chain_id, self.dst_client_id,
+        )
+    }
+}
+
 /// A wallet worker which monitors the balance of the wallet in use by Hermes
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Wallet {
@@ -164,6 +195,8 @@ pub enum Object {
     Channel(Channel),
     /// See [`Packet`].
     Packet(Packet),
+    /// See [`PacketV2`].
+    PacketV2(PacketV2),
     /// See [`Wallet`]
     Wallet(Wallet),
     /// See [`CrossChainQuery`]
@@ -216,6 +249,7 @@ impl Object {
             Object::Connection(c) => &c.src_chain_id == src_chain_id,
             Object::Channel(c) => &c.src_chain_id == src_chain_id,
             Object::Packet(p) => &p.src_chain_id == src_chain_id,
+            Object::PacketV2(p) => &p.src_chain_id == src_chain_id,
             Object::Wallet(_) => false,
             Object::CrossChainQuery(c) => &c.src_chain_id == src_chain_id,
         }
@@ -228,6 +262,7 @@ impl Object {
             Object::Connection(c) => &c.src_chain_id == chain_id || &c.dst_chain_id == chain_id,
             Object::Channel(c) => &c.src_chain_id == chain_id || &c.dst_chain_id == chain_id,
             Object::Packet(p) => &p.src_chain_id == chain_id || &p.dst_chain_id == chain_id,
+            Object::PacketV2(p) => &p.src_chain_id == chain_id || &p.dst_chain_id == chain_id,
             Object::Wallet(w) => &w.chain_id == chain_id,
             Object::CrossChainQuery(c) => {
                 &c.src_chain_id == chain_id || &c.dst_chain_id == chain_id
@@ -242,6 +277,7 @@ impl Object {
             Object::Channel(_) => ObjectType::Channel,
             Object::Connection(_) => ObjectType::Connection,
             Object::Packet(_) => ObjectType::Packet,
+            Object::PacketV2(_) => ObjectType::PacketV2,
             Object::Wallet(_) => ObjectType::Wallet,
             Object::CrossChainQuery(_) => ObjectType::CrossChainQuery,
         }
@@ -255,6 +291,7 @@ pub enum ObjectType {
     Channel,
     Connection,
     Packet,
+    PacketV2,
     Wallet,
     CrossChainQuery,
 }
@@ -266,6 +303,7 @@ impl Display for ObjectType {
             ObjectType::Channel => write!(f, "channel"),
             ObjectType::Connection => write!(f, "connection"),
             ObjectType::Packet => write!(f, "packet"),
+            ObjectType::PacketV2 => write!(f, "packet_v2"),
             ObjectType::Wallet => write!(f, "wallet"),
             ObjectType::CrossChainQuery => write!(f, "cross_chain_query"),
         }
@@ -296,6 +334,12 @@ impl From<Packet> for Object {
     }
 }
 
+impl From<PacketV2> for Object {
+    fn from(p: PacketV2) -> Self {
+        Self::PacketV2(p)
+    }
+}
+
 impl From<Wallet> for Object {
     fn from(w: Wallet) -> Self {
         Self::Wallet(w)
@@ -315,6 +359,7 @@ impl Object {
             Self::Connection(ref connection) => &connection.src_chain_id,
             Self::Channel(ref channel) => &channel.src_chain_id,
             Self::Packet(ref path) => &path.src_chain_id,
+            Self::PacketV2(ref path) => &path.src_chain_id,
             Self::Wallet(ref wallet) => &wallet.chain_id,
             Self::CrossChainQuery(ref query) => &query.src_chain_id,
         }
@@ -326,6 +371,7 @@ impl Object {
             Self::Connection(ref connection) => &connection.dst_chain_id,
             Self::Channel(ref channel) => &channel.dst_chain_id,
             Self::Packet(ref path) => &path.dst_chain_id,
+            Self::PacketV2(ref path) => &path.dst_chain_id,
             Self::Wallet(ref wallet) => &wallet.chain_id,
             Self::CrossChainQuery(ref query) => &query.dst_chain_id,
         }
@@ -337,6 +383,7 @@ impl Object {
             Self::Connection(ref connection) => connection.short_name(),
             Self::Channel(ref channel) => channel.short_name(),
             Self::Packet(ref path) => path.short_name(),
+            Self::PacketV2(ref path) => path.short_name(),
             Self::Wallet(ref wallet) => wallet.short_name(),
             Self::CrossChainQuery(ref query) => query.short_name(),
         }
@@ -542,6 +589,50 @@ impl Object {
         .into())
     }
 
+    /// Build the object associated with the given IBC v2 [`SendPacketV2`] event.
+    pub fn for_send_packet_v2(
+        e: &SendPacketV2,
+        src_chain: &impl ChainHandle,
+    ) -> Result<Self, ObjectError> {
+        Self::packet_v2(e.src_client_id(), e.dst_client_id(), src_chain)
+    }
+
+    /// Build the object associated with the given IBC v2 [`WriteAcknowledgementV2`] event.
+    ///
+    /// The acknowledgement is written on the packet's destination chain, which
+    /// is therefore the source chain of the object.
+    pub fn for_write_ack_v2(
+        e: &WriteAcknowledgementV2,
+        src_chain: &impl ChainHandle,
+    ) -> Result<Self, ObjectError> {
+        Self::packet_v2(e.dst_client_id(), e.src_client_id(), src_chain)
+    }
+
+    fn packet_v2(
+        src_client_id: &ClientId,
+        dst_client_id: &ClientId,
+        src_chain: &impl ChainHandle,
+    ) -> Result<Self, ObjectError> {
+        // The counterparty chain is the one tracked by the source client
+        let (client_state, _) = src_chain
+            .query_client_state(
+                QueryClientStateRequest {
+                    client_id: src_client_id.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map_err(ObjectError::relayer)?;
+
+        Ok(PacketV2 {
+            dst_chain_id: client_state.chain_id(),
+            src_chain_id: src_chain.id(),
+            src_client_id: src_client_id.clone(),
+            dst_client_id: dst_client_id.clone(),
+        }
+        .into())
+    }
+
     /// Build the object associated with the given [`CloseInit`] event.
     pub fn for_close_init_channel(
         e: &CloseInit,
@@ -0,0 +1,211 @@
+//! Prices of the denominations used for paying transaction fees and
+//! incentivizing packets, expressed in a common unit, eg. USD.
+//!
+//! The price table is used to compare the fees earned by relaying an
+//! incentivized packet against the fees paid for relaying it, when
+//! these are denominated in different tokens.
+//!
+//! It also records the value of the fees actually paid per packet message
+//! in the transactions submitted to each chain, which is used to estimate
+//! the cost of messages which cannot be simulated in advance, such as the
+//! acknowledgement of a packet which has not been received yet.
+
+use alloc::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use ibc_relayer_types::applications::transfer::RawCoin;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::config::{Error as ConfigError, PricesConfig};
+use crate::error::Error;
+use crate::util::lock::{LockExt, RwArc};
+
+/// Prices indexed by denomination.
+pub type Prices = BTreeMap<String, f64>;
+
+/// The weight of a new observation in the moving average of the
+/// value of the fees paid per packet message.
+const PACKET_MSG_COST_WEIGHT: f64 = 0.2;
+
+/// A price table which can be shared among workers and updated at runtime,
+/// eg. through the REST API.
+#[derive(Clone, Debug, Default)]
+pub struct PriceTable {
+    prices: RwArc<Prices>,
+    packet_msg_costs: RwArc<BTreeMap<ChainId, f64>>,
+}
+
+impl PriceTable {
+    pub fn new(prices: Prices) -> Self {
+        Self {
+            prices: RwArc::new_lock(prices),
+            packet_msg_costs: RwArc::new_lock(BTreeMap::new()),
+        }
+    }
+
+    /// Builds the price table from the prices loaded from the file specified
+    /// in the configuration, if any, overridden by the prices listed in the
+    /// configuration itself.
+    pub fn from_config(config: &PricesConfig) -> Result<Self, Error> {
+        let mut prices = match &config.file {
+            Some(path) => load_prices(path).map_err(Error::config)?,
+            None => Prices::new(),
+        };
+
+        validate_prices(&config.denoms).map_err(Error::config)?;
+        prices.extend(config.denoms.clone());
+
+        Ok(Self::new(prices))
+    }
+
+    /// Returns a copy of all the prices in the table.
+    pub fn prices(&self) -> Prices {
+        self.prices.acquire_read().clone()
+    }
+
+    /// Returns the price of the given denomination, if known.
+    pub fn price(&self, denom: &str) -> Option<f64> {
+        self.prices.acquire_read().get(denom).copied()
+    }
+
+    /// Inserts the given prices in the table, replacing the existing
+    /// prices of the same denominations.
+    pub fn update(&self, prices: Prices) -> Result<(), ConfigError> {
+        validate_prices(&prices)?;
+
+        self.prices.acquire_write().extend(prices);
+
+        Ok(())
+    }
+
+    /// Returns the total value of the given coins, or `None` if the price
+    /// of any of their denominations is unknown.
+    pub fn value_of(&self, coins: &[RawCoin]) -> Option<f64> {
+        let prices = self.prices.acquire_read();
+
+        coins
+            .iter()
+            .map(|coin| {
+                let price = prices.get(&coin.denom)?;
+                let amount = coin.amount.to_string().parse::<f64>().ok()?;
+
+                Some(amount * price)
+            })
+            .sum()
+    }
+
+    /// Records the value of the fees paid per packet message in a transaction
+    /// committed on the given chain, as a moving average of the observed values.
+    pub fn observe_packet_msg_cost(&self, chain_id: &ChainId, cost: f64) {
+        self.packet_msg_costs
+            .acquire_write()
+            .entry(chain_id.clone())
+            .and_modify(|average| {
+                *average += PACKET_MSG_COST_WEIGHT * (cost - *average);
+            })
+            .or_insert(cost);
+    }
+
+    /// Returns the average value of the fees paid per packet message in the
+    /// transactions committed on the given chain, if any was observed.
+    pub fn packet_msg_cost(&self, chain_id: &ChainId) -> Option<f64> {
+        self.packet_msg_costs.acquire_read().get(chain_id).copied()
+    }
+}
+
+/// Loads prices from a TOML file mapping denominations to their price, eg.
+///
+/// ```toml
+/// uatom = 0.000008
+/// 'ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2' = 0.000008
+/// ```
+pub fn load_prices(path: impl AsRef<Path>) -> Result<Prices, ConfigError> {
+    let prices_toml = fs::read_to_string(path).map_err(ConfigError::io)?;
+
+    let prices = toml::from_str::<Prices>(&prices_toml).map_err(ConfigError::decode)?;
+
+    validate_prices(&prices)?;
+
+    Ok(prices)
+}
+
+/// Checks that all the given prices are finite and non-negative.
+pub fn validate_prices(prices: &Prices) -> Result<(), ConfigError> {
+    match prices
+        .iter()
+        .find(|(_, price)| !price.is_finite() || price.is_sign_negative())
+    {
+        Some((denom, price)) => Err(ConfigError::invalid_price(denom.clone(), *price)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::applications::transfer::Amount;
+
+    fn coin(amount: u64, denom: &str) -> RawCoin {
+        RawCoin::new(denom.to_string(), Amount::from(amount))
+    }
+
+    #[test]
+    fn value_of_coins() {
+        let table = PriceTable::new(Prices::from([
+            ("uatom".to_string(), 0.5),
+            ("stake".to_string(), 0.25),
+        ]));
+
+        assert_eq!(table.value_of(&[]), Some(0.0));
+        assert_eq!(table.value_of(&[coin(10, "uatom")]), Some(5.0));
+        assert_eq!(
+            table.value_of(&[coin(10, "uatom"), coin(4, "stake")]),
+            Some(6.0)
+        );
+        assert_eq!(table.value_of(&[coin(10, "uatom"), coin(4, "uosmo")]), None);
+    }
+
+    #[test]
+    fn update_prices() {
+        let table = PriceTable::new(Prices::from([("uatom".to_string(), 0.5)]));
+
+        table
+            .update(Prices::from([
+                ("uatom".to_string(), 1.0),
+                ("uosmo".to_string(), 2.0),
+            ]))
+            .unwrap();
+
+        assert_eq!(table.price("uatom"), Some(1.0));
+        assert_eq!(table.price("uosmo"), Some(2.0));
+
+        assert!(table
+            .update(Prices::from([("uatom".to_string(), -1.0)]))
+            .is_err());
+        assert!(table
+            .update(Prices::from([("uatom".to_string(), f64::NAN)]))
+            .is_err());
+        assert_eq!(table.price("uatom"), Some(1.0));
+    }
+
+    #[test]
+    fn observe_packet_msg_costs() {
+        let table = PriceTable::default();
+        let chain_id = ChainId::from_string("chain-a");
+
+        assert_eq!(table.packet_msg_cost(&chain_id), None);
+
+        table.observe_packet_msg_cost(&chain_id, 10.0);
+        assert_eq!(table.packet_msg_cost(&chain_id), Some(10.0));
+
+        table.observe_packet_msg_cost(&chain_id, 20.0);
+        assert_eq!(table.packet_msg_cost(&chain_id), Some(12.0));
+
+        assert_eq!(
+            table.packet_msg_cost(&ChainId::from_string("chain-b")),
+            None
+        );
+    }
+}
@@ -10,7 +10,7 @@ use tracing::{trace, warn};
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
 use crate::{
-    chain::handle::ChainHandle,
+    chain::{capabilities::ChainCapabilities, handle::ChainHandle},
     config::Config,
     spawn::{spawn_chain_runtime, SpawnError},
     util::lock::RwArc,
@@ -23,6 +23,9 @@ use crate::{
 pub struct Registry<Chain: ChainHandle> {
     config: Config,
     handles: HashMap<ChainId, Chain>,
+    /// The capabilities of the chains, which are static, queried once
+    /// when their runtime is registered.
+    capabilities: HashMap<ChainId, ChainCapabilities>,
     rt: Arc<TokioRuntime>,
 }
 
@@ -37,6 +40,7 @@ impl<Chain: ChainHandle> Registry<Chain> {
         Self {
             config,
             handles: HashMap::new(),
+            capabilities: HashMap::new(),
             rt: Arc::new(TokioRuntime::new().unwrap()),
         }
     }
@@ -73,7 +77,7 @@ impl<Chain: ChainHandle> Registry<Chain> {
     pub fn spawn(&mut self, chain_id: &ChainId) -> Result<bool, SpawnError> {
         if !self.handles.contains_key(chain_id) {
             let handle = spawn_chain_runtime(&self.config, chain_id, self.rt.clone())?;
-            self.handles.insert(chain_id.clone(), handle);
+            self.insert(handle);
             trace!(chain = %chain_id, "spawned chain runtime");
             Ok(true)
         } else {
@@ -81,8 +85,34 @@ impl<Chain: ChainHandle> Registry<Chain> {
         }
     }
 
+    /// Register the handle of a chain runtime which was spawned outside of
+    /// the registry, replacing any handle for the same chain.
+    pub fn insert(&mut self, handle: Chain) {
+        let chain_id = handle.id();
+
+        match handle.capabilities() {
+            Ok(capabilities) => {
+                self.capabilities.insert(chain_id.clone(), capabilities);
+            }
+            Err(e) => {
+                warn!(chain = %chain_id, "failed to query the capabilities of the chain: {e}");
+                self.capabilities.remove(&chain_id);
+            }
+        }
+
+        self.handles.insert(chain_id, handle);
+    }
+
+    /// The capabilities of the chain with the given [`ChainId`], if its runtime
+    /// is registered and they could be queried when it was.
+    pub fn capabilities(&self, chain_id: &ChainId) -> Option<ChainCapabilities> {
+        self.capabilities.get(chain_id).copied()
+    }
+
     /// Shutdown the runtime associated with the given chain identifier.
     pub fn shutdown(&mut self, chain_id: &ChainId) {
+        self.capabilities.remove(chain_id);
+
         if let Some(handle) = self.handles.remove(chain_id) {
             if let Err(e) = handle.shutdown() {
                 warn!(chain = %chain_id, "chain runtime might have failed to shutdown properly: {}", e);
@@ -4,6 +4,8 @@ use tracing::{error, trace};
 
 use crate::{
     config::Config,
+    fee_accounting::FeeReport,
+    price::Prices,
     rest::request::ReplySender,
     rest::request::{Request, VersionInfo},
     supervisor::dump_state::SupervisorState,
@@ -33,6 +35,9 @@ pub type Receiver = crossbeam_channel::Receiver<Request>;
 pub enum Command {
     DumpState(ReplySender<SupervisorState>),
     ClearPackets(Option<ChainId>, ReplySender<()>),
+    GetPrices(ReplySender<Prices>),
+    UpdatePrices(Prices, ReplySender<()>),
+    GetFeeReport(Option<ChainId>, ReplySender<FeeReport>),
 }
 
 /// Process incoming REST requests.
@@ -90,6 +95,24 @@ pub fn process_incoming_requests(config: &Config, channel: &Receiver) -> Option<
 
                 return Some(Command::ClearPackets(chain_id, reply_to));
             }
+
+            Request::GetPrices { reply_to } => {
+                trace!("GetPrices");
+
+                return Some(Command::GetPrices(reply_to));
+            }
+
+            Request::UpdatePrices { prices, reply_to } => {
+                trace!("UpdatePrices");
+
+                return Some(Command::UpdatePrices(prices, reply_to));
+            }
+
+            Request::GetFeeReport { chain_id, reply_to } => {
+                trace!("GetFeeReport");
+
+                return Some(Command::GetFeeReport(chain_id, reply_to));
+            }
         },
         Err(e) => {
             if !matches!(e, TryRecvError::Empty) {
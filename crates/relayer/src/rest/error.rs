@@ -23,6 +23,12 @@ pub enum RestApiError {
     #[error("failed while parsing the request body into a chain configuration: {0}")]
     InvalidChainConfig(String),
 
+    #[error("failed to update the prices: {0}")]
+    InvalidPrices(String),
+
+    #[error("fee accounting is disabled, see the `fee_accounting` section of the configuration")]
+    FeeAccountingDisabled,
+
     #[error("not implemented")]
     Unimplemented,
 }
@@ -36,6 +42,8 @@ impl RestApiError {
             RestApiError::ChainConfigNotFound(_) => "ChainConfigNotFound",
             RestApiError::InvalidChainId(_, _) => "InvalidChainId",
             RestApiError::InvalidChainConfig(_) => "InvalidChainConfig",
+            RestApiError::InvalidPrices(_) => "InvalidPrices",
+            RestApiError::FeeAccountingDisabled => "FeeAccountingDisabled",
             RestApiError::Unimplemented => "Unimplemented",
         }
     }
@@ -2,7 +2,10 @@ use serde::{Deserialize, Serialize};
 
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
-use crate::{config::ChainConfig, rest::RestApiError, supervisor::dump_state::SupervisorState};
+use crate::{
+    config::ChainConfig, fee_accounting::FeeReport, price::Prices, rest::RestApiError,
+    supervisor::dump_state::SupervisorState,
+};
 
 pub type ReplySender<T> = crossbeam_channel::Sender<Result<T, RestApiError>>;
 pub type ReplyReceiver<T> = crossbeam_channel::Receiver<Result<T, RestApiError>>;
@@ -41,4 +44,18 @@ pub enum Request {
         chain_id: Option<ChainId>,
         reply_to: ReplySender<()>,
     },
+
+    GetPrices {
+        reply_to: ReplySender<Prices>,
+    },
+
+    UpdatePrices {
+        prices: Prices,
+        reply_to: ReplySender<()>,
+    },
+
+    GetFeeReport {
+        chain_id: Option<ChainId>,
+        reply_to: ReplySender<FeeReport>,
+    },
 }
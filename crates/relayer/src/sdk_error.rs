@@ -34,6 +34,10 @@ define_error! {
             { code: u32, amount: u64 }
             |e| { format_args!("the issue might have been caused by a misconfiguration of Hermes. Please check the Hermes config.toml and increase either the `max_gas` or `gas_multiplier` settings. Simulated gas was: {}", e.amount) },
 
+        OutOfGasLearned
+            { code: u32, amount: u64 }
+            |e| { format_args!("the gas was estimated from the gas used by previous transactions with the same messages. Please check the Hermes config.toml and increase either the `gas_multiplier` or `gas_model.min_samples` settings, or disable `gas_model.skip_simulation`. Estimated gas was: {}", e.amount) },
+
         InsufficientFee
             { code: u32 }
             |_| { "the price configuration for this chain may be too low! please check the `gas_price.price` Hermes config.toml".to_string() },
@@ -200,6 +204,7 @@ pub fn sdk_error_from_tx_sync_error_code(code: u32, estimated_gas: EstimatedGas)
         11 => match estimated_gas {
             EstimatedGas::Default(amount) => SdkError::out_of_gas_default(code, amount),
             EstimatedGas::Simulated(amount) => SdkError::out_of_gas_simulated(code, amount),
+            EstimatedGas::Learned(amount) => SdkError::out_of_gas_learned(code, amount),
         },
         13 => SdkError::insufficient_fee(code),
         _ => SdkError::unknown_tx_sync(code),
@@ -16,15 +16,19 @@ use ibc_relayer_types::{
 };
 
 use crate::{
-    chain::{endpoint::HealthCheck, handle::ChainHandle, tracking::TrackingId},
+    chain::{
+        capabilities::Capability, endpoint::HealthCheck, handle::ChainHandle, tracking::TrackingId,
+    },
     config::Config,
     event::{
         source::{self, Error as EventError, ErrorDetail as EventErrorDetail, EventBatch},
         IbcEventWithHeight,
     },
-    object::Object,
+    fee_accounting::FeeAccounting,
+    object::{Client, Object},
+    price::PriceTable,
     registry::{Registry, SharedRegistry},
-    rest,
+    rest::{self, RestApiError},
     supervisor::scan::ScanMode,
     telemetry,
     util::{
@@ -170,6 +174,9 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
         }
     }
 
+    // The prices shared by the workers, which can be updated through the REST API
+    let prices = PriceTable::from_config(&config.prices).map_err(Error::relayer)?;
+
     let workers = Arc::new(RwLock::new(WorkerMap::new()));
     let client_state_filter = Arc::new(RwLock::new(FilterPolicy::default()));
 
@@ -190,17 +197,37 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
         info!("scanned chains:");
         info!("{}", scan);
 
-        spawn_context(&config, &mut registry.write(), &mut workers.acquire_write())
-            .spawn_workers(scan);
+        spawn_context(
+            &config,
+            &mut registry.write(),
+            &mut workers.acquire_write(),
+            &prices,
+        )
+        .spawn_workers(scan);
     }
 
     let subscriptions = init_subscriptions(&config, &mut registry.write())?;
 
+    let fee_accounting =
+        FeeAccounting::from_config(&config.fee_accounting).map_err(Error::relayer)?;
+
+    // If fee accounting is enabled, for each chain register the relayer's address
+    // as the address expected to be credited with the fees of the relayed packets.
+    if let Some(fee_accounting) = &fee_accounting {
+        for chain in registry.read().chains() {
+            if let Ok(key) = chain.get_key() {
+                fee_accounting.set_relayer_address(chain.id(), key.account());
+            }
+        }
+    }
+
     let batch_tasks = spawn_batch_workers(
         &config,
         registry.clone(),
         client_state_filter,
         workers.clone(),
+        prices.clone(),
+        fee_accounting.clone(),
         subscriptions,
     );
 
@@ -210,7 +237,14 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
     tasks.extend(batch_tasks);
 
     if let Some(rest_rx) = rest_rx {
-        let rest_task = spawn_rest_worker(config, registry, workers.clone(), rest_rx);
+        let rest_task = spawn_rest_worker(
+            config,
+            registry,
+            workers.clone(),
+            prices,
+            fee_accounting,
+            rest_rx,
+        );
         tasks.push(rest_task);
     }
 
@@ -225,6 +259,8 @@ fn spawn_batch_workers<Chain: ChainHandle>(
     registry: SharedRegistry<Chain>,
     client_state_filter: Arc<RwLock<FilterPolicy>>,
     workers: Arc<RwLock<WorkerMap>>,
+    prices: PriceTable,
+    fee_accounting: Option<FeeAccounting>,
     subscriptions: Vec<(Chain, Subscription)>,
 ) -> Vec<TaskHandle> {
     let mut handles = Vec::with_capacity(subscriptions.len());
@@ -234,6 +270,8 @@ fn spawn_batch_workers<Chain: ChainHandle>(
         let registry = registry.clone();
         let client_state_filter = client_state_filter.clone();
         let workers = workers.clone();
+        let prices = prices.clone();
+        let fee_accounting = fee_accounting.clone();
 
         let handle = spawn_background_task(
             error_span!("worker.batch", chain = %chain.id()),
@@ -245,6 +283,8 @@ fn spawn_batch_workers<Chain: ChainHandle>(
                         &mut registry.write(),
                         &mut client_state_filter.acquire_write(),
                         &mut workers.acquire_write(),
+                        &prices,
+                        fee_accounting.as_ref(),
                         chain.clone(),
                         batch,
                     );
@@ -286,13 +326,22 @@ pub fn spawn_rest_worker<Chain: ChainHandle>(
     config: Config,
     registry: SharedRegistry<Chain>,
     workers: Arc<RwLock<WorkerMap>>,
+    prices: PriceTable,
+    fee_accounting: Option<FeeAccounting>,
     rest_rx: rest::Receiver,
 ) -> TaskHandle {
     spawn_background_task(
         error_span!("rest"),
         Some(Duration::from_millis(500)),
         move || -> Result<Next, TaskError<Infallible>> {
-            handle_rest_requests(&config, &registry.read(), &workers.acquire_read(), &rest_rx);
+            handle_rest_requests(
+                &config,
+                &registry.read(),
+                &workers.acquire_read(),
+                &prices,
+                fee_accounting.as_ref(),
+                &rest_rx,
+            );
 
             Ok(Next::Continue)
         },
@@ -381,6 +430,17 @@ fn relay_on_object<Chain: ChainHandle>(
         Object::Connection(conn) => client_state_filter.control_conn_object(registry, conn),
         Object::Channel(chan) => client_state_filter.control_chan_object(registry, chan),
         Object::Packet(packet) => client_state_filter.control_packet_object(registry, packet),
+        Object::PacketV2(packet) => {
+            // IBC v2 packets are routed by clients, so apply the filter
+            // to the client hosted on the destination chain
+            let client = Client {
+                dst_chain_id: packet.dst_chain_id.clone(),
+                dst_client_id: packet.dst_client_id.clone(),
+                src_chain_id: packet.src_chain_id.clone(),
+            };
+
+            client_state_filter.control_client_object(registry, &client)
+        }
         Object::CrossChainQuery(_ccq) => Ok(Permission::Allow),
         Object::Wallet(_wallet) => Ok(Permission::Allow),
     };
@@ -590,6 +650,22 @@ pub fn collect_events(
                     || Object::for_write_ack(packet, src_chain).ok(),
                 );
             }
+            IbcEvent::SendPacketV2(ref packet) => {
+                collect_event(
+                    &mut collected,
+                    event_with_height.clone(),
+                    mode.packets.enabled,
+                    || Object::for_send_packet_v2(packet, src_chain).ok(),
+                );
+            }
+            IbcEvent::WriteAcknowledgementV2(ref packet) => {
+                collect_event(
+                    &mut collected,
+                    event_with_height.clone(),
+                    mode.packets.enabled,
+                    || Object::for_write_ack_v2(packet, src_chain).ok(),
+                );
+            }
             IbcEvent::CloseInitChannel(ref packet) => {
                 collect_event(
                     &mut collected,
@@ -626,8 +702,9 @@ fn spawn_context<'a, Chain: ChainHandle>(
     config: &'a Config,
     registry: &'a mut Registry<Chain>,
     workers: &'a mut WorkerMap,
+    prices: &'a PriceTable,
 ) -> SpawnContext<'a, Chain> {
-    SpawnContext::new(config, registry, workers)
+    SpawnContext::new(config, registry, workers, prices)
 }
 
 fn chain_scanner<'a, Chain: ChainHandle>(
@@ -732,10 +809,12 @@ fn handle_rest_requests<Chain: ChainHandle>(
     config: &Config,
     registry: &Registry<Chain>,
     workers: &WorkerMap,
+    prices: &PriceTable,
+    fee_accounting: Option<&FeeAccounting>,
     rest_rx: &rest::Receiver,
 ) {
     if let Some(cmd) = rest::process_incoming_requests(config, rest_rx) {
-        handle_rest_cmd(registry, workers, cmd);
+        handle_rest_cmd(registry, workers, prices, fee_accounting, cmd);
     }
 }
 
@@ -743,6 +822,8 @@ fn handle_rest_requests<Chain: ChainHandle>(
 fn handle_rest_cmd<Chain: ChainHandle>(
     registry: &Registry<Chain>,
     workers: &WorkerMap,
+    prices: &PriceTable,
+    fee_accounting: Option<&FeeAccounting>,
     cmd: rest::Command,
 ) {
     match cmd {
@@ -773,6 +854,38 @@ fn handle_rest_cmd<Chain: ChainHandle>(
                 .send(Ok(()))
                 .unwrap_or_else(|e| error!("error replying to a REST request {e}"));
         }
+
+        rest::Command::GetPrices(reply) => {
+            reply
+                .send(Ok(prices.prices()))
+                .unwrap_or_else(|e| error!("error replying to a REST request {e}"));
+        }
+
+        rest::Command::UpdatePrices(new_prices, reply) => {
+            info!("updating prices after REST request");
+
+            let result = prices
+                .update(new_prices)
+                .map_err(|e| RestApiError::InvalidPrices(e.to_string()));
+
+            reply
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {e}"));
+        }
+
+        rest::Command::GetFeeReport(chain_id, reply) => {
+            let result = match (fee_accounting, chain_id) {
+                (Some(fee_accounting), Some(chain_id)) => {
+                    Ok(fee_accounting.report().for_chain(&chain_id))
+                }
+                (Some(fee_accounting), None) => Ok(fee_accounting.report()),
+                (None, _) => Err(RestApiError::FeeAccountingDisabled),
+            };
+
+            reply
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {e}"));
+        }
     }
 }
 
@@ -802,6 +915,8 @@ fn process_batch<Chain: ChainHandle>(
     registry: &mut Registry<Chain>,
     client_state_filter: &mut FilterPolicy,
     workers: &mut WorkerMap,
+    prices: &PriceTable,
+    fee_accounting: Option<&FeeAccounting>,
     src_chain: Chain,
     batch: &EventBatch,
 ) -> Result<(), Error> {
@@ -809,6 +924,10 @@ fn process_batch<Chain: ChainHandle>(
 
     telemetry!(received_event_batch, batch.tracking_id);
 
+    if let Some(fee_accounting) = fee_accounting {
+        fee_accounting.record_events(&batch.chain_id, &batch.events);
+    }
+
     let collected = collect_events(config, workers, &src_chain, batch);
 
     // If there is a NewBlock event, forward this event first to any workers affected by it.
@@ -858,6 +977,25 @@ fn process_batch<Chain: ChainHandle>(
             continue;
         }
 
+        if let Some(capability) = required_capability(&object, &events_with_heights) {
+            let unsupported = [&src_chain, &dst_chain].into_iter().find(|chain| {
+                registry
+                    .capabilities(&chain.id())
+                    .is_some_and(|capabilities| !capabilities.supports(capability))
+            });
+
+            if let Some(chain) = unsupported {
+                warn!(
+                    "skipping events for '{}': chain '{}' does not support {}",
+                    object.short_name(),
+                    chain.id(),
+                    capability
+                );
+
+                continue;
+            }
+        }
+
         if let Object::Packet(ref _path) = object {
             telemetry!(send_telemetry(
                 &src_chain,
@@ -867,7 +1005,7 @@ fn process_batch<Chain: ChainHandle>(
             ));
         }
 
-        let worker = workers.get_or_spawn(object, src_chain, dst_chain, config);
+        let worker = workers.get_or_spawn(object, src_chain, dst_chain, config, prices);
 
         worker.send_events(
             batch.height,
@@ -880,6 +1018,29 @@ fn process_batch<Chain: ChainHandle>(
     Ok(())
 }
 
+/// Returns the capability which both chains must support for
+/// the worker handling the given object and events to make progress.
+fn required_capability(object: &Object, events: &[IbcEventWithHeight]) -> Option<Capability> {
+    match object {
+        Object::CrossChainQuery(_) => Some(Capability::CrossChainQueries),
+        Object::Channel(_)
+            if events.iter().any(|e| {
+                matches!(
+                    e.event,
+                    IbcEvent::UpgradeInitChannel(..)
+                        | IbcEvent::UpgradeTryChannel(..)
+                        | IbcEvent::UpgradeAckChannel(..)
+                        | IbcEvent::UpgradeOpenChannel(..)
+                        | IbcEvent::UpgradeErrorChannel(..)
+                )
+            }) =>
+        {
+            Some(Capability::ChannelUpgrades)
+        }
+        _ => None,
+    }
+}
+
 /// This method parses a list of IbcEvent and record the following three metrics if there is
 /// the corresponding event:
 /// * send_packet_events: The number of SendPacket events received
@@ -950,6 +1111,8 @@ fn handle_batch<Chain: ChainHandle>(
     registry: &mut Registry<Chain>,
     client_state_filter: &mut FilterPolicy,
     workers: &mut WorkerMap,
+    prices: &PriceTable,
+    fee_accounting: Option<&FeeAccounting>,
     chain: Chain,
     batch: ArcBatch,
 ) {
@@ -957,9 +1120,16 @@ fn handle_batch<Chain: ChainHandle>(
 
     match batch.deref() {
         Ok(batch) => {
-            if let Err(e) =
-                process_batch(config, registry, client_state_filter, workers, chain, batch)
-            {
+            if let Err(e) = process_batch(
+                config,
+                registry,
+                client_state_filter,
+                workers,
+                prices,
+                fee_accounting,
+                chain,
+                batch,
+            ) {
                 error!("error during batch processing: {}", e);
             }
         }
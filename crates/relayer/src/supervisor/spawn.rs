@@ -1,15 +1,22 @@
-use tracing::{error, info};
+use tracing::{debug, error, info};
 
 use ibc_relayer_types::core::{
     ics03_connection::connection::IdentifiedConnectionEnd,
     ics04_channel::channel::State as ChannelState,
 };
+use ibc_relayer_types::events::{IbcEvent, WithBlockDataType};
 
 use crate::{
-    chain::{counterparty::connection_state_on_destination, handle::ChainHandle},
+    chain::{
+        capabilities::Capability,
+        counterparty::connection_state_on_destination,
+        handle::ChainHandle,
+        requests::{Paginate, QueryPacketCommitmentsV2Request, QueryPacketEventDataV2Request},
+    },
     client_state::IdentifiedAnyClientState,
     config::Config,
-    object::{Channel, Client, Connection, Object, Packet, Wallet},
+    object::{Channel, Client, Connection, Object, Packet, PacketV2, Wallet},
+    price::PriceTable,
     registry::Registry,
     supervisor::error::Error as SupervisorError,
     telemetry,
@@ -26,6 +33,7 @@ pub struct SpawnContext<'a, Chain: ChainHandle> {
     config: &'a Config,
     registry: &'a mut Registry<Chain>,
     workers: &'a mut WorkerMap,
+    prices: &'a PriceTable,
 }
 
 impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
@@ -33,11 +41,13 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
         config: &'a Config,
         registry: &'a mut Registry<Chain>,
         workers: &'a mut WorkerMap,
+        prices: &'a PriceTable,
     ) -> Self {
         Self {
             config,
             registry,
             workers,
+            prices,
         }
     }
 
@@ -78,12 +88,32 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
     }
 
     pub fn spawn_wallet_worker(&mut self, chain: Chain) {
+        let no_balances = chain
+            .capabilities()
+            .is_ok_and(|capabilities| !capabilities.supports(Capability::Balances));
+
+        if no_balances {
+            info!(
+                chain = %chain.id(),
+                "skipping Wallet worker, chain does not support {}",
+                Capability::Balances
+            );
+
+            return;
+        }
+
         let wallet_object = Object::Wallet(Wallet {
             chain_id: chain.id(),
         });
 
         self.workers
-            .spawn(chain.clone(), chain, &wallet_object, self.config)
+            .spawn(
+                chain.clone(),
+                chain,
+                &wallet_object,
+                self.config,
+                self.prices,
+            )
             .then(|| {
                 info!("spawning Wallet worker: {}", wallet_object.short_name());
             });
@@ -95,6 +125,95 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
         for (_, connection_scan) in client_scan.connections {
             self.spawn_workers_for_connection(chain.clone(), &client_scan.client, connection_scan);
         }
+
+        self.spawn_packet_v2_workers(chain, &client_scan.client);
+    }
+
+    /// Spawns the IBC v2 packet workers between the given client and its counterparty
+    /// if packets sent over the client are pending, so that they clear these on start.
+    ///
+    /// IBC v2 packets are routed between a pair of clients rather than over a channel,
+    /// so the counterparty client is found from the event of the first pending packet.
+    fn spawn_packet_v2_workers(&mut self, chain: Chain, client: &IdentifiedAnyClientState) {
+        let supports_v2 = chain
+            .capabilities()
+            .is_ok_and(|capabilities| capabilities.supports(Capability::IbcV2));
+
+        if !self.config.mode.packets.enabled || !supports_v2 {
+            return;
+        }
+
+        let pending = chain.query_packet_commitments_v2(QueryPacketCommitmentsV2Request {
+            client_id: client.client_id.clone(),
+            pagination: Paginate::All,
+        });
+
+        let sequence = match pending {
+            Ok((sequences, _)) => match sequences.first() {
+                Some(sequence) => *sequence,
+                None => return,
+            },
+            Err(e) => {
+                debug!("skipping IBC v2 packet workers, failed to query packet commitments: {e}");
+                return;
+            }
+        };
+
+        let events = chain.query_packet_events_v2(QueryPacketEventDataV2Request {
+            event_id: WithBlockDataType::SendPacket,
+            client_id: client.client_id.clone(),
+            sequences: vec![sequence],
+        });
+
+        let object = match events
+            .as_ref()
+            .map(|events| events.first().map(|e| &e.event))
+        {
+            Ok(Some(IbcEvent::SendPacketV2(send_packet))) => {
+                Object::for_send_packet_v2(send_packet, &chain)
+            }
+            Ok(_) => return,
+            Err(e) => {
+                error!("failed to query the event of IBC v2 packet {sequence}: {e}");
+                return;
+            }
+        };
+
+        let path = match object {
+            Ok(Object::PacketV2(path)) => path,
+            Ok(_) => return,
+            Err(e) => {
+                error!("failed to build IBC v2 packet worker: {e}");
+                return;
+            }
+        };
+
+        let counterparty_chain = match self.registry.get_or_spawn(&path.dst_chain_id) {
+            Ok(counterparty_chain) => counterparty_chain,
+            Err(e) => {
+                error!("skipping IBC v2 packet workers, reason: {e}");
+                return;
+            }
+        };
+
+        // The reverse path relays the acknowledgements of the pending packets back
+        let reverse = PacketV2 {
+            dst_chain_id: path.src_chain_id.clone(),
+            src_chain_id: path.dst_chain_id.clone(),
+            src_client_id: path.dst_client_id.clone(),
+            dst_client_id: path.src_client_id.clone(),
+        };
+
+        for (src, dst, path) in [
+            (chain.clone(), counterparty_chain.clone(), path),
+            (counterparty_chain, chain, reverse),
+        ] {
+            let object = Object::PacketV2(path);
+
+            self.workers
+                .spawn(src, dst, &object, self.config, self.prices)
+                .then(|| info!("spawned packet v2 worker: {}", object.short_name()));
+        }
     }
 
     pub fn spawn_workers_for_connection(
@@ -198,7 +317,13 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
             });
 
             self.workers
-                .spawn(chain, counterparty_chain, &connection_object, self.config)
+                .spawn(
+                    chain,
+                    counterparty_chain,
+                    &connection_object,
+                    self.config,
+                    self.prices,
+                )
                 .then(|| {
                     info!(
                         "spawning Connection worker: {}",
@@ -265,6 +390,7 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
                         chain.clone(),
                         &client_object,
                         self.config,
+                        self.prices,
                     )
                     .then(|| info!("spawned client worker: {}", client_object.short_name()));
             }
@@ -300,6 +426,7 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
                             counterparty_chain.clone(),
                             &path_object,
                             self.config,
+                            self.prices,
                         )
                         .then(|| info!("spawned packet worker: {}", path_object.short_name()));
                 }
@@ -335,7 +462,13 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
                 });
 
                 self.workers
-                    .spawn(chain, counterparty_chain, &channel_object, self.config)
+                    .spawn(
+                        chain,
+                        counterparty_chain,
+                        &channel_object,
+                        self.config,
+                        self.prices,
+                    )
                     .then(|| info!("spawned channel worker: {}", channel_object.short_name()));
 
                 Ok(true)
@@ -343,6 +476,17 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
                 Ok(false)
             }
         } else if is_channel_upgrading {
+            // Both chains need to support channel upgrades for the
+            // upgrade handshake to be completed
+            for handle in [&chain, &counterparty_chain] {
+                handle
+                    .capabilities()
+                    .and_then(|capabilities| {
+                        capabilities.ensure(&handle.id(), Capability::ChannelUpgrades)
+                    })
+                    .map_err(SupervisorError::relayer)?;
+            }
+
             let path_object = Object::Packet(Packet {
                 dst_chain_id: counterparty_chain.id(),
                 src_chain_id: chain.id(),
@@ -356,6 +500,7 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
                     counterparty_chain.clone(),
                     &path_object,
                     self.config,
+                    self.prices,
                 )
                 .then(|| info!("spawned packet worker: {}", path_object.short_name()));
 
@@ -367,7 +512,13 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
             });
 
             self.workers
-                .spawn(chain, counterparty_chain, &channel_object, self.config)
+                .spawn(
+                    chain,
+                    counterparty_chain,
+                    &channel_object,
+                    self.config,
+                    self.prices,
+                )
                 .then(|| info!("spawned channel worker: {}", channel_object.short_name()));
 
             Ok(true)
@@ -2,14 +2,15 @@ use ibc_relayer_types::signer::SignerError;
 use std::ops::Add;
 use std::str::FromStr;
 
+use core::fmt::{Debug, Formatter, Result as FmtResult};
 use core::time::Duration;
 
 use flex_error::{define_error, DetailOnly};
 use ibc_proto::cosmos::base::v1beta1::Coin;
 use ibc_proto::google::protobuf::Any;
 use ibc_relayer_types::applications::transfer::error::Error as Ics20Error;
-use ibc_relayer_types::applications::transfer::msgs::transfer::MsgTransfer;
-use ibc_relayer_types::applications::transfer::Amount;
+use ibc_relayer_types::applications::transfer::msgs::transfer::{Forwarding, MsgTransfer};
+use ibc_relayer_types::applications::transfer::{Amount, RawCoin};
 use ibc_relayer_types::core::ics04_channel::timeout::TimeoutHeight;
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc_relayer_types::events::IbcEvent;
@@ -17,8 +18,10 @@ use ibc_relayer_types::signer::Signer;
 use ibc_relayer_types::timestamp::{Timestamp, TimestampOverflowError};
 use ibc_relayer_types::tx_msg::Msg;
 
+use crate::chain::capabilities::Capability;
 use crate::chain::endpoint::ChainStatus;
 use crate::chain::handle::ChainHandle;
+use crate::chain::requests::{IncludeProof, QueryChannelRequest, QueryHeight};
 use crate::chain::tracking::TrackedMsgs;
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
@@ -68,6 +71,10 @@ define_error! {
 
         ZeroTimeout
             | _ | { "packet timeout height and packet timeout timestamp cannot both be 0" },
+
+        ShieldedTransfer
+            { reason: String }
+            |e| { format!("invalid shielded transfer: {}", e.reason) },
     }
 }
 
@@ -120,13 +127,93 @@ impl TransferTimeout {
 pub struct TransferOptions {
     pub src_port_id: PortId,
     pub src_channel_id: ChannelId,
-    pub amount: Amount,
-    pub denom: String,
+    /// The tokens to transfer. Transferring more than one token at once
+    /// requires an `ics20-2` channel.
+    pub tokens: Vec<RawCoin>,
     pub receiver: Option<String>,
     pub timeout_height_offset: u64,
     pub timeout_duration: Duration,
     pub number_msgs: usize,
     pub memo: Option<String>,
+    /// Optional forwarding hops, which require an `ics20-2` channel.
+    pub forwarding: Option<Forwarding>,
+}
+
+/// The MASP addresses involved in a shielded IBC transfer, which require
+/// the chains holding them to support shielded transfers.
+#[derive(Clone, Default)]
+pub struct ShieldedTransferOptions {
+    /// The MASP spending key on the source chain from which the tokens are unshielded
+    pub source: Option<String>,
+    /// A block height preceding the creation of the spending key, from which
+    /// its notes are scanned. Defaults to the genesis of the source chain.
+    pub birthday: Option<u64>,
+    /// The MASP payment address on the destination chain to which the tokens are shielded
+    pub target: Option<String>,
+}
+
+// Do not leak the spending key in the logs
+impl Debug for ShieldedTransferOptions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ShieldedTransferOptions")
+            .field("source", &self.source.as_ref().map(|_| "<redacted>"))
+            .field("birthday", &self.birthday)
+            .field("target", &self.target)
+            .finish()
+    }
+}
+
+/// Used to generate the receiver and memo of an IBC transfer shielding
+/// the received tokens to a MASP payment address.
+#[derive(Clone, Debug)]
+pub struct IbcShieldingRequest {
+    /// The port on the chain receiving the tokens
+    pub port_id: PortId,
+    /// The channel on the chain receiving the tokens
+    pub channel_id: ChannelId,
+    /// The tokens, denominated as on the sending chain
+    pub token: RawCoin,
+    /// The MASP payment address to which the tokens are shielded
+    pub target: String,
+}
+
+/// The receiver and memo to set in an IBC transfer for the received
+/// tokens to be shielded.
+#[derive(Clone, Debug)]
+pub struct IbcShielding {
+    pub receiver: String,
+    pub memo: String,
+}
+
+/// Used to send an IBC transfer unshielding tokens from a MASP spending key.
+#[derive(Clone)]
+pub struct ShieldedTransferRequest {
+    pub spending_key: String,
+    pub birthday: Option<u64>,
+    pub src_port_id: PortId,
+    pub src_channel_id: ChannelId,
+    pub token: RawCoin,
+    pub receiver: String,
+    pub timeout_height: TimeoutHeight,
+    pub timeout_duration: Duration,
+    pub memo: Option<String>,
+}
+
+// Do not leak the spending key in the logs
+impl Debug for ShieldedTransferRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ShieldedTransferRequest")
+            .field("spending_key", &"<redacted>")
+            .field("birthday", &self.birthday)
+            .field("src_port_id", &self.src_port_id)
+            .field("src_channel_id", &self.src_channel_id)
+            .field("token", &self.token)
+            .field("receiver", &self.receiver)
+            .field("timeout_height", &self.timeout_height)
+            .field("timeout_duration", &self.timeout_duration)
+            .field("memo", &self.memo)
+            .finish()
+    }
 }
 
 pub fn build_transfer_message(
@@ -147,11 +234,41 @@ pub fn build_transfer_message(
             denom,
             amount: amount.to_string(),
         },
+        tokens: vec![],
         sender,
         receiver,
         timeout_height,
         timeout_timestamp,
         memo,
+        forwarding: None,
+    };
+
+    msg.to_any()
+}
+
+/// Builds an `ics20-2` transfer message, which can carry several tokens
+/// and forward them through intermediate chains.
+pub fn build_transfer_message_v2(
+    src_port_id: PortId,
+    src_channel_id: ChannelId,
+    tokens: Vec<RawCoin>,
+    sender: Signer,
+    receiver: Signer,
+    timeout: TransferTimeout,
+    memo: Option<String>,
+    forwarding: Option<Forwarding>,
+) -> Any {
+    let msg = MsgTransfer {
+        source_port: src_port_id,
+        source_channel: src_channel_id,
+        token: Coin::default(),
+        tokens: tokens.into_iter().map(Coin::from).collect(),
+        sender,
+        receiver,
+        timeout_height: timeout.timeout_height,
+        timeout_timestamp: timeout.timeout_timestamp,
+        memo,
+        forwarding,
     };
 
     msg.to_any()
@@ -179,31 +296,55 @@ pub fn build_transfer_messages<SrcChain: ChainHandle, DstChain: ChainHandle>(
         &destination_chain_status,
     )?;
 
-    let message = build_transfer_message(
-        opts.src_port_id.clone(),
-        opts.src_channel_id.clone(),
-        opts.amount,
-        opts.denom.clone(),
-        sender,
-        receiver,
-        timeout.timeout_height,
-        timeout.timeout_timestamp,
-        opts.memo.clone(),
-    );
+    // Single-token transfers without forwarding are sent as `ics20-1`
+    // messages, so that they can go through channels of either version.
+    let message = match (opts.tokens.as_slice(), &opts.forwarding) {
+        ([token], None) => build_transfer_message(
+            opts.src_port_id.clone(),
+            opts.src_channel_id.clone(),
+            token.amount,
+            token.denom.clone(),
+            sender,
+            receiver,
+            timeout.timeout_height,
+            timeout.timeout_timestamp,
+            opts.memo.clone(),
+        ),
+        ([], _) => return Err(TransferError::token_transfer(Ics20Error::invalid_token())),
+        _ => build_transfer_message_v2(
+            opts.src_port_id.clone(),
+            opts.src_channel_id.clone(),
+            opts.tokens.clone(),
+            sender,
+            receiver,
+            timeout,
+            opts.memo.clone(),
+            opts.forwarding.clone(),
+        ),
+    };
 
     let msgs = vec![message; opts.number_msgs];
 
     Ok(msgs)
 }
 
+/// Submits the messages to `chain` under the given tracking name and waits
+/// for their commit, failing if the chain rejected the transaction.
 pub fn send_messages<Chain: ChainHandle>(
     chain: &Chain,
     msgs: Vec<Any>,
+    name: &'static str,
 ) -> Result<Vec<IbcEventWithHeight>, TransferError> {
     let events_with_heights = chain
-        .send_messages_and_wait_commit(TrackedMsgs::new_static(msgs, "ft-transfer"))
+        .send_messages_and_wait_commit(TrackedMsgs::new_static(msgs, name))
         .map_err(|e| TransferError::submit(chain.id(), e))?;
 
+    check_chain_errors(events_with_heights)
+}
+
+fn check_chain_errors(
+    events_with_heights: Vec<IbcEventWithHeight>,
+) -> Result<Vec<IbcEventWithHeight>, TransferError> {
     // Check if the chain rejected the transaction
     let result = events_with_heights
         .iter()
@@ -230,5 +371,139 @@ pub fn build_and_send_transfer_messages<SrcChain: ChainHandle, DstChain: ChainHa
     opts: &TransferOptions,
 ) -> Result<Vec<IbcEventWithHeight>, TransferError> {
     let msgs = build_transfer_messages(src_chain, dst_chain, opts)?;
-    send_messages(src_chain, msgs)
+    send_messages(src_chain, msgs, "ft-transfer")
+}
+
+/// Builds and sends an IBC transfer of a single token, which is unshielded
+/// from a MASP spending key on the source chain and/or shielded to a MASP
+/// payment address on the destination chain.
+///
+/// When shielding, the receiver and memo of the transfer are generated by
+/// the destination chain and override the ones given in the options.
+pub fn build_and_send_shielded_transfer<SrcChain: ChainHandle, DstChain: ChainHandle>(
+    // the chain whose account is debited
+    src_chain: &SrcChain,
+    // the chain whose account eventually gets credited
+    dst_chain: &DstChain,
+    // options describing the transfer
+    opts: &TransferOptions,
+    // the MASP addresses of the transfer
+    shielded: &ShieldedTransferOptions,
+) -> Result<Vec<IbcEventWithHeight>, TransferError> {
+    let token = match (opts.tokens.as_slice(), &opts.forwarding) {
+        ([token], None) => token.clone(),
+        _ => {
+            return Err(TransferError::shielded_transfer(
+                "only a single token without forwarding can be sent".to_string(),
+            ))
+        }
+    };
+
+    if shielded.source.is_some() {
+        ensure_shielded_transfers(src_chain)?;
+    }
+
+    if shielded.target.is_some() {
+        ensure_shielded_transfers(dst_chain)?;
+    }
+
+    let destination_chain_status = dst_chain
+        .query_application_status()
+        .map_err(TransferError::relayer)?;
+
+    let timeout = TransferTimeout::new(
+        opts.timeout_height_offset,
+        opts.timeout_duration,
+        &destination_chain_status,
+    )?;
+
+    let (receiver, memo) = match &shielded.target {
+        Some(target) => {
+            let (channel_end, _) = src_chain
+                .query_channel(
+                    QueryChannelRequest {
+                        port_id: opts.src_port_id.clone(),
+                        channel_id: opts.src_channel_id.clone(),
+                        height: QueryHeight::Latest,
+                    },
+                    IncludeProof::No,
+                )
+                .map_err(TransferError::relayer)?;
+
+            let counterparty = channel_end.counterparty();
+            let channel_id = counterparty.channel_id.clone().ok_or_else(|| {
+                TransferError::shielded_transfer(format!(
+                    "channel '{}' has no counterparty channel",
+                    opts.src_channel_id
+                ))
+            })?;
+
+            let shielding = dst_chain
+                .build_ibc_shielding(IbcShieldingRequest {
+                    port_id: counterparty.port_id.clone(),
+                    channel_id,
+                    token: token.clone(),
+                    target: target.clone(),
+                })
+                .map_err(TransferError::relayer)?;
+
+            (shielding.receiver, Some(shielding.memo))
+        }
+        None => {
+            let receiver = match &opts.receiver {
+                Some(receiver) => receiver.clone(),
+                None => dst_chain
+                    .get_signer()
+                    .map_err(TransferError::key)?
+                    .to_string(),
+            };
+
+            (receiver, opts.memo.clone())
+        }
+    };
+
+    match &shielded.source {
+        Some(spending_key) => {
+            let events_with_heights = src_chain
+                .send_shielded_transfer(ShieldedTransferRequest {
+                    spending_key: spending_key.clone(),
+                    birthday: shielded.birthday,
+                    src_port_id: opts.src_port_id.clone(),
+                    src_channel_id: opts.src_channel_id.clone(),
+                    token,
+                    receiver,
+                    timeout_height: timeout.timeout_height,
+                    timeout_duration: opts.timeout_duration,
+                    memo,
+                })
+                .map_err(|e| TransferError::submit(src_chain.id(), e))?;
+
+            check_chain_errors(events_with_heights)
+        }
+        None => {
+            let sender = src_chain.get_signer().map_err(TransferError::key)?;
+            let receiver = Signer::from_str(&receiver).map_err(TransferError::receiver_address)?;
+
+            let message = build_transfer_message(
+                opts.src_port_id.clone(),
+                opts.src_channel_id.clone(),
+                token.amount,
+                token.denom,
+                sender,
+                receiver,
+                timeout.timeout_height,
+                timeout.timeout_timestamp,
+                memo,
+            );
+
+            send_messages(src_chain, vec![message; opts.number_msgs], "ft-transfer")
+        }
+    }
+}
+
+fn ensure_shielded_transfers<Chain: ChainHandle>(chain: &Chain) -> Result<(), TransferError> {
+    chain
+        .capabilities()
+        .and_then(|capabilities| capabilities.ensure(&chain.id(), Capability::ShieldedTransfers))
+        .map_err(TransferError::relayer)
 }
@@ -1,7 +1,7 @@
 use core::fmt::Display;
 use core::mem;
 use core::time::Duration;
-use crossbeam_channel::{bounded, Sender};
+use crossbeam_channel::{bounded, RecvTimeoutError, Sender};
 use std::sync::{Arc, RwLock};
 use std::thread;
 use tracing::{debug, error, warn};
@@ -71,7 +71,8 @@ pub enum Next {
    The function is also given a task name string, which is used for logging
    information about the execution of the task. An optional [`Duration`]
    argument is also given for the task runner to sleep for the given
-   duration before calling the step runner again.
+   duration before calling the step runner again, unless the task is
+   shut down in the meantime.
 
    The function returns a [`TaskHandle`] that can be used to shutdown the
    background task. If the [`TaskHandle`] is dropped or if explicit shutdown
@@ -120,7 +121,11 @@ pub fn spawn_background_task<E: Display>(
                 },
             }
             if let Some(interval) = interval_pause {
-                thread::sleep(interval);
+                // Stop waiting as soon as the task is shut down
+                match receiver.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+                }
             }
         }
 
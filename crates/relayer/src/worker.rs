@@ -3,14 +3,15 @@ use core::fmt::{Display, Error as FmtError, Formatter};
 use ibc_relayer_types::core::ics04_channel::channel::Ordering;
 use serde::{Deserialize, Serialize};
 use std::sync::Mutex;
-use tracing::error;
+use tracing::{error, warn};
 
 use crate::foreign_client::ForeignClient;
 use crate::link::{Link, LinkParameters, Resubmit};
 use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
-    config::Config,
+    config::{ChainConfig, Config, EventSourceMode},
     object::Object,
+    price::PriceTable,
 };
 
 pub mod retry_strategy;
@@ -32,6 +33,7 @@ pub mod client;
 pub mod connection;
 pub mod cross_chain_query;
 pub mod packet;
+pub mod packet_v2;
 pub mod wallet;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -59,6 +61,7 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
     id: WorkerId,
     object: Object,
     config: &Config,
+    prices: &PriceTable,
 ) -> WorkerHandle {
     let mut task_handles = Vec::new();
 
@@ -180,6 +183,31 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
                     );
                     task_handles.push(clear_task);
 
+                    // The packets predicted to time out are recovered on restart by clearing them
+                    if should_clear_on_start {
+                        link.lock().unwrap().a_to_b.predict_timeouts();
+                    }
+
+                    // The realized profit is recorded from the results of the transactions,
+                    // which are only available when these are confirmed
+                    if fee_filter
+                        .as_ref()
+                        .is_some_and(|filter| filter.profitability().is_some())
+                    {
+                        if packets_config.tx_confirmation {
+                            link.lock()
+                                .unwrap()
+                                .a_to_b
+                                .track_realized_profit(prices.clone());
+                        } else {
+                            warn!(
+                                "the realized profit of relaying the incentivized packets of channel {} \
+                                is not recorded, as `tx_confirmation` is disabled",
+                                path.src_channel_id
+                            );
+                        }
+                    }
+
                     // Only spawn the incentivized worker if a fee filter is specified in the configuration
                     let packet_task = match fee_filter {
                         Some(filter) => packet::spawn_incentivized_packet_cmd_worker(
@@ -187,6 +215,7 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
                             link.clone(),
                             path.clone(),
                             filter,
+                            prices.clone(),
                         ),
                         None => packet::spawn_packet_cmd_worker(
                             clear_cmd_rx,
@@ -211,6 +240,44 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
             }
         }
 
+        Object::PacketV2(path) => {
+            let packets_config = config.mode.packets;
+
+            // IBC v2 packets are unordered
+            let clear_on_start = should_clear_on_start(&packets_config, Ordering::Unordered);
+
+            let chain_config = config.find_chain(&chains.a.id());
+
+            let clear_interval = chain_config
+                .and_then(|chain_config| chain_config.clear_interval())
+                .unwrap_or(packets_config.clear_interval);
+
+            if let Some(ChainConfig::CosmosSdk(chain_config)) = chain_config {
+                if let EventSourceMode::Push { .. } = chain_config.event_source {
+                    warn!(
+                        "the push event source of chain {} does not receive IBC v2 packet events, \
+                         so the packets of {} are only relayed by the packet clearing, \
+                         consider using the pull event source instead",
+                        chains.a.id(),
+                        path.short_name(),
+                    );
+                }
+            }
+
+            let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
+            let packet_task = packet_v2::spawn_packet_v2_worker(
+                path.clone(),
+                chains,
+                cmd_rx,
+                clear_on_start,
+                clear_interval,
+                packets_config.clear_limit,
+            );
+            task_handles.push(packet_task);
+
+            (Some(cmd_tx), None)
+        }
+
         Object::Wallet(wallet) => {
             assert_eq!(wallet.chain_id, chains.a.id());
 
@@ -6,7 +6,7 @@ use ibc_relayer_types::core::ics02_client::error::Error as Ics02Error;
 use crate::channel::ChannelError;
 use crate::connection::ConnectionError;
 use crate::error::Error as RelayerError;
-use crate::foreign_client::ForeignClientError;
+use crate::foreign_client::{ForeignClientError, HasExpiredOrFrozenError};
 use crate::link::error::LinkError;
 
 define_error! {
@@ -48,3 +48,29 @@ define_error! {
             | _ | { "error receiving from channel: sender end has been closed" },
     }
 }
+
+impl HasExpiredOrFrozenError for RunErrorDetail {
+    fn is_frozen_error(&self) -> bool {
+        match self {
+            Self::ForeignClient(e) => e.source.is_frozen_error(),
+            _ => false,
+        }
+    }
+
+    fn is_expired_error(&self) -> bool {
+        match self {
+            Self::ForeignClient(e) => e.source.is_expired_error(),
+            _ => false,
+        }
+    }
+}
+
+impl HasExpiredOrFrozenError for RunError {
+    fn is_frozen_error(&self) -> bool {
+        self.detail().is_frozen_error()
+    }
+
+    fn is_expired_error(&self) -> bool {
+        self.detail().is_expired_error()
+    }
+}
@@ -10,6 +10,7 @@ use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
     config::Config,
     object::Object,
+    price::PriceTable,
     telemetry,
 };
 
@@ -123,6 +124,7 @@ impl WorkerMap {
         src: Chain,
         dst: Chain,
         config: &Config,
+        prices: &PriceTable,
     ) -> &WorkerHandle {
         if self.workers.contains_key(&object) {
             if self.workers[&object].shutdown_stopped_tasks() {
@@ -131,13 +133,13 @@ impl WorkerMap {
                     self.workers[&object].object().clone(),
                 );
 
-                let worker = self.spawn_worker(src, dst, &object, config);
+                let worker = self.spawn_worker(src, dst, &object, config, prices);
                 self.workers.entry(object).or_insert(worker)
             } else {
                 &self.workers[&object]
             }
         } else {
-            let worker = self.spawn_worker(src, dst, &object, config);
+            let worker = self.spawn_worker(src, dst, &object, config, prices);
             self.workers.entry(object).or_insert(worker)
         }
     }
@@ -151,9 +153,10 @@ impl WorkerMap {
         dst: Chain,
         object: &Object,
         config: &Config,
+        prices: &PriceTable,
     ) -> bool {
         if !self.workers.contains_key(object) {
-            let worker = self.spawn_worker(src, dst, object, config);
+            let worker = self.spawn_worker(src, dst, object, config, prices);
             self.workers.entry(object.clone()).or_insert(worker);
             true
         } else {
@@ -168,6 +171,7 @@ impl WorkerMap {
         dst: Chain,
         object: &Object,
         config: &Config,
+        prices: &PriceTable,
     ) -> WorkerHandle {
         telemetry!(worker, metric_type(object), 1);
 
@@ -176,6 +180,7 @@ impl WorkerMap {
             self.next_worker_id(),
             object.clone(),
             config,
+            prices,
         )
     }
 
@@ -260,6 +265,7 @@ fn metric_type(o: &Object) -> ibc_telemetry::state::WorkerType {
         Object::Connection(_) => WorkerType::Connection,
         Object::Channel(_) => WorkerType::Channel,
         Object::Packet(_) => WorkerType::Packet,
+        Object::PacketV2(_) => WorkerType::PacketV2,
         Object::Wallet(_) => WorkerType::Wallet,
         Object::CrossChainQuery(_) => WorkerType::CrossChainQuery,
     }
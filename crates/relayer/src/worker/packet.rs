@@ -13,7 +13,7 @@ use ibc_relayer_types::applications::ics29_fee::events::IncentivizedPacket;
 use ibc_relayer_types::applications::transfer::{Amount, Coin, RawCoin};
 use ibc_relayer_types::core::ics04_channel::channel::Ordering;
 use ibc_relayer_types::core::ics04_channel::events::WriteAcknowledgement;
-use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics04_channel::packet::{Packet as PacketData, Sequence};
 use ibc_relayer_types::core::ics24_host::identifier::ChannelId;
 use ibc_relayer_types::core::ics24_host::identifier::PortId;
 use ibc_relayer_types::events::{IbcEvent, IbcEventType};
@@ -21,13 +21,14 @@ use ibc_relayer_types::Height;
 
 use crate::chain::handle::ChainHandle;
 use crate::chain::requests::QueryHeight;
-use crate::config::filter::FeePolicy;
+use crate::config::filter::{FeePolicy, Profitability};
 use crate::event::source::EventBatch;
 use crate::event::IbcEventWithHeight;
 use crate::foreign_client::HasExpiredOrFrozenError;
 use crate::link::Resubmit;
 use crate::link::{error::LinkError, Link};
 use crate::object::Packet;
+use crate::price::PriceTable;
 use crate::telemetry;
 use crate::util::lock::{LockExt, RwArc};
 use crate::util::task::{spawn_background_task, Next, TaskError, TaskHandle};
@@ -38,6 +39,11 @@ use super::WorkerCmd;
 const INCENTIVIZED_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
 const INCENTIVIZED_CACHE_MAX_CAPACITY: u64 = 1000;
 
+// The estimated cost of receiving a packet is reused for all the packets
+// sent at the same height, which are relayed in the same transactions.
+const RECV_COST_CACHE_TTL: Duration = Duration::from_secs(60);
+const RECV_COST_CACHE_MAX_CAPACITY: u64 = 16;
+
 // Number of NewBlock consecutive NewBlock events before aborting the
 // packet cmd worker.
 const IDLE_TIMEOUT_BLOCKS: u64 = 100;
@@ -148,6 +154,7 @@ pub fn spawn_incentivized_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHa
     link: Arc<Mutex<Link<ChainA, ChainB>>>,
     path: Packet,
     fee_filter: FeePolicy,
+    prices: PriceTable,
 ) -> TaskHandle {
     let span = {
         let relay_path = &link.lock().unwrap().a_to_b;
@@ -169,6 +176,12 @@ pub fn spawn_incentivized_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHa
             .build(),
     );
 
+    // This Cache will store the estimated cost of receiving the packets sent at a given height.
+    let recv_cost_cache: Cache<Height, Vec<RawCoin>> = Cache::builder()
+        .time_to_live(RECV_COST_CACHE_TTL)
+        .max_capacity(RECV_COST_CACHE_MAX_CAPACITY)
+        .build();
+
     spawn_background_task(span, Some(Duration::from_millis(200)), move || {
         if let Ok(cmd) = cmd_rx.try_recv() {
             handle_incentivized_packet_cmd(
@@ -176,7 +189,9 @@ pub fn spawn_incentivized_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHa
                 &path,
                 cmd,
                 &incentivized_recv_cache,
+                &recv_cost_cache,
                 &fee_filter,
+                &prices,
             )?;
         }
 
@@ -361,7 +376,8 @@ fn handle_clear_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
 /// Receives incentivized worker commands and handles them accordingly.
 ///
 /// Given an `IbcEvent` command, filters the SendPacket and WriteAcknowledgment
-/// events using the FeesFilters and updates the schedule.
+/// events using the FeesFilters, and the profitability of relaying them if
+/// configured, and updates the schedule.
 ///
 /// The incentivized worker does not clear packet, so it only looks for
 /// `IbcEvent` commands.
@@ -373,7 +389,9 @@ fn handle_incentivized_packet_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
     path: &Packet,
     cmd: WorkerCmd,
     incentivized_recv_cache: &RwArc<Cache<Sequence, IncentivizedPacket>>,
+    recv_cost_cache: &Cache<Height, Vec<RawCoin>>,
     fee_filter: &FeePolicy,
+    prices: &PriceTable,
 ) -> Result<(), TaskError<RunError>> {
     // Handle command-specific task
     if let WorkerCmd::IbcEvents { mut batch } = cmd {
@@ -394,6 +412,17 @@ fn handle_incentivized_packet_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
             //IbcEvent::WriteAcknowledgement(ack) => get_incentivized_for_write_acknowledgement(link, ack, event.height.revision_height(), incentivized_ack_cache.clone()),
         }
         filter_batch(batch.borrow_mut(), incentivized_recv_cache, fee_filter);
+
+        if let Some(profitability) = fee_filter.profitability() {
+            filter_unprofitable_packets(
+                link,
+                batch.borrow_mut(),
+                incentivized_recv_cache,
+                recv_cost_cache,
+                profitability,
+                prices,
+            );
+        }
         handle_update_schedule(link, 0, path, batch)
     } else {
         Ok(())
@@ -464,6 +493,107 @@ fn filter_batch(
     });
 }
 
+/// Using the configured prices, only retain the SendPacket events of the incentivized
+/// packets for which the fees earned by relaying them cover the estimated cost of
+/// relaying them, plus the configured margin.
+fn filter_unprofitable_packets<ChainA: ChainHandle, ChainB: ChainHandle>(
+    link: &Link<ChainA, ChainB>,
+    batch: &mut EventBatch,
+    incentivized_recv_cache: &RwArc<Cache<Sequence, IncentivizedPacket>>,
+    recv_cost_cache: &Cache<Height, Vec<RawCoin>>,
+    profitability: &Profitability,
+    prices: &PriceTable,
+) {
+    batch.events.retain(|e| match &e.event {
+        IbcEvent::SendPacket(send_packet) => incentivized_recv_cache
+            .acquire_read()
+            .get(&send_packet.packet.sequence)
+            .is_some_and(|incentivized_event| {
+                is_profitable(
+                    link,
+                    &send_packet.packet,
+                    e.height,
+                    &incentivized_event,
+                    recv_cost_cache,
+                    profitability,
+                    prices,
+                )
+            }),
+        _ => true,
+    });
+}
+
+/// Compares the fees earned by relaying the given packet, committed at the given height,
+/// with the estimated cost of relaying it, converting both using the configured prices.
+///
+/// Receiving the packet earns its `recv_fee`, at the cost estimated by simulating the
+/// transaction which receives it, which is done once for all the packets sent at the
+/// same height. Its acknowledgement cannot be simulated before the packet is received,
+/// so it is only accounted for, along with the `ack_fee` it earns, once the fees paid
+/// per packet message on the source chain have been observed.
+fn is_profitable<ChainA: ChainHandle, ChainB: ChainHandle>(
+    link: &Link<ChainA, ChainB>,
+    packet: &PacketData,
+    height: Height,
+    incentivized_packet: &IncentivizedPacket,
+    recv_cost_cache: &Cache<Height, Vec<RawCoin>>,
+    profitability: &Profitability,
+    prices: &PriceTable,
+) -> bool {
+    let relay_path = &link.a_to_b;
+
+    let recv_cost = match recv_cost_cache.try_get_with(height, || {
+        relay_path.estimate_recv_packet_fee(packet, height)
+    }) {
+        Ok(recv_cost) => recv_cost,
+        Err(e) => {
+            warn!(
+                "failed to estimate the cost of relaying packet {packet}, not relaying packet: {e}"
+            );
+            return false;
+        }
+    };
+
+    let (Some(recv_fee), Some(ack_fee), Some(recv_cost)) = (
+        prices.value_of(&incentivized_packet.total_recv_fee),
+        prices.value_of(&incentivized_packet.total_ack_fee),
+        prices.value_of(&recv_cost),
+    ) else {
+        warn!("missing price for the fees or the cost of relaying packet {packet}, not relaying packet");
+        return false;
+    };
+
+    let (fees, cost) = match prices.packet_msg_cost(&relay_path.src_chain().id()) {
+        Some(ack_cost) => (recv_fee + ack_fee, recv_cost + ack_cost),
+        None => (recv_fee, recv_cost),
+    };
+
+    if !profitability.is_profitable(fees, cost) {
+        debug!(
+            fees,
+            cost,
+            margin = profitability.margin(),
+            "relaying packet {packet} is not profitable, not relaying packet"
+        );
+
+        telemetry!(
+            filtered_packets,
+            &relay_path.src_chain().id(),
+            &relay_path.dst_chain().id(),
+            &packet.source_channel,
+            &packet.destination_channel,
+            &packet.source_port,
+            &packet.destination_port,
+            "unprofitable",
+            1
+        );
+
+        return false;
+    }
+
+    true
+}
+
 /// Multiple fees with different denoms can be specified as rewards,
 /// in an `IncentivizedPacket`. This method extract all and groups all
 /// the fees with the same denom.
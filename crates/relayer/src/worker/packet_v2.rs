@@ -0,0 +1,562 @@
+use alloc::collections::BTreeSet;
+use core::mem;
+use core::time::Duration;
+
+use crossbeam_channel::Receiver;
+use tracing::{debug, error_span, info, warn};
+
+use ibc_proto::google::protobuf::Any;
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics04_channel::v2::msgs::acknowledgement::MsgAcknowledgement;
+use ibc_relayer_types::core::ics04_channel::v2::msgs::recv_packet::MsgRecvPacket;
+use ibc_relayer_types::core::ics04_channel::v2::msgs::timeout::MsgTimeout;
+use ibc_relayer_types::core::ics04_channel::v2::packet::{Packet, PacketMsgType};
+use ibc_relayer_types::core::ics24_host::identifier::ClientId;
+use ibc_relayer_types::events::{IbcEvent, WithBlockDataType};
+use ibc_relayer_types::proofs::Proofs;
+use ibc_relayer_types::signer::Signer;
+use ibc_relayer_types::tx_msg::Msg;
+use ibc_relayer_types::Height;
+
+use crate::chain::endpoint::ChainStatus;
+use crate::chain::handle::{ChainHandle, ChainHandlePair};
+use crate::chain::requests::{
+    IncludeProof, Paginate, QueryHeight, QueryPacketAcknowledgementV2Request,
+    QueryPacketCommitmentV2Request, QueryPacketCommitmentsV2Request, QueryPacketEventDataV2Request,
+    QueryPacketReceiptV2Request,
+};
+use crate::chain::tracking::TrackedMsgs;
+use crate::event::IbcEventWithHeight;
+use crate::foreign_client::{ForeignClient, HasExpiredOrFrozenError};
+use crate::object::PacketV2;
+use crate::util::task::{spawn_background_task, Next, TaskError, TaskHandle};
+
+use super::error::RunError;
+use super::WorkerCmd;
+
+/// Maximum number of times the relaying of an event is retried before giving
+/// up on it, in which case its packet is left to the next packet clearing.
+const MAX_RETRIES: u32 = 5;
+
+/// An event to relay, along with the number of failed attempts at relaying it.
+struct PendingEvent {
+    event_with_height: IbcEventWithHeight,
+    attempts: u32,
+
+    /// Height of chain `a` from which the next attempt is due.
+    retry_at: u64,
+}
+
+impl PendingEvent {
+    fn new(event_with_height: IbcEventWithHeight) -> Self {
+        Self {
+            event_with_height,
+            attempts: 0,
+            retry_at: 0,
+        }
+    }
+
+    fn sequence(&self) -> Option<Sequence> {
+        self.event_with_height
+            .event
+            .packet_v2()
+            .map(|packet| packet.sequence)
+    }
+}
+
+/// Spawns an IBC v2 packet worker task in the background.
+///
+/// The worker relays the IBC v2 packets sent from chain `ChainA` over the
+/// client `path.src_client_id`, as well as the acknowledgements written on
+/// `ChainA` for packets sent from `ChainB`.
+///
+/// The messages built for a batch of events are submitted together, along with
+/// a single client update for each destination chain. Events which could not be
+/// relayed are retried with an exponential backoff, counted in blocks committed
+/// on `ChainA`, at most [`MAX_RETRIES`] times.
+///
+/// The pending packets of the path, ie. those whose commitment is still stored,
+/// are cleared on start if `clear_on_start` is set, every `clear_interval` blocks
+/// unless it is zero, and upon [`WorkerCmd::ClearPendingPackets`]. At most
+/// `clear_limit` packets and as many acknowledgements are cleared at once.
+pub fn spawn_packet_v2_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
+    path: PacketV2,
+    chains: ChainHandlePair<ChainA, ChainB>,
+    cmd_rx: Receiver<WorkerCmd>,
+    clear_on_start: bool,
+    clear_interval: u64,
+    clear_limit: usize,
+) -> TaskHandle {
+    let mut pending: Vec<PendingEvent> = Vec::new();
+    let mut should_clear = clear_on_start;
+    let mut current_height = 0;
+
+    spawn_background_task(
+        error_span!("worker.packet_v2", packet = %path.short_name()),
+        Some(Duration::from_millis(200)),
+        move || {
+            let mut batch = Vec::new();
+
+            if let Ok(cmd) = cmd_rx.try_recv() {
+                match cmd {
+                    WorkerCmd::IbcEvents { batch: events } => {
+                        current_height = current_height.max(events.height.revision_height());
+                        batch.extend(events.events.into_iter().map(PendingEvent::new));
+                    }
+                    WorkerCmd::NewBlock { height, .. } => {
+                        current_height = height.revision_height();
+
+                        if clear_interval != 0 && current_height % clear_interval == 0 {
+                            should_clear = true;
+                        }
+
+                        let (due, waiting) = mem::take(&mut pending)
+                            .into_iter()
+                            .partition(|p: &PendingEvent| p.retry_at <= current_height);
+
+                        pending = waiting;
+                        batch.extend(due);
+                    }
+                    WorkerCmd::ClearPendingPackets => should_clear = true,
+                }
+            }
+
+            if should_clear {
+                should_clear = false;
+
+                match pending_events(&path, &chains, clear_limit) {
+                    Ok(events) => batch.extend(events.into_iter().map(PendingEvent::new)),
+                    Err(e) => warn!("failed to clear pending packets: {e}"),
+                }
+            }
+
+            let failed = relay_batch(&path, &chains, batch).map_err(TaskError::Fatal)?;
+
+            for (mut event, e) in failed {
+                event.attempts += 1;
+
+                if event.attempts > MAX_RETRIES {
+                    warn!(
+                        "giving up on relaying {} after {} attempts, \
+                         its packet is left to the next packet clearing: {e}",
+                        event.event_with_height.event, event.attempts
+                    );
+                } else {
+                    let delay = 1 << event.attempts;
+                    event.retry_at = current_height + delay;
+
+                    warn!(
+                        "failed to relay {}, will retry in {delay} blocks: {e}",
+                        event.event_with_height.event
+                    );
+
+                    pending.push(event);
+                }
+            }
+
+            Ok(Next::Continue)
+        },
+    )
+}
+
+/// Lists the events of the packets sent from chain `a` which were neither received
+/// on chain `b` nor timed out, and of the acknowledgements written on chain `a`
+/// for packets sent from chain `b` which were not relayed back to chain `b` yet.
+fn pending_events<ChainA: ChainHandle, ChainB: ChainHandle>(
+    path: &PacketV2,
+    chains: &ChainHandlePair<ChainA, ChainB>,
+    clear_limit: usize,
+) -> Result<Vec<IbcEventWithHeight>, RunError> {
+    let (sent, _) = chains
+        .a
+        .query_packet_commitments_v2(QueryPacketCommitmentsV2Request {
+            client_id: path.src_client_id.clone(),
+            pagination: Paginate::All,
+        })
+        .map_err(RunError::relayer)?;
+
+    let mut unreceived = Vec::new();
+    for sequence in sent {
+        if unreceived.len() >= clear_limit {
+            break;
+        }
+        if !has_packet_receipt(&chains.b, &path.dst_client_id, sequence)? {
+            unreceived.push(sequence);
+        }
+    }
+
+    let (received, _) = chains
+        .b
+        .query_packet_commitments_v2(QueryPacketCommitmentsV2Request {
+            client_id: path.dst_client_id.clone(),
+            pagination: Paginate::All,
+        })
+        .map_err(RunError::relayer)?;
+
+    let mut unrelayed_acks = Vec::new();
+    for sequence in received {
+        if unrelayed_acks.len() >= clear_limit {
+            break;
+        }
+        if has_packet_ack(&chains.a, &path.src_client_id, sequence)? {
+            unrelayed_acks.push(sequence);
+        }
+    }
+
+    if !unreceived.is_empty() || !unrelayed_acks.is_empty() {
+        info!(
+            "clearing {} pending packets and {} pending acknowledgements",
+            unreceived.len(),
+            unrelayed_acks.len()
+        );
+    }
+
+    let mut events = Vec::new();
+
+    for (event_id, sequences) in [
+        (WithBlockDataType::SendPacket, unreceived),
+        (WithBlockDataType::WriteAck, unrelayed_acks),
+    ] {
+        if sequences.is_empty() {
+            continue;
+        }
+
+        let found = chains
+            .a
+            .query_packet_events_v2(QueryPacketEventDataV2Request {
+                event_id,
+                client_id: path.src_client_id.clone(),
+                sequences,
+            })
+            .map_err(RunError::relayer)?;
+
+        events.extend(found);
+    }
+
+    Ok(events)
+}
+
+/// Relays the given events, and returns those which could not be relayed
+/// along with the reason why.
+///
+/// Packets sent from chain `a` are received on chain `b`, or timed out on
+/// chain `a` if they have expired, and acknowledgements written on chain `a`
+/// are relayed to chain `b`, each kind of message in a single submission.
+///
+/// Fails only if the client of either chain has expired or is frozen.
+fn relay_batch<ChainA: ChainHandle, ChainB: ChainHandle>(
+    path: &PacketV2,
+    chains: &ChainHandlePair<ChainA, ChainB>,
+    batch: Vec<PendingEvent>,
+) -> Result<Vec<(PendingEvent, String)>, RunError> {
+    let mut failed = Vec::new();
+
+    let mut recvs = Vec::new();
+    let mut timeouts = Vec::new();
+    let mut acks = Vec::new();
+
+    let mut dst_status = None;
+    let mut seen = BTreeSet::new();
+
+    for event in batch {
+        let Some(sequence) = event.sequence() else {
+            continue;
+        };
+
+        // The same packet may be both retried and found by the clearing
+        let kind = event.event_with_height.event.event_type();
+        if !seen.insert((kind.as_str(), sequence)) {
+            continue;
+        }
+
+        let result = match &event.event_with_height.event {
+            IbcEvent::SendPacketV2(e) => {
+                classify_send_packet(path, chains, &e.packet, &mut dst_status)
+            }
+            IbcEvent::WriteAcknowledgementV2(_) => {
+                has_packet_commitment(&chains.b, &path.dst_client_id, sequence).map(|pending| {
+                    if pending {
+                        Some(PacketMsgType::Ack)
+                    } else {
+                        debug!("packet {sequence} was already acknowledged");
+                        None
+                    }
+                })
+            }
+            _ => Ok(None),
+        };
+
+        match result {
+            Ok(Some(PacketMsgType::Recv)) => recvs.push(event),
+            Ok(Some(PacketMsgType::Timeout)) => timeouts.push(event),
+            Ok(Some(PacketMsgType::Ack)) => acks.push(event),
+            Ok(None) => {}
+            Err(e) => failed.push((event, e.to_string())),
+        }
+    }
+
+    let to_b = ForeignClient::restore(
+        path.dst_client_id.clone(),
+        chains.b.clone(),
+        chains.a.clone(),
+    );
+
+    let to_a = ForeignClient::restore(
+        path.src_client_id.clone(),
+        chains.a.clone(),
+        chains.b.clone(),
+    );
+
+    // Prove the commitments at the height of the latest event, which all packets are committed at
+    if let Some(height) = max_height(&recvs) {
+        failed.extend(relay_packets(
+            &to_b,
+            &path.src_client_id,
+            PacketMsgType::Recv,
+            height,
+            recvs,
+            |event, proofs, signer| {
+                let packet = event.packet_v2()?.clone();
+                Some(MsgRecvPacket::new(packet, proofs, signer).to_any())
+            },
+            "PacketV2RecvPacket",
+        )?);
+    }
+
+    // Prove the absence of a receipt on chain `b` at the height its timestamp was read at
+    if let Some(dst_status) = dst_status.filter(|_| !timeouts.is_empty()) {
+        let dst_status = dst_status.height;
+        failed.extend(relay_packets(
+            &to_a,
+            &path.dst_client_id,
+            PacketMsgType::Timeout,
+            dst_status,
+            timeouts,
+            |event, proofs, signer| {
+                let packet = event.packet_v2()?.clone();
+                Some(MsgTimeout::new(packet, proofs, signer).to_any())
+            },
+            "PacketV2Timeout",
+        )?);
+    }
+
+    if let Some(height) = max_height(&acks) {
+        failed.extend(relay_packets(
+            &to_b,
+            &path.src_client_id,
+            PacketMsgType::Ack,
+            height,
+            acks,
+            |event, proofs, signer| match event {
+                IbcEvent::WriteAcknowledgementV2(e) => Some(
+                    MsgAcknowledgement::new(e.packet.clone(), e.ack.clone(), proofs, signer)
+                        .to_any(),
+                ),
+                _ => None,
+            },
+            "PacketV2Acknowledgement",
+        )?);
+    }
+
+    Ok(failed)
+}
+
+/// Determines whether the given packet sent from chain `a` must be received on
+/// chain `b` or timed out, if it was neither yet.
+///
+/// The height and timestamp of chain `b` are only queried once per batch.
+fn classify_send_packet<ChainA: ChainHandle, ChainB: ChainHandle>(
+    path: &PacketV2,
+    chains: &ChainHandlePair<ChainA, ChainB>,
+    packet: &Packet,
+    dst_status: &mut Option<ChainStatus>,
+) -> Result<Option<PacketMsgType>, RunError> {
+    if !has_packet_commitment(&chains.a, &path.src_client_id, packet.sequence)? {
+        debug!("packet {} was already acknowledged or timed out", packet);
+        return Ok(None);
+    }
+
+    if has_packet_receipt(&chains.b, &path.dst_client_id, packet.sequence)? {
+        debug!("packet {} was already received", packet);
+        return Ok(None);
+    }
+
+    let status = match dst_status {
+        Some(status) => status,
+        None => {
+            let status = chains
+                .b
+                .query_application_status()
+                .map_err(RunError::relayer)?;
+
+            dst_status.insert(status)
+        }
+    };
+
+    if packet.timed_out(&status.timestamp) {
+        Ok(Some(PacketMsgType::Timeout))
+    } else {
+        Ok(Some(PacketMsgType::Recv))
+    }
+}
+
+fn max_height(events: &[PendingEvent]) -> Option<Height> {
+    events.iter().map(|e| e.event_with_height.height).max()
+}
+
+/// Builds the messages of the given type for the given events, with proofs queried
+/// on the source chain of `client` at `proof_height`, and submits them to its host
+/// chain, along with an update of `client`, in a single transaction.
+///
+/// Returns the events which could not be relayed. Fails only if `client`
+/// has expired or is frozen.
+fn relay_packets<DstChain: ChainHandle, SrcChain: ChainHandle>(
+    client: &ForeignClient<DstChain, SrcChain>,
+    proof_client_id: &ClientId,
+    packet_type: PacketMsgType,
+    proof_height: Height,
+    events: Vec<PendingEvent>,
+    to_msg: impl Fn(&IbcEvent, Proofs, Signer) -> Option<Any>,
+    name: &'static str,
+) -> Result<Vec<(PendingEvent, String)>, RunError> {
+    let signer = match client.dst_chain().get_signer() {
+        Ok(signer) => signer,
+        Err(e) => {
+            let e = e.to_string();
+            return Ok(events.into_iter().map(|ev| (ev, e.clone())).collect());
+        }
+    };
+
+    let mut failed = Vec::new();
+    let mut msgs = Vec::new();
+    let mut submitted = Vec::new();
+
+    for event in events {
+        let Some(sequence) = event.sequence() else {
+            continue;
+        };
+
+        let proofs = client.src_chain().build_packet_v2_proofs(
+            packet_type,
+            proof_client_id,
+            sequence,
+            proof_height,
+        );
+
+        match proofs {
+            Ok(proofs) => {
+                if let Some(msg) = to_msg(&event.event_with_height.event, proofs, signer.clone()) {
+                    msgs.push(msg);
+                    submitted.push(event);
+                }
+            }
+            Err(e) => failed.push((event, e.to_string())),
+        }
+    }
+
+    if msgs.is_empty() {
+        return Ok(failed);
+    }
+
+    match submit(client, proof_height.increment(), msgs, name) {
+        Ok(()) => {
+            info!("relayed {} {packet_type} messages", submitted.len());
+        }
+        Err(e) if e.is_expired_or_frozen_error() => {
+            // There is no point in relaying further packets
+            // over an expired or frozen client.
+            return Err(e);
+        }
+        Err(e) => {
+            let e = e.to_string();
+            failed.extend(submitted.into_iter().map(|ev| (ev, e.clone())));
+        }
+    }
+
+    Ok(failed)
+}
+
+/// Updates the given client up to `proof_height` and submits `msgs`
+/// to its host chain, in a single transaction.
+fn submit<DstChain: ChainHandle, SrcChain: ChainHandle>(
+    client: &ForeignClient<DstChain, SrcChain>,
+    proof_height: Height,
+    msgs: Vec<Any>,
+    name: &'static str,
+) -> Result<(), RunError> {
+    let mut all_msgs = client
+        .wait_and_build_update_client(proof_height)
+        .map_err(RunError::foreign_client)?;
+
+    all_msgs.extend(msgs);
+
+    let dst_chain = client.dst_chain();
+    let events = dst_chain
+        .send_messages_and_wait_commit(TrackedMsgs::new_static(all_msgs, name))
+        .map_err(RunError::relayer)?;
+
+    for event_with_height in events {
+        if let IbcEvent::ChainError(e) = event_with_height.event {
+            warn!("{} failed on chain {}: {}", name, dst_chain.id(), e);
+        }
+    }
+
+    Ok(())
+}
+
+fn has_packet_commitment(
+    chain: &impl ChainHandle,
+    client_id: &ClientId,
+    sequence: Sequence,
+) -> Result<bool, RunError> {
+    let (commitment, _) = chain
+        .query_packet_commitment_v2(
+            QueryPacketCommitmentV2Request {
+                client_id: client_id.clone(),
+                sequence,
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(RunError::relayer)?;
+
+    Ok(!commitment.is_empty())
+}
+
+fn has_packet_receipt(
+    chain: &impl ChainHandle,
+    client_id: &ClientId,
+    sequence: Sequence,
+) -> Result<bool, RunError> {
+    let (receipt, _) = chain
+        .query_packet_receipt_v2(
+            QueryPacketReceiptV2Request {
+                client_id: client_id.clone(),
+                sequence,
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(RunError::relayer)?;
+
+    Ok(!receipt.is_empty())
+}
+
+fn has_packet_ack(
+    chain: &impl ChainHandle,
+    client_id: &ClientId,
+    sequence: Sequence,
+) -> Result<bool, RunError> {
+    let (ack, _) = chain
+        .query_packet_acknowledgement_v2(
+            QueryPacketAcknowledgementV2Request {
+                client_id: client_id.clone(),
+                sequence,
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(RunError::relayer)?;
+
+    Ok(!ack.is_empty())
+}
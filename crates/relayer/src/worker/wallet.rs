@@ -4,6 +4,7 @@ use tracing::{error_span, trace, warn};
 
 use crate::{
     chain::handle::ChainHandle,
+    config::ChainConfig,
     telemetry,
     util::task::{spawn_background_task, Next, TaskError, TaskHandle},
 };
@@ -16,16 +17,25 @@ pub fn spawn_wallet_worker<Chain: ChainHandle>(chain: Chain) -> TaskHandle {
             .config()
             .map_err(|e| TaskError::Fatal(format!("failed to get chain config: {e}")))?;
 
-        if !chain_config.keyring_support() {
-            return Err(TaskError::Ignore(format!(
-                "chain {} does not support keyring",
-                chain.id()
-            )));
-        }
+        let account = match &chain_config {
+            // Penumbra keys are not stored in the keyring, so we identify
+            // the wallet of the relayer by its address instead.
+            ChainConfig::Penumbra(config) => config.address(),
+            _ => {
+                if !chain_config.keyring_support() {
+                    return Err(TaskError::Ignore(format!(
+                        "chain {} does not support keyring",
+                        chain.id()
+                    )));
+                }
 
-        let key = chain.get_key().map_err(|e| {
-            TaskError::Fatal(format!("failed to get key in use by the relayer: {e}"))
-        })?;
+                let key = chain.get_key().map_err(|e| {
+                    TaskError::Fatal(format!("failed to get key in use by the relayer: {e}"))
+                })?;
+
+                key.account()
+            }
+        };
 
         let balance = chain.query_balance(None, None).map_err(|e| {
             TaskError::Ignore(format!("failed to query balance for the account: {e}"))
@@ -36,21 +46,16 @@ pub fn spawn_wallet_worker<Chain: ChainHandle>(chain: Chain) -> TaskHandle {
                 telemetry!(
                     wallet_balance,
                     &chain.id(),
-                    &key.account(),
+                    &account,
                     amount,
                     &balance.denom,
                 );
-                trace!(%amount, denom = %balance.denom, account = %key.account(), "wallet balance");
-                telemetry!(
-                    update_period_fees,
-                    &chain.id(),
-                    &key.account(),
-                    &balance.denom
-                );
+                trace!(%amount, denom = %balance.denom, account = %account, "wallet balance");
+                telemetry!(update_period_fees, &chain.id(), &account, &balance.denom);
             }
             Err(e) => {
                 warn!(
-                    %balance.amount, denom = %balance.denom, account = %key.account(),
+                    %balance.amount, denom = %balance.denom, account = %account,
                     "unable to parse the wallet balance into a f64, the balance will therefore not be reported to telemetry. Reason: {}", e
                 );
             }
@@ -75,6 +75,7 @@ pub enum WorkerType {
     Connection,
     Channel,
     Packet,
+    PacketV2,
     Wallet,
     CrossChainQuery,
 }
@@ -86,6 +87,7 @@ impl Display for WorkerType {
             Self::Connection => write!(f, "connection"),
             Self::Channel => write!(f, "channel"),
             Self::Packet => write!(f, "packet"),
+            Self::PacketV2 => write!(f, "packet-v2"),
             Self::Wallet => write!(f, "wallet"),
             Self::CrossChainQuery => write!(f, "cross-chain-query"),
         }
@@ -213,9 +215,33 @@ pub struct TelemetryState {
     /// The EIP-1559 base fee successfully queried
     dynamic_gas_queried_success_fees: ObservableGauge<f64>,
 
-    /// Number of ICS-20 packets filtered because the memo and/or the receiver fields were exceeding the configured limits
+    /// Number of transactions whose fees were paid with the given token, per chain
+    fee_token_selected: Counter<u64>,
+
+    /// Number of packets filtered, either because the memo and/or the receiver fields of ICS-20 packets
+    /// were exceeding the configured limits, because of the configured ICS-20 rules, or because relaying
+    /// incentivized packets was not profitable, per reason
     filtered_packets: Counter<u64>,
 
+    /// Realized profit, or loss if negative, of relaying incentivized packets, per channel
+    ics29_relaying_profit: UpDownCounter<f64>,
+
+    /// Number of incentivized packets whose fees were distributed without crediting the relayer
+    ics29_uncredited_packets: Counter<u64>,
+
+    /// Number of packets predicted to time out before being received which did time out,
+    /// for which no failing `MsgRecvPacket` was submitted, per channel
+    predicted_timeouts: Counter<u64>,
+
+    /// Number of ICS-721 packets processed, per class
+    ics721_packets: Counter<u64>,
+
+    /// Number of `ics20-2` packets processed, and whether they are forwarded
+    ics20_v2_packets: Counter<u64>,
+
+    /// Number of tokens transferred by the `ics20-2` packets processed
+    ics20_v2_tokens: Counter<u64>,
+
     /// Observed ICS31 CrossChainQueries
     cross_chain_queries: Counter<u64>,
 
@@ -428,9 +454,44 @@ impl TelemetryState {
                 .with_description("The EIP-1559 base fee successfully queried")
                 .init(),
 
+            fee_token_selected: meter
+                .u64_counter("fee_token_selected")
+                .with_description("Number of transactions whose fees were paid with the given token, per chain")
+                .init(),
+
             filtered_packets: meter
                 .u64_counter("filtered_packets")
-                .with_description("Number of ICS-20 packets filtered because the memo and/or the receiver fields were exceeding the configured limits")
+                .with_description("Number of packets filtered because the memo and/or the receiver fields of ICS-20 packets were exceeding the configured limits, because of the configured ICS-20 rules, or because relaying incentivized packets was not profitable")
+                .init(),
+
+            ics29_relaying_profit: meter
+                .f64_up_down_counter("ics29_relaying_profit")
+                .with_description("Realized profit, or loss if negative, of relaying incentivized packets: the ICS-29 rewards credited to the relayer minus the fees paid for its transactions, expressed in the unit of the configured prices")
+                .init(),
+
+            ics29_uncredited_packets: meter
+                .u64_counter("ics29_uncredited_packets")
+                .with_description("Number of incentivized packets whose fees were distributed without crediting the relayer")
+                .init(),
+
+            predicted_timeouts: meter
+                .u64_counter("predicted_timeouts")
+                .with_description("Number of packets predicted to time out before being received which did time out without being received, for which submitting a failing MsgRecvPacket was avoided")
+                .init(),
+
+            ics721_packets: meter
+                .u64_counter("ics721_packets")
+                .with_description("Number of ICS-721 packets processed, per class")
+                .init(),
+
+            ics20_v2_packets: meter
+                .u64_counter("ics20_v2_packets")
+                .with_description("Number of ics20-2 packets processed, and whether they are forwarded")
+                .init(),
+
+            ics20_v2_tokens: meter
+                .u64_counter("ics20_v2_tokens")
+                .with_description("Number of tokens transferred by the ics20-2 packets processed")
                 .init(),
 
             cross_chain_queries: meter
@@ -1233,7 +1294,19 @@ impl TelemetryState {
             .observe(&cx, amount, labels);
     }
 
-    /// Increment number of packets filtered because the memo field is too big
+    /// Record the token which paid the fees of a transaction accepted by the chain
+    pub fn fee_token_selected(&self, chain_id: &ChainId, token: &str) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("token", token.to_string()),
+        ];
+
+        self.fee_token_selected.add(&cx, 1, labels);
+    }
+
+    /// Increment number of packets filtered for the given reason
     #[allow(clippy::too_many_arguments)]
     pub fn filtered_packets(
         &self,
@@ -1243,6 +1316,7 @@ impl TelemetryState {
         dst_channel: &ChannelId,
         src_port: &PortId,
         dst_port: &PortId,
+        reason: &str,
         count: u64,
     ) {
         let cx = Context::current();
@@ -1255,12 +1329,129 @@ impl TelemetryState {
                 KeyValue::new("dst_channel", dst_channel.to_string()),
                 KeyValue::new("src_port", src_port.to_string()),
                 KeyValue::new("dst_port", dst_port.to_string()),
+                KeyValue::new("reason", reason.to_string()),
             ];
 
             self.filtered_packets.add(&cx, count, labels);
         }
     }
 
+    /// Record the realized profit, or loss if negative, of a batch of relayed packets
+    pub fn ics29_relaying_profit(
+        &self,
+        src_chain: &ChainId,
+        dst_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        profit: f64,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("dst_chain", dst_chain.to_string()),
+            KeyValue::new("src_channel", src_channel.to_string()),
+            KeyValue::new("src_port", src_port.to_string()),
+        ];
+
+        self.ics29_relaying_profit.add(&cx, profit, labels);
+    }
+
+    /// Increment number of incentivized packets whose fees were distributed
+    /// without crediting the relayer
+    pub fn ics29_uncredited_packets(
+        &self,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        count: u64,
+    ) {
+        let cx = Context::current();
+
+        if count > 0 {
+            let labels = &[
+                KeyValue::new("chain", chain_id.to_string()),
+                KeyValue::new("channel", channel_id.to_string()),
+                KeyValue::new("port", port_id.to_string()),
+            ];
+
+            self.ics29_uncredited_packets.add(&cx, count, labels);
+        }
+    }
+
+    /// Increment number of packets predicted to time out before being received which did time out
+    pub fn predicted_timeouts(
+        &self,
+        src_chain: &ChainId,
+        dst_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        count: u64,
+    ) {
+        let cx = Context::current();
+
+        if count > 0 {
+            let labels = &[
+                KeyValue::new("src_chain", src_chain.to_string()),
+                KeyValue::new("dst_chain", dst_chain.to_string()),
+                KeyValue::new("src_channel", src_channel.to_string()),
+                KeyValue::new("src_port", src_port.to_string()),
+            ];
+
+            self.predicted_timeouts.add(&cx, count, labels);
+        }
+    }
+
+    /// Increment number of ICS-721 packets processed for the given class
+    pub fn ics721_packets(
+        &self,
+        src_chain: &ChainId,
+        dst_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        class_id: &str,
+        count: u64,
+    ) {
+        let cx = Context::current();
+
+        if count > 0 {
+            let labels = &[
+                KeyValue::new("src_chain", src_chain.to_string()),
+                KeyValue::new("dst_chain", dst_chain.to_string()),
+                KeyValue::new("src_channel", src_channel.to_string()),
+                KeyValue::new("src_port", src_port.to_string()),
+                KeyValue::new("class_id", class_id.to_string()),
+            ];
+
+            self.ics721_packets.add(&cx, count, labels);
+        }
+    }
+
+    /// Increment number of `ics20-2` packets processed, along with the number of tokens they transfer
+    pub fn ics20_v2_packets(
+        &self,
+        src_chain: &ChainId,
+        dst_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        tokens: u64,
+        forwarded: bool,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("dst_chain", dst_chain.to_string()),
+            KeyValue::new("src_channel", src_channel.to_string()),
+            KeyValue::new("src_port", src_port.to_string()),
+        ];
+
+        let packet_labels = &[labels, &[KeyValue::new("forwarded", forwarded)][..]].concat();
+
+        self.ics20_v2_packets.add(&cx, 1, packet_labels);
+        self.ics20_v2_tokens.add(&cx, tokens, labels);
+    }
+
     pub fn cross_chain_queries(&self, src_chain: &ChainId, dst_chain: &ChainId, count: usize) {
         let cx = Context::current();
 
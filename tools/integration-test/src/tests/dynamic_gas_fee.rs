@@ -14,7 +14,7 @@
 //! order to ensure that the first IBC transfer will cost more if dynamic
 //! gas is disabled.
 
-use ibc_relayer::config::dynamic_gas::DynamicGasPrice;
+use ibc_relayer::config::dynamic_gas::{DynamicGasPrice, DynamicGasPriceProvider};
 use ibc_relayer::config::ChainConfig;
 use ibc_relayer::config::GasPrice;
 use ibc_test_framework::prelude::*;
@@ -61,7 +61,8 @@ impl TestOverrides for DynamicGasTest {
                 chain_config_b.gas_price =
                     GasPrice::new(0.1, chain_config_b.gas_price.denom.clone());
                 chain_config_b.dynamic_gas_price =
-                    DynamicGasPrice::unsafe_new(self.dynamic_gas_enabled, 1.1, 0.6);
+                    DynamicGasPrice::unsafe_new(self.dynamic_gas_enabled, 1.1, 0.6)
+                        .with_provider(DynamicGasPriceProvider::Feemarket);
             }
             ChainConfig::Namada(_) => {}
             ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
@@ -14,7 +14,7 @@
 //! order to ensure that the first IBC transfer will cost more if dynamic
 //! gas is disabled.
 
-use ibc_relayer::config::dynamic_gas::DynamicGasPrice;
+use ibc_relayer::config::dynamic_gas::{DynamicGasPrice, DynamicGasPriceProvider};
 use ibc_relayer::config::gas_multiplier::GasMultiplier;
 use ibc_relayer::config::ChainConfig;
 use ibc_relayer::config::GasPrice;
@@ -97,7 +97,8 @@ impl TestOverrides for DynamicGasTest {
                 chain_config_b.gas_multiplier = Some(GasMultiplier::unsafe_new(1.8));
 
                 chain_config_b.dynamic_gas_price =
-                    DynamicGasPrice::unsafe_new(self.dynamic_gas_enabled, 1.1, 0.6);
+                    DynamicGasPrice::unsafe_new(self.dynamic_gas_enabled, 1.1, 0.6)
+                        .with_provider(DynamicGasPriceProvider::Feemarket);
             }
             ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
         }
@@ -24,6 +24,7 @@ pub mod memo;
 #[cfg(not(feature = "namada"))]
 pub mod python;
 pub mod query_packet;
+pub mod replay;
 pub mod supervisor;
 pub mod tendermint;
 #[cfg(not(any(feature = "celestia")))]
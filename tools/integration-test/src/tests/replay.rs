@@ -0,0 +1,73 @@
+/*!
+   Regression tests which replay the RPC traffic recorded from a node,
+   and therefore need no running chain.
+
+   To reproduce an incident, set `record_fixtures = '<path>'` in the
+   configuration of the affected chain, trim the recorded file down
+   to the relevant requests, add it to `tools/integration-test/fixtures`
+   and query it from a chain bootstrapped with
+   [`bootstrap_replayed_chain`].
+*/
+
+use std::path::PathBuf;
+
+use ibc_relayer::chain::endpoint::ChainEndpoint;
+use ibc_relayer::chain::requests::{Qualified, QueryHeight, QueryPacketEventDataRequest};
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::events::{IbcEvent, WithBlockDataType};
+use ibc_relayer_types::Height;
+
+use ibc_test_framework::bootstrap::fixture::bootstrap_replayed_chain;
+use ibc_test_framework::prelude::*;
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(name)
+}
+
+/**
+   Since CometBFT 0.38, the events emitted at the end of a block are only
+   returned in the `finalize_block_events` of `block_results`, which must
+   still be searched for the packets sent by the chain.
+*/
+#[test]
+fn test_query_send_packet_in_finalize_block_events() -> Result<(), Error> {
+    let replayed = bootstrap_replayed_chain(
+        &ChainId::from_string("ibc-0"),
+        &fixture("send_packet_in_finalize_block_events.jsonl"),
+    )?;
+
+    let height = Height::new(0, 25).map_err(handle_generic_error)?;
+    let timeout_height = Height::new(1, 1000).map_err(handle_generic_error)?;
+
+    let events = replayed
+        .chain
+        .query_packet_events(QueryPacketEventDataRequest {
+            event_id: WithBlockDataType::SendPacket,
+            source_channel_id: ChannelId::new(0),
+            source_port_id: PortId::transfer(),
+            destination_channel_id: ChannelId::new(1),
+            destination_port_id: PortId::transfer(),
+            sequences: vec![Sequence::from(1)],
+            height: Qualified::Equal(QueryHeight::Specific(height)),
+        })?;
+
+    let [event] = events.as_slice() else {
+        return Err(Error::generic(eyre!(
+            "expected a single send packet event, got {events:?}"
+        )));
+    };
+
+    let IbcEvent::SendPacket(send_packet) = &event.event else {
+        return Err(Error::generic(eyre!(
+            "expected a send packet event, got {event:?}"
+        )));
+    };
+
+    assert_eq!(event.height, height);
+    assert_eq!(send_packet.packet.sequence, Sequence::from(1));
+    assert_eq!(send_packet.packet.timeout_height, timeout_height.into());
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+/*!
+   Helper functions for bootstrapping a chain which replays the RPC and gRPC
+   traffic recorded from a node with the `record_fixtures` chain setting.
+
+   This makes it possible to turn the responses a node gave during an
+   incident into a reproducible test, without having to run any node.
+*/
+use std::path::Path;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+use ibc_relayer::chain::cosmos::config::CosmosSdkConfig;
+use ibc_relayer::chain::cosmos::fixture::{read_fixtures, FixtureServer, Interaction};
+use ibc_relayer::chain::cosmos::CosmosSdkChain;
+use ibc_relayer::chain::endpoint::ChainEndpoint;
+use ibc_relayer::config::ChainConfig;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::error::{handle_generic_error, Error};
+
+/**
+   A chain endpoint whose requests are answered by a [`FixtureServer`].
+*/
+pub struct ReplayedChain {
+    pub chain: CosmosSdkChain,
+
+    /// Serves the recorded responses for as long as the chain is in use.
+    pub server: FixtureServer,
+}
+
+/**
+   Bootstrap a [`CosmosSdkChain`] with the given chain ID, which is
+   served the interactions recorded in the fixture file at `path`.
+
+   The chain uses the event source the fixtures were recorded with,
+   ie. the `push` event source if they contain WebSocket subscriptions,
+   and an in-memory key store which is left empty.
+*/
+pub fn bootstrap_replayed_chain(chain_id: &ChainId, path: &Path) -> Result<ReplayedChain, Error> {
+    let interactions = read_fixtures(path)?;
+
+    let has_subscriptions = interactions.iter().any(|interaction| {
+        matches!(interaction, Interaction::JsonRpc { method, .. } if method == "subscribe")
+    });
+
+    let runtime = Arc::new(Runtime::new()?);
+    let server = runtime.block_on(FixtureServer::replay(interactions))?;
+
+    let event_source = if has_subscriptions {
+        format!("{{ mode = 'push', url = '{}' }}", server.websocket_url())
+    } else {
+        "{ mode = 'pull', interval = '1s' }".to_string()
+    };
+
+    let config: CosmosSdkConfig = toml::from_str(&format!(
+        r#"
+        id = '{chain_id}'
+        rpc_addr = '{url}'
+        grpc_addr = '{url}'
+        event_source = {event_source}
+        account_prefix = 'cosmos'
+        key_name = 'relayer'
+        key_store_type = 'Memory'
+        store_prefix = 'ibc'
+        gas_price = {{ price = 0.0, denom = 'stake' }}
+        "#,
+        url = server.url(),
+    ))
+    .map_err(handle_generic_error)?;
+
+    let chain = CosmosSdkChain::bootstrap(ChainConfig::CosmosSdk(config), runtime)?;
+
+    Ok(ReplayedChain { chain, server })
+}
@@ -13,6 +13,7 @@
 
 pub mod binary;
 pub mod consumer;
+pub mod fixture;
 pub mod init;
 pub mod namada;
 pub mod nary;
@@ -22,8 +22,10 @@
 
 use crossbeam_channel as channel;
 use ibc_proto::ibc::core::channel::v1::{QueryUpgradeErrorRequest, QueryUpgradeRequest};
+use ibc_relayer::chain::capabilities::ChainCapabilities;
 use ibc_relayer::chain::version::Specs;
 use ibc_relayer_types::applications::ics28_ccv::msgs::{ConsumerChain, ConsumerId};
+use ibc_relayer_types::applications::ics29_fee::packet_fee::IdentifiedPacketFees;
 use ibc_relayer_types::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use tracing::Span;
 
@@ -45,7 +47,10 @@ use ibc_relayer::error::Error;
 use ibc_relayer::event::IbcEventWithHeight;
 use ibc_relayer::keyring::AnySigningKeyPair;
 use ibc_relayer::misbehaviour::MisbehaviourEvidence;
+use ibc_relayer::nft_transfer::ClassTrace;
+use ibc_relayer::transfer::{IbcShielding, IbcShieldingRequest, ShieldedTransferRequest};
 use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
+use ibc_relayer_types::applications::transfer::RawCoin;
 use ibc_relayer_types::core::ics02_client::events::UpdateClient;
 use ibc_relayer_types::core::ics02_client::header::AnyHeader;
 use ibc_relayer_types::core::ics03_connection::connection::ConnectionEnd;
@@ -54,6 +59,7 @@ use ibc_relayer_types::core::ics03_connection::version::Version;
 use ibc_relayer_types::core::ics04_channel::channel::ChannelEnd;
 use ibc_relayer_types::core::ics04_channel::channel::IdentifiedChannelEnd;
 use ibc_relayer_types::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc_relayer_types::core::ics04_channel::v2::packet::PacketMsgType as PacketMsgTypeV2;
 use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentPrefix;
 use ibc_relayer_types::core::ics23_commitment::merkle::MerkleProof;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
@@ -109,6 +115,25 @@ where
         self.value().send_messages_and_wait_check_tx(tracked_msgs)
     }
 
+    fn estimate_fee(&self, tracked_msgs: TrackedMsgs) -> Result<Vec<RawCoin>, Error> {
+        self.value().estimate_fee(tracked_msgs)
+    }
+
+    fn query_tx_fee(&self, tx_hash: QueryTxHash) -> Result<Vec<RawCoin>, Error> {
+        self.value().query_tx_fee(tx_hash)
+    }
+
+    fn build_ibc_shielding(&self, request: IbcShieldingRequest) -> Result<IbcShielding, Error> {
+        self.value().build_ibc_shielding(request)
+    }
+
+    fn send_shielded_transfer(
+        &self,
+        request: ShieldedTransferRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.value().send_shielded_transfer(request)
+    }
+
     fn get_signer(&self) -> Result<Signer, Error> {
         self.value().get_signer()
     }
@@ -129,6 +154,10 @@ where
         self.value().version_specs()
     }
 
+    fn capabilities(&self) -> Result<ChainCapabilities, Error> {
+        self.value().capabilities()
+    }
+
     fn query_application_status(&self) -> Result<ChainStatus, Error> {
         self.value().query_application_status()
     }
@@ -370,6 +399,57 @@ where
         self.value().query_packet_acknowledgements(request)
     }
 
+    fn build_packet_v2_proofs(
+        &self,
+        packet_type: PacketMsgTypeV2,
+        client_id: &ClientId,
+        sequence: Sequence,
+        height: Height,
+    ) -> Result<Proofs, Error> {
+        self.value()
+            .build_packet_v2_proofs(packet_type, client_id, sequence, height)
+    }
+
+    fn query_packet_commitment_v2(
+        &self,
+        request: QueryPacketCommitmentV2Request,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.value()
+            .query_packet_commitment_v2(request, include_proof)
+    }
+
+    fn query_packet_receipt_v2(
+        &self,
+        request: QueryPacketReceiptV2Request,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.value().query_packet_receipt_v2(request, include_proof)
+    }
+
+    fn query_packet_acknowledgement_v2(
+        &self,
+        request: QueryPacketAcknowledgementV2Request,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.value()
+            .query_packet_acknowledgement_v2(request, include_proof)
+    }
+
+    fn query_packet_commitments_v2(
+        &self,
+        request: QueryPacketCommitmentsV2Request,
+    ) -> Result<(Vec<Sequence>, Height), Error> {
+        self.value().query_packet_commitments_v2(request)
+    }
+
+    fn query_packet_events_v2(
+        &self,
+        request: QueryPacketEventDataV2Request,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.value().query_packet_events_v2(request)
+    }
+
     fn query_unreceived_acknowledgements(
         &self,
         request: QueryUnreceivedAcksRequest,
@@ -421,6 +501,18 @@ where
         self.value().query_denom_trace(hash)
     }
 
+    fn query_class_trace(&self, hash: String) -> Result<ClassTrace, Error> {
+        self.value().query_class_trace(hash)
+    }
+
+    fn query_interchain_account(
+        &self,
+        owner: Signer,
+        connection_id: ConnectionId,
+    ) -> Result<String, Error> {
+        self.value().query_interchain_account(owner, connection_id)
+    }
+
     fn cross_chain_query(
         &self,
         request: Vec<CrossChainQueryRequest>,
@@ -435,6 +527,14 @@ where
         self.value().query_incentivized_packet(request)
     }
 
+    fn query_incentivized_packets(
+        &self,
+        channel_id: ChannelId,
+        port_id: PortId,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error> {
+        self.value().query_incentivized_packets(channel_id, port_id)
+    }
+
     fn query_consumer_chains(&self) -> Result<Vec<ConsumerChain>, Error> {
         self.value().query_consumer_chains()
     }
@@ -1,13 +1,15 @@
+use alloc::sync::Arc;
 use core::str::FromStr;
 use core::time::Duration;
 
 use http::uri::Uri;
 
 use ibc_proto::cosmos::tx::v1beta1::Fee;
+use ibc_relayer::chain::cosmos::backend::CosmosSdkBackend;
 use ibc_relayer::chain::cosmos::gas::calculate_fee;
 use ibc_relayer::chain::cosmos::types::config::TxConfig;
 use ibc_relayer::chain::cosmos::types::gas::GasConfig;
-use ibc_relayer::config::dynamic_gas::DynamicGasPrice;
+use ibc_relayer::config::dynamic_gas::{DynamicGasPrice, DynamicGasPriceProvider};
 use ibc_relayer::config::{AddressType, GasPrice};
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use tendermint_rpc::Url;
@@ -40,6 +42,7 @@ pub fn gas_config_for_test(native_token: String, chain_type: ChainType) -> GasCo
 
     let dynamic_gas_price = if chain_type.enable_dynamic_fee() {
         DynamicGasPrice::unsafe_new(true, 1.3, 5.0)
+            .with_provider(DynamicGasPriceProvider::Feemarket)
     } else {
         DynamicGasPrice::disabled()
     };
@@ -70,10 +73,18 @@ pub fn new_tx_config_for_test(
     let max_msg_num = Default::default();
     let max_tx_size = Default::default();
     let extension_options = Default::default();
+    let gas_model = Default::default();
+    let fee_granters = Default::default();
+    let tx_replacement = Default::default();
+    let tx_replacement_support = Default::default();
+    let unordered_tx_timeout = None;
+    let backend = Arc::new(CosmosSdkBackend);
 
     Ok(TxConfig {
         chain_id,
         gas_config,
+        fee_granters,
+        gas_model,
         rpc_address,
         grpc_address,
         rpc_timeout,
@@ -81,5 +92,9 @@ pub fn new_tx_config_for_test(
         max_msg_num,
         max_tx_size,
         extension_options,
+        tx_replacement,
+        tx_replacement_support,
+        unordered_tx_timeout,
+        backend,
     })
 }
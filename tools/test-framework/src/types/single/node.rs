@@ -9,7 +9,7 @@ use eyre::Report as Error;
 use ibc_relayer::chain::cosmos::config::CosmosSdkConfig;
 use ibc_relayer::config;
 use ibc_relayer::config::compat_mode::CompatMode;
-use ibc_relayer::config::dynamic_gas::DynamicGasPrice;
+use ibc_relayer::config::dynamic_gas::{DynamicGasPrice, DynamicGasPriceProvider};
 use ibc_relayer::config::gas_multiplier::GasMultiplier;
 use ibc_relayer::keyring::Store;
 use ibc_relayer::util::excluded_sequences::ExcludedSequences;
@@ -159,6 +159,7 @@ impl FullNode {
 
         let dynamic_gas_price = if chain_type.enable_dynamic_fee() {
             DynamicGasPrice::unsafe_new(true, 1.3, 5.0)
+                .with_provider(DynamicGasPriceProvider::Feemarket)
         } else {
             DynamicGasPrice::disabled()
         };
@@ -178,6 +179,8 @@ impl FullNode {
                 },
                 rpc_timeout: config::default::rpc_timeout(),
                 trusted_node: false,
+                witnesses: Vec::new(),
+                min_witnesses: 1,
                 genesis_restart: None,
                 account_prefix: self.chain_driver.account_prefix.clone(),
                 key_name: self.wallets.relayer.id.0.clone(),
@@ -190,6 +193,7 @@ impl FullNode {
                 gas_multiplier: Some(GasMultiplier::unsafe_new(1.5)),
                 dynamic_gas_price,
                 fee_granter: None,
+                fee_granters: Default::default(),
                 max_msg_num: Default::default(),
                 max_tx_size: Default::default(),
                 max_grpc_decoding_size: config::default::max_grpc_decoding_size(),
@@ -201,7 +205,12 @@ impl FullNode {
                 ccv_consumer_chain: false,
                 trust_threshold: Default::default(),
                 gas_price,
+                fallback_fee_tokens: Vec::new(),
                 packet_filter: Default::default(),
+                packet_priority: Default::default(),
+                gas_model: Default::default(),
+                tx_replacement: Default::default(),
+                unordered_tx: Default::default(),
                 address_type: chain_type.address_type(),
                 memo_prefix: Default::default(),
                 memo_overwrite: None,
@@ -212,6 +221,8 @@ impl FullNode {
                 clear_interval: None,
                 excluded_sequences: ExcludedSequences::new(BTreeMap::new()),
                 allow_ccq: true,
+                backend: None,
+                record_fixtures: None,
             }),
             TestedChainType::Namada => config::ChainConfig::Namada(CosmosSdkConfig {
                 id: self.chain_driver.chain_id.clone(),
@@ -223,6 +234,8 @@ impl FullNode {
                 },
                 rpc_timeout: config::default::rpc_timeout(),
                 trusted_node: false,
+                witnesses: Vec::new(),
+                min_witnesses: 1,
                 genesis_restart: None,
                 account_prefix: "".to_owned(),
                 key_name: self.wallets.relayer.id.0.clone(),
@@ -235,6 +248,7 @@ impl FullNode {
                 gas_multiplier: Some(GasMultiplier::unsafe_new(1.2)),
                 dynamic_gas_price: DynamicGasPrice::default(),
                 fee_granter: None,
+                fee_granters: Default::default(),
                 max_msg_num: Default::default(),
                 max_tx_size: Default::default(),
                 max_grpc_decoding_size: config::default::max_grpc_decoding_size(),
@@ -246,7 +260,12 @@ impl FullNode {
                 ccv_consumer_chain: false,
                 trust_threshold: Default::default(),
                 gas_price,
+                fallback_fee_tokens: Vec::new(),
                 packet_filter: Default::default(),
+                packet_priority: Default::default(),
+                gas_model: Default::default(),
+                tx_replacement: Default::default(),
+                unordered_tx: Default::default(),
                 address_type: chain_type.address_type(),
                 memo_prefix: Default::default(),
                 memo_overwrite: None,
@@ -257,6 +276,8 @@ impl FullNode {
                 clear_interval: None,
                 excluded_sequences: ExcludedSequences::new(BTreeMap::new()),
                 allow_ccq: false,
+                backend: None,
+                record_fixtures: None,
             }),
         };
 